@@ -1,51 +1,220 @@
-use serde_json::json;
+use serde_json::{json, Value};
+use tauri::Manager;
 use tauri_plugin_aptabase::EventTracker;
 
-use crate::AppRuntime;
-
-pub fn track_transcription_completed(
-    app: &tauri::AppHandle<AppRuntime>,
-    mode: &str,
-    engine: &str,
-    model: Option<&str>,
-    llm_cleaned: bool,
-    duration_secs: f64,
-) {
-    let props = json!({
-        "mode": mode,
-        "engine": engine,
-        "model": model.unwrap_or("unknown"),
-        "llm_cleaned": llm_cleaned,
-        "duration_secs": duration_secs
-    });
-    let _ = app.track_event("transcription_completed", Some(props));
-}
-
-pub fn track_transcription_failed(
-    app: &tauri::AppHandle<AppRuntime>,
-    stage: &str,
-    engine: &str,
-    reason: &str,
-) {
-    let props = json!({
-        "stage": stage,
-        "engine": engine,
-        "reason": reason
-    });
-    let _ = app.track_event("transcription_failed", Some(props));
-}
-
-pub fn track_model_downloaded(app: &tauri::AppHandle<AppRuntime>, model: &str, size_mb: f32) {
-    let props = json!({
-        "model": model,
-        "size_mb": size_mb
-    });
-    let _ = app.track_event("model_downloaded", Some(props));
-}
-
-pub fn track_onboarding_completed(app: &tauri::AppHandle<AppRuntime>, model_selected: &str) {
-    let props = json!({
-        "model_selected": model_selected
-    });
-    let _ = app.track_event("onboarding_completed", Some(props));
+use crate::session::SessionStats;
+use crate::{AppRuntime, AppState};
+
+/// Every event the app reports, with its properties.
+#[derive(Debug, Clone)]
+pub enum AnalyticsEvent {
+    AppStarted,
+    AppExited,
+    /// The last event sent when the user turns analytics off, so opt-outs can be counted.
+    AnalyticsOptOut,
+    OnboardingCompleted {
+        model_selected: String,
+    },
+    TranscriptionCompleted {
+        mode: String,
+        engine: String,
+        model: String,
+        word_count: u32,
+        duration_seconds: f32,
+        llm_cleaned: bool,
+    },
+    TranscriptionFailed {
+        stage: String,
+        engine: String,
+        reason: String,
+    },
+    ModelDownloaded {
+        model: String,
+        size_mb: f32,
+    },
+    /// The local model selection changed.
+    ModelSwitched {
+        from: String,
+        to: String,
+    },
+    /// A recording shortcut was bound or rebound; `mode` is smart, hold, toggle, copy_latest
+    /// or continuous.
+    ShortcutRegistered {
+        mode: String,
+    },
+    SessionSummary(SessionStats),
+}
+
+/// Aptabase's event name and properties for the event.
+impl From<AnalyticsEvent> for (&'static str, Option<Value>) {
+    fn from(event: AnalyticsEvent) -> Self {
+        match event {
+            AnalyticsEvent::AppStarted => ("app_started", None),
+            AnalyticsEvent::AppExited => ("app_exited", None),
+            AnalyticsEvent::AnalyticsOptOut => ("analytics_opt_out", None),
+            AnalyticsEvent::OnboardingCompleted { model_selected } => (
+                "onboarding_completed",
+                Some(json!({ "model_selected": model_selected })),
+            ),
+            AnalyticsEvent::TranscriptionCompleted {
+                mode,
+                engine,
+                model,
+                word_count,
+                duration_seconds,
+                llm_cleaned,
+            } => (
+                "transcription_completed",
+                Some(json!({
+                    "mode": mode,
+                    "engine": engine,
+                    "model": model,
+                    "word_count": word_count,
+                    "llm_cleaned": llm_cleaned,
+                    "duration_secs": duration_seconds
+                })),
+            ),
+            AnalyticsEvent::TranscriptionFailed {
+                stage,
+                engine,
+                reason,
+            } => (
+                "transcription_failed",
+                Some(json!({
+                    "stage": stage,
+                    "engine": engine,
+                    "reason": reason
+                })),
+            ),
+            AnalyticsEvent::ModelDownloaded { model, size_mb } => (
+                "model_downloaded",
+                Some(json!({
+                    "model": model,
+                    "size_mb": size_mb
+                })),
+            ),
+            AnalyticsEvent::ModelSwitched { from, to } => (
+                "model_switched",
+                Some(json!({
+                    "from": from,
+                    "to": to
+                })),
+            ),
+            AnalyticsEvent::ShortcutRegistered { mode } => {
+                ("shortcut_registered", Some(json!({ "mode": mode })))
+            }
+            AnalyticsEvent::SessionSummary(stats) => (
+                "session_summary",
+                Some(json!({
+                    "recordings_count": stats.recordings_count,
+                    "total_audio_secs": stats.total_audio_seconds,
+                    "total_words": stats.total_words,
+                    "avg_latency_ms": stats.average_latency_ms
+                })),
+            ),
+        }
+    }
+}
+
+/// Where analytics events go: Aptabase through the app handle, or a stand-in in tests.
+trait Tracker {
+    fn track(&self, event: &str, props: Option<Value>);
+}
+
+impl Tracker for tauri::AppHandle<AppRuntime> {
+    fn track(&self, event: &str, props: Option<Value>) {
+        let _ = self.track_event(event, props);
+    }
+}
+
+fn track_with(tracker: &impl Tracker, enabled: bool, event: AnalyticsEvent) {
+    if enabled {
+        let (name, props): (&str, Option<Value>) = event.into();
+        tracker.track(name, props);
+    }
+}
+
+/// Send an event unless the user opted out of analytics. Nothing is sent before the app
+/// state, and with it the setting, is available.
+pub fn track(app: &tauri::AppHandle<AppRuntime>, event: AnalyticsEvent) {
+    let enabled = app
+        .try_state::<AppState>()
+        .is_some_and(|state| state.current_settings().analytics_enabled);
+    track_with(app, enabled, event);
+}
+
+/// Report the opt-out itself, which has to go out after the setting is already off.
+pub fn track_opt_out(app: &tauri::AppHandle<AppRuntime>) {
+    track_with(app, true, AnalyticsEvent::AnalyticsOptOut);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    #[derive(Default)]
+    struct MockTracker {
+        events: RefCell<Vec<(String, Option<Value>)>>,
+    }
+
+    impl Tracker for MockTracker {
+        fn track(&self, event: &str, props: Option<Value>) {
+            self.events.borrow_mut().push((event.to_string(), props));
+        }
+    }
+
+    #[test]
+    fn test_nothing_is_tracked_when_analytics_is_disabled() {
+        let tracker = MockTracker::default();
+        track_with(&tracker, false, AnalyticsEvent::AppStarted);
+        track_with(
+            &tracker,
+            false,
+            AnalyticsEvent::ModelDownloaded {
+                model: "x".to_string(),
+                size_mb: 1.0,
+            },
+        );
+        assert!(tracker.events.borrow().is_empty());
+
+        track_with(&tracker, true, AnalyticsEvent::AppStarted);
+        assert_eq!(
+            *tracker.events.borrow(),
+            vec![("app_started".to_string(), None)]
+        );
+    }
+
+    #[test]
+    fn test_events_serialize_to_aptabase_props() {
+        let (name, props): (&str, Option<Value>) = AnalyticsEvent::TranscriptionCompleted {
+            mode: "smart".to_string(),
+            engine: "local".to_string(),
+            model: "parakeet".to_string(),
+            word_count: 12,
+            duration_seconds: 4.5,
+            llm_cleaned: true,
+        }
+        .into();
+        assert_eq!(name, "transcription_completed");
+        assert_eq!(
+            props,
+            Some(json!({
+                "mode": "smart",
+                "engine": "local",
+                "model": "parakeet",
+                "word_count": 12,
+                "llm_cleaned": true,
+                "duration_secs": 4.5
+            }))
+        );
+
+        let (name, props): (&str, Option<Value>) = AnalyticsEvent::ModelSwitched {
+            from: "parakeet".to_string(),
+            to: "whisper".to_string(),
+        }
+        .into();
+        assert_eq!(name, "model_switched");
+        assert_eq!(props, Some(json!({ "from": "parakeet", "to": "whisper" })));
+    }
 }
@@ -6,8 +6,13 @@ use arboard::{Clipboard, ImageData, SetExtApple};
 use core_graphics::event::{CGEvent, CGEventFlags, CGEventTapLocation, CGKeyCode};
 #[cfg(target_os = "macos")]
 use core_graphics::event_source::{CGEventSource, CGEventSourceStateID};
-#[cfg(target_os = "macos")]
+#[cfg(any(target_os = "macos", target_os = "windows"))]
 use std::{thread, time::Duration};
+#[cfg(target_os = "windows")]
+use windows::Win32::UI::Input::KeyboardAndMouse::{
+    SendInput, INPUT, INPUT_0, INPUT_KEYBOARD, KEYBDINPUT, KEYBD_EVENT_FLAGS, KEYEVENTF_KEYUP,
+    VIRTUAL_KEY, VK_CONTROL, VK_V,
+};
 
 #[cfg(target_os = "macos")]
 pub fn paste_text(text: &str) -> Result<()> {
@@ -113,7 +118,127 @@ fn send_paste_keystroke() -> Result<()> {
     Ok(())
 }
 
-#[cfg(not(target_os = "macos"))]
+/// Types `text` into the focused app as individual key events, leaving the clipboard untouched.
+#[cfg(target_os = "macos")]
+pub fn paste_via_key_events(text: &str) -> Result<()> {
+    // macOS virtual key codes for keys that don't insert a character on their own
+    const RETURN_KEY: CGKeyCode = 36;
+    const TAB_KEY: CGKeyCode = 48;
+
+    let source = CGEventSource::new(CGEventSourceStateID::CombinedSessionState)
+        .map_err(|_| anyhow!("Failed to create CGEventSource"))?;
+
+    for ch in text.chars() {
+        let (key_code, unicode) = match ch {
+            '\n' | '\r' => (RETURN_KEY, None),
+            '\t' => (TAB_KEY, None),
+            _ => (0, Some(ch.to_string())),
+        };
+
+        for key_down in [true, false] {
+            let event = CGEvent::new_keyboard_event(source.clone(), key_code, key_down)
+                .map_err(|_| anyhow!("Failed to create key event"))?;
+            if let Some(unicode) = &unicode {
+                event.set_string(unicode);
+            }
+            event.post(CGEventTapLocation::HID);
+        }
+
+        // Some apps drop characters when events arrive back to back
+        thread::sleep(Duration::from_millis(2));
+    }
+
+    Ok(())
+}
+
+#[cfg(target_os = "windows")]
+pub fn paste_text(text: &str) -> Result<()> {
+    let mut clipboard =
+        arboard::Clipboard::new().map_err(|e| anyhow!("Failed to access clipboard: {e}"))?;
+
+    let previous_text = clipboard.get_text().ok();
+    let previous_image = clipboard.get_image().ok().map(|img| img.to_owned());
+
+    clipboard
+        .set_text(text.to_string())
+        .map_err(|e| anyhow!("Failed to set clipboard: {e}"))?;
+
+    let paste_result = send_paste_keystroke();
+
+    // Give the focused app time to read the clipboard before it's put back
+    thread::sleep(Duration::from_millis(50));
+
+    if let Some(image) = previous_image {
+        let _ = clipboard.set_image(image);
+    } else if let Some(text) = previous_text {
+        let _ = clipboard.set_text(text);
+    } else {
+        let _ = clipboard.clear();
+    }
+
+    paste_result
+}
+
+/// Simulates pressing Ctrl+V to trigger a paste action.
+#[cfg(target_os = "windows")]
+fn send_paste_keystroke() -> Result<()> {
+    fn key(vk: VIRTUAL_KEY, flags: KEYBD_EVENT_FLAGS) -> INPUT {
+        INPUT {
+            r#type: INPUT_KEYBOARD,
+            Anonymous: INPUT_0 {
+                ki: KEYBDINPUT {
+                    wVk: vk,
+                    wScan: 0,
+                    dwFlags: flags,
+                    time: 0,
+                    dwExtraInfo: 0,
+                },
+            },
+        }
+    }
+
+    let inputs = [
+        key(VK_CONTROL, KEYBD_EVENT_FLAGS(0)),
+        key(VK_V, KEYBD_EVENT_FLAGS(0)),
+        key(VK_V, KEYEVENTF_KEYUP),
+        key(VK_CONTROL, KEYEVENTF_KEYUP),
+    ];
+
+    let sent = unsafe { SendInput(&inputs, std::mem::size_of::<INPUT>() as i32) };
+    if sent as usize != inputs.len() {
+        return Err(anyhow!("Failed to send paste keystroke"));
+    }
+    Ok(())
+}
+
+/// Places text on the system clipboard without pasting it.
+pub fn copy_text(text: &str) -> Result<()> {
+    let mut clipboard =
+        arboard::Clipboard::new().map_err(|e| anyhow!("Failed to access clipboard: {e}"))?;
+    clipboard
+        .set_text(text.to_string())
+        .map_err(|e| anyhow!("Failed to set clipboard: {e}"))
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
 pub fn paste_text(_text: &str) -> Result<()> {
+    Err(anyhow!(
+        "Assistive paste is only supported on macOS and Windows"
+    ))
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn paste_via_key_events(_text: &str) -> Result<()> {
     Err(anyhow!("Assistive paste is only supported on macOS"))
 }
+
+#[cfg(all(test, target_os = "windows"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paste_text_does_not_panic() {
+        // No focused window or clipboard on CI, so only a clean return is expected
+        let _ = paste_text("test");
+    }
+}
@@ -1,6 +1,25 @@
-use anyhow::Result;
-use cpal::traits::{DeviceTrait, HostTrait};
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Context, Result};
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{FromSample, Sample, SampleFormat, SizedSample, Stream};
+use crossbeam_channel::{bounded, RecvTimeoutError, Sender};
 use serde::Serialize;
+use tracing::error;
+
+/// How often the level meter reports, roughly 10 times a second.
+const LEVEL_METER_INTERVAL: Duration = Duration::from_millis(100);
+/// Time constant of the peak level's exponential release.
+const PEAK_RELEASE: Duration = Duration::from_secs(2);
+/// Level reported for silence, in dBFS.
+const SILENCE_DB: f32 = -100.0;
+/// Sample rate the speech models take audio in at.
+const TRANSCRIPTION_SAMPLE_RATE: u32 = 16_000;
 
 #[derive(Debug, Serialize, Clone)]
 pub struct DeviceInfo {
@@ -9,6 +28,329 @@ pub struct DeviceInfo {
     pub is_default: bool,
 }
 
+/// An input device along with the formats it can capture in.
+#[derive(Debug, Serialize, Clone)]
+pub struct DeviceCapabilities {
+    pub name: String,
+    pub id: String,
+    pub is_default: bool,
+    /// Distinct sample rates across the device's input configs, ascending.
+    pub supported_sample_rates: Vec<u32>,
+    pub max_channels: u16,
+}
+
+impl DeviceCapabilities {
+    /// Whether audio from this device has to be resampled before transcription.
+    pub fn needs_resampling(&self) -> bool {
+        self.supported_sample_rates
+            .first()
+            .is_some_and(|&rate| rate != TRANSCRIPTION_SAMPLE_RATE)
+    }
+}
+
+/// Whether the configured microphone can be used for the next recording.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MicrophoneStatus {
+    Available,
+    /// The configured device is missing; carries the name of the device used instead.
+    Unavailable(String),
+    NotConfigured,
+}
+
+/// Check the configured device against the currently connected inputs.
+pub fn microphone_status(device_id: Option<&str>) -> MicrophoneStatus {
+    let Some(device_id) = device_id.filter(|id| !id.is_empty()) else {
+        return MicrophoneStatus::NotConfigured;
+    };
+
+    let host = cpal::default_host();
+    let available = host
+        .input_devices()
+        .map(|mut devices| devices.any(|d| d.name().map(|n| n == device_id).unwrap_or(false)))
+        .unwrap_or(false);
+    if available {
+        return MicrophoneStatus::Available;
+    }
+
+    let fallback = host
+        .default_input_device()
+        .and_then(|d| d.name().ok())
+        .unwrap_or_else(|| "system default".to_string());
+    MicrophoneStatus::Unavailable(fallback)
+}
+
+/// Length of an MP3 file in seconds, read from its frame headers without decoding audio.
+pub fn get_audio_duration_from_mp3(path: &Path) -> Result<f32> {
+    let data = fs::read(path)
+        .with_context(|| format!("Failed to open audio file at {}", path.display()))?;
+    mp3_duration_seconds(&data)
+        .map(|seconds| seconds as f32)
+        .ok_or_else(|| anyhow!("No MP3 frames found in {}", path.display()))
+}
+
+/// The parts of an MPEG Layer III frame header needed to measure a file.
+struct Mp3FrameHeader {
+    sample_rate: u32,
+    samples: u32,
+    /// Whole frame length in bytes, header included.
+    len: usize,
+    /// Bytes of side information after the header, where a Xing/Info tag would start.
+    side_info_len: usize,
+}
+
+fn parse_mp3_frame_header(bytes: &[u8]) -> Option<Mp3FrameHeader> {
+    const MPEG1_BITRATES: [u32; 15] = [
+        0, 32, 40, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320,
+    ];
+    const MPEG2_BITRATES: [u32; 15] =
+        [0, 8, 16, 24, 32, 40, 48, 56, 64, 80, 96, 112, 128, 144, 160];
+    const MPEG1_SAMPLE_RATES: [u32; 3] = [44_100, 48_000, 32_000];
+
+    let header = bytes.get(..4)?;
+    if header[0] != 0xFF || header[1] & 0xE0 != 0xE0 {
+        return None;
+    }
+    // Version 3 is MPEG-1, 2 is MPEG-2, 0 is MPEG-2.5 and 1 is reserved. Only Layer III
+    // (layer bits 01) is recorded.
+    let version = (header[1] >> 3) & 0b11;
+    if version == 1 || (header[1] >> 1) & 0b11 != 1 {
+        return None;
+    }
+    let mpeg1 = version == 3;
+    let bitrate_index = usize::from(header[2] >> 4);
+    let sample_rate_index = usize::from((header[2] >> 2) & 0b11);
+    // Free-format (0) and invalid (15) bitrates can't be measured from the header
+    if bitrate_index == 0 || bitrate_index == 15 || sample_rate_index == 3 {
+        return None;
+    }
+
+    let bitrate = if mpeg1 {
+        MPEG1_BITRATES[bitrate_index]
+    } else {
+        MPEG2_BITRATES[bitrate_index]
+    } * 1000;
+    let sample_rate = MPEG1_SAMPLE_RATES[sample_rate_index]
+        >> match version {
+            3 => 0,
+            2 => 1,
+            _ => 2,
+        };
+    let samples = if mpeg1 { 1152 } else { 576 };
+    let padding = u32::from((header[2] >> 1) & 1);
+    let mono = header[3] >> 6 == 0b11;
+    let side_info_len = match (mpeg1, mono) {
+        (true, true) => 17,
+        (true, false) => 32,
+        (false, true) => 9,
+        (false, false) => 17,
+    };
+
+    Some(Mp3FrameHeader {
+        sample_rate,
+        samples,
+        len: (samples / 8 * bitrate / sample_rate + padding) as usize,
+        side_info_len,
+    })
+}
+
+/// Length in seconds of the MP3 stream in `data`. Uses the frame count from a Xing/Info
+/// tag when the first frame carries one, and otherwise adds up the frames one header at a
+/// time. Returns `None` when no frames are found.
+fn mp3_duration_seconds(data: &[u8]) -> Option<f64> {
+    let mut offset = 0;
+    // Skip an ID3v2 tag: a 10-byte header with a synchsafe size, plus an optional footer
+    if data.len() >= 10 && &data[..3] == b"ID3" {
+        let size = data[6..10]
+            .iter()
+            .fold(0usize, |size, byte| (size << 7) | usize::from(byte & 0x7F));
+        let footer = if data[5] & 0x10 != 0 { 10 } else { 0 };
+        offset = 10 + size + footer;
+    }
+
+    let mut first_frame = true;
+    let mut seconds = 0.0f64;
+    let mut found = false;
+    while offset + 4 <= data.len() {
+        let Some(header) = parse_mp3_frame_header(&data[offset..]) else {
+            offset += 1;
+            continue;
+        };
+        if header.len < 4 || offset + header.len > data.len() {
+            offset += 1;
+            continue;
+        }
+
+        if first_frame {
+            first_frame = false;
+            let tag_start = offset + 4 + header.side_info_len;
+            if let Some(tag) = data.get(tag_start..tag_start + 12) {
+                if &tag[..4] == b"Xing" || &tag[..4] == b"Info" {
+                    let flags = u32::from_be_bytes([tag[4], tag[5], tag[6], tag[7]]);
+                    if flags & 1 != 0 {
+                        let frames = u32::from_be_bytes([tag[8], tag[9], tag[10], tag[11]]);
+                        return Some(
+                            f64::from(frames) * f64::from(header.samples)
+                                / f64::from(header.sample_rate),
+                        );
+                    }
+                    // The tag frame holds no audio
+                    offset += header.len;
+                    continue;
+                }
+            }
+        }
+
+        seconds += f64::from(header.samples) / f64::from(header.sample_rate);
+        found = true;
+        offset += header.len;
+    }
+
+    found.then_some(seconds)
+}
+
+/// Reports the microphone level from its own input stream, independent of the recorder.
+/// The stream runs on a dedicated thread and is closed when the meter is dropped.
+pub struct LevelMeter {
+    stop: Sender<()>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl LevelMeter {
+    /// Open `device_id` (or the default input) and call `on_level(db, peak_db)` about
+    /// every 100ms until the meter is dropped.
+    pub fn start<F>(device_id: Option<String>, on_level: F) -> Result<Self>
+    where
+        F: Fn(f32, f32) + Send + 'static,
+    {
+        let (stop_tx, stop_rx) = bounded::<()>(1);
+        let (ready_tx, ready_rx) = bounded::<Result<()>>(1);
+
+        let thread = std::thread::Builder::new()
+            .name("glimpse-level-meter".into())
+            .spawn(move || {
+                // Loudest buffer RMS since the last report, as `f32` bits
+                let loudest = Arc::new(AtomicU32::new(0));
+                let stream = match open_level_stream(device_id.as_deref(), loudest.clone()) {
+                    Ok(stream) => stream,
+                    Err(err) => {
+                        let _ = ready_tx.send(Err(err));
+                        return;
+                    }
+                };
+                let _ = ready_tx.send(Ok(()));
+
+                let mut peak = 0.0f32;
+                let mut last_report = Instant::now();
+                while let Err(RecvTimeoutError::Timeout) =
+                    stop_rx.recv_timeout(LEVEL_METER_INTERVAL)
+                {
+                    let level = f32::from_bits(loudest.swap(0, Ordering::Relaxed));
+                    peak = release_peak(peak, last_report.elapsed()).max(level);
+                    last_report = Instant::now();
+                    on_level(amplitude_to_db(level), amplitude_to_db(peak));
+                }
+                drop(stream);
+            })
+            .context("Failed to spawn level meter thread")?;
+
+        match ready_rx.recv() {
+            Ok(Ok(())) => Ok(Self {
+                stop: stop_tx,
+                thread: Some(thread),
+            }),
+            Ok(Err(err)) => {
+                let _ = thread.join();
+                Err(err)
+            }
+            Err(_) => {
+                let _ = thread.join();
+                Err(anyhow!(
+                    "Level meter thread exited before opening the stream"
+                ))
+            }
+        }
+    }
+}
+
+impl Drop for LevelMeter {
+    fn drop(&mut self) {
+        let _ = self.stop.try_send(());
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn open_level_stream(device_id: Option<&str>, loudest: Arc<AtomicU32>) -> Result<Stream> {
+    let host = cpal::default_host();
+    let device = match device_id {
+        Some(id) => host
+            .input_devices()
+            .context("Failed to list input devices")?
+            .find(|d| d.name().map(|n| n == id).unwrap_or(false))
+            .or_else(|| host.default_input_device())
+            .context("Selected device not found and no default available")?,
+        None => host
+            .default_input_device()
+            .context("No default input device found")?,
+    };
+
+    let config = device
+        .default_input_config()
+        .context("No supported input configuration found")?;
+    let format = config.sample_format();
+    let stream_config: cpal::StreamConfig = config.into();
+
+    let stream = match format {
+        SampleFormat::F32 => build_level_stream::<f32>(&device, &stream_config, loudest)?,
+        SampleFormat::I16 => build_level_stream::<i16>(&device, &stream_config, loudest)?,
+        SampleFormat::U16 => build_level_stream::<u16>(&device, &stream_config, loudest)?,
+        _ => return Err(anyhow!("Unsupported sample format")),
+    };
+    stream.play()?;
+    Ok(stream)
+}
+
+fn build_level_stream<T>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    loudest: Arc<AtomicU32>,
+) -> Result<Stream>
+where
+    T: SizedSample,
+    f32: FromSample<T>,
+{
+    let stream = device.build_input_stream(
+        config,
+        move |data: &[T], _| {
+            if data.is_empty() {
+                return;
+            }
+            let sum_squares: f32 = data
+                .iter()
+                .map(|&sample| f32::from_sample(sample).powi(2))
+                .sum();
+            let rms = (sum_squares / data.len() as f32).sqrt();
+            // Non-negative floats order the same as their bit patterns
+            loudest.fetch_max(rms.to_bits(), Ordering::Relaxed);
+        },
+        |err| error!("Level meter stream error: {err}"),
+        None,
+    )?;
+    Ok(stream)
+}
+
+fn amplitude_to_db(amplitude: f32) -> f32 {
+    if amplitude <= 0.0 {
+        return SILENCE_DB;
+    }
+    (20.0 * amplitude.log10()).max(SILENCE_DB)
+}
+
+fn release_peak(peak: f32, elapsed: Duration) -> f32 {
+    peak * (-elapsed.as_secs_f32() / PEAK_RELEASE.as_secs_f32()).exp()
+}
+
 #[tauri::command]
 pub fn list_input_devices() -> Result<Vec<DeviceInfo>, String> {
     let host = cpal::default_host();
@@ -46,3 +388,145 @@ pub fn list_input_devices() -> Result<Vec<DeviceInfo>, String> {
 
     Ok(result)
 }
+
+/// Like [`list_input_devices`], with the sample rates and channel counts of each device.
+pub fn list_devices_with_capabilities() -> Result<Vec<DeviceCapabilities>> {
+    let host = cpal::default_host();
+    let default_name = host.default_input_device().and_then(|d| d.name().ok());
+
+    let devices = host
+        .input_devices()
+        .context("Failed to list input devices")?;
+
+    let mut result = Vec::new();
+    for device in devices {
+        let Ok(name) = device.name() else {
+            continue;
+        };
+        // A device that can't report its formats can still be picked
+        let ranges = device
+            .supported_input_configs()
+            .map(|configs| {
+                configs
+                    .map(|c| (c.min_sample_rate().0, c.max_sample_rate().0, c.channels()))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        let (supported_sample_rates, max_channels) = summarize_config_ranges(ranges);
+
+        result.push(DeviceCapabilities {
+            id: name.clone(),
+            is_default: default_name.as_deref() == Some(&name),
+            name,
+            supported_sample_rates,
+            max_channels,
+        });
+    }
+
+    // Default first, then alphabetical
+    result.sort_by(|a, b| {
+        b.is_default
+            .cmp(&a.is_default)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    Ok(result)
+}
+
+#[tauri::command]
+pub fn list_input_devices_with_capabilities() -> Result<Vec<DeviceCapabilities>, String> {
+    list_devices_with_capabilities().map_err(|err| err.to_string())
+}
+
+/// Collapse `(min_rate, max_rate, channels)` config ranges into the distinct rates at
+/// their bounds and the highest channel count.
+fn summarize_config_ranges(ranges: impl IntoIterator<Item = (u32, u32, u16)>) -> (Vec<u32>, u16) {
+    let mut rates = Vec::new();
+    let mut max_channels = 0;
+    for (min_rate, max_rate, channels) in ranges {
+        rates.push(min_rate);
+        rates.push(max_rate);
+        max_channels = max_channels.max(channels);
+    }
+    rates.sort_unstable();
+    rates.dedup();
+    (rates, max_channels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_amplitude_to_db() {
+        assert_eq!(amplitude_to_db(1.0), 0.0);
+        assert!((amplitude_to_db(0.1) + 20.0).abs() < 0.001);
+        assert_eq!(amplitude_to_db(0.0), SILENCE_DB);
+        assert_eq!(amplitude_to_db(1e-9), SILENCE_DB);
+    }
+
+    #[test]
+    fn test_release_peak_decays_exponentially() {
+        assert_eq!(release_peak(0.5, Duration::ZERO), 0.5);
+        let after_release = release_peak(1.0, PEAK_RELEASE);
+        assert!((after_release - (-1.0f32).exp()).abs() < 0.001);
+        assert!(release_peak(1.0, Duration::from_secs(10)) < 0.01);
+    }
+
+    #[test]
+    fn test_mp3_duration_from_info_tag() {
+        let fixture =
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/silence_960ms.mp3");
+        let seconds = get_audio_duration_from_mp3(&fixture).unwrap();
+        assert!((seconds - 0.96).abs() < 0.001, "got {seconds}");
+    }
+
+    #[test]
+    fn test_mp3_duration_from_frame_headers() {
+        let fixture = fs::read(
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/silence_960ms.mp3"),
+        )
+        .unwrap();
+        // Drop the Info frame so every frame header has to be counted
+        let info_len = parse_mp3_frame_header(&fixture).unwrap().len;
+        let frames = &fixture[info_len..];
+        let seconds = mp3_duration_seconds(frames).unwrap();
+        assert!((seconds - 0.96).abs() < 0.001, "got {seconds}");
+
+        // An ID3v2 tag in front is skipped, even when it holds bytes that look like a sync
+        let mut tagged = b"ID3\x04\x00\x00\x00\x00\x00\x04\xFF\xFB\x54\xC4".to_vec();
+        tagged.extend_from_slice(frames);
+        let seconds = mp3_duration_seconds(&tagged).unwrap();
+        assert!((seconds - 0.96).abs() < 0.001, "got {seconds}");
+
+        assert_eq!(mp3_duration_seconds(b"not an mp3"), None);
+    }
+
+    #[test]
+    fn test_summarize_config_ranges() {
+        let (rates, channels) = summarize_config_ranges([
+            (48_000, 48_000, 2),
+            (44_100, 48_000, 1),
+            (16_000, 16_000, 1),
+        ]);
+        assert_eq!(rates, vec![16_000, 44_100, 48_000]);
+        assert_eq!(channels, 2);
+
+        assert_eq!(summarize_config_ranges([]), (Vec::new(), 0));
+    }
+
+    #[test]
+    fn test_needs_resampling() {
+        let device = |supported_sample_rates: Vec<u32>| DeviceCapabilities {
+            name: "Mic".to_string(),
+            id: "Mic".to_string(),
+            is_default: false,
+            supported_sample_rates,
+            max_channels: 1,
+        };
+        assert!(device(vec![48_000]).needs_resampling());
+        assert!(!device(vec![16_000, 48_000]).needs_resampling());
+        // Nothing reported, so nothing to warn about
+        assert!(!device(Vec::new()).needs_resampling());
+    }
+}
@@ -0,0 +1,143 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Local};
+
+use crate::storage::StorageManager;
+
+/// Saved transcriptions between automatic backups.
+pub const AUTO_BACKUP_INTERVAL: u32 = 10;
+
+/// History file written by versions before the SQLite store, backed up too when still around.
+const LEGACY_HISTORY_FILE_NAME: &str = "transcriptions.json";
+
+/// Check that `path` is an existing, writable folder outside `app_data_dir`, returning
+/// its canonical form.
+pub fn validate_backup_dir(path: &Path, app_data_dir: &Path) -> Result<PathBuf> {
+    let dir = path
+        .canonicalize()
+        .with_context(|| format!("Backup folder does not exist: {}", path.display()))?;
+    if !dir.is_dir() {
+        bail!("Backup path is not a folder: {}", dir.display());
+    }
+
+    // Backups next to the data they protect don't survive losing that folder
+    if let Ok(app_data_dir) = app_data_dir.canonicalize() {
+        if dir.starts_with(&app_data_dir) {
+            bail!("Backup folder must be outside the app data directory");
+        }
+    }
+
+    // Permission bits don't reflect read-only mounts or sandboxing, so try a real write
+    let probe = dir.join(format!(".glimpse-backup-check-{}", std::process::id()));
+    fs::write(&probe, b"")
+        .with_context(|| format!("Backup folder is not writable: {}", dir.display()))?;
+    let _ = fs::remove_file(&probe);
+
+    Ok(dir)
+}
+
+/// Snapshot the transcription database, and the legacy JSON history if present, into
+/// `backup_dir` under names stamped with `now`. Returns the path of the database copy.
+pub fn write_backup(
+    storage: &StorageManager,
+    app_data_dir: &Path,
+    backup_dir: &Path,
+    now: DateTime<Local>,
+) -> Result<PathBuf> {
+    // No colons, which Windows doesn't allow in file names
+    let stamp = now.format("%Y-%m-%dT%H-%M-%S");
+
+    let db_dest = backup_dir.join(format!("transcriptions_{stamp}.db"));
+    if db_dest.exists() {
+        bail!("A backup already exists at {}", db_dest.display());
+    }
+    storage.backup_to(&db_dest)?;
+
+    let legacy_history = app_data_dir.join(LEGACY_HISTORY_FILE_NAME);
+    if legacy_history.is_file() {
+        let json_dest = backup_dir.join(format!("transcriptions_{stamp}.json"));
+        fs::copy(&legacy_history, &json_dest)
+            .with_context(|| format!("Failed to copy {}", legacy_history.display()))?;
+    }
+
+    Ok(db_dest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{TranscriptionMetadata, TranscriptionStatus};
+    use uuid::Uuid;
+
+    fn temp_dir(label: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("glimpse-{label}-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_validate_backup_dir() {
+        let app_data = temp_dir("app-data");
+        let backups = temp_dir("backups");
+
+        assert!(validate_backup_dir(&app_data.join("missing"), &app_data).is_err());
+
+        let file = backups.join("not-a-folder.txt");
+        fs::write(&file, b"").unwrap();
+        assert!(validate_backup_dir(&file, &app_data).is_err());
+
+        assert!(validate_backup_dir(&app_data, &app_data).is_err());
+        let nested = app_data.join("backups");
+        fs::create_dir_all(&nested).unwrap();
+        assert!(validate_backup_dir(&nested, &app_data).is_err());
+
+        let valid = validate_backup_dir(&backups, &app_data).unwrap();
+        assert_eq!(valid, backups.canonicalize().unwrap());
+        // The write probe is cleaned up again
+        assert_eq!(fs::read_dir(&backups).unwrap().count(), 1);
+
+        let _ = fs::remove_dir_all(&app_data);
+        let _ = fs::remove_dir_all(&backups);
+    }
+
+    #[test]
+    fn test_write_backup_copies_database_and_legacy_history() {
+        let app_data = temp_dir("app-data");
+        let backups = temp_dir("backups");
+
+        let storage = StorageManager::new(app_data.join("transcriptions.db")).unwrap();
+        storage
+            .save_transcription(
+                "backed up".to_string(),
+                String::new(),
+                TranscriptionStatus::Success,
+                None,
+                None,
+                TranscriptionMetadata::default(),
+            )
+            .unwrap();
+        fs::write(app_data.join(LEGACY_HISTORY_FILE_NAME), b"[]").unwrap();
+
+        let now = Local::now();
+        let db_copy = write_backup(&storage, &app_data, &backups, now).unwrap();
+        let stamp = now.format("%Y-%m-%dT%H-%M-%S");
+        assert_eq!(db_copy, backups.join(format!("transcriptions_{stamp}.db")));
+        assert_eq!(
+            fs::read(backups.join(format!("transcriptions_{stamp}.json"))).unwrap(),
+            b"[]"
+        );
+
+        let restored = StorageManager::new(db_copy).unwrap();
+        let records = restored.get_all();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].text, "backed up");
+
+        // A second backup in the same second must not overwrite the first
+        assert!(write_backup(&storage, &app_data, &backups, now).is_err());
+
+        let _ = fs::remove_dir_all(&app_data);
+        let _ = fs::remove_dir_all(&backups);
+    }
+}
@@ -0,0 +1,240 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use serde::Serialize;
+
+use crate::local_transcription::LocalTranscriber;
+use crate::settings::{self, SettingsStore, UserSettings};
+use crate::{model_manager, APP_IDENTIFIER};
+
+const BATCH_FLAG: &str = "--batch-transcribe";
+pub(crate) const MAX_AUDIO_SIZE_BYTES: u64 = 200 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchOutputFormat {
+    Csv,
+    Json,
+}
+
+#[derive(Debug, Clone)]
+pub struct BatchArgs {
+    pub dir: PathBuf,
+    pub model: Option<String>,
+    pub format: BatchOutputFormat,
+    pub output_file: Option<PathBuf>,
+}
+
+#[derive(Serialize)]
+struct BatchProgress<'a> {
+    file: &'a str,
+    status: &'a str,
+    words: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[derive(Serialize)]
+struct BatchResult {
+    file: String,
+    text: String,
+    words: u32,
+}
+
+impl BatchArgs {
+    /// Parse `--batch-transcribe <dir> [--model <key>] [--output <csv|json>] [--output-file <path>]`.
+    /// Returns `None` when the app was launched normally.
+    pub fn from_env() -> Option<Result<Self>> {
+        let args: Vec<String> = std::env::args().skip(1).collect();
+        if !args.iter().any(|arg| arg == BATCH_FLAG) {
+            return None;
+        }
+        Some(Self::parse(&args))
+    }
+
+    fn parse(args: &[String]) -> Result<Self> {
+        let mut dir = None;
+        let mut model = None;
+        let mut format = BatchOutputFormat::Json;
+        let mut output_file = None;
+
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            let mut value = || {
+                iter.next()
+                    .cloned()
+                    .ok_or_else(|| anyhow!("Missing value for {arg}"))
+            };
+            match arg.as_str() {
+                BATCH_FLAG => dir = Some(PathBuf::from(value()?)),
+                "--model" => model = Some(value()?),
+                "--output" => {
+                    format = match value()?.to_ascii_lowercase().as_str() {
+                        "csv" => BatchOutputFormat::Csv,
+                        "json" => BatchOutputFormat::Json,
+                        other => return Err(anyhow!("Unsupported output format: {other}")),
+                    }
+                }
+                "--output-file" => output_file = Some(PathBuf::from(value()?)),
+                _ => {}
+            }
+        }
+
+        let dir = dir.ok_or_else(|| anyhow!("{BATCH_FLAG} requires a directory"))?;
+        if !dir.is_dir() {
+            return Err(anyhow!("{} is not a directory", dir.display()));
+        }
+
+        Ok(Self {
+            dir,
+            model,
+            format,
+            output_file,
+        })
+    }
+}
+
+/// Transcribe every audio file under `args.dir` and return the process exit code.
+/// Runs before Tauri starts, so settings and models are found the way Tauri resolves its
+/// app directories.
+pub fn run(args: &BatchArgs) -> i32 {
+    match run_inner(args) {
+        Ok(()) => 0,
+        Err(err) => {
+            eprintln!("Batch transcription failed: {err}");
+            1
+        }
+    }
+}
+
+fn run_inner(args: &BatchArgs) -> Result<()> {
+    let config_dir = dirs::config_dir()
+        .context("Unable to resolve config directory")?
+        .join(APP_IDENTIFIER);
+    let data_dir = dirs::data_dir()
+        .context("Unable to resolve app data directory")?
+        .join(APP_IDENTIFIER);
+
+    let settings = load_settings(settings::db_path_in(config_dir));
+    let model_key = args
+        .model
+        .clone()
+        .unwrap_or_else(|| settings.local_model.clone());
+    let model_dir = model_manager::model_dir_in(data_dir, &model_key);
+    let model = model_manager::ensure_model_ready_in(&model_dir, &model_key)?;
+    let dictionary_prompt = crate::dictionary_prompt_for_model(&model, &settings);
+    let transcriber = LocalTranscriber::new();
+
+    let mut files = Vec::new();
+    collect_audio_files(&args.dir, &mut files)?;
+    files.sort();
+
+    let mut results = Vec::new();
+    for path in files {
+        let display = path.display().to_string();
+
+        let size = path.metadata().map(|meta| meta.len()).unwrap_or(0);
+        if size > MAX_AUDIO_SIZE_BYTES {
+            report_progress(&display, "error", 0, Some("File too large".to_string()));
+            continue;
+        }
+
+        let outcome = crate::load_audio_for_transcription(&path).and_then(|(samples, rate)| {
+            transcriber.transcribe(
+                &model,
+                &samples,
+                rate,
+                dictionary_prompt.as_deref(),
+                settings.language.code(),
+            )
+        });
+
+        match outcome {
+            Ok(result) => {
+                let text =
+                    crate::apply_replacements(&result.transcript, settings.active_replacements());
+                let words = crate::count_words(&text);
+                report_progress(&display, "ok", words, None);
+                results.push(BatchResult {
+                    file: display,
+                    text,
+                    words,
+                });
+            }
+            Err(err) => report_progress(&display, "error", 0, Some(err.to_string())),
+        }
+    }
+
+    let output = match args.format {
+        BatchOutputFormat::Json => serde_json::to_string_pretty(&results)?,
+        BatchOutputFormat::Csv => {
+            let mut csv = String::from("file,text,words\n");
+            for result in &results {
+                csv.push_str(&format!(
+                    "{},{},{}\n",
+                    crate::csv_field(&result.file),
+                    crate::csv_field(&result.text),
+                    result.words
+                ));
+            }
+            csv
+        }
+    };
+
+    match &args.output_file {
+        Some(path) => std::fs::write(path, output)
+            .with_context(|| format!("Failed to write results to {}", path.display()))?,
+        None => {
+            let mut stdout = std::io::stdout().lock();
+            stdout.write_all(output.as_bytes())?;
+            stdout.flush()?;
+        }
+    }
+
+    Ok(())
+}
+
+/// The app's saved settings, or the defaults when there are none yet.
+fn load_settings(path: PathBuf) -> UserSettings {
+    match SettingsStore::open(path).and_then(|store| store.load()) {
+        Ok(settings) => settings,
+        Err(err) => {
+            eprintln!("Using default settings, failed to load saved ones: {err}");
+            UserSettings::default()
+        }
+    }
+}
+
+fn collect_audio_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in std::fs::read_dir(dir)
+        .with_context(|| format!("Failed to read directory {}", dir.display()))?
+    {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_audio_files(&path, files)?;
+            continue;
+        }
+        let is_audio = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| matches!(ext.to_ascii_lowercase().as_str(), "mp3" | "wav"))
+            .unwrap_or(false);
+        if is_audio {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Progress goes to stderr as JSON lines so stdout stays clean for the results.
+fn report_progress(file: &str, status: &str, words: u32, error: Option<String>) {
+    let progress = BatchProgress {
+        file,
+        status,
+        words,
+        error,
+    };
+    if let Ok(line) = serde_json::to_string(&progress) {
+        eprintln!("{line}");
+    }
+}
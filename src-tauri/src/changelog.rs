@@ -0,0 +1,187 @@
+use serde::Serialize;
+
+const CHANGELOG: &str = include_str!("../../CHANGELOG.md");
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ChangelogEntry {
+    pub version: String,
+    /// Release date as written in the changelog, empty for unreleased entries.
+    pub date: String,
+    pub sections: Vec<ChangelogSection>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ChangelogSection {
+    pub title: String,
+    pub items: Vec<String>,
+}
+
+/// Release notes for the most recent `max_versions` versions, newest first.
+#[tauri::command]
+pub fn get_changelog(max_versions: u32) -> Vec<ChangelogEntry> {
+    parse_changelog(CHANGELOG, max_versions as usize)
+}
+
+/// Parse a Keep a Changelog style document (`## [x.y.z] - YYYY-MM-DD` followed by
+/// `### Added`-style sections of bullet items). Anything it doesn't recognise is skipped.
+pub fn parse_changelog(markdown: &str, max_versions: usize) -> Vec<ChangelogEntry> {
+    let mut entries: Vec<ChangelogEntry> = Vec::new();
+    // Lines under an unrecognised `##` heading belong to no entry
+    let mut in_entry = false;
+
+    for line in markdown.lines() {
+        let trimmed = line.trim();
+
+        if let Some(header) = trimmed.strip_prefix("## ") {
+            if entries.len() == max_versions {
+                break;
+            }
+            match parse_version_header(header) {
+                Some((version, date)) => {
+                    entries.push(ChangelogEntry {
+                        version,
+                        date,
+                        sections: Vec::new(),
+                    });
+                    in_entry = true;
+                }
+                None => in_entry = false,
+            }
+            continue;
+        }
+
+        if !in_entry {
+            continue;
+        }
+        let Some(entry) = entries.last_mut() else {
+            continue;
+        };
+
+        if let Some(title) = trimmed.strip_prefix("### ") {
+            entry.sections.push(ChangelogSection {
+                title: title.trim().to_string(),
+                items: Vec::new(),
+            });
+        } else if let Some(item) = trimmed
+            .strip_prefix("- ")
+            .or_else(|| trimmed.strip_prefix("* "))
+        {
+            if entry.sections.is_empty() {
+                entry.sections.push(ChangelogSection {
+                    title: "Changes".to_string(),
+                    items: Vec::new(),
+                });
+            }
+            if let Some(section) = entry.sections.last_mut() {
+                section.items.push(item.trim().to_string());
+            }
+        } else if !trimmed.is_empty() && line.starts_with([' ', '\t']) {
+            // Wrapped continuation of the previous bullet
+            if let Some(last) = entry
+                .sections
+                .last_mut()
+                .and_then(|section| section.items.last_mut())
+            {
+                last.push(' ');
+                last.push_str(trimmed);
+            }
+        }
+    }
+
+    entries
+}
+
+fn parse_version_header(header: &str) -> Option<(String, String)> {
+    let header = header.trim();
+    let (version, rest) = match header.strip_prefix('[') {
+        Some(bracketed) => {
+            let end = bracketed.find(']')?;
+            (&bracketed[..end], &bracketed[end + 1..])
+        }
+        None => {
+            // Unbracketed headings only count when they start with a version number
+            let (version, rest) = header.split_once(' ').unwrap_or((header, ""));
+            if !version
+                .trim_start_matches('v')
+                .starts_with(|c: char| c.is_ascii_digit())
+            {
+                return None;
+            }
+            (version, rest)
+        }
+    };
+
+    let version = version.trim().trim_start_matches('v');
+    if version.is_empty() {
+        return None;
+    }
+
+    let date = rest.trim().trim_start_matches(['-', '–']).trim();
+    Some((version.to_string(), date.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "# Changelog
+
+Intro text that isn't part of any release.
+
+## [Unreleased]
+
+### Added
+- Something new
+
+## [1.2.0] - 2024-05-01
+
+### Added
+- Feature one
+- Feature two that wraps
+  onto a second line
+
+### Fixed
+* A bug
+
+## [1.1.0] - 2024-04-01
+
+- Loose item without a section
+";
+
+    #[test]
+    fn test_parse_changelog() {
+        let entries = parse_changelog(SAMPLE, 10);
+        assert_eq!(entries.len(), 3);
+
+        assert_eq!(entries[0].version, "Unreleased");
+        assert_eq!(entries[0].date, "");
+
+        let release = &entries[1];
+        assert_eq!(release.version, "1.2.0");
+        assert_eq!(release.date, "2024-05-01");
+        assert_eq!(release.sections.len(), 2);
+        assert_eq!(release.sections[0].title, "Added");
+        assert_eq!(
+            release.sections[0].items,
+            vec!["Feature one", "Feature two that wraps onto a second line"]
+        );
+        assert_eq!(release.sections[1].items, vec!["A bug"]);
+
+        assert_eq!(entries[2].sections[0].title, "Changes");
+    }
+
+    #[test]
+    fn test_parse_changelog_limits_versions() {
+        let entries = parse_changelog(SAMPLE, 2);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].version, "1.2.0");
+
+        assert!(parse_changelog(SAMPLE, 0).is_empty());
+        assert!(parse_changelog("not a changelog", 5).is_empty());
+    }
+
+    #[test]
+    fn test_bundled_changelog_parses() {
+        assert!(!get_changelog(1).is_empty());
+    }
+}
@@ -12,6 +12,7 @@ use std::sync::OnceLock;
 const PBKDF2_ITERATIONS: u32 = 100_000;
 const NONCE_SIZE: usize = 12;
 const SALT: &[u8] = b"glimpse_api_key_v1";
+const PASSWORD_SALT_SIZE: usize = 16;
 
 static CACHED_KEY: OnceLock<(String, [u8; 32])> = OnceLock::new();
 
@@ -48,6 +49,25 @@ pub fn get_hardware_uuid() -> Option<String> {
 
 #[cfg(target_os = "windows")]
 pub fn get_hardware_uuid() -> Option<String> {
+    machine_guid().or_else(wmic_uuid)
+}
+
+#[cfg(target_os = "windows")]
+fn machine_guid() -> Option<String> {
+    use winreg::enums::HKEY_LOCAL_MACHINE;
+    use winreg::RegKey;
+
+    let cryptography = RegKey::predef(HKEY_LOCAL_MACHINE)
+        .open_subkey("SOFTWARE\\Microsoft\\Cryptography")
+        .ok()?;
+    let guid: String = cryptography.get_value("MachineGuid").ok()?;
+    let guid = guid.trim();
+    (!guid.is_empty()).then(|| guid.to_string())
+}
+
+/// `wmic` is deprecated and missing on newer Windows 11 installs, so this is only a fallback.
+#[cfg(target_os = "windows")]
+fn wmic_uuid() -> Option<String> {
     let output = Command::new("wmic")
         .args(["csproduct", "get", "uuid"])
         .output()
@@ -63,6 +83,18 @@ pub fn get_hardware_uuid() -> Option<String> {
     None
 }
 
+/// Identifiers earlier versions derived keys from, for decrypting values saved before the
+/// current identifier was adopted.
+#[cfg(target_os = "windows")]
+pub fn legacy_hardware_uuids() -> Vec<String> {
+    wmic_uuid().into_iter().collect()
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn legacy_hardware_uuids() -> Vec<String> {
+    Vec::new()
+}
+
 #[cfg(target_os = "linux")]
 pub fn get_hardware_uuid() -> Option<String> {
     std::fs::read_to_string("/etc/machine-id")
@@ -127,6 +159,47 @@ pub fn decrypt(encrypted: &str, hardware_uuid: &str) -> Result<String, String> {
     String::from_utf8(plaintext).map_err(|e| format!("Invalid UTF-8 in decrypted data: {}", e))
 }
 
+/// Encrypt with a key derived from `password` and a random salt. The output is
+/// `salt || nonce || ciphertext`, so it can be decrypted with just the password.
+pub fn encrypt_with_password(plaintext: &[u8], password: &str) -> Result<Vec<u8>, String> {
+    let mut salt = [0u8; PASSWORD_SALT_SIZE];
+    rand::thread_rng().fill_bytes(&mut salt);
+    let key = pbkdf2_hmac_array::<Sha256, 32>(password.as_bytes(), &salt, PBKDF2_ITERATIONS);
+
+    let cipher =
+        Aes256Gcm::new_from_slice(&key).map_err(|e| format!("Failed to create cipher: {}", e))?;
+
+    let mut nonce_bytes = [0u8; NONCE_SIZE];
+    rand::thread_rng().fill_bytes(&mut nonce_bytes);
+    let nonce = Nonce::from_slice(&nonce_bytes);
+
+    let ciphertext = cipher
+        .encrypt(nonce, plaintext)
+        .map_err(|e| format!("Encryption failed: {}", e))?;
+
+    let mut combined = salt.to_vec();
+    combined.extend(nonce_bytes);
+    combined.extend(ciphertext);
+    Ok(combined)
+}
+
+pub fn decrypt_with_password(data: &[u8], password: &str) -> Result<Vec<u8>, String> {
+    if data.len() < PASSWORD_SALT_SIZE + NONCE_SIZE {
+        return Err("Ciphertext too short".to_string());
+    }
+
+    let (salt, rest) = data.split_at(PASSWORD_SALT_SIZE);
+    let (nonce_bytes, ciphertext) = rest.split_at(NONCE_SIZE);
+    let key = pbkdf2_hmac_array::<Sha256, 32>(password.as_bytes(), salt, PBKDF2_ITERATIONS);
+
+    let cipher =
+        Aes256Gcm::new_from_slice(&key).map_err(|e| format!("Failed to create cipher: {}", e))?;
+
+    cipher
+        .decrypt(Nonce::from_slice(nonce_bytes), ciphertext)
+        .map_err(|_| "Decryption failed - wrong password or corrupted data".to_string())
+}
+
 pub fn looks_encrypted(value: &str) -> bool {
     if value.is_empty() || value.len() < 40 {
         return false;
@@ -223,6 +296,31 @@ mod tests {
         assert_eq!(decrypted, plaintext_key);
     }
 
+    #[test]
+    fn test_password_roundtrip() {
+        let plaintext = b"{\"llm_api_key\":\"sk-secret\"}";
+
+        let encrypted = encrypt_with_password(plaintext, "hunter2").expect("encryption failed");
+        assert_ne!(
+            encrypted,
+            encrypt_with_password(plaintext, "hunter2").expect("encryption failed"),
+            "salt and nonce should be random"
+        );
+
+        let decrypted = decrypt_with_password(&encrypted, "hunter2").expect("decryption failed");
+        assert_eq!(decrypted, plaintext);
+
+        assert!(decrypt_with_password(&encrypted, "wrong").is_err());
+        assert!(decrypt_with_password(&encrypted[..10], "hunter2").is_err());
+    }
+
+    #[cfg(target_os = "windows")]
+    #[test]
+    fn test_windows_machine_guid_available() {
+        assert!(machine_guid().is_some());
+        assert!(get_hardware_uuid().is_some());
+    }
+
     #[test]
     fn test_hardware_uuid_available() {
         let uuid = get_hardware_uuid();
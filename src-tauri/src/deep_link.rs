@@ -0,0 +1,289 @@
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::Local;
+use tauri::{async_runtime, AppHandle, Emitter, Manager, Url};
+use tracing::{error, info, warn};
+
+use crate::pill::PillStatus;
+use crate::recorder::CompletedRecording;
+use crate::{batch, toast, tray, AppRuntime, AppState};
+
+pub const SCHEME: &str = "glimpse";
+/// Text handed over in a link must be shorter than this, in bytes.
+const MAX_TEXT_BYTES: usize = 10 * 1024;
+/// Tabs of the settings modal a link may open.
+const SETTINGS_TABS: [&str; 4] = ["general", "account", "models", "about"];
+const IMPORT_EXTENSIONS: [&str; 2] = ["wav", "mp3"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeepLink {
+    /// `glimpse://transcribe?text=<encoded>`
+    Transcribe { text: Option<String> },
+    /// `glimpse://settings?tab=<name>`
+    Settings { tab: String },
+    /// `glimpse://import?path=<encoded>`; the path is checked against the filesystem
+    /// separately by [`validate_import_path`].
+    Import { path: PathBuf },
+}
+
+/// Parse and validate a `glimpse://` link. Query values arrive URL-decoded.
+pub fn parse(url: &Url) -> Result<DeepLink> {
+    if url.scheme() != SCHEME {
+        bail!("Unsupported link scheme: {}", url.scheme());
+    }
+    let param = |name: &str| {
+        url.query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+    };
+
+    match url.host_str().unwrap_or_default() {
+        "transcribe" => {
+            let text = param("text").filter(|text| !text.trim().is_empty());
+            if text
+                .as_ref()
+                .is_some_and(|text| text.len() >= MAX_TEXT_BYTES)
+            {
+                bail!("Link text must be under {MAX_TEXT_BYTES} bytes");
+            }
+            Ok(DeepLink::Transcribe { text })
+        }
+        "settings" => {
+            let tab = param("tab").ok_or_else(|| anyhow!("Settings link has no tab"))?;
+            if !SETTINGS_TABS.contains(&tab.as_str()) {
+                bail!("Unknown settings tab: {tab}");
+            }
+            Ok(DeepLink::Settings { tab })
+        }
+        "import" => {
+            let path = param("path")
+                .filter(|path| !path.is_empty())
+                .ok_or_else(|| anyhow!("Import link has no path"))?;
+            Ok(DeepLink::Import {
+                path: PathBuf::from(path),
+            })
+        }
+        other => bail!("Unknown link action: {other}"),
+    }
+}
+
+/// Resolve `path` to a readable WAV or MP3 file inside one of `allowed_roots`.
+pub fn validate_import_path(path: &Path, allowed_roots: &[PathBuf]) -> Result<PathBuf> {
+    let path = path
+        .canonicalize()
+        .with_context(|| format!("Audio file not found: {}", path.display()))?;
+    if !path.is_file() {
+        bail!("Not a file: {}", path.display());
+    }
+
+    let supported = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            IMPORT_EXTENSIONS
+                .iter()
+                .any(|allowed| ext.eq_ignore_ascii_case(allowed))
+        });
+    if !supported {
+        bail!("Only WAV and MP3 files can be imported");
+    }
+
+    // Canonicalizing first means `..` segments and symlinks can't escape the roots
+    let inside_root = allowed_roots
+        .iter()
+        .filter_map(|root| root.canonicalize().ok())
+        .any(|root| path.starts_with(root));
+    if !inside_root {
+        bail!("Audio file must be inside your home folder");
+    }
+
+    let size = path.metadata()?.len();
+    if size > batch::MAX_AUDIO_SIZE_BYTES {
+        bail!("Audio file is too large ({} MB)", size / (1024 * 1024));
+    }
+    File::open(&path).with_context(|| format!("Audio file is not readable: {}", path.display()))?;
+
+    Ok(path)
+}
+
+/// Act on a link opened by another app. Invalid links are logged and ignored.
+pub fn handle(app: &AppHandle<AppRuntime>, url: &Url) {
+    let link = match parse(url) {
+        Ok(link) => link,
+        Err(err) => {
+            warn!("Ignoring deep link: {err}");
+            return;
+        }
+    };
+
+    match link {
+        DeepLink::Transcribe { text } => {
+            info!("Deep link: start dictation");
+            if text.is_some() {
+                warn!("Ignoring text from deep link, editing selected text isn't supported");
+            }
+            let state = app.state::<AppState>();
+            // Only start; a link shouldn't stop or cancel what the user is doing
+            if state.pill().status() == PillStatus::Idle {
+                state.pill().handle_toggle_press(app);
+            }
+        }
+        DeepLink::Settings { tab } => {
+            info!(%tab, "Deep link: open settings");
+            open_settings_tab(app, tab);
+        }
+        DeepLink::Import { path } => {
+            info!("Deep link: import audio");
+            let app = app.clone();
+            async_runtime::spawn_blocking(move || {
+                if let Err(err) = import_audio(&app, &path) {
+                    error!("Failed to import audio from deep link: {err}");
+                    toast::show(&app, "error", Some("Import failed"), &err.to_string());
+                }
+            });
+        }
+    }
+}
+
+fn open_settings_tab(app: &AppHandle<AppRuntime>, tab: String) {
+    if let Err(err) = tray::toggle_settings_window(app) {
+        error!("Failed to open settings window: {err}");
+        return;
+    }
+
+    // Give a freshly created window time to register its listeners
+    let app = app.clone();
+    std::thread::spawn(move || {
+        std::thread::sleep(std::time::Duration::from_millis(500));
+        if let Err(err) = app.emit(&format!("navigate:{tab}"), ()) {
+            error!("Failed to emit navigate:{tab}: {err}");
+        }
+    });
+}
+
+/// Run an audio file through the same save, validation and transcription steps as a
+/// recording that just stopped.
+fn import_audio(app: &AppHandle<AppRuntime>, path: &Path) -> Result<()> {
+    let home_dir = app.path().home_dir().context("Home directory not found")?;
+    let path = validate_import_path(path, &[home_dir])?;
+
+    // Claim the pill before decoding so a recording can't start in the meantime
+    let state = app.state::<AppState>();
+    if !state
+        .pill()
+        .transition_from(app, PillStatus::Idle, PillStatus::Processing)
+    {
+        bail!("Finish the current recording before importing audio");
+    }
+
+    match load_recording(&path) {
+        Ok(recording) => {
+            crate::persist_recording_async(app.clone(), recording, None, false);
+            Ok(())
+        }
+        Err(err) => {
+            state
+                .pill()
+                .transition_from(app, PillStatus::Processing, PillStatus::Idle);
+            Err(err)
+        }
+    }
+}
+
+fn load_recording(path: &Path) -> Result<CompletedRecording> {
+    let (samples, sample_rate) = crate::load_audio_for_transcription(path)?;
+    let ended_at = Local::now();
+    let duration_ms = samples.len() as i64 * 1000 / i64::from(sample_rate.max(1));
+    Ok(CompletedRecording {
+        samples,
+        sample_rate,
+        channels: 1,
+        started_at: ended_at - chrono::Duration::milliseconds(duration_ms),
+        ended_at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn link(url: &str) -> Result<DeepLink> {
+        parse(&Url::parse(url).unwrap())
+    }
+
+    #[test]
+    fn test_parse_transcribe_link() {
+        assert_eq!(
+            link("glimpse://transcribe").unwrap(),
+            DeepLink::Transcribe { text: None }
+        );
+        assert_eq!(
+            link("glimpse://transcribe?text=hello%20world%26more").unwrap(),
+            DeepLink::Transcribe {
+                text: Some("hello world&more".to_string())
+            }
+        );
+        assert_eq!(
+            link("glimpse://transcribe?text=%20").unwrap(),
+            DeepLink::Transcribe { text: None }
+        );
+
+        let too_long = "a".repeat(MAX_TEXT_BYTES);
+        assert!(link(&format!("glimpse://transcribe?text={too_long}")).is_err());
+    }
+
+    #[test]
+    fn test_parse_settings_and_import_links() {
+        assert_eq!(
+            link("glimpse://settings?tab=models").unwrap(),
+            DeepLink::Settings {
+                tab: "models".to_string()
+            }
+        );
+        assert!(link("glimpse://settings").is_err());
+        assert!(link("glimpse://settings?tab=../../etc").is_err());
+
+        assert_eq!(
+            link("glimpse://import?path=%2FUsers%2Fme%2Fmemo.mp3").unwrap(),
+            DeepLink::Import {
+                path: PathBuf::from("/Users/me/memo.mp3")
+            }
+        );
+        assert!(link("glimpse://import?path=").is_err());
+
+        assert!(link("glimpse://delete-everything").is_err());
+        assert!(link("https://transcribe?text=hi").is_err());
+    }
+
+    #[test]
+    fn test_validate_import_path() {
+        let root = std::env::temp_dir().join(format!("glimpse-import-{}", uuid::Uuid::new_v4()));
+        let allowed = root.join("home");
+        let outside = root.join("elsewhere");
+        fs::create_dir_all(&allowed).unwrap();
+        fs::create_dir_all(&outside).unwrap();
+
+        let memo = allowed.join("memo.MP3");
+        fs::write(&memo, b"id3").unwrap();
+        let notes = allowed.join("notes.txt");
+        fs::write(&notes, b"text").unwrap();
+        let foreign = outside.join("memo.wav");
+        fs::write(&foreign, b"riff").unwrap();
+        let roots = [allowed.clone()];
+
+        assert_eq!(
+            validate_import_path(&memo, &roots).unwrap(),
+            memo.canonicalize().unwrap()
+        );
+        assert!(validate_import_path(&notes, &roots).is_err());
+        assert!(validate_import_path(&foreign, &roots).is_err());
+        assert!(validate_import_path(&allowed.join("../elsewhere/memo.wav"), &roots).is_err());
+        assert!(validate_import_path(&allowed.join("missing.wav"), &roots).is_err());
+        assert!(validate_import_path(&allowed, &roots).is_err());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
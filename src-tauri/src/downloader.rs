@@ -1,15 +1,61 @@
 use anyhow::{anyhow, Context, Result};
-use reqwest::Client;
+use parking_lot::Mutex;
+use reqwest::header::{ACCEPT_RANGES, RANGE};
+use reqwest::{Client, StatusCode};
 use serde::Serialize;
-use std::fs::File;
-use std::io::Write;
-use std::path::Path;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
 use tauri::{AppHandle, Emitter, Runtime};
 
+const PARTIAL_SUFFIX: &str = ".part";
+
 #[derive(Debug, Clone, Copy)]
 pub struct ModelFileDescriptor {
     pub url: &'static str,
     pub name: &'static str,
+    /// Expected lowercase hex SHA-256 of the file. Checked once, right after it downloads.
+    pub checksum_sha256: Option<&'static str>,
+}
+
+/// Latest progress of a model file being downloaded, kept so a window opened mid-download
+/// can catch up on the progress events it missed.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DownloadProgress {
+    pub model: String,
+    pub file: String,
+    pub bytes_downloaded: u64,
+    pub total_bytes: u64,
+    pub percent: f32,
+}
+
+/// Progress of every model download in flight, keyed by model.
+#[derive(Debug, Default)]
+pub struct DownloadProgressMap {
+    entries: Mutex<HashMap<String, DownloadProgress>>,
+}
+
+impl DownloadProgressMap {
+    pub fn update(&self, progress: DownloadProgress) {
+        self.entries.lock().insert(progress.model.clone(), progress);
+    }
+
+    pub fn remove(&self, model: &str) {
+        self.entries.lock().remove(model);
+    }
+
+    pub fn get(&self, model: &str) -> Option<DownloadProgress> {
+        self.entries.lock().get(model).cloned()
+    }
+
+    pub fn all(&self) -> Vec<DownloadProgress> {
+        let mut all: Vec<_> = self.entries.lock().values().cloned().collect();
+        all.sort_by(|a, b| a.model.cmp(&b.model));
+        all
+    }
 }
 
 #[derive(Serialize, Clone)]
@@ -21,6 +67,14 @@ struct DownloadProgressPayload {
     percent: f64,
 }
 
+#[derive(Serialize, Clone)]
+struct ModelDownloadProgressPayload {
+    model: String,
+    file: String,
+    bytes_written: u64,
+    total_bytes: u64,
+}
+
 #[derive(Serialize, Clone)]
 struct DownloadCompletePayload {
     model: String,
@@ -37,33 +91,26 @@ pub async fn download_file<R: Runtime>(
     client: &Client,
     url: &str,
     file_name: &str,
+    checksum_sha256: Option<&str>,
     model_name: &str,
     target_dir: &Path,
+    progress: &DownloadProgressMap,
 ) -> Result<()> {
     let target_path = target_dir.join(file_name);
-    let mut res = client
-        .get(url)
-        .send()
-        .await
-        .context("Failed to make request")?;
-    let total_size = res.content_length().unwrap_or(0);
-
-    if !res.status().is_success() {
-        return Err(anyhow!("Download failed with status: {}", res.status()));
-    }
-
-    let mut file = File::create(&target_path).context("Failed to create file")?;
-    let mut downloaded: u64 = 0;
-
-    while let Some(chunk) = res.chunk().await.context("Failed to read chunk")? {
-        file.write_all(&chunk).context("Failed to write to file")?;
-        downloaded += chunk.len() as u64;
 
+    download_resumable(client, url, &target_path, |downloaded, total_size| {
         let percent = if total_size > 0 {
             (downloaded as f64 / total_size as f64) * 100.0
         } else {
             0.0
         };
+        progress.update(DownloadProgress {
+            model: model_name.to_string(),
+            file: file_name.to_string(),
+            bytes_downloaded: downloaded,
+            total_bytes: total_size,
+            percent: percent as f32,
+        });
 
         app.emit(
             "download:progress",
@@ -75,17 +122,162 @@ pub async fn download_file<R: Runtime>(
                 percent,
             },
         )?;
+        app.emit(
+            "model:download-progress",
+            ModelDownloadProgressPayload {
+                model: model_name.to_string(),
+                file: file_name.to_string(),
+                bytes_written: downloaded,
+                total_bytes: total_size,
+            },
+        )?;
+        Ok(())
+    })
+    .await?;
+
+    if let Some(expected) = checksum_sha256 {
+        // Hashing a multi-gigabyte model would stall the async runtime
+        let expected = expected.to_string();
+        tauri::async_runtime::spawn_blocking(move || verify_checksum(&target_path, &expected))
+            .await
+            .map_err(|err| anyhow!("Checksum task failed: {err}"))??;
     }
 
     Ok(())
 }
 
+pub fn sha256_file(path: &Path) -> Result<String> {
+    let mut file = File::open(path)
+        .with_context(|| format!("Failed to open {} for hashing", path.display()))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 1024 * 1024];
+    loop {
+        let read = file
+            .read(&mut buffer)
+            .context("Failed to read file for hashing")?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Compare a freshly downloaded file against its expected SHA-256. A corrupted file is
+/// deleted so the model shows as not installed and downloading it again starts clean.
+pub fn verify_checksum(path: &Path, expected: &str) -> Result<()> {
+    let actual = sha256_file(path)?;
+    if actual.eq_ignore_ascii_case(expected) {
+        return Ok(());
+    }
+
+    let _ = std::fs::remove_file(path);
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    Err(anyhow!(
+        "{name} failed verification (expected SHA-256 {expected}, got {actual}). Download the model again."
+    ))
+}
+
+fn partial_path(target_path: &Path) -> PathBuf {
+    let mut name = target_path.as_os_str().to_owned();
+    name.push(PARTIAL_SUFFIX);
+    PathBuf::from(name)
+}
+
+/// Where to start writing and how large the finished file will be for a given response.
+/// Returns `None` when the server ignored the range and sent the whole file.
+fn resume_offset(
+    status: StatusCode,
+    existing_len: u64,
+    content_length: Option<u64>,
+) -> Option<(u64, u64)> {
+    if status == StatusCode::PARTIAL_CONTENT && existing_len > 0 {
+        let total = content_length.map(|len| existing_len + len).unwrap_or(0);
+        Some((existing_len, total))
+    } else {
+        None
+    }
+}
+
+/// Download `url` to `target_path`, resuming from a `.part` file left by an earlier attempt.
+/// The file only appears at `target_path` once the transfer completes.
+async fn download_resumable(
+    client: &Client,
+    url: &str,
+    target_path: &Path,
+    mut on_progress: impl FnMut(u64, u64) -> Result<()>,
+) -> Result<()> {
+    let partial = partial_path(target_path);
+    let existing_len = partial.metadata().map(|meta| meta.len()).unwrap_or(0);
+
+    let mut request = client.get(url);
+    if existing_len > 0 {
+        request = request.header(RANGE, format!("bytes={existing_len}-"));
+    }
+    let mut res = request.send().await.context("Failed to make request")?;
+
+    if res.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+        // The partial file is stale or already complete; start over.
+        std::fs::remove_file(&partial).context("Failed to remove partial download")?;
+        res = client
+            .get(url)
+            .send()
+            .await
+            .context("Failed to make request")?;
+    }
+
+    if !res.status().is_success() {
+        return Err(anyhow!("Download failed with status: {}", res.status()));
+    }
+
+    let supports_ranges = res
+        .headers()
+        .get(ACCEPT_RANGES)
+        .map(|value| value != "none")
+        .unwrap_or(res.status() == StatusCode::PARTIAL_CONTENT);
+    let resume = if supports_ranges {
+        resume_offset(res.status(), existing_len, res.content_length())
+    } else {
+        None
+    };
+
+    let (mut file, mut downloaded, total_size) = match resume {
+        Some((offset, total)) => {
+            let file = OpenOptions::new()
+                .append(true)
+                .open(&partial)
+                .context("Failed to open partial file")?;
+            (file, offset, total)
+        }
+        None => {
+            let file = File::create(&partial).context("Failed to create file")?;
+            (file, 0, res.content_length().unwrap_or(0))
+        }
+    };
+
+    while let Some(chunk) = res.chunk().await.context("Failed to read chunk")? {
+        file.write_all(&chunk).context("Failed to write to file")?;
+        downloaded += chunk.len() as u64;
+        on_progress(downloaded, total_size)?;
+    }
+
+    file.flush().context("Failed to flush file")?;
+    drop(file);
+    std::fs::rename(&partial, target_path).context("Failed to finalize download")?;
+
+    Ok(())
+}
+
 pub async fn download_model_files<R: Runtime>(
     app: &AppHandle<R>,
     client: &Client,
     model: &str,
     files: &[ModelFileDescriptor],
     target_dir: &Path,
+    progress: &DownloadProgressMap,
 ) -> Result<()> {
     if !target_dir.exists() {
         std::fs::create_dir_all(target_dir).context("Failed to create model directory")?;
@@ -97,11 +289,14 @@ pub async fn download_model_files<R: Runtime>(
             client,
             descriptor.url,
             descriptor.name,
+            descriptor.checksum_sha256,
             model,
             target_dir,
+            progress,
         )
         .await
         {
+            progress.remove(model);
             let _ = app.emit(
                 "download:error",
                 DownloadErrorPayload {
@@ -113,6 +308,7 @@ pub async fn download_model_files<R: Runtime>(
         }
     }
 
+    progress.remove(model);
     let _ = app.emit(
         "download:complete",
         DownloadCompletePayload {
@@ -121,3 +317,319 @@ pub async fn download_model_files<R: Runtime>(
     );
     Ok(())
 }
+
+/// A model download waiting in, or running from, a [`DownloadQueue`].
+#[derive(Debug, Clone)]
+pub struct QueuedDownload {
+    pub model: String,
+    pub files: &'static [ModelFileDescriptor],
+    pub dir: PathBuf,
+}
+
+/// The model is already waiting in the queue or downloading, queued or not.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateQueued {
+    pub model: String,
+}
+
+impl fmt::Display for DuplicateQueued {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} is already queued for download", self.model)
+    }
+}
+
+impl std::error::Error for DuplicateQueued {}
+
+#[derive(Debug, Default)]
+struct QueueState {
+    active: Option<String>,
+    pending: VecDeque<QueuedDownload>,
+    worker_running: bool,
+    /// Models being downloaded directly, outside the queue.
+    direct: HashSet<String>,
+}
+
+impl QueueState {
+    fn is_downloading(&self, model: &str) -> bool {
+        self.active.as_deref() == Some(model)
+            || self.pending.iter().any(|job| job.model == model)
+            || self.direct.contains(model)
+    }
+}
+
+/// Runs model downloads one at a time, in the order they were queued.
+#[derive(Debug, Default)]
+pub struct DownloadQueue {
+    state: Mutex<QueueState>,
+}
+
+impl DownloadQueue {
+    /// Add a download to the back of the queue. Returns `true` when no worker is draining
+    /// the queue, in which case the caller must start one.
+    pub fn enqueue(
+        &self,
+        model: String,
+        files: &'static [ModelFileDescriptor],
+        dir: PathBuf,
+    ) -> std::result::Result<bool, DuplicateQueued> {
+        let mut state = self.state.lock();
+        if state.is_downloading(&model) {
+            return Err(DuplicateQueued { model });
+        }
+
+        state
+            .pending
+            .push_back(QueuedDownload { model, files, dir });
+        let start_worker = !state.worker_running;
+        state.worker_running = true;
+        Ok(start_worker)
+    }
+
+    /// Take the next download and mark it in flight. `None` means the queue is drained and
+    /// the worker should stop.
+    pub fn next_job(&self) -> Option<QueuedDownload> {
+        let mut state = self.state.lock();
+        let job = state.pending.pop_front();
+        state.active = job.as_ref().map(|job| job.model.clone());
+        if job.is_none() {
+            state.worker_running = false;
+        }
+        job
+    }
+
+    /// Reserve `model` for a download that bypasses the queue, so the queue won't start the
+    /// same model while it runs. The reservation lasts until the returned guard is dropped.
+    pub fn claim_direct(
+        &self,
+        model: &str,
+    ) -> std::result::Result<DirectDownload<'_>, DuplicateQueued> {
+        let mut state = self.state.lock();
+        if state.is_downloading(model) {
+            return Err(DuplicateQueued {
+                model: model.to_string(),
+            });
+        }
+        state.direct.insert(model.to_string());
+        Ok(DirectDownload {
+            queue: self,
+            model: model.to_string(),
+        })
+    }
+
+    /// Each queued model with its position: 0 for the running download, then 1, 2, ... for
+    /// those waiting behind it.
+    pub fn positions(&self) -> Vec<(String, usize)> {
+        let state = self.state.lock();
+        let offset = usize::from(state.active.is_some());
+        state
+            .active
+            .iter()
+            .cloned()
+            .map(|model| (model, 0))
+            .chain(
+                state
+                    .pending
+                    .iter()
+                    .enumerate()
+                    .map(|(index, job)| (job.model.clone(), index + offset)),
+            )
+            .collect()
+    }
+}
+
+/// A download running outside the queue; see [`DownloadQueue::claim_direct`].
+pub struct DirectDownload<'a> {
+    queue: &'a DownloadQueue,
+    model: String,
+}
+
+impl Drop for DirectDownload<'_> {
+    fn drop(&mut self) {
+        self.queue.state.lock().direct.remove(&self.model);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read};
+    use std::net::TcpListener;
+
+    /// Serve a single request, answering a `Range` request with 206 and the remaining bytes.
+    fn serve_once(body: &'static [u8]) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut offset = 0usize;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" || line.is_empty() {
+                    break;
+                }
+                if let Some(range) = line.to_ascii_lowercase().strip_prefix("range: bytes=") {
+                    offset = range.trim().trim_end_matches('-').parse().unwrap();
+                }
+            }
+            let remaining = &body[offset..];
+            let status = if offset > 0 {
+                "206 Partial Content"
+            } else {
+                "200 OK"
+            };
+            let header = format!(
+                "HTTP/1.1 {status}\r\nAccept-Ranges: bytes\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                remaining.len()
+            );
+            stream.write_all(header.as_bytes()).unwrap();
+            stream.write_all(remaining).unwrap();
+        });
+        format!("http://{addr}/model.bin")
+    }
+
+    #[test]
+    fn test_verify_checksum_mismatch_deletes_file() {
+        let path = std::env::temp_dir().join(format!("glimpse-sha-{}.bin", std::process::id()));
+        std::fs::write(&path, b"glimpse").unwrap();
+        let actual = sha256_file(&path).unwrap();
+        assert!(verify_checksum(&path, &actual).is_ok());
+
+        let expected = "0".repeat(64);
+        let err = verify_checksum(&path, &expected).unwrap_err().to_string();
+        assert!(err.contains(&expected));
+        assert!(err.contains(&actual));
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_resume_offset() {
+        assert_eq!(
+            resume_offset(StatusCode::PARTIAL_CONTENT, 100, Some(50)),
+            Some((100, 150))
+        );
+        assert_eq!(resume_offset(StatusCode::OK, 100, Some(150)), None);
+        assert_eq!(
+            resume_offset(StatusCode::PARTIAL_CONTENT, 0, Some(150)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_download_resumes_from_partial_file() {
+        const BODY: &[u8] = b"0123456789abcdefghij";
+        let dir = std::env::temp_dir().join(format!("glimpse-dl-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let target = dir.join("model.bin");
+        std::fs::write(partial_path(&target), &BODY[..8]).unwrap();
+
+        let url = serve_once(BODY);
+        let client = Client::new();
+        let mut progress = Vec::new();
+        tauri::async_runtime::block_on(download_resumable(
+            &client,
+            &url,
+            &target,
+            |written, total| {
+                progress.push((written, total));
+                Ok(())
+            },
+        ))
+        .unwrap();
+
+        let mut written = Vec::new();
+        File::open(&target)
+            .unwrap()
+            .read_to_end(&mut written)
+            .unwrap();
+        assert_eq!(written, BODY);
+        assert!(!partial_path(&target).exists());
+        assert_eq!(
+            progress.last(),
+            Some(&(BODY.len() as u64, BODY.len() as u64))
+        );
+        assert!(progress.iter().all(|(written, _)| *written > 8));
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_download_progress_map_tracks_latest_file() {
+        let progress = |model: &str, file: &str, bytes_downloaded: u64| DownloadProgress {
+            model: model.to_string(),
+            file: file.to_string(),
+            bytes_downloaded,
+            total_bytes: 100,
+            percent: bytes_downloaded as f32,
+        };
+        let map = DownloadProgressMap::default();
+        map.update(progress("whisper", "encoder.bin", 40));
+        map.update(progress("parakeet", "model.onnx", 10));
+        map.update(progress("whisper", "decoder.bin", 5));
+
+        assert_eq!(
+            map.get("whisper"),
+            Some(progress("whisper", "decoder.bin", 5))
+        );
+        let models: Vec<_> = map.all().into_iter().map(|entry| entry.model).collect();
+        assert_eq!(models, ["parakeet", "whisper"]);
+
+        map.remove("whisper");
+        assert_eq!(map.get("whisper"), None);
+        assert_eq!(map.all().len(), 1);
+    }
+
+    #[test]
+    fn test_download_queue_runs_in_order() {
+        let queue = DownloadQueue::default();
+        assert_eq!(queue.enqueue("a".into(), &[], PathBuf::new()), Ok(true));
+        assert_eq!(queue.enqueue("b".into(), &[], PathBuf::new()), Ok(false));
+        assert_eq!(
+            queue.positions(),
+            vec![("a".to_string(), 0), ("b".to_string(), 1)]
+        );
+
+        assert_eq!(queue.next_job().unwrap().model, "a");
+        assert_eq!(
+            queue.enqueue("a".into(), &[], PathBuf::new()),
+            Err(DuplicateQueued {
+                model: "a".to_string()
+            })
+        );
+        assert!(queue.enqueue("b".into(), &[], PathBuf::new()).is_err());
+        assert_eq!(queue.enqueue("c".into(), &[], PathBuf::new()), Ok(false));
+        assert_eq!(
+            queue.positions(),
+            vec![
+                ("a".to_string(), 0),
+                ("b".to_string(), 1),
+                ("c".to_string(), 2)
+            ]
+        );
+
+        assert_eq!(queue.next_job().unwrap().model, "b");
+        assert_eq!(queue.next_job().unwrap().model, "c");
+        assert!(queue.next_job().is_none());
+        assert!(queue.positions().is_empty());
+
+        // A drained queue needs a new worker
+        assert_eq!(queue.enqueue("a".into(), &[], PathBuf::new()), Ok(true));
+    }
+
+    #[test]
+    fn test_direct_download_excludes_queue() {
+        let queue = DownloadQueue::default();
+        assert_eq!(queue.enqueue("a".into(), &[], PathBuf::new()), Ok(true));
+        assert!(queue.claim_direct("a").is_err());
+
+        let direct = queue.claim_direct("b").unwrap();
+        assert!(queue.claim_direct("b").is_err());
+        assert!(queue.enqueue("b".into(), &[], PathBuf::new()).is_err());
+        // Direct downloads don't take a place in the queue
+        assert_eq!(queue.positions(), vec![("a".to_string(), 0)]);
+
+        drop(direct);
+        assert_eq!(queue.enqueue("b".into(), &[], PathBuf::new()), Ok(false));
+    }
+}
@@ -0,0 +1,298 @@
+//! Coarse spectral fingerprints for spotting the same speech recorded twice in a row.
+
+use std::collections::VecDeque;
+
+use chrono::{DateTime, Duration, Local};
+
+use crate::recorder::fft;
+
+/// Length of each analysis frame; rounded down to a power of two for the FFT.
+const FRAME_MS: u32 = 32;
+/// Frequency bands per slice, spaced logarithmically between `MIN_BAND_HZ` and `MAX_BAND_HZ`.
+/// Neighbouring bands are compared, giving `BANDS - 1` bits per slice.
+const BANDS: usize = 9;
+/// Recordings are split into this many time slices; each slice after the first contributes
+/// `BANDS - 1` bits, for 64 in total.
+const SLICES: usize = 9;
+const MIN_BAND_HZ: f32 = 150.0;
+/// Speech energy sits well below this, so higher bins are ignored.
+const MAX_BAND_HZ: f32 = 4000.0;
+/// Changes in spectral slope smaller than this (in natural-log energy) are treated as no
+/// change, so rounding noise can't flip bits between two copies of the same audio.
+const SLOPE_DEAD_ZONE: f32 = 1e-3;
+/// Shorter recordings carry too little speech to fingerprint reliably and are never
+/// treated as duplicates.
+pub const MIN_FINGERPRINT_MS: u32 = 500;
+/// Fingerprints remembered for duplicate checks.
+const MAX_RECENT_FINGERPRINTS: usize = 20;
+/// Only recordings made this close together are compared.
+const DUPLICATE_WINDOW_SECONDS: i64 = 10;
+/// Fingerprints differing in fewer bits than this are treated as the same recording.
+const DUPLICATE_MAX_DISTANCE: u32 = 5;
+/// Recordings whose lengths differ by more than this fraction can't be the same one.
+const DUPLICATE_MAX_LENGTH_RATIO: f32 = 0.1;
+
+/// A recording's fingerprint together with how long it was.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AudioFingerprint {
+    pub bits: u64,
+    pub duration_ms: u32,
+}
+
+impl AudioFingerprint {
+    /// Hash `samples` into 64 bits, or `None` when the recording is shorter than
+    /// `MIN_FINGERPRINT_MS`. The recording is cut into 9 time slices and each slice's
+    /// average spectrum is reduced to 9 log-spaced band energies. Each bit records whether
+    /// the level difference between two neighbouring bands grew since the previous slice.
+    /// Working with changes in log energy cancels both volume and the steady downward tilt
+    /// every voice has, so the bits follow what was said rather than who said it.
+    pub fn compute(samples: &[f32], sample_rate: u32) -> Option<Self> {
+        let duration_ms = (samples.len() as u64 * 1000 / u64::from(sample_rate.max(1))) as u32;
+        let frame_target = (sample_rate * FRAME_MS / 1000) as usize;
+        if duration_ms < MIN_FINGERPRINT_MS || frame_target < 2 {
+            return None;
+        }
+        let frame_len = 1 << (usize::BITS - 1 - frame_target.leading_zeros());
+        let band_edges = band_edges(sample_rate, frame_len);
+
+        let mut re = vec![0.0f32; frame_len];
+        let mut im = vec![0.0f32; frame_len];
+        let mut levels = [[0.0f32; BANDS]; SLICES];
+        for (slice, level) in levels.iter_mut().enumerate() {
+            let start = samples.len() * slice / SLICES;
+            let end = samples.len() * (slice + 1) / SLICES;
+            // Frames overlap by half; a slice shorter than a frame still gets one, taken
+            // from its start
+            let last_start = end.saturating_sub(frame_len).max(start);
+            let last_start = last_start.min(samples.len() - frame_len);
+            let mut energy = [0.0f32; BANDS];
+            let mut frames = 0;
+            let mut frame_start = start.min(last_start);
+            while frame_start <= last_start {
+                re.copy_from_slice(&samples[frame_start..frame_start + frame_len]);
+                im.fill(0.0);
+                fft(&mut re, &mut im);
+                for (band, total) in energy.iter_mut().enumerate() {
+                    *total += (band_edges[band]..band_edges[band + 1])
+                        .map(|bin| re[bin] * re[bin] + im[bin] * im[bin])
+                        .sum::<f32>();
+                }
+                frames += 1;
+                frame_start += frame_len / 2;
+            }
+            for (band, total) in energy.iter().enumerate() {
+                level[band] = (total / frames as f32 + f32::MIN_POSITIVE).ln();
+            }
+        }
+
+        let mut bits = 0u64;
+        let mut bit = 0;
+        for slice in 1..SLICES {
+            for band in 0..BANDS - 1 {
+                let slope = levels[slice][band] - levels[slice][band + 1];
+                let previous = levels[slice - 1][band] - levels[slice - 1][band + 1];
+                if slope - previous > SLOPE_DEAD_ZONE {
+                    bits |= 1 << bit;
+                }
+                bit += 1;
+            }
+        }
+        Some(Self { bits, duration_ms })
+    }
+
+    /// Number of bits that differ between two fingerprints.
+    pub fn distance(&self, other: &Self) -> u32 {
+        (self.bits ^ other.bits).count_ones()
+    }
+
+    /// Whether the two recordings are close enough in length to be the same one.
+    fn similar_length(&self, other: &Self) -> bool {
+        let longer = self.duration_ms.max(other.duration_ms) as f32;
+        let shorter = self.duration_ms.min(other.duration_ms) as f32;
+        longer - shorter <= longer * DUPLICATE_MAX_LENGTH_RATIO
+    }
+}
+
+/// FFT bin boundaries of each band: band `i` covers bins `edges[i]..edges[i + 1]`. Every band
+/// gets at least one bin, and bin 0 (the DC offset) is never used.
+fn band_edges(sample_rate: u32, frame_len: usize) -> [usize; BANDS + 1] {
+    let bin_hz = sample_rate as f32 / frame_len as f32;
+    let max_bin = frame_len / 2;
+    let mut edges = [0usize; BANDS + 1];
+    for (index, edge) in edges.iter_mut().enumerate() {
+        let hz = MIN_BAND_HZ * (MAX_BAND_HZ / MIN_BAND_HZ).powf(index as f32 / BANDS as f32);
+        *edge = ((hz / bin_hz).round() as usize).max(1);
+    }
+    for index in 1..edges.len() {
+        edges[index] = edges[index].max(edges[index - 1] + 1);
+    }
+    edges.map(|edge| edge.min(max_bin))
+}
+
+/// Fingerprints of the last few accepted recordings, oldest first.
+#[derive(Debug, Default)]
+pub struct RecentFingerprints {
+    entries: VecDeque<(AudioFingerprint, DateTime<Local>)>,
+}
+
+impl RecentFingerprints {
+    /// Smallest distance to a similar-length fingerprint recorded within the last 10 seconds
+    /// of `now`, if it is close enough to count as a duplicate.
+    pub fn find_duplicate(
+        &self,
+        fingerprint: &AudioFingerprint,
+        now: DateTime<Local>,
+    ) -> Option<u32> {
+        let cutoff = now - Duration::seconds(DUPLICATE_WINDOW_SECONDS);
+        self.entries
+            .iter()
+            .filter(|(recent, recorded_at)| {
+                *recorded_at >= cutoff && recent.similar_length(fingerprint)
+            })
+            .map(|(recent, _)| recent.distance(fingerprint))
+            .filter(|distance| *distance < DUPLICATE_MAX_DISTANCE)
+            .min()
+    }
+
+    pub fn push(&mut self, fingerprint: AudioFingerprint, recorded_at: DateTime<Local>) {
+        if self.entries.len() >= MAX_RECENT_FINGERPRINTS {
+            self.entries.pop_front();
+        }
+        self.entries.push_back((fingerprint, recorded_at));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+
+    const RATE: u32 = 16000;
+
+    /// A stand-in for voiced speech: a pulse train at `pitch` Hz whose harmonics fall off
+    /// like a real voice, shaped by formants that move through `vowels` (F1, F2 pairs) and
+    /// pulsed at a syllable rate.
+    fn speech(vowels: &[(f32, f32)], pitch: f32, seconds: f32) -> Vec<f32> {
+        let len = (seconds * RATE as f32) as usize;
+        let harmonics = (MAX_BAND_HZ / pitch) as usize;
+        (0..len)
+            .map(|i| {
+                let t = i as f32 / RATE as f32;
+                let (f1, f2) = vowels[i * vowels.len() / len];
+                let formant = |f: f32, center: f32| 1.0 / (1.0 + ((f - center) / 80.0).powi(2));
+                let voice: f32 = (1..=harmonics)
+                    .map(|h| {
+                        let f = h as f32 * pitch;
+                        let gain = (formant(f, f1) + 0.5 * formant(f, f2)) / h as f32;
+                        gain * (2.0 * PI * f * t).sin()
+                    })
+                    .sum();
+                let syllables = 0.6 - 0.4 * (2.0 * PI * 4.0 * t).cos();
+                0.2 * syllables * voice
+            })
+            .collect()
+    }
+
+    /// Deterministic low-level noise, like a microphone's hiss.
+    fn with_hiss(samples: &[f32], level: f32) -> Vec<f32> {
+        let mut state = 0x2545_f491u32;
+        samples
+            .iter()
+            .map(|sample| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                sample + level * (state as f32 / u32::MAX as f32 - 0.5)
+            })
+            .collect()
+    }
+
+    const A: (f32, f32) = (730.0, 1090.0);
+    const E: (f32, f32) = (530.0, 1840.0);
+    const I: (f32, f32) = (270.0, 2290.0);
+    const O: (f32, f32) = (570.0, 840.0);
+    const U: (f32, f32) = (300.0, 870.0);
+
+    fn utterances(seconds: f32) -> Vec<Vec<f32>> {
+        vec![
+            speech(&[A, E, I, O, U, A, E, I, O], 120.0, seconds),
+            speech(&[O, I, E, U, A, O, I, E, U], 120.0, seconds),
+            speech(&[I, I, A, A, U, U, E, E, O], 210.0, seconds),
+            speech(&[U, O, A, E, I, U, O, A, E], 180.0, seconds),
+            speech(&[E, A, U, I, O, E, A, U, I], 100.0, seconds),
+        ]
+    }
+
+    fn assert_no_collisions(clips: &[Vec<f32>]) {
+        let fingerprints: Vec<_> = clips
+            .iter()
+            .map(|clip| AudioFingerprint::compute(clip, RATE).unwrap())
+            .collect();
+        for (i, a) in fingerprints.iter().enumerate() {
+            for (j, b) in fingerprints.iter().enumerate().skip(i + 1) {
+                assert!(
+                    a.distance(b) >= 3 * DUPLICATE_MAX_DISTANCE,
+                    "utterances {i} and {j} differ in only {} bits",
+                    a.distance(b)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_fingerprint_matches_the_same_speech() {
+        let clip = speech(&[A, E, I, O, U, A, E, I, O], 140.0, 2.0);
+        let fingerprint = AudioFingerprint::compute(&clip, RATE).unwrap();
+        assert_eq!(fingerprint.duration_ms, 2000);
+
+        let quieter: Vec<f32> = clip.iter().map(|sample| sample * 0.5).collect();
+        assert_eq!(AudioFingerprint::compute(&quieter, RATE), Some(fingerprint));
+
+        let hissy = AudioFingerprint::compute(&with_hiss(&clip, 0.002), RATE).unwrap();
+        assert!(fingerprint.distance(&hissy) < DUPLICATE_MAX_DISTANCE);
+    }
+
+    #[test]
+    fn test_different_speech_does_not_collide() {
+        assert_no_collisions(&utterances(2.0));
+    }
+
+    #[test]
+    fn test_short_clips_do_not_collide() {
+        assert_no_collisions(&utterances(0.6));
+        assert_no_collisions(&utterances(0.9));
+
+        let too_short = speech(&[A, E], 120.0, 0.4);
+        assert_eq!(AudioFingerprint::compute(&too_short, RATE), None);
+        assert_eq!(AudioFingerprint::compute(&[], RATE), None);
+    }
+
+    #[test]
+    fn test_recent_fingerprints_only_match_recent_recordings() {
+        let print = |bits: u64| AudioFingerprint {
+            bits,
+            duration_ms: 3000,
+        };
+        let now = Local::now();
+        let mut recent = RecentFingerprints::default();
+        recent.push(print(0b1011), now - Duration::seconds(30));
+        assert_eq!(recent.find_duplicate(&print(0b1011), now), None);
+
+        recent.push(print(0b1111), now - Duration::seconds(3));
+        assert_eq!(recent.find_duplicate(&print(0b1011), now), Some(1));
+        assert_eq!(recent.find_duplicate(&print(u64::MAX), now), None);
+
+        // The same bits from a recording twice as long aren't the same recording
+        let longer = AudioFingerprint {
+            bits: 0b1111,
+            duration_ms: 6000,
+        };
+        assert_eq!(recent.find_duplicate(&longer, now), None);
+
+        for i in 0..MAX_RECENT_FINGERPRINTS as u64 {
+            recent.push(print(u64::MAX << i), now);
+        }
+        assert_eq!(recent.entries.len(), MAX_RECENT_FINGERPRINTS);
+        assert_eq!(recent.find_duplicate(&print(0b1111), now), None);
+    }
+}
@@ -0,0 +1,110 @@
+//! Paragraph layout for long transcripts, which speech models return as one block.
+
+use crate::punctuation::ends_sentence;
+
+/// Transcripts with this many words or fewer are short dictations and stay one paragraph.
+pub const PARAGRAPH_MIN_WORDS: u32 = 50;
+
+/// Group the sentences of `text` into paragraphs of `sentences_per_paragraph`, separated by
+/// blank lines. A sentence ends at a word ending in `.`, `?` or `!`, unless it's an
+/// abbreviation such as "Dr.". Words are rejoined with single spaces. Text that already has
+/// paragraph breaks is returned unchanged, as is everything when `sentences_per_paragraph`
+/// is 0.
+pub fn paragraphize(text: &str, sentences_per_paragraph: u32) -> String {
+    if sentences_per_paragraph == 0 || text.contains("\n\n") {
+        return text.to_string();
+    }
+
+    let mut paragraphs = Vec::new();
+    let mut current: Vec<&str> = Vec::new();
+    let mut sentences = 0;
+    for word in text.split_whitespace() {
+        current.push(word);
+        if !ends_sentence(word) {
+            continue;
+        }
+        sentences += 1;
+        if sentences == sentences_per_paragraph {
+            paragraphs.push(current.join(" "));
+            current.clear();
+            sentences = 0;
+        }
+    }
+    if !current.is_empty() {
+        paragraphs.push(current.join(" "));
+    }
+    paragraphs.join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_groups_sentences_into_paragraphs() {
+        assert_eq!(
+            paragraphize("One. Two. Three. Four. Five.", 2),
+            "One. Two.\n\nThree. Four.\n\nFive."
+        );
+    }
+
+    #[test]
+    fn test_question_and_exclamation_marks_end_sentences() {
+        assert_eq!(
+            paragraphize("Ready? Yes! Go now. Done.", 2),
+            "Ready? Yes!\n\nGo now. Done."
+        );
+    }
+
+    #[test]
+    fn test_abbreviations_do_not_end_sentences() {
+        assert_eq!(
+            paragraphize("I met Mr. Smith and Dr. Jones. They said hi. We left.", 1),
+            "I met Mr. Smith and Dr. Jones.\n\nThey said hi.\n\nWe left."
+        );
+    }
+
+    #[test]
+    fn test_trailing_text_without_punctuation_is_kept() {
+        assert_eq!(
+            paragraphize("First one. Second one. and then", 2),
+            "First one. Second one.\n\nand then"
+        );
+    }
+
+    #[test]
+    fn test_punctuation_inside_closers_ends_sentence() {
+        assert_eq!(
+            paragraphize("She said \"stop.\" (Then left.) Bye.", 1),
+            "She said \"stop.\"\n\n(Then left.)\n\nBye."
+        );
+    }
+
+    #[test]
+    fn test_single_sentence_is_unchanged() {
+        assert_eq!(
+            paragraphize("Just one sentence here.", 4),
+            "Just one sentence here."
+        );
+        assert_eq!(
+            paragraphize("no punctuation at all", 1),
+            "no punctuation at all"
+        );
+    }
+
+    #[test]
+    fn test_decimal_points_do_not_split() {
+        assert_eq!(
+            paragraphize("It costs 3.5 dollars. Fine.", 1),
+            "It costs 3.5 dollars.\n\nFine."
+        );
+    }
+
+    #[test]
+    fn test_existing_paragraphs_and_zero_are_left_alone() {
+        let formatted = "Already. Split.\n\nInto paragraphs.";
+        assert_eq!(paragraphize(formatted, 1), formatted);
+        assert_eq!(paragraphize("One. Two.", 0), "One. Two.");
+        assert_eq!(paragraphize("", 4), "");
+    }
+}
@@ -0,0 +1,149 @@
+use serde::{Deserialize, Serialize};
+
+/// The user's keyboard layout. Shortcuts are registered by physical key, named after the
+/// QWERTY key in that position, so other layouts need their labels translated.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyboardLayout {
+    #[default]
+    Qwerty,
+    Dvorak,
+    Colemak,
+}
+
+/// What each QWERTY key types on Dvorak.
+const DVORAK: &[(char, char)] = &[
+    ('q', '\''),
+    ('w', ','),
+    ('e', '.'),
+    ('r', 'p'),
+    ('t', 'y'),
+    ('y', 'f'),
+    ('u', 'g'),
+    ('i', 'c'),
+    ('o', 'r'),
+    ('p', 'l'),
+    ('[', '/'),
+    (']', '='),
+    ('s', 'o'),
+    ('d', 'e'),
+    ('f', 'u'),
+    ('g', 'i'),
+    ('h', 'd'),
+    ('j', 'h'),
+    ('k', 't'),
+    ('l', 'n'),
+    (';', 's'),
+    ('\'', '-'),
+    ('z', ';'),
+    ('x', 'q'),
+    ('c', 'j'),
+    ('v', 'k'),
+    ('b', 'x'),
+    ('n', 'b'),
+    (',', 'w'),
+    ('.', 'v'),
+    ('/', 'z'),
+    ('-', '['),
+    ('=', ']'),
+];
+
+/// What each QWERTY key types on Colemak, where it differs.
+const COLEMAK: &[(char, char)] = &[
+    ('e', 'f'),
+    ('r', 'p'),
+    ('t', 'g'),
+    ('y', 'j'),
+    ('u', 'l'),
+    ('i', 'u'),
+    ('o', 'y'),
+    ('p', ';'),
+    ('s', 'r'),
+    ('d', 's'),
+    ('f', 't'),
+    ('g', 'd'),
+    ('j', 'n'),
+    ('k', 'e'),
+    ('l', 'i'),
+    (';', 'o'),
+    ('n', 'k'),
+];
+
+/// Shortcut names for punctuation keys, as accepted by the global shortcut plugin.
+const NAMED_KEYS: &[(&str, char)] = &[
+    ("Semicolon", ';'),
+    ("Quote", '\''),
+    ("Comma", ','),
+    ("Period", '.'),
+    ("Slash", '/'),
+    ("BracketLeft", '['),
+    ("BracketRight", ']'),
+    ("Minus", '-'),
+    ("Equal", '='),
+];
+
+/// Relabel the keys of a shortcut such as `"Control+KeyS"` with what they type on `layout`,
+/// e.g. `"Control+O"` on Dvorak. Modifiers and keys that don't move between layouts are
+/// left as they are. Only for display: registration still uses the original string.
+pub fn normalize_shortcut_display(shortcut: &str, layout: KeyboardLayout) -> String {
+    let table = match layout {
+        KeyboardLayout::Qwerty => return shortcut.to_string(),
+        KeyboardLayout::Dvorak => DVORAK,
+        KeyboardLayout::Colemak => COLEMAK,
+    };
+
+    shortcut
+        .split('+')
+        .map(|key| {
+            qwerty_char(key.trim())
+                .and_then(|qwerty| table.iter().find(|(from, _)| *from == qwerty))
+                .map(|(_, typed)| typed.to_ascii_uppercase().to_string())
+                .unwrap_or_else(|| key.to_string())
+        })
+        .collect::<Vec<_>>()
+        .join("+")
+}
+
+/// The character a key types on QWERTY, for single characters, `KeyX` codes and named
+/// punctuation keys.
+fn qwerty_char(key: &str) -> Option<char> {
+    let key = key
+        .strip_prefix("Key")
+        .filter(|letter| letter.len() == 1)
+        .unwrap_or(key);
+    let mut chars = key.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(c.to_ascii_lowercase());
+    }
+    NAMED_KEYS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(key))
+        .map(|(_, c)| *c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_shortcut_display() {
+        let cases = [
+            ("Control+KeyS", KeyboardLayout::Qwerty, "Control+KeyS"),
+            ("Control+KeyS", KeyboardLayout::Dvorak, "Control+O"),
+            ("Alt+Semicolon", KeyboardLayout::Dvorak, "Alt+S"),
+            ("Shift+q", KeyboardLayout::Dvorak, "Shift+'"),
+            ("Control+E", KeyboardLayout::Colemak, "Control+F"),
+            ("Control+KeyA", KeyboardLayout::Colemak, "Control+KeyA"),
+            ("Control+Space", KeyboardLayout::Dvorak, "Control+Space"),
+            ("Shift+1", KeyboardLayout::Colemak, "Shift+1"),
+            ("Control+F5", KeyboardLayout::Dvorak, "Control+F5"),
+        ];
+        for (shortcut, layout, expected) in cases {
+            assert_eq!(
+                normalize_shortcut_display(shortcut, layout),
+                expected,
+                "{shortcut} on {layout:?}"
+            );
+        }
+    }
+}
@@ -1,39 +1,58 @@
 mod analytics;
 mod assistive;
 mod audio;
+mod backup;
+mod batch;
+mod changelog;
 mod crypto;
+mod deep_link;
 mod downloader;
+mod fingerprint;
+mod formatting;
+mod keyboard;
 mod llm_cleanup;
 mod local_transcription;
+mod logging;
 mod model_manager;
 mod permissions;
 mod pill;
 mod platform;
+mod punctuation;
 mod recorder;
+mod session;
 mod settings;
+mod shortcuts_display;
 mod storage;
 mod toast;
 mod transcription;
 mod tray;
+mod webhook;
 
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
+use analytics::AnalyticsEvent;
 use anyhow::{anyhow, Context, Result};
-use pill::PillController;
+use fingerprint::AudioFingerprint;
+use keyboard::KeyboardLayout;
+use pill::{ChunkInFlight, ContinuousChunk, PillController};
 use recorder::{
-    validate_recording, CompletedRecording, RecorderManager, RecordingRejectionReason,
-    RecordingSaved,
+    validate_recording_with_config, CompletedRecording, RecorderManager, RecordingRejectionReason,
+    RecordingSaved, ValidationConfig,
 };
 use reqwest::Client;
 use serde::Serialize;
 use settings::{
-    default_local_model, LlmProvider, Replacement, SettingsStore, TranscriptionMode, UserSettings,
+    default_local_model, DoubleTapAction, LlmProvider, OverlayPosition, PasteMode, Replacement,
+    ReplacementProfile, SettingsStore, TranscriptionMode, TrayIconMode, TrimMode, UserSettings,
 };
+use shortcuts_display::ShortcutDisplayFormat;
+use storage::MAX_REPORT_DAYS;
 use tauri::async_runtime;
+use tauri::menu::MenuItem;
 use tauri::tray::TrayIcon;
 use tauri::Emitter;
 use tauri::{AppHandle, Manager, Wry};
@@ -41,22 +60,60 @@ use tauri::{AppHandle, Manager, Wry};
 #[cfg(target_os = "macos")]
 use tauri::ActivationPolicy;
 use tauri_plugin_aptabase::EventTracker;
+use tauri_plugin_deep_link::DeepLinkExt;
+use tauri_plugin_notification::NotificationExt;
 use tauri_plugin_opener::OpenerExt;
+use tracing::{error, info, warn};
 
 pub(crate) const MAIN_WINDOW_LABEL: &str = "main";
 pub(crate) const SETTINGS_WINDOW_LABEL: &str = "settings";
 pub(crate) const EVENT_RECORDING_START: &str = "recording:start";
 pub(crate) const EVENT_RECORDING_STOP: &str = "recording:stop";
+pub(crate) const EVENT_RECORDING_AMPLITUDE: &str = "recording:amplitude";
 pub(crate) const EVENT_RECORDING_COMPLETE: &str = "recording:complete";
 pub(crate) const EVENT_RECORDING_ERROR: &str = "recording:error";
+pub(crate) const EVENT_RECORDING_DEVICE_RECONNECTED: &str = "recording:device-reconnected";
+pub(crate) const EVENT_RECORDING_CALIBRATING: &str = "recording:calibrating";
+pub(crate) const EVENT_RECORDING_TICK: &str = "recording:tick";
+pub(crate) const EVENT_RECORDING_PEAK_DB: &str = "recording:peak_db";
+pub(crate) const EVENT_RECORDING_CHUNK_SAVED: &str = "recording:chunk_saved";
 pub(crate) const EVENT_TRANSCRIPTION_START: &str = "transcription:start";
 pub(crate) const EVENT_TRANSCRIPTION_COMPLETE: &str = "transcription:complete";
 pub(crate) const EVENT_TRANSCRIPTION_ERROR: &str = "transcription:error";
+pub(crate) const EVENT_TRANSCRIPTION_FALLBACK: &str = "transcription:fallback";
+pub(crate) const EVENT_TRANSCRIPTION_MODEL_LOADING: &str = "transcription:model_loading";
+pub(crate) const EVENT_TRANSCRIPTION_MODEL_READY: &str = "transcription:model_ready";
+pub(crate) const EVENT_TRANSCRIPTION_LLM_STREAMING: &str = "transcription:llm_streaming";
+pub(crate) const EVENT_TRANSCRIPTION_ESTIMATED_DURATION: &str =
+    "transcription:estimated_duration_ms";
 pub(crate) const EVENT_SETTINGS_CHANGED: &str = "settings:changed";
+pub(crate) const EVENT_AUDIO_LEVEL: &str = "audio:level";
+pub(crate) const EVENT_MODEL_UNLOADED: &str = "model:unloaded";
+pub(crate) const EVENT_GOAL_REACHED: &str = "goal:reached";
+pub(crate) const EVENT_SESSION_SUMMARY: &str = "app:session_summary";
 pub(crate) const FEEDBACK_URL: &str = "https://github.com/LegendarySpy/Glimpse/issues";
+/// Matches `identifier` in tauri.conf.json, for finding the app directories before Tauri
+/// has started.
+pub(crate) const APP_IDENTIFIER: &str = "dev.glimpse.glimpse";
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    let log_guard = logging::init();
+
+    // The batch CLI works on files only, so it runs without starting the app
+    if let Some(batch_args) = batch::BatchArgs::from_env() {
+        let code = match batch_args {
+            Ok(args) => batch::run(&args),
+            Err(err) => {
+                error!("Invalid batch arguments: {err}");
+                2
+            }
+        };
+        // Exiting skips destructors, so flush buffered log lines first
+        drop(log_guard);
+        std::process::exit(code);
+    }
+
     let rt = tokio::runtime::Runtime::new().expect("Failed to create Tokio runtime");
     let _guard = rt.enter();
     tauri::async_runtime::set(rt.handle().clone());
@@ -68,6 +125,7 @@ pub fn run() {
         .plugin(tauri_plugin_global_shortcut::Builder::new().build())
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_deep_link::init())
+        .plugin(tauri_plugin_notification::init())
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_process::init());
 
@@ -88,7 +146,7 @@ pub fn run() {
             if model_manager::definition(&settings.local_model).is_none() {
                 settings.local_model = default_local_model();
                 if let Err(err) = settings_store.save(&settings) {
-                    eprintln!("Failed to persist default local model: {err}");
+                    error!("Failed to persist default local model: {err}");
                 }
             }
             app.manage(AppState::new(
@@ -97,9 +155,13 @@ pub fn run() {
                 &handle,
             ));
 
+            backfill_word_counts_once(&settings_store, &handle.state::<AppState>().storage());
+            start_webhook_retry_loop(&handle);
+
             if let Some(window) = handle.get_webview_window(MAIN_WINDOW_LABEL) {
                 let _ = window.hide();
-                platform::overlay::init(&handle, &window);
+                let settings = handle.state::<AppState>().current_settings();
+                platform::overlay::init(&handle, &window, &settings);
             }
 
             if let Some(toast_window) = handle.get_webview_window(toast::WINDOW_LABEL) {
@@ -107,19 +169,45 @@ pub fn run() {
                 platform::toast::init(&handle, &toast_window);
             }
 
+            if handle
+                .state::<AppState>()
+                .current_settings()
+                .llm_api_key_needs_reentry
+            {
+                toast::show(
+                    &handle,
+                    "warning",
+                    Some("API Key Needs Re-entry"),
+                    "Your LLM API key couldn't be decrypted on this device. Enter it again in Settings.",
+                );
+            }
+
             if let Ok(tray) = tray::build_tray(&handle) {
                 handle.state::<AppState>().store_tray(tray);
             }
 
             if let Err(err) = pill::register_shortcuts(&handle) {
-                eprintln!("Failed to register shortcuts: {err}");
+                error!("Failed to register shortcuts: {err}");
+            }
+
+            let link_handle = handle.clone();
+            handle.deep_link().on_open_url(move |event| {
+                for url in event.urls() {
+                    deep_link::handle(&link_handle, &url);
+                }
+            });
+            // A link that launched the app arrived before the handler was registered
+            if let Ok(Some(urls)) = handle.deep_link().get_current() {
+                for url in urls {
+                    deep_link::handle(&handle, &url);
+                }
             }
 
             if let Err(err) = tray::toggle_settings_window(&handle) {
-                eprintln!("Failed to open settings window on launch: {err}");
+                error!("Failed to open settings window on launch: {err}");
             }
 
-            let _ = app.track_event("app_started", None);
+            analytics::track(handle, AnalyticsEvent::AppStarted);
 
             Ok(())
         })
@@ -128,24 +216,78 @@ pub fn run() {
             update_settings,
             get_dictionary,
             set_dictionary,
+            import_dictionary_from_file,
             get_replacements,
             set_replacements,
+            get_replacement_profiles,
+            set_replacement_profiles,
+            set_active_profile,
             get_app_info,
+            vacuum_database,
+            checkpoint_database,
+            set_backup_path,
+            trigger_backup,
             open_data_dir,
             get_transcriptions,
             list_transcriptions_paginated,
             get_transcription_count,
+            export_settings,
+            import_settings,
+            export_transcriptions,
+            tag_transcription,
+            get_transcriptions_by_tag,
+            search_transcriptions,
+            export_audio_as_wav,
+            update_validation_config,
+            calibrate_noise,
+            get_recording_duration_ms,
+            start_level_meter,
+            set_model_idle_timeout,
+            stop_level_meter,
+            get_session_stats,
+            get_session_clipboard_history,
+            clear_session_clipboard_history,
+            set_overlay_opacity,
+            rate_transcription,
+            flag_transcription,
+            get_flagged_transcriptions,
+            get_model_accuracy_report,
+            get_usage_stats_by_model,
+            get_usage_stats_by_day,
+            get_transcriptions_in_range,
+            get_daily_word_counts,
+            get_transcription_heatmap_data,
+            get_peak_recording_hour,
+            get_transcription_streak,
+            get_daily_progress,
+            get_longest_streak,
+            recalculate_word_counts,
             delete_transcription,
+            restore_transcription,
+            get_deleted_transcriptions,
+            anonymize_transcription,
+            merge_transcriptions,
+            get_duplicate_transcriptions,
+            delete_duplicate_keeping_longer,
             delete_all_transcriptions,
             retry_transcription,
+            retry_all_failed_transcriptions,
             retry_llm_cleanup,
             undo_llm_cleanup,
-            model_manager::list_models,
-            model_manager::check_model_status,
+            undo_replacement,
+            model_manager::list_models_with_status,
+            model_manager::get_model_benchmark,
             model_manager::download_model,
+            model_manager::queue_model_download,
+            model_manager::get_model_download_progress,
+            model_manager::get_all_download_progress,
             model_manager::delete_model,
             audio::list_input_devices,
+            audio::list_input_devices_with_capabilities,
+            logging::get_log_path,
+            changelog::get_changelog,
             toast_dismissed,
+            toast::toast_queue_depth,
             check_microphone_permission,
             request_microphone_permission,
             check_accessibility_permission,
@@ -153,18 +295,36 @@ pub fn run() {
             open_microphone_settings,
             complete_onboarding,
             cancel_recording,
+            stop_continuous_recording,
+            get_continuous_session_ids,
             reset_onboarding,
             import_transcription_from_cloud,
             mark_transcription_synced,
             debug_show_toast,
             fetch_llm_models,
-            open_whats_new
+            open_whats_new,
+            toggle_focus_mode,
+            format_shortcut,
+            get_shortcut_display_name,
+            push_transcription_to_webhook
         ])
         .build(tauri::generate_context!())
         .expect("error while building tauri application")
         .run(|handler, event| match event {
             tauri::RunEvent::Exit { .. } => {
-                let _ = handler.track_event("app_exited", None);
+                if let Some(state) = handler.try_state::<AppState>() {
+                    let stats = state.session_stats();
+                    info!(
+                        recordings = stats.recordings_count,
+                        audio_seconds = stats.total_audio_seconds,
+                        words = stats.total_words,
+                        average_latency_ms = ?stats.average_latency_ms,
+                        "Session summary"
+                    );
+                    let _ = handler.emit(EVENT_SESSION_SUMMARY, &stats);
+                    analytics::track(handler, AnalyticsEvent::SessionSummary(stats));
+                }
+                analytics::track(handler, AnalyticsEvent::AppExited);
                 handler.flush_events_blocking();
             }
             _ => {}
@@ -183,9 +343,22 @@ pub struct AppState {
     settings_store: Arc<SettingsStore>,
     settings: parking_lot::Mutex<UserSettings>,
     pub(crate) tray: parking_lot::Mutex<Option<TrayIcon<AppRuntime>>>,
+    pub(crate) tray_record_item: parking_lot::Mutex<Option<MenuItem<AppRuntime>>>,
     pub(crate) settings_close_handler_registered: AtomicBool,
     transcription_cancelled: AtomicBool,
+    /// Set while `retry_all_failed_transcriptions` works through its queue.
+    retrying_failed: AtomicBool,
     pending_recording_path: parking_lot::Mutex<Option<PathBuf>>,
+    session: parking_lot::Mutex<session::RecordingSession>,
+    clipboard_history: parking_lot::Mutex<session::ClipboardHistory>,
+    recent_fingerprints: parking_lot::Mutex<fingerprint::RecentFingerprints>,
+    /// Transcriptions of the latest continuous session's chunks, for merging into one.
+    continuous_session_ids: parking_lot::Mutex<Vec<String>>,
+    download_queue: Arc<downloader::DownloadQueue>,
+    download_progress: Arc<downloader::DownloadProgressMap>,
+    level_meter: parking_lot::Mutex<Option<audio::LevelMeter>>,
+    transcription_count_since_backup: AtomicU32,
+    toast_queue: toast::ToastQueue,
 }
 
 impl AppState {
@@ -205,22 +378,62 @@ impl AppState {
             .expect("Failed to resolve app data directory")
             .join("transcriptions.db");
 
-        let storage = storage::StorageManager::new(storage_path)
-            .expect("Failed to initialize transcription storage");
+        let storage = Arc::new(
+            storage::StorageManager::new(storage_path)
+                .expect("Failed to initialize transcription storage"),
+        );
+        storage.set_auto_checkpoint(settings.wal_auto_checkpoint_enabled);
+
+        match storage.purge_old_deleted() {
+            Ok(0) => {}
+            Ok(purged) => info!("Purged {purged} transcriptions deleted over 30 days ago"),
+            Err(err) => error!("Failed to purge deleted transcriptions: {err}"),
+        }
+
+        if let Ok(base_dir) = recordings_root(app_handle) {
+            let settings_store = Arc::clone(&settings_store);
+            let storage = Arc::clone(&storage);
+            async_runtime::spawn_blocking(move || {
+                backfill_audio_durations_once(&settings_store, &storage, &base_dir);
+            });
+        }
 
         let recorder = Arc::new(RecorderManager::new());
 
+        let local_transcriber = Arc::new(local_transcription::LocalTranscriber::new());
+        local_transcriber.set_idle_timeout(settings.model_idle_timeout_seconds);
+        let unload_app = app_handle.clone();
+        local_transcriber.start_idle_monitor(move |model_key| {
+            info!(model = %model_key, "Unloaded idle local model");
+            emit_event(
+                &unload_app,
+                EVENT_MODEL_UNLOADED,
+                ModelUnloadedPayload { model_key },
+            );
+        });
+
         Self {
             pill: Arc::new(PillController::new(Arc::clone(&recorder))),
             http,
-            local_transcriber: Arc::new(local_transcription::LocalTranscriber::new()),
-            storage: Arc::new(storage),
+            local_transcriber,
+            storage,
             settings_store,
             settings: parking_lot::Mutex::new(settings),
             tray: parking_lot::Mutex::new(None),
+            tray_record_item: parking_lot::Mutex::new(None),
             settings_close_handler_registered: AtomicBool::new(false),
             transcription_cancelled: AtomicBool::new(false),
+            retrying_failed: AtomicBool::new(false),
             pending_recording_path: parking_lot::Mutex::new(None),
+            session: parking_lot::Mutex::new(session::RecordingSession::default()),
+            clipboard_history: parking_lot::Mutex::new(session::ClipboardHistory::default()),
+            recent_fingerprints: parking_lot::Mutex::new(fingerprint::RecentFingerprints::default()),
+            continuous_session_ids: parking_lot::Mutex::new(Vec::new()),
+            download_queue: Arc::new(downloader::DownloadQueue::default()),
+            download_progress: Arc::new(downloader::DownloadProgressMap::default()),
+            level_meter: parking_lot::Mutex::new(None),
+            transcription_count_since_backup: AtomicU32::new(0),
+            toast_queue: toast::ToastQueue::default(),
         }
     }
 
@@ -231,7 +444,7 @@ impl AppState {
                 latest
             }
             Err(err) => {
-                eprintln!("Failed to load settings from DB, using cache: {err}");
+                warn!("Failed to load settings from DB, using cache: {err}");
                 self.settings.lock().clone()
             }
         }
@@ -247,10 +460,22 @@ impl AppState {
         &self.pill
     }
 
+    pub(crate) fn toast_queue(&self) -> &toast::ToastQueue {
+        &self.toast_queue
+    }
+
     fn http(&self) -> Client {
         self.http.clone()
     }
 
+    fn download_queue(&self) -> Arc<downloader::DownloadQueue> {
+        Arc::clone(&self.download_queue)
+    }
+
+    fn download_progress(&self) -> Arc<downloader::DownloadProgressMap> {
+        Arc::clone(&self.download_progress)
+    }
+
     fn local_transcriber(&self) -> Arc<local_transcription::LocalTranscriber> {
         Arc::clone(&self.local_transcriber)
     }
@@ -275,6 +500,38 @@ impl AppState {
         self.transcription_cancelled.store(false, Ordering::SeqCst);
     }
 
+    pub fn record_session_transcription(&self, audio_seconds: f32, words: u32, latency: Duration) {
+        self.session.lock().record(audio_seconds, words, latency);
+    }
+
+    pub fn session_stats(&self) -> session::SessionStats {
+        self.session.lock().snapshot()
+    }
+
+    pub fn record_pasted_text(&self, text: String, transcription_id: Option<String>) {
+        self.clipboard_history.lock().push(session::ClipboardEntry {
+            text,
+            pasted_at: chrono::Local::now(),
+            transcription_id,
+        });
+    }
+
+    /// Remember the transcription of a continuous session's chunk. Chunks of a session that
+    /// has since been replaced by a new one are left out.
+    pub fn record_continuous_chunk(&self, chunk: &ContinuousChunk, transcription_id: String) {
+        if self.pill.continuous_session_id().as_deref() == Some(chunk.session_id.as_str()) {
+            self.continuous_session_ids.lock().push(transcription_id);
+        }
+    }
+
+    pub fn clear_continuous_session_ids(&self) {
+        self.continuous_session_ids.lock().clear();
+    }
+
+    pub fn continuous_session_ids(&self) -> Vec<String> {
+        self.continuous_session_ids.lock().clone()
+    }
+
     pub fn set_pending_path(&self, path: Option<PathBuf>) {
         *self.pending_recording_path.lock() = path;
     }
@@ -282,6 +539,21 @@ impl AppState {
     pub fn take_pending_path(&self) -> Option<PathBuf> {
         self.pending_recording_path.lock().take()
     }
+
+    pub fn has_pending_path(&self) -> bool {
+        self.pending_recording_path.lock().is_some()
+    }
+
+    /// Called as the transcription task for `path` exits, however it exits. Leaves the state
+    /// alone if a newer transcription has taken over since.
+    fn finish_transcription_task(&self, path: &Path) {
+        let mut pending = self.pending_recording_path.lock();
+        if pending.as_deref().is_some_and(|pending| pending != path) {
+            return;
+        }
+        *pending = None;
+        self.clear_cancellation();
+    }
 }
 
 #[tauri::command]
@@ -325,7 +597,12 @@ fn complete_onboarding(
     state
         .persist_settings(settings)
         .map_err(|err| err.to_string())?;
-    analytics::track_onboarding_completed(&app, &model);
+    analytics::track(
+        &app,
+        AnalyticsEvent::OnboardingCompleted {
+            model_selected: model,
+        },
+    );
     Ok(())
 }
 
@@ -342,53 +619,55 @@ fn reset_onboarding(
     Ok(())
 }
 
-#[tauri::command]
-#[allow(non_snake_case)]
-fn update_settings(
-    smartShortcut: String,
-    smartEnabled: bool,
-    holdShortcut: String,
-    holdEnabled: bool,
-    toggleShortcut: String,
-    toggleEnabled: bool,
-    transcriptionMode: TranscriptionMode,
-    localModel: String,
-    microphoneDevice: Option<String>,
-    language: String,
-    llmCleanupEnabled: bool,
-    llmProvider: LlmProvider,
-    llmEndpoint: String,
-    llmApiKey: String,
-    llmModel: String,
-    userContext: String,
-    app: AppHandle<AppRuntime>,
-    state: tauri::State<AppState>,
-) -> Result<UserSettings, String> {
-    if smartEnabled && smartShortcut.trim().is_empty() {
-        return Err("Smart shortcut cannot be empty when enabled".into());
-    }
-
-    if holdEnabled && holdShortcut.trim().is_empty() {
-        return Err("Hold shortcut cannot be empty when enabled".into());
-    }
-
-    if toggleEnabled && toggleShortcut.trim().is_empty() {
-        return Err("Toggle shortcut cannot be empty when enabled".into());
-    }
-
-    if !smartEnabled && !holdEnabled && !toggleEnabled {
+/// Longer cooldowns would start swallowing deliberate quick presses.
+const MAX_SHORTCUT_COOLDOWN_MS: u64 = 1000;
+const SMART_TAP_THRESHOLD_RANGE_MS: std::ops::RangeInclusive<u64> = 100..=800;
+const MIN_RECORDING_DURATION_RANGE_MS: std::ops::RangeInclusive<u64> = 100..=2000;
+/// Past this, two separate recordings would start being read as a double tap.
+const MAX_DOUBLE_TAP_WINDOW_MS: u64 = 1000;
+/// Large models on slow machines legitimately take a while on long recordings.
+const MIN_LOCAL_TRANSCRIPTION_TIMEOUT_SECONDS: u32 = 30;
+/// Shorter chunks spend more time on per-recording overhead than on speech.
+const MIN_CHUNK_DURATION_SECONDS: u32 = 30;
+/// Shorter waits would cut recordings off at an ordinary pause for thought.
+const MIN_AUTO_STOP_SILENCE_SECONDS: u32 = 5;
+/// Below this, microphone hiss never counts as silence; above it, quiet speech does.
+const AUTO_STOP_SILENCE_RMS_RANGE: std::ops::RangeInclusive<f32> = 0.001..=0.1;
+/// A recording left waiting longer than this for its microphone has likely been forgotten.
+const MAX_RECONNECT_GRACE_SECONDS: u32 = 60;
+
+/// Check settings as a whole before they are saved, whether they come from the settings
+/// window or an imported backup.
+fn validate_settings(settings: &UserSettings) -> Result<(), String> {
+    let recording_shortcuts = [
+        ("Smart", settings.smart_enabled, &settings.smart_shortcut),
+        ("Hold", settings.hold_enabled, &settings.hold_shortcut),
+        ("Toggle", settings.toggle_enabled, &settings.toggle_shortcut),
+    ];
+    if recording_shortcuts.iter().all(|(_, enabled, _)| !enabled) {
         return Err("At least one recording mode must be enabled".into());
     }
 
     let mut enabled_shortcuts: Vec<(&str, &str)> = vec![];
-    if smartEnabled {
-        enabled_shortcuts.push(("Smart", smartShortcut.trim()));
-    }
-    if holdEnabled {
-        enabled_shortcuts.push(("Hold", holdShortcut.trim()));
-    }
-    if toggleEnabled {
-        enabled_shortcuts.push(("Toggle", toggleShortcut.trim()));
+    for (name, enabled, shortcut) in recording_shortcuts.into_iter().chain([
+        (
+            "Copy latest",
+            settings.copy_latest_enabled,
+            &settings.copy_latest_shortcut,
+        ),
+        (
+            "Continuous",
+            settings.continuous_enabled,
+            &settings.continuous_shortcut,
+        ),
+    ]) {
+        if !enabled {
+            continue;
+        }
+        if shortcut.trim().is_empty() {
+            return Err(format!("{name} shortcut cannot be empty when enabled"));
+        }
+        enabled_shortcuts.push((name, shortcut.trim()));
     }
 
     for i in 0..enabled_shortcuts.len() {
@@ -404,19 +683,206 @@ fn update_settings(
         }
     }
 
-    if model_manager::definition(&localModel).is_none() {
+    if model_manager::definition(&settings.local_model).is_none() {
         return Err("Unknown model selection".into());
     }
 
-    if llmCleanupEnabled && !matches!(llmProvider, LlmProvider::None) {
-        if matches!(llmProvider, LlmProvider::Custom) && llmEndpoint.trim().is_empty() {
-            return Err("Custom LLM endpoint cannot be empty".into());
+    if settings.llm_cleanup_enabled {
+        match settings.llm_provider {
+            LlmProvider::Custom if settings.llm_endpoint.trim().is_empty() => {
+                return Err("Custom LLM endpoint cannot be empty".into());
+            }
+            LlmProvider::OpenAI if settings.llm_api_key.trim().is_empty() => {
+                return Err("OpenAI API key is required".into());
+            }
+            LlmProvider::Anthropic if settings.llm_api_key.trim().is_empty() => {
+                return Err("Anthropic API key is required".into());
+            }
+            _ => {}
+        }
+    }
+
+    if !(1..=100).contains(&settings.llm_cleanup_min_words) {
+        return Err("LLM cleanup minimum words must be between 1 and 100".into());
+    }
+
+    validate_audio_bitrate(settings.audio_bitrate_kbps)?;
+
+    if !(0.0..=1.0).contains(&settings.min_word_confidence) {
+        return Err("Minimum word confidence must be between 0 and 1".into());
+    }
+
+    if settings.sentences_per_paragraph == 0 {
+        return Err("Sentences per paragraph must be at least 1".into());
+    }
+
+    transcription::validate_custom_headers(&settings.custom_api_headers)?;
+
+    if let OverlayPosition::Custom {
+        x_percent,
+        y_percent,
+    } = settings.overlay_position
+    {
+        if !(0.0..=1.0).contains(&x_percent) || !(0.0..=1.0).contains(&y_percent) {
+            return Err("Custom overlay position must be between 0 and 1".into());
+        }
+    }
+
+    if settings.shortcut_cooldown_ms > MAX_SHORTCUT_COOLDOWN_MS {
+        return Err(format!(
+            "Shortcut cooldown must be at most {MAX_SHORTCUT_COOLDOWN_MS} ms"
+        ));
+    }
+
+    if settings.double_tap_window_ms > MAX_DOUBLE_TAP_WINDOW_MS {
+        return Err(format!(
+            "Double tap window must be at most {MAX_DOUBLE_TAP_WINDOW_MS} ms"
+        ));
+    }
+
+    if !SMART_TAP_THRESHOLD_RANGE_MS.contains(&settings.smart_tap_threshold_ms) {
+        return Err(format!(
+            "Smart tap threshold must be between {} and {} ms",
+            SMART_TAP_THRESHOLD_RANGE_MS.start(),
+            SMART_TAP_THRESHOLD_RANGE_MS.end()
+        ));
+    }
+
+    if !MIN_RECORDING_DURATION_RANGE_MS.contains(&settings.min_recording_duration_ms) {
+        return Err(format!(
+            "Minimum recording duration must be between {} and {} ms",
+            MIN_RECORDING_DURATION_RANGE_MS.start(),
+            MIN_RECORDING_DURATION_RANGE_MS.end()
+        ));
+    }
+    if settings.local_transcription_timeout_seconds < MIN_LOCAL_TRANSCRIPTION_TIMEOUT_SECONDS {
+        return Err(format!(
+            "Local transcription timeout must be at least {MIN_LOCAL_TRANSCRIPTION_TIMEOUT_SECONDS} seconds"
+        ));
+    }
+    if settings.chunk_duration_seconds < MIN_CHUNK_DURATION_SECONDS {
+        return Err(format!(
+            "Chunk duration must be at least {MIN_CHUNK_DURATION_SECONDS} seconds"
+        ));
+    }
+
+    if let Some(url) = settings.webhook_url.as_deref().map(str::trim) {
+        if !url.is_empty() {
+            webhook::validate_url(url)?;
         }
-        if matches!(llmProvider, LlmProvider::OpenAI) && llmApiKey.trim().is_empty() {
-            return Err("OpenAI API key is required".into());
+    }
+
+    if let Some(prompt) = settings
+        .llm_system_prompt_override
+        .as_deref()
+        .map(str::trim)
+    {
+        if !prompt.is_empty() {
+            llm_cleanup::validate_system_prompt(prompt)?;
         }
     }
 
+    if settings.hybrid_local_timeout_seconds == 0 {
+        return Err("Hybrid local timeout must be at least 1 second".into());
+    }
+
+    if settings
+        .auto_stop_silence_seconds
+        .is_some_and(|seconds| seconds < MIN_AUTO_STOP_SILENCE_SECONDS)
+    {
+        return Err(format!(
+            "Auto-stop silence must be at least {MIN_AUTO_STOP_SILENCE_SECONDS} seconds"
+        ));
+    }
+    if !AUTO_STOP_SILENCE_RMS_RANGE.contains(&settings.auto_stop_silence_rms) {
+        return Err(format!(
+            "Auto-stop silence threshold must be between {} and {}",
+            AUTO_STOP_SILENCE_RMS_RANGE.start(),
+            AUTO_STOP_SILENCE_RMS_RANGE.end()
+        ));
+    }
+
+    if settings
+        .reconnect_grace_seconds
+        .is_some_and(|seconds| seconds > MAX_RECONNECT_GRACE_SECONDS)
+    {
+        return Err(format!(
+            "Microphone reconnect wait must be at most {MAX_RECONNECT_GRACE_SECONDS} seconds"
+        ));
+    }
+
+    if settings.auto_backup_enabled && settings.backup_path.is_none() {
+        return Err("Choose a backup folder before turning on automatic backups".into());
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+fn update_settings(
+    smartShortcut: String,
+    smartEnabled: bool,
+    holdShortcut: String,
+    holdEnabled: bool,
+    toggleShortcut: String,
+    toggleEnabled: bool,
+    transcriptionMode: TranscriptionMode,
+    localModel: String,
+    microphoneDevice: Option<String>,
+    language: String,
+    llmCleanupEnabled: bool,
+    llmProvider: LlmProvider,
+    llmEndpoint: String,
+    llmApiKey: String,
+    llmModel: String,
+    userContext: String,
+    shortcutDisplayFormat: Option<ShortcutDisplayFormat>,
+    llmCleanupMinWords: Option<u32>,
+    webhookUrl: Option<String>,
+    webhookSecret: Option<String>,
+    webhookOnEveryTranscription: Option<bool>,
+    hybridLocalTimeoutSeconds: Option<u32>,
+    copyLatestShortcut: Option<String>,
+    copyLatestEnabled: Option<bool>,
+    pasteMode: Option<PasteMode>,
+    audioBitrateKbps: Option<u32>,
+    minWordConfidence: Option<f32>,
+    shortcutCooldownMs: Option<u64>,
+    smartTapThresholdMs: Option<u64>,
+    minRecordingDurationMs: Option<u64>,
+    dailyWordGoal: Option<u32>,
+    localTranscriptionTimeoutSeconds: Option<u32>,
+    spotlightIndexingEnabled: Option<bool>,
+    trimMode: Option<TrimMode>,
+    doubleTapWindowMs: Option<u64>,
+    doubleTapAction: Option<DoubleTapAction>,
+    llmSystemPromptOverride: Option<String>,
+    smartPunctuationEnabled: Option<bool>,
+    trayIconMode: Option<TrayIconMode>,
+    clipboardHistoryEnabled: Option<bool>,
+    replaceConsecutiveSpaces: Option<bool>,
+    stripTrailingPeriod: Option<bool>,
+    llmStreamingEnabled: Option<bool>,
+    overlayCornerRadius: Option<f32>,
+    autoParagraphEnabled: Option<bool>,
+    sentencesPerParagraph: Option<u32>,
+    analyticsEnabled: Option<bool>,
+    keyboardLayout: Option<KeyboardLayout>,
+    walAutoCheckpointEnabled: Option<bool>,
+    customApiHeaders: Option<Vec<(String, String)>>,
+    overlayPosition: Option<OverlayPosition>,
+    continuousShortcut: Option<String>,
+    continuousEnabled: Option<bool>,
+    chunkDurationSeconds: Option<u32>,
+    duplicateDetectionEnabled: Option<bool>,
+    autoStopSilenceSeconds: Option<u32>,
+    autoStopSilenceRms: Option<f32>,
+    autoBackupEnabled: Option<bool>,
+    reconnectGraceSeconds: Option<u32>,
+    app: AppHandle<AppRuntime>,
+    state: tauri::State<AppState>,
+) -> Result<UserSettings, String> {
     let mut next = state.current_settings();
     let prev = next.clone();
     next.smart_shortcut = smartShortcut;
@@ -428,37 +894,284 @@ fn update_settings(
     next.transcription_mode = transcriptionMode;
     next.local_model = localModel;
     next.microphone_device = microphoneDevice;
-    next.language = language;
+    next.language = language.into();
     next.llm_cleanup_enabled = llmCleanupEnabled;
     next.llm_provider = llmProvider;
     next.llm_endpoint = llmEndpoint;
     next.llm_api_key = llmApiKey;
     next.llm_model = llmModel;
     next.user_context = userContext;
+    if let Some(format) = shortcutDisplayFormat {
+        next.shortcut_display_format = format;
+    }
+    if let Some(min_words) = llmCleanupMinWords {
+        next.llm_cleanup_min_words = min_words;
+    }
+    if let Some(url) = webhookUrl {
+        let url = url.trim().to_string();
+        next.webhook_url = if url.is_empty() { None } else { Some(url) };
+    }
+    if let Some(secret) = webhookSecret {
+        next.webhook_secret = if secret.is_empty() {
+            None
+        } else {
+            Some(secret)
+        };
+    }
+    if let Some(enabled) = webhookOnEveryTranscription {
+        next.webhook_on_every_transcription = enabled;
+    }
+    if let Some(seconds) = hybridLocalTimeoutSeconds {
+        next.hybrid_local_timeout_seconds = seconds;
+    }
+    if let Some(shortcut) = copyLatestShortcut {
+        next.copy_latest_shortcut = shortcut.trim().to_string();
+    }
+    if let Some(enabled) = copyLatestEnabled {
+        next.copy_latest_enabled = enabled;
+    }
+    if let Some(shortcut) = continuousShortcut {
+        next.continuous_shortcut = shortcut.trim().to_string();
+    }
+    if let Some(enabled) = continuousEnabled {
+        next.continuous_enabled = enabled;
+    }
+    if let Some(mode) = pasteMode {
+        next.paste_mode = mode;
+    }
+    if let Some(kbps) = audioBitrateKbps {
+        next.audio_bitrate_kbps = kbps;
+    }
+    if let Some(confidence) = minWordConfidence {
+        next.min_word_confidence = confidence;
+    }
+    if let Some(cooldown) = shortcutCooldownMs {
+        next.shortcut_cooldown_ms = cooldown;
+    }
+    if let Some(threshold) = smartTapThresholdMs {
+        next.smart_tap_threshold_ms = threshold;
+    }
+    if let Some(duration) = minRecordingDurationMs {
+        next.min_recording_duration_ms = duration;
+    }
+    if let Some(goal) = dailyWordGoal {
+        // 0 turns the goal off
+        next.daily_word_goal = (goal > 0).then_some(goal);
+    }
+    if let Some(seconds) = localTranscriptionTimeoutSeconds {
+        next.local_transcription_timeout_seconds = seconds;
+    }
+    if let Some(enabled) = spotlightIndexingEnabled {
+        next.spotlight_indexing_enabled = enabled;
+    }
+    if let Some(mode) = trimMode {
+        next.trim_mode = mode;
+    }
+    if let Some(window) = doubleTapWindowMs {
+        next.double_tap_window_ms = window;
+    }
+    if let Some(action) = doubleTapAction {
+        next.double_tap_action = action;
+    }
+    if let Some(prompt) = llmSystemPromptOverride {
+        let prompt = prompt.trim().to_string();
+        next.llm_system_prompt_override = (!prompt.is_empty()).then_some(prompt);
+    }
+    if let Some(enabled) = smartPunctuationEnabled {
+        next.smart_punctuation_enabled = enabled;
+    }
+    if let Some(mode) = trayIconMode {
+        next.tray_icon_mode = mode;
+    }
+    if let Some(enabled) = clipboardHistoryEnabled {
+        next.clipboard_history_enabled = enabled;
+    }
+    if let Some(enabled) = replaceConsecutiveSpaces {
+        next.replace_consecutive_spaces = enabled;
+    }
+    if let Some(enabled) = stripTrailingPeriod {
+        next.strip_trailing_period = enabled;
+    }
+    match llmStreamingEnabled {
+        Some(enabled) => next.llm_streaming_enabled = enabled,
+        // Switching provider picks up the new provider's default
+        None if prev.llm_provider != next.llm_provider => {
+            next.llm_streaming_enabled =
+                settings::default_llm_streaming_enabled(&next.llm_provider);
+        }
+        None => {}
+    }
+    if let Some(radius) = overlayCornerRadius {
+        next.overlay_corner_radius = radius.max(0.0);
+    }
+    if let Some(enabled) = autoParagraphEnabled {
+        next.auto_paragraph_enabled = enabled;
+    }
+    if let Some(sentences) = sentencesPerParagraph {
+        next.sentences_per_paragraph = sentences;
+    }
+    if let Some(enabled) = analyticsEnabled {
+        next.analytics_enabled = enabled;
+    }
+    if let Some(layout) = keyboardLayout {
+        next.keyboard_layout = layout;
+    }
+    if let Some(enabled) = walAutoCheckpointEnabled {
+        next.wal_auto_checkpoint_enabled = enabled;
+    }
+    if let Some(headers) = customApiHeaders {
+        next.custom_api_headers = headers;
+    }
+    if let Some(position) = overlayPosition {
+        next.overlay_position = position;
+    }
+    if let Some(seconds) = chunkDurationSeconds {
+        next.chunk_duration_seconds = seconds;
+    }
+    if let Some(enabled) = duplicateDetectionEnabled {
+        next.duplicate_detection_enabled = enabled;
+    }
+    if let Some(seconds) = autoStopSilenceSeconds {
+        next.auto_stop_silence_seconds = (seconds > 0).then_some(seconds);
+    }
+    if let Some(rms) = autoStopSilenceRms {
+        next.auto_stop_silence_rms = rms;
+    }
+    if let Some(enabled) = autoBackupEnabled {
+        next.auto_backup_enabled = enabled;
+    }
+    if let Some(seconds) = reconnectGraceSeconds {
+        // 0 gives up on a disconnected microphone straight away
+        next.reconnect_grace_seconds = (seconds > 0).then_some(seconds);
+    }
 
+    validate_settings(&next)?;
     let next = state
         .persist_settings(next)
         .map_err(|err| err.to_string())?;
+    if prev.clipboard_history_enabled && !next.clipboard_history_enabled {
+        state.clipboard_history.lock().clear();
+    }
 
     pill::register_shortcuts(&app).map_err(|err| err.to_string())?;
+    track_settings_changes(&app, &prev, &next);
 
     if prev.transcription_mode != next.transcription_mode
         || prev.local_model != next.local_model
         || prev.microphone_device != next.microphone_device
+        || prev.smart_shortcut != next.smart_shortcut
+        || prev.smart_enabled != next.smart_enabled
+        || prev.hold_shortcut != next.hold_shortcut
+        || prev.hold_enabled != next.hold_enabled
+        || prev.toggle_shortcut != next.toggle_shortcut
+        || prev.toggle_enabled != next.toggle_enabled
+        || prev.shortcut_display_format != next.shortcut_display_format
+        || prev.keyboard_layout != next.keyboard_layout
     {
         if let Err(err) = tray::refresh_tray_menu(&app, &next) {
-            eprintln!("Failed to refresh tray menu: {err}");
+            error!("Failed to refresh tray menu: {err}");
+        }
+    }
+    if prev.tray_icon_mode != next.tray_icon_mode {
+        if let Err(err) = tray::update_tray_icon(&app, state.pill().status()) {
+            error!("Failed to update tray icon: {err}");
+        }
+    }
+    if prev.analytics_enabled && !next.analytics_enabled {
+        analytics::track_opt_out(&app);
+    }
+    state
+        .storage()
+        .set_auto_checkpoint(next.wal_auto_checkpoint_enabled);
+    if prev.overlay_corner_radius != next.overlay_corner_radius {
+        if let Some(window) = app.get_webview_window(MAIN_WINDOW_LABEL) {
+            platform::overlay::set_backdrop_corner_radius(&window, next.overlay_corner_radius);
         }
     }
 
     if let Err(err) = app.emit(EVENT_SETTINGS_CHANGED, &next) {
-        eprintln!("Failed to emit settings change: {err}");
+        error!("Failed to emit settings change: {err}");
     }
 
     Ok(next)
 }
 
+/// Report model switches and shortcuts that were bound or rebound.
+fn track_settings_changes(app: &AppHandle<AppRuntime>, prev: &UserSettings, next: &UserSettings) {
+    if prev.local_model != next.local_model {
+        analytics::track(
+            app,
+            AnalyticsEvent::ModelSwitched {
+                from: prev.local_model.clone(),
+                to: next.local_model.clone(),
+            },
+        );
+    }
+
+    let shortcut = |enabled: bool, shortcut: &str| enabled.then(|| shortcut.to_string());
+    let shortcuts = [
+        (
+            "smart",
+            shortcut(prev.smart_enabled, &prev.smart_shortcut),
+            shortcut(next.smart_enabled, &next.smart_shortcut),
+        ),
+        (
+            "hold",
+            shortcut(prev.hold_enabled, &prev.hold_shortcut),
+            shortcut(next.hold_enabled, &next.hold_shortcut),
+        ),
+        (
+            "toggle",
+            shortcut(prev.toggle_enabled, &prev.toggle_shortcut),
+            shortcut(next.toggle_enabled, &next.toggle_shortcut),
+        ),
+        (
+            "copy_latest",
+            shortcut(prev.copy_latest_enabled, &prev.copy_latest_shortcut),
+            shortcut(next.copy_latest_enabled, &next.copy_latest_shortcut),
+        ),
+        (
+            "continuous",
+            shortcut(prev.continuous_enabled, &prev.continuous_shortcut),
+            shortcut(next.continuous_enabled, &next.continuous_shortcut),
+        ),
+    ];
+    for (mode, before, after) in shortcuts {
+        if after.is_some() && before != after {
+            analytics::track(
+                app,
+                AnalyticsEvent::ShortcutRegistered {
+                    mode: mode.to_string(),
+                },
+            );
+        }
+    }
+}
+
+fn validate_audio_bitrate(kbps: u32) -> Result<(), String> {
+    if recorder::SUPPORTED_BITRATES_KBPS.contains(&kbps) {
+        Ok(())
+    } else {
+        let allowed = recorder::SUPPORTED_BITRATES_KBPS
+            .iter()
+            .map(|kbps| kbps.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        Err(format!("Audio bitrate must be one of {allowed} kbps"))
+    }
+}
+
+const MAX_DICTIONARY_ENTRIES: usize = 64;
+const MAX_DICTIONARY_FILE_BYTES: u64 = 1024 * 1024;
+
 fn sanitize_dictionary_entries(entries: &[String]) -> Vec<String> {
+    let mut cleaned = dedupe_dictionary_entries(entries);
+    cleaned.truncate(MAX_DICTIONARY_ENTRIES);
+    cleaned
+}
+
+/// Trimmed, capped entries without blanks or case-insensitive repeats, in their original order.
+fn dedupe_dictionary_entries(entries: &[String]) -> Vec<String> {
     let mut seen = HashSet::new();
     let mut cleaned = Vec::new();
 
@@ -474,8 +1187,36 @@ fn sanitize_dictionary_entries(entries: &[String]) -> Vec<String> {
             let capped = capped.trim_end().to_string();
             cleaned.push(capped);
         }
-        if cleaned.len() >= 64 {
-            break;
+    }
+
+    cleaned
+}
+
+/// The existing dictionary followed by one entry per line of `text`, sanitized as usual.
+/// Also returns how many entries were dropped for going over the limit.
+fn merge_dictionary_import(existing: &[String], text: &str) -> (Vec<String>, usize) {
+    let combined: Vec<String> = existing
+        .iter()
+        .cloned()
+        .chain(text.lines().map(str::to_string))
+        .collect();
+    let mut merged = dedupe_dictionary_entries(&combined);
+    let dropped = merged.len().saturating_sub(MAX_DICTIONARY_ENTRIES);
+    merged.truncate(MAX_DICTIONARY_ENTRIES);
+    (merged, dropped)
+}
+
+fn sanitize_tags(tags: &[String]) -> Vec<String> {
+    let mut seen = HashSet::new();
+    let mut cleaned = Vec::new();
+
+    for raw in tags.iter().flat_map(|tag| tag.split(',')) {
+        let trimmed = raw.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        if seen.insert(trimmed.to_lowercase()) {
+            cleaned.push(trimmed.to_string());
         }
     }
 
@@ -499,6 +1240,33 @@ fn build_dictionary_prompt(entries: &[String]) -> Option<String> {
     Some(prompt)
 }
 
+/// Run rule-based punctuation over a local transcript LLM cleanup didn't touch, unless the
+/// model is Whisper, which punctuates its own output.
+fn punctuate_if_needed(
+    transcript: String,
+    settings: &settings::UserSettings,
+    use_local: bool,
+    llm_cleaned: bool,
+) -> String {
+    let needs_punctuation = model_manager::definition(&settings.local_model)
+        .is_some_and(|model| !matches!(model.engine, model_manager::LocalModelEngine::Whisper));
+    if !settings.smart_punctuation_enabled || !use_local || llm_cleaned || !needs_punctuation {
+        return transcript;
+    }
+    punctuation::add_punctuation(&transcript)
+}
+
+/// Break long transcripts into paragraphs when the user turned that on. Short dictations
+/// are left as one block.
+fn paragraphize_if_needed(transcript: String, settings: &settings::UserSettings) -> String {
+    if !settings.auto_paragraph_enabled
+        || count_words(&transcript) <= formatting::PARAGRAPH_MIN_WORDS
+    {
+        return transcript;
+    }
+    formatting::paragraphize(&transcript, settings.sentences_per_paragraph)
+}
+
 fn dictionary_prompt_for_model(
     model: &model_manager::ReadyModel,
     settings: &settings::UserSettings,
@@ -539,7 +1307,58 @@ fn set_dictionary(
     Ok(cleaned)
 }
 
-fn sanitize_replacements(replacements: &[Replacement]) -> Vec<Replacement> {
+/// Add the lines of a plain text word list in the user's home folder to the dictionary.
+/// Returns the full dictionary, plus a warning when entries didn't fit.
+#[tauri::command]
+fn import_dictionary_from_file(
+    path: String,
+    app: AppHandle<AppRuntime>,
+    state: tauri::State<AppState>,
+) -> Result<(Vec<String>, Option<String>), String> {
+    let text = read_dictionary_file(&app, Path::new(&path))
+        .map_err(|err| format!("Failed to import dictionary: {err}"))?;
+
+    let mut settings = state.current_settings();
+    let (merged, dropped) = merge_dictionary_import(&settings.dictionary, &text);
+    settings.dictionary = merged.clone();
+    state
+        .persist_settings(settings)
+        .map_err(|err| err.to_string())?;
+
+    let warning = (dropped > 0).then(|| {
+        format!(
+            "The dictionary holds up to {MAX_DICTIONARY_ENTRIES} entries, so {dropped} from the file were skipped"
+        )
+    });
+    Ok((merged, warning))
+}
+
+fn read_dictionary_file(app: &AppHandle<AppRuntime>, path: &Path) -> Result<String> {
+    let path = path
+        .canonicalize()
+        .with_context(|| format!("File not found: {}", path.display()))?;
+    let is_text = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("txt"));
+    if !path.is_file() || !is_text {
+        return Err(anyhow!("Only .txt files can be imported"));
+    }
+
+    let home_dir = app.path().home_dir().context("Home directory not found")?;
+    let home_dir = home_dir.canonicalize().unwrap_or(home_dir);
+    if !path.starts_with(&home_dir) {
+        return Err(anyhow!("File must be inside your home folder"));
+    }
+
+    let size = path.metadata()?.len();
+    if size > MAX_DICTIONARY_FILE_BYTES {
+        return Err(anyhow!("File is larger than 1 MB"));
+    }
+    std::fs::read_to_string(&path).context("File is not valid UTF-8 text")
+}
+
+fn sanitize_replacements(replacements: &[Replacement]) -> Vec<Replacement> {
     let mut seen = HashSet::new();
     let mut cleaned = Vec::new();
 
@@ -556,6 +1375,7 @@ fn sanitize_replacements(replacements: &[Replacement]) -> Vec<Replacement> {
             cleaned.push(Replacement {
                 from: from_capped.trim().to_string(),
                 to: to_capped.trim().to_string(),
+                priority: r.priority,
             });
         }
         if cleaned.len() >= 64 {
@@ -566,13 +1386,44 @@ fn sanitize_replacements(replacements: &[Replacement]) -> Vec<Replacement> {
     cleaned
 }
 
+/// Last pass over a transcript, after cleanup and replacements, so it also tidies whatever
+/// those steps left behind.
+fn finalize_transcript(text: String, settings: &UserSettings) -> String {
+    let mut text = if settings.replace_consecutive_spaces {
+        // Blank lines are paragraph breaks, so only whitespace within a paragraph collapses
+        text.split("\n\n")
+            .map(|paragraph| paragraph.split_whitespace().collect::<Vec<_>>().join(" "))
+            .filter(|paragraph| !paragraph.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    } else {
+        text
+    };
+    if settings.strip_trailing_period {
+        let trimmed = text.trim_end();
+        // Leave an ellipsis alone
+        if trimmed.ends_with('.') && !trimmed.ends_with("..") {
+            text.truncate(trimmed.len() - 1);
+        }
+    }
+    text
+}
+
 pub fn apply_replacements(text: &str, replacements: &[Replacement]) -> String {
     if replacements.is_empty() {
         return text.to_string();
     }
 
+    // Specific rules like "Open AI" must run before overlapping ones like "AI".
+    let mut ordered: Vec<&Replacement> = replacements.iter().collect();
+    ordered.sort_by(|a, b| {
+        b.priority
+            .cmp(&a.priority)
+            .then_with(|| b.from.len().cmp(&a.from.len()))
+    });
+
     let mut result = text.to_string();
-    for r in replacements {
+    for r in ordered {
         if r.from.is_empty() {
             continue;
         }
@@ -641,15 +1492,103 @@ fn set_replacements(
     Ok(cleaned)
 }
 
+#[tauri::command]
+fn get_replacement_profiles(
+    state: tauri::State<AppState>,
+) -> Result<Vec<ReplacementProfile>, String> {
+    Ok(state.current_settings().replacement_profiles)
+}
+
+#[tauri::command]
+fn set_replacement_profiles(
+    profiles: Vec<ReplacementProfile>,
+    state: tauri::State<AppState>,
+) -> Result<Vec<ReplacementProfile>, String> {
+    let cleaned = sanitize_replacement_profiles(&profiles);
+    let mut settings = state.current_settings();
+    if let Some(active_id) = settings.active_replacement_profile_id.as_deref() {
+        if !cleaned.iter().any(|profile| profile.id == active_id) {
+            settings.active_replacement_profile_id = None;
+        }
+    }
+    settings.replacement_profiles = cleaned.clone();
+    state
+        .persist_settings(settings)
+        .map_err(|err| err.to_string())?;
+    Ok(cleaned)
+}
+
+/// Switch the replacement profile applied to new transcriptions; `None` selects the default.
+#[tauri::command]
+fn set_active_profile(
+    id: Option<String>,
+    app: AppHandle<AppRuntime>,
+    state: tauri::State<AppState>,
+) -> Result<UserSettings, String> {
+    let mut settings = state.current_settings();
+    if let Some(id) = id.as_deref() {
+        if !settings
+            .replacement_profiles
+            .iter()
+            .any(|profile| profile.id == id)
+        {
+            return Err("Replacement profile not found".into());
+        }
+    }
+
+    settings.active_replacement_profile_id = id;
+    let saved = state
+        .persist_settings(settings)
+        .map_err(|err| err.to_string())?;
+
+    if let Err(err) = app.emit(EVENT_SETTINGS_CHANGED, &saved) {
+        error!("Failed to emit settings change: {err}");
+    }
+
+    Ok(saved)
+}
+
+fn sanitize_replacement_profiles(profiles: &[ReplacementProfile]) -> Vec<ReplacementProfile> {
+    let mut seen = HashSet::new();
+    let mut cleaned = Vec::new();
+
+    for profile in profiles {
+        let name: String = profile.name.trim().chars().take(50).collect();
+        if name.is_empty() {
+            continue;
+        }
+        let id = match profile.id.trim() {
+            "" => uuid::Uuid::new_v4().to_string(),
+            id => id.to_string(),
+        };
+        if seen.insert(id.clone()) {
+            cleaned.push(ReplacementProfile {
+                id,
+                name,
+                replacements: sanitize_replacements(&profile.replacements),
+            });
+        }
+        if cleaned.len() >= 16 {
+            break;
+        }
+    }
+
+    cleaned
+}
+
 #[derive(Serialize)]
 struct AppInfo {
     version: String,
     data_dir_size_bytes: u64,
+    db_size_bytes: u64,
     data_dir_path: String,
 }
 
 #[tauri::command]
-fn get_app_info(app: AppHandle<AppRuntime>) -> Result<AppInfo, String> {
+fn get_app_info(
+    app: AppHandle<AppRuntime>,
+    state: tauri::State<AppState>,
+) -> Result<AppInfo, String> {
     let version = env!("CARGO_PKG_VERSION").to_string();
 
     let data_dir = app
@@ -660,14 +1599,114 @@ fn get_app_info(app: AppHandle<AppRuntime>) -> Result<AppInfo, String> {
     let data_dir_path = data_dir.display().to_string();
 
     let data_dir_size_bytes = calculate_dir_size(&data_dir).unwrap_or(0);
+    let db_size_bytes = state.storage().db_size_bytes();
 
     Ok(AppInfo {
         version,
         data_dir_size_bytes,
+        db_size_bytes,
         data_dir_path,
     })
 }
 
+#[tauri::command]
+async fn vacuum_database(state: tauri::State<'_, AppState>) -> Result<u64, String> {
+    let storage = state.storage();
+    async_runtime::spawn_blocking(move || storage.vacuum())
+        .await
+        .map_err(|err| format!("Vacuum task failed: {err}"))?
+        .map_err(|err| format!("Failed to vacuum database: {err}"))
+}
+
+#[tauri::command]
+async fn checkpoint_database(
+    state: tauri::State<'_, AppState>,
+) -> Result<storage::CheckpointStats, String> {
+    let storage = state.storage();
+    async_runtime::spawn_blocking(move || storage.checkpoint())
+        .await
+        .map_err(|err| format!("Checkpoint task failed: {err}"))?
+        .map_err(|err| format!("Failed to checkpoint database: {err}"))
+}
+
+#[tauri::command]
+fn set_backup_path(
+    path: String,
+    app: AppHandle<AppRuntime>,
+    state: tauri::State<AppState>,
+) -> Result<(), String> {
+    let data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|err| format!("Failed to resolve app data directory: {err}"))?;
+    let dir = backup::validate_backup_dir(Path::new(path.trim()), &data_dir)
+        .map_err(|err| err.to_string())?;
+
+    let mut settings = state.current_settings();
+    settings.backup_path = Some(dir.to_string_lossy().into_owned());
+    let saved = state
+        .persist_settings(settings)
+        .map_err(|err| err.to_string())?;
+
+    emit_event(&app, EVENT_SETTINGS_CHANGED, &saved);
+    Ok(())
+}
+
+#[tauri::command]
+async fn trigger_backup(app: AppHandle<AppRuntime>) -> Result<String, String> {
+    async_runtime::spawn_blocking(move || run_backup(&app))
+        .await
+        .map_err(|err| format!("Backup task failed: {err}"))?
+        .map(|path| path.to_string_lossy().into_owned())
+        .map_err(|err| format!("Failed to back up transcriptions: {err}"))
+}
+
+fn run_backup(app: &AppHandle<AppRuntime>) -> GlimpseResult<PathBuf> {
+    let state = app.state::<AppState>();
+    let backup_path = state
+        .current_settings()
+        .backup_path
+        .ok_or_else(|| anyhow!("No backup folder has been set"))?;
+    let data_dir = app
+        .path()
+        .app_data_dir()
+        .context("App data directory not found")?;
+
+    // The folder may have been removed or unmounted since it was chosen
+    let dir = backup::validate_backup_dir(Path::new(&backup_path), &data_dir)?;
+    let dest = backup::write_backup(&state.storage(), &data_dir, &dir, chrono::Local::now())?;
+    state
+        .transcription_count_since_backup
+        .store(0, Ordering::SeqCst);
+    Ok(dest)
+}
+
+/// Count a saved transcription, backing up in the background once enough have piled up.
+fn schedule_auto_backup(app: &AppHandle<AppRuntime>, settings: &UserSettings) {
+    if !settings.auto_backup_enabled || settings.backup_path.is_none() {
+        return;
+    }
+
+    let state = app.state::<AppState>();
+    let count = state
+        .transcription_count_since_backup
+        .fetch_add(1, Ordering::SeqCst)
+        + 1;
+    if count < backup::AUTO_BACKUP_INTERVAL {
+        return;
+    }
+    // Reset up front so transcriptions finishing mid-backup don't queue another one
+    state
+        .transcription_count_since_backup
+        .store(0, Ordering::SeqCst);
+
+    let app = app.clone();
+    async_runtime::spawn_blocking(move || match run_backup(&app) {
+        Ok(path) => info!(path = %path.display(), "Automatic backup written"),
+        Err(err) => error!("Automatic backup failed: {err}"),
+    });
+}
+
 #[tauri::command]
 fn debug_show_toast(
     toast_type: String,
@@ -703,6 +1742,7 @@ async fn fetch_llm_models(
         "lmstudio" => LlmProvider::LmStudio,
         "ollama" => LlmProvider::Ollama,
         "openai" => LlmProvider::OpenAI,
+        "anthropic" => LlmProvider::Anthropic,
         "custom" => LlmProvider::Custom,
         "none" => LlmProvider::None,
         _ => LlmProvider::Custom,
@@ -716,7 +1756,7 @@ async fn fetch_llm_models(
 #[tauri::command]
 fn open_whats_new(app: AppHandle<AppRuntime>) {
     if let Err(err) = tray::toggle_settings_window(&app) {
-        eprintln!("Failed to open settings window: {err}");
+        error!("Failed to open settings window: {err}");
         return;
     }
 
@@ -724,134 +1764,1099 @@ fn open_whats_new(app: AppHandle<AppRuntime>) {
     std::thread::spawn(move || {
         std::thread::sleep(std::time::Duration::from_millis(500));
         if let Err(e) = app_clone.emit("navigate:about", ()) {
-            eprintln!("Failed to emit navigate:about: {e}");
+            error!("Failed to emit navigate:about: {e}");
         }
         std::thread::sleep(std::time::Duration::from_millis(400));
         if let Err(e) = app_clone.emit("open_whats_new", ()) {
-            eprintln!("Failed to emit open_whats_new: {e}");
+            error!("Failed to emit open_whats_new: {e}");
         }
     });
 }
 
 #[tauri::command]
-fn open_data_dir(path: Option<String>, app: AppHandle<AppRuntime>) -> Result<(), String> {
-    let path = path.ok_or_else(|| "Path is empty".to_string())?;
-    let path = PathBuf::from(path);
+fn format_shortcut(
+    raw: String,
+    format: Option<ShortcutDisplayFormat>,
+    state: tauri::State<AppState>,
+) -> String {
+    let format = format.unwrap_or_else(|| state.current_settings().shortcut_display_format);
+    shortcuts_display::format_shortcut(&raw, format)
+}
 
-    if !path.exists() {
-        return Err("Path does not exist".to_string());
+/// Label for a shortcut as the user sees it: keys relabelled for `layout` (the saved layout
+/// by default), then rendered in the chosen display format.
+#[tauri::command]
+fn get_shortcut_display_name(
+    shortcut: String,
+    layout: Option<KeyboardLayout>,
+    state: tauri::State<AppState>,
+) -> String {
+    let settings = state.current_settings();
+    let layout = layout.unwrap_or(settings.keyboard_layout);
+    shortcuts_display::format_shortcut(
+        &keyboard::normalize_shortcut_display(&shortcut, layout),
+        settings.shortcut_display_format,
+    )
+}
+
+const MAX_MODEL_IDLE_TIMEOUT_SECONDS: u32 = 24 * 60 * 60;
+
+/// Unload the local model after `seconds` without use; 0 keeps it loaded.
+#[tauri::command]
+fn set_model_idle_timeout(
+    seconds: u32,
+    app: AppHandle<AppRuntime>,
+    state: tauri::State<AppState>,
+) -> Result<UserSettings, String> {
+    if seconds > MAX_MODEL_IDLE_TIMEOUT_SECONDS {
+        return Err(format!(
+            "Model idle timeout must be at most {MAX_MODEL_IDLE_TIMEOUT_SECONDS} seconds"
+        ));
     }
 
-    app.opener()
-        .reveal_item_in_dir(&path)
-        .map_err(|err| format!("Failed to open path: {err}"))
+    let mut settings = state.current_settings();
+    settings.model_idle_timeout_seconds = seconds;
+    let saved = state
+        .persist_settings(settings)
+        .map_err(|err| err.to_string())?;
+    state.local_transcriber().set_idle_timeout(seconds);
+
+    emit_event(&app, EVENT_SETTINGS_CHANGED, &saved);
+    Ok(saved)
 }
 
-fn calculate_dir_size(path: &std::path::Path) -> Result<u64> {
-    let mut total_size = 0u64;
+#[tauri::command]
+fn update_validation_config(
+    min_duration_ms: i64,
+    min_rms_energy: f32,
+    min_speech_percentage: f32,
+    max_spectral_flatness: Option<f32>,
+    app: AppHandle<AppRuntime>,
+    state: tauri::State<AppState>,
+) -> Result<UserSettings, String> {
+    if min_duration_ms < 0 {
+        return Err("Minimum duration cannot be negative".into());
+    }
+    if !(0.00001..=0.1).contains(&min_rms_energy) {
+        return Err("Minimum RMS energy must be between 0.00001 and 0.1".into());
+    }
+    if !(0.0..=100.0).contains(&min_speech_percentage) {
+        return Err("Minimum speech percentage must be between 0 and 100".into());
+    }
+    if max_spectral_flatness.is_some_and(|flatness| !(0.0..=1.0).contains(&flatness)) {
+        return Err("Maximum spectral flatness must be between 0 and 1".into());
+    }
 
-    if !path.exists() {
-        return Ok(0);
+    let mut settings = state.current_settings();
+    settings.validation_min_duration_ms = min_duration_ms;
+    settings.validation_min_rms_energy = min_rms_energy;
+    settings.validation_min_speech_percentage = min_speech_percentage;
+    if let Some(flatness) = max_spectral_flatness {
+        settings.spectral_flatness_threshold = flatness;
     }
+    let saved = state
+        .persist_settings(settings)
+        .map_err(|err| err.to_string())?;
 
-    if path.is_file() {
-        return Ok(path.metadata()?.len());
+    if let Err(err) = app.emit(EVENT_SETTINGS_CHANGED, &saved) {
+        error!("Failed to emit settings change: {err}");
     }
 
-    if path.is_dir() {
-        for entry in std::fs::read_dir(path)? {
-            let entry = entry?;
-            let metadata = entry.metadata()?;
+    Ok(saved)
+}
 
-            if metadata.is_file() {
-                total_size += metadata.len();
-            } else if metadata.is_dir() {
-                total_size += calculate_dir_size(&entry.path())?;
-            }
+const DEFAULT_CALIBRATION_MS: u32 = 2000;
+const MAX_CALIBRATION_MS: u32 = 5000;
+const CALIBRATION_PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+/// Headroom above the measured noise floor before audio counts as speech.
+const CALIBRATION_MARGIN: f32 = 1.5;
+
+/// Record ambient noise for `duration_ms` and raise the RMS validation threshold just above it.
+/// Returns the measured noise floor.
+#[tauri::command]
+async fn calibrate_noise(
+    duration_ms: Option<u32>,
+    app: AppHandle<AppRuntime>,
+    state: tauri::State<'_, AppState>,
+) -> Result<f32, String> {
+    let duration_ms = duration_ms.unwrap_or(DEFAULT_CALIBRATION_MS);
+    if duration_ms == 0 || duration_ms > MAX_CALIBRATION_MS {
+        return Err(format!(
+            "Calibration duration must be between 1 and {MAX_CALIBRATION_MS} ms"
+        ));
+    }
+
+    // Calibration records from the same microphone, so it holds the pill busy like a
+    // transcription would and shortcuts can't start a recording underneath it
+    if !state
+        .pill()
+        .transition_from(&app, pill::PillStatus::Idle, pill::PillStatus::Processing)
+    {
+        return Err("Finish the current recording before calibrating".into());
+    }
+    let measured = measure_noise_floor(&app, &state, duration_ms).await;
+    state
+        .pill()
+        .transition_from(&app, pill::PillStatus::Processing, pill::PillStatus::Idle);
+    let noise_floor = measured?;
+
+    let threshold = (noise_floor * CALIBRATION_MARGIN).clamp(0.00001, 0.1);
+    info!(noise_floor, threshold, "Noise calibration finished");
+
+    let mut next = state.current_settings();
+    next.validation_min_rms_energy = threshold;
+    let saved = state
+        .persist_settings(next)
+        .map_err(|err| format!("Failed to save calibration: {err}"))?;
+    emit_event(&app, EVENT_SETTINGS_CHANGED, &saved);
+
+    Ok(noise_floor)
+}
+
+/// RMS of the input over `duration_ms`, reporting progress as it goes. Cancelling from the
+/// overlay stops the recorder and ends the measurement early.
+async fn measure_noise_floor(
+    app: &AppHandle<AppRuntime>,
+    state: &AppState,
+    duration_ms: u32,
+) -> Result<f32, String> {
+    state.clear_cancellation();
+    let settings = state.current_settings();
+    let recorder = state.pill().recorder();
+    recorder
+        .start(settings.microphone_device.clone(), None)
+        .map_err(|err| format!("Failed to start calibration: {err}"))?;
+    let amplitude = recorder.subscribe_amplitude();
+
+    let total = Duration::from_millis(u64::from(duration_ms));
+    let started = Instant::now();
+    let mut sum_squares = 0.0f64;
+    let mut buffers = 0u32;
+    loop {
+        tokio::time::sleep(CALIBRATION_PROGRESS_INTERVAL).await;
+        if state.is_cancelled() {
+            state.clear_cancellation();
+            return Err("Calibration cancelled".into());
+        }
+        for rms in amplitude.try_iter() {
+            sum_squares += f64::from(rms).powi(2);
+            buffers += 1;
+        }
+
+        let elapsed = started.elapsed();
+        let percent = (elapsed.as_secs_f32() / total.as_secs_f32() * 100.0).min(100.0) as u32;
+        emit_event(
+            app,
+            EVENT_RECORDING_CALIBRATING,
+            RecordingCalibratingPayload { percent },
+        );
+        if elapsed >= total {
+            break;
         }
     }
 
-    Ok(total_size)
+    if let Err(err) = recorder.stop() {
+        warn!("Failed to stop calibration recording: {err}");
+    }
+
+    if buffers == 0 {
+        return Err("No audio was captured during calibration".into());
+    }
+    Ok((sum_squares / f64::from(buffers)).sqrt() as f32)
 }
 
 #[tauri::command]
-fn get_transcriptions(
-    state: tauri::State<AppState>,
-) -> Result<Vec<storage::TranscriptionRecord>, String> {
-    Ok(state.storage().get_all())
+fn get_recording_duration_ms(state: tauri::State<AppState>) -> Option<i64> {
+    state.pill().recorder().get_recording_duration_ms()
 }
 
+/// Emit `audio:level` for the given input device about 10 times a second, without recording.
+/// Replaces any level meter that is already running.
 #[tauri::command]
-fn list_transcriptions_paginated(
+fn start_level_meter(
+    device_id: Option<String>,
+    app: AppHandle<AppRuntime>,
     state: tauri::State<AppState>,
-    limit: u32,
-    offset: u32,
-    search_query: Option<String>,
-) -> Result<Vec<storage::TranscriptionRecord>, String> {
-    state
-        .storage()
-        .get_paginated(limit, offset, search_query.as_deref())
-        .map_err(|err| format!("Failed to list transcriptions: {err}"))
+) -> Result<(), String> {
+    let mut level_meter = state.level_meter.lock();
+    // Close the previous stream before opening the device again
+    level_meter.take();
+
+    let device_id = device_id.filter(|id| !id.trim().is_empty());
+    let meter = audio::LevelMeter::start(device_id, move |db, peak_db| {
+        emit_event(&app, EVENT_AUDIO_LEVEL, AudioLevelPayload { db, peak_db });
+    })
+    .map_err(|err| format!("Failed to start level meter: {err}"))?;
+    *level_meter = Some(meter);
+    Ok(())
 }
 
 #[tauri::command]
-fn get_transcription_count(
-    state: tauri::State<AppState>,
-    search_query: Option<String>,
-) -> Result<usize, String> {
-    state
-        .storage()
-        .get_count(search_query.as_deref())
-        .map_err(|err| format!("Failed to get transcription count: {err}"))
+fn stop_level_meter(state: tauri::State<AppState>) -> Result<(), String> {
+    state.level_meter.lock().take();
+    Ok(())
 }
 
 #[tauri::command]
-fn import_transcription_from_cloud(
-    record: storage::TranscriptionRecord,
-    state: tauri::State<AppState>,
-) -> Result<bool, String> {
-    state
-        .storage()
-        .import_transcription(record)
-        .map_err(|err| format!("Failed to import transcription: {err}"))
+fn get_session_stats(state: tauri::State<AppState>) -> session::SessionStats {
+    state.session_stats()
 }
 
 #[tauri::command]
-fn mark_transcription_synced(id: String, state: tauri::State<AppState>) -> Result<(), String> {
-    state
-        .storage()
-        .mark_as_synced(&id)
-        .map_err(|err| format!("Failed to mark transcription as synced: {err}"))
+fn get_session_clipboard_history(state: tauri::State<AppState>) -> Vec<session::ClipboardEntry> {
+    state.clipboard_history.lock().entries()
 }
 
 #[tauri::command]
-fn delete_transcription(id: String, state: tauri::State<AppState>) -> Result<bool, String> {
-    match state.storage().delete(&id) {
-        Ok(Some(audio_path)) => {
-            let path = PathBuf::from(audio_path);
-            if path.exists() {
-                let _ = std::fs::remove_file(path);
+fn clear_session_clipboard_history(state: tauri::State<AppState>) -> Result<(), String> {
+    state.clipboard_history.lock().clear();
+    Ok(())
+}
+
+/// Set how opaque the recording pill is. Values are clamped to 0.3–1.
+#[tauri::command]
+fn set_overlay_opacity(
+    opacity: f32,
+    app: AppHandle<AppRuntime>,
+    state: tauri::State<AppState>,
+) -> Result<(), String> {
+    let mut next = state.current_settings();
+    next.overlay_opacity = platform::overlay::clamp_opacity(opacity);
+    let saved = state
+        .persist_settings(next)
+        .map_err(|err| err.to_string())?;
+
+    // The overlay page applies the new opacity when it hears about the change
+    if let Err(err) = app.emit(EVENT_SETTINGS_CHANGED, &saved) {
+        error!("Failed to emit settings change: {err}");
+    }
+    Ok(())
+}
+
+#[tauri::command]
+fn toggle_focus_mode(app: AppHandle<AppRuntime>) -> Result<bool, String> {
+    switch_focus_mode(&app).map_err(|err| err.to_string())
+}
+
+/// Flips focus mode, persists it and announces the change through a system notification.
+pub(crate) fn switch_focus_mode(app: &AppHandle<AppRuntime>) -> GlimpseResult<bool> {
+    let state = app.state::<AppState>();
+    let mut settings = state.current_settings();
+    settings.focus_mode = !settings.focus_mode;
+    let saved = state.persist_settings(settings)?;
+
+    if let Err(err) = tray::refresh_tray_menu(app, &saved) {
+        error!("Failed to refresh tray menu: {err}");
+    }
+    if let Err(err) = app.emit(EVENT_SETTINGS_CHANGED, &saved) {
+        error!("Failed to emit settings change: {err}");
+    }
+
+    if saved.focus_mode {
+        if let Err(err) = app
+            .notification()
+            .builder()
+            .title("Glimpse")
+            .body("Focus Mode enabled — transcriptions saved silently")
+            .show()
+        {
+            error!("Failed to show focus mode notification: {err}");
+        }
+    }
+
+    Ok(saved.focus_mode)
+}
+
+#[tauri::command]
+fn open_data_dir(path: Option<String>, app: AppHandle<AppRuntime>) -> Result<(), String> {
+    let path = path.ok_or_else(|| "Path is empty".to_string())?;
+    let path = PathBuf::from(path);
+
+    if !path.exists() {
+        return Err("Path does not exist".to_string());
+    }
+
+    app.opener()
+        .reveal_item_in_dir(&path)
+        .map_err(|err| format!("Failed to open path: {err}"))
+}
+
+fn calculate_dir_size(path: &std::path::Path) -> Result<u64> {
+    let mut total_size = 0u64;
+
+    if !path.exists() {
+        return Ok(0);
+    }
+
+    if path.is_file() {
+        return Ok(path.metadata()?.len());
+    }
+
+    if path.is_dir() {
+        for entry in std::fs::read_dir(path)? {
+            let entry = entry?;
+            let metadata = entry.metadata()?;
+
+            if metadata.is_file() {
+                total_size += metadata.len();
+            } else if metadata.is_dir() {
+                total_size += calculate_dir_size(&entry.path())?;
+            }
+        }
+    }
+
+    Ok(total_size)
+}
+
+#[tauri::command]
+fn get_transcriptions(
+    state: tauri::State<AppState>,
+) -> Result<Vec<storage::TranscriptionRecord>, String> {
+    Ok(state.storage().get_all())
+}
+
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+fn list_transcriptions_paginated(
+    state: tauri::State<AppState>,
+    limit: u32,
+    offset: u32,
+    search_query: Option<String>,
+    since_timestamp: Option<String>,
+    until_timestamp: Option<String>,
+    status_filter: Option<storage::TranscriptionStatus>,
+    include_redacted: Option<bool>,
+    flagged_only: Option<bool>,
+) -> Result<Vec<storage::TranscriptionRecord>, String> {
+    let since = since_timestamp
+        .as_deref()
+        .map(parse_timestamp_param)
+        .transpose()?;
+    let until = until_timestamp
+        .as_deref()
+        .map(parse_timestamp_param)
+        .transpose()?;
+    let filter = storage::TranscriptionFilter {
+        search_query: search_query.as_deref(),
+        since,
+        until,
+        status: status_filter,
+        include_redacted: include_redacted.unwrap_or(true),
+        flagged_only: flagged_only.unwrap_or(false),
+    };
+    state
+        .storage()
+        .get_paginated(limit, offset, &filter)
+        .map_err(|err| format!("Failed to list transcriptions: {err}"))
+}
+
+#[tauri::command]
+fn get_transcription_count(
+    state: tauri::State<AppState>,
+    search_query: Option<String>,
+    since_timestamp: Option<String>,
+    until_timestamp: Option<String>,
+    status_filter: Option<storage::TranscriptionStatus>,
+    include_redacted: Option<bool>,
+    flagged_only: Option<bool>,
+) -> Result<usize, String> {
+    let since = since_timestamp
+        .as_deref()
+        .map(parse_timestamp_param)
+        .transpose()?;
+    let until = until_timestamp
+        .as_deref()
+        .map(parse_timestamp_param)
+        .transpose()?;
+    let filter = storage::TranscriptionFilter {
+        search_query: search_query.as_deref(),
+        since,
+        until,
+        status: status_filter,
+        include_redacted: include_redacted.unwrap_or(true),
+        flagged_only: flagged_only.unwrap_or(false),
+    };
+    state
+        .storage()
+        .get_count(&filter)
+        .map_err(|err| format!("Failed to get transcription count: {err}"))
+}
+
+/// Write the current settings, including the LLM API key, to `path` encrypted with `password`.
+#[tauri::command]
+fn export_settings(
+    path: String,
+    password: String,
+    state: tauri::State<AppState>,
+) -> Result<(), String> {
+    if password.is_empty() {
+        return Err("A password is required to export settings".to_string());
+    }
+
+    let output_path = PathBuf::from(path.trim());
+    if output_path.as_os_str().is_empty() || output_path.is_dir() {
+        return Err("Export path must be a file".to_string());
+    }
+
+    let contents = settings::export_encrypted(&state.current_settings(), &password)
+        .map_err(|err| format!("Failed to export settings: {err}"))?;
+    std::fs::write(&output_path, contents).map_err(|err| {
+        format!(
+            "Failed to write settings to {}: {err}",
+            output_path.display()
+        )
+    })
+}
+
+/// Replace the current settings with a backup written by `export_settings`.
+#[tauri::command]
+fn import_settings(
+    path: String,
+    password: String,
+    app: AppHandle<AppRuntime>,
+    state: tauri::State<AppState>,
+) -> Result<UserSettings, String> {
+    let input_path = PathBuf::from(path.trim());
+    let contents = std::fs::read_to_string(&input_path).map_err(|err| {
+        format!(
+            "Failed to read settings from {}: {err}",
+            input_path.display()
+        )
+    })?;
+
+    let mut imported = settings::import_encrypted(&contents, &password)
+        .map_err(|err| format!("Failed to import settings: {err}"))?;
+    if model_manager::definition(&imported.local_model).is_none() {
+        imported.local_model = default_local_model();
+    }
+    validate_settings(&imported).map_err(|err| format!("Imported settings are invalid: {err}"))?;
+
+    let next = state
+        .persist_settings(imported)
+        .map_err(|err| format!("Failed to save imported settings: {err}"))?;
+    state
+        .local_transcriber()
+        .set_idle_timeout(next.model_idle_timeout_seconds);
+    state
+        .storage()
+        .set_auto_checkpoint(next.wal_auto_checkpoint_enabled);
+
+    pill::register_shortcuts(&app).map_err(|err| err.to_string())?;
+    if let Err(err) = tray::refresh_tray_menu(&app, &next) {
+        error!("Failed to refresh tray menu: {err}");
+    }
+    emit_event(&app, EVENT_SETTINGS_CHANGED, &next);
+
+    Ok(next)
+}
+
+#[tauri::command]
+fn export_transcriptions(
+    state: tauri::State<AppState>,
+    path: String,
+    format: String,
+    since: Option<String>,
+    until: Option<String>,
+    include_audio: Option<bool>,
+) -> Result<String, String> {
+    let since = since.as_deref().map(parse_timestamp_param).transpose()?;
+    let until = until.as_deref().map(parse_timestamp_param).transpose()?;
+
+    let output_path = PathBuf::from(path.trim());
+    if output_path.as_os_str().is_empty() || output_path.is_dir() {
+        return Err("Export path must be a file".to_string());
+    }
+    match output_path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() && !parent.is_dir() => {
+            return Err(format!(
+                "Export directory does not exist: {}",
+                parent.display()
+            ));
+        }
+        _ => {}
+    }
+
+    let records = state
+        .storage()
+        .export_range(since, until)
+        .map_err(|err| format!("Failed to load transcriptions: {err}"))?;
+
+    let contents = match format.to_ascii_lowercase().as_str() {
+        "csv" => transcriptions_to_csv(&records),
+        "json" => serde_json::to_string_pretty(&records)
+            .map_err(|err| format!("Failed to serialize transcriptions: {err}"))?,
+        other => return Err(format!("Unsupported export format: {other}")),
+    };
+
+    std::fs::write(&output_path, contents)
+        .map_err(|err| format!("Failed to write export to {}: {err}", output_path.display()))?;
+
+    if include_audio.unwrap_or(false) {
+        copy_export_audio(&output_path, &records)?;
+    }
+
+    Ok(output_path.to_string_lossy().to_string())
+}
+
+fn parse_timestamp_param(value: &str) -> Result<chrono::DateTime<chrono::Local>, String> {
+    chrono::DateTime::parse_from_rfc3339(value.trim())
+        .map(|dt| dt.with_timezone(&chrono::Local))
+        .map_err(|err| format!("Invalid timestamp '{value}': {err}"))
+}
+
+fn transcriptions_to_csv(records: &[storage::TranscriptionRecord]) -> String {
+    let mut csv = String::from(
+        "id,timestamp,text,raw_text,speech_model,llm_model,word_count,audio_duration_seconds\n",
+    );
+    for record in records {
+        let fields = [
+            csv_field(&record.id),
+            csv_field(&record.timestamp.to_rfc3339()),
+            csv_field(&record.text),
+            csv_field(record.raw_text.as_deref().unwrap_or_default()),
+            csv_field(&record.speech_model),
+            csv_field(record.llm_model.as_deref().unwrap_or_default()),
+            record.word_count.to_string(),
+            record.audio_duration_seconds.to_string(),
+        ];
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Copy the audio for exported records into a `<name>_audio` folder next to the export.
+fn copy_export_audio(
+    output_path: &Path,
+    records: &[storage::TranscriptionRecord],
+) -> Result<(), String> {
+    let stem = output_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "transcriptions".to_string());
+    let audio_dir = output_path.with_file_name(format!("{stem}_audio"));
+    std::fs::create_dir_all(&audio_dir).map_err(|err| {
+        format!(
+            "Failed to create audio export directory {}: {err}",
+            audio_dir.display()
+        )
+    })?;
+
+    for record in records {
+        let source = Path::new(&record.audio_path);
+        let Some(file_name) = source.file_name() else {
+            continue;
+        };
+        if !source.exists() {
+            continue;
+        }
+        if let Err(err) = std::fs::copy(source, audio_dir.join(file_name)) {
+            error!("Failed to export audio {}: {err}", source.display());
+        }
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+async fn export_audio_as_wav(
+    id: String,
+    dest_path: Option<String>,
+    app: AppHandle<AppRuntime>,
+    state: tauri::State<'_, AppState>,
+) -> Result<String, String> {
+    let record = state
+        .storage()
+        .get_by_id(&id)
+        .ok_or_else(|| "Transcription not found".to_string())?;
+
+    let audio_path = PathBuf::from(&record.audio_path);
+    if !audio_path.exists() {
+        return Err("Audio file not found".to_string());
+    }
+
+    let output_path = match dest_path {
+        Some(dest) => PathBuf::from(dest.trim()),
+        None => audio_path.with_extension("wav"),
+    };
+    let recordings_dir = recordings_root(&app).map_err(|err| err.to_string())?;
+    let output_path = resolve_export_path(&output_path, &recordings_dir)?;
+
+    let wav_path = output_path.clone();
+    async_runtime::spawn_blocking(move || -> Result<()> {
+        let (samples, sample_rate) = load_audio_for_transcription(&audio_path)?;
+        recorder::write_wav(&wav_path, &samples, sample_rate)
+    })
+    .await
+    .map_err(|err| format!("WAV export task failed: {err}"))?
+    .map_err(|err| format!("Failed to export WAV: {err}"))?;
+
+    Ok(output_path.to_string_lossy().to_string())
+}
+
+/// Canonicalize an export destination and require it to be inside the recordings
+/// directory, so `..` segments and symlinks can't write elsewhere.
+fn resolve_export_path(path: &Path, recordings_dir: &Path) -> Result<PathBuf, String> {
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| "Export path must name a file".to_string())?;
+    let parent = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .ok_or_else(|| "Export path must be absolute".to_string())?
+        .canonicalize()
+        .map_err(|_| "Path does not exist".to_string())?;
+    let root = recordings_dir
+        .canonicalize()
+        .map_err(|_| "Recordings folder not found".to_string())?;
+
+    let resolved = parent.join(file_name);
+    // An existing file may itself be a symlink pointing elsewhere
+    let target = resolved.canonicalize().unwrap_or_else(|_| resolved.clone());
+    if !parent.starts_with(&root) || !target.starts_with(&root) {
+        return Err("Audio can only be exported inside the recordings folder".to_string());
+    }
+    Ok(resolved)
+}
+
+#[tauri::command]
+fn rate_transcription(
+    id: String,
+    positive: bool,
+    app: AppHandle<AppRuntime>,
+    state: tauri::State<AppState>,
+) -> Result<storage::TranscriptionRecord, String> {
+    let record = state
+        .storage()
+        .set_feedback(&id, positive)
+        .map_err(|err| format!("Failed to rate transcription: {err}"))?
+        .ok_or_else(|| "Transcription not found".to_string())?;
+
+    if let Err(err) = tray::refresh_tray_menu(&app, &state.current_settings()) {
+        error!("Failed to refresh tray menu: {err}");
+    }
+
+    Ok(record)
+}
+
+#[tauri::command]
+fn flag_transcription(
+    id: String,
+    flagged: bool,
+    state: tauri::State<AppState>,
+) -> Result<(), String> {
+    let updated = state
+        .storage()
+        .set_flagged(&id, flagged)
+        .map_err(|err| format!("Failed to flag transcription: {err}"))?;
+    if updated {
+        Ok(())
+    } else {
+        Err("Transcription not found".to_string())
+    }
+}
+
+#[tauri::command]
+fn get_flagged_transcriptions(
+    state: tauri::State<AppState>,
+) -> Result<Vec<storage::TranscriptionRecord>, String> {
+    state
+        .storage()
+        .get_flagged()
+        .map_err(|err| format!("Failed to load flagged transcriptions: {err}"))
+}
+
+#[tauri::command]
+fn get_model_accuracy_report(
+    state: tauri::State<AppState>,
+) -> Result<Vec<storage::ModelAccuracy>, String> {
+    state
+        .storage()
+        .get_model_accuracy_report()
+        .map_err(|err| format!("Failed to build accuracy report: {err}"))
+}
+
+#[tauri::command]
+fn get_usage_stats_by_model(
+    state: tauri::State<AppState>,
+) -> Result<Vec<storage::ModelUsageStats>, String> {
+    state
+        .storage()
+        .get_usage_stats_by_model()
+        .map_err(|err| format!("Failed to load usage stats: {err}"))
+}
+
+#[tauri::command]
+fn get_usage_stats_by_day(
+    days: u32,
+    state: tauri::State<AppState>,
+) -> Result<Vec<storage::DailyStats>, String> {
+    if days > MAX_REPORT_DAYS {
+        return Err(format!("Days must be at most {MAX_REPORT_DAYS}"));
+    }
+
+    state
+        .storage()
+        .get_usage_stats_by_day(days)
+        .map_err(|err| format!("Failed to load usage stats: {err}"))
+}
+
+#[tauri::command]
+fn get_transcriptions_in_range(
+    since_rfc3339: String,
+    until_rfc3339: String,
+    state: tauri::State<AppState>,
+) -> Result<Vec<storage::TranscriptionRecord>, String> {
+    let since = parse_timestamp_param(&since_rfc3339)?;
+    let until = parse_timestamp_param(&until_rfc3339)?;
+    validate_report_range(since, until)?;
+
+    state
+        .storage()
+        .get_range(since, until)
+        .map_err(|err| format!("Failed to load transcriptions: {err}"))
+}
+
+fn validate_report_range(
+    since: chrono::DateTime<chrono::Local>,
+    until: chrono::DateTime<chrono::Local>,
+) -> Result<(), String> {
+    if until < since {
+        return Err("Range end must not be before its start".to_string());
+    }
+    if until - since > chrono::Duration::days(i64::from(MAX_REPORT_DAYS)) {
+        return Err(format!("Range must span at most {MAX_REPORT_DAYS} days"));
+    }
+    Ok(())
+}
+
+#[tauri::command]
+fn get_daily_word_counts(
+    days: u32,
+    state: tauri::State<AppState>,
+) -> Result<Vec<storage::DailyWordCount>, String> {
+    if days > MAX_REPORT_DAYS {
+        return Err(format!("Days must be at most {MAX_REPORT_DAYS}"));
+    }
+
+    state
+        .storage()
+        .get_daily_word_counts(days)
+        .map_err(|err| format!("Failed to load daily word counts: {err}"))
+}
+
+#[tauri::command]
+fn get_transcription_heatmap_data(
+    state: tauri::State<AppState>,
+) -> Result<Vec<storage::HeatmapCell>, String> {
+    state
+        .storage()
+        .get_heatmap_data()
+        .map_err(|err| format!("Failed to load activity heatmap: {err}"))
+}
+
+#[tauri::command]
+fn get_peak_recording_hour(state: tauri::State<AppState>) -> Result<Option<u8>, String> {
+    state
+        .storage()
+        .get_peak_recording_hour()
+        .map_err(|err| format!("Failed to load peak recording hour: {err}"))
+}
+
+#[tauri::command]
+fn recalculate_word_counts(state: tauri::State<AppState>) -> Result<u32, String> {
+    state
+        .storage()
+        .backfill_word_counts()
+        .map_err(|err| format!("Failed to recalculate word counts: {err}"))
+}
+
+/// Bump when the backfill logic changes and existing installs should run it again.
+const WORD_COUNT_BACKFILL_VERSION: u32 = 1;
+
+fn backfill_word_counts_once(settings_store: &SettingsStore, storage: &storage::StorageManager) {
+    match settings_store.maintenance_version("word_count_backfill") {
+        Ok(version) if version >= WORD_COUNT_BACKFILL_VERSION => return,
+        Ok(_) => {}
+        Err(err) => {
+            error!("Failed to read word count backfill marker: {err}");
+            return;
+        }
+    }
+
+    match storage.backfill_word_counts() {
+        Ok(updated) => {
+            if updated > 0 {
+                info!("Backfilled word counts for {updated} transcriptions");
+            }
+            if let Err(err) = settings_store
+                .set_maintenance_version("word_count_backfill", WORD_COUNT_BACKFILL_VERSION)
+            {
+                error!("Failed to record word count backfill: {err}");
+            }
+        }
+        Err(err) => error!("Failed to backfill word counts: {err}"),
+    }
+}
+
+/// Bump when the backfill logic changes and existing installs should run it again.
+const AUDIO_DURATION_BACKFILL_VERSION: u32 = 1;
+
+fn backfill_audio_durations_once(
+    settings_store: &SettingsStore,
+    storage: &storage::StorageManager,
+    base_dir: &Path,
+) {
+    match settings_store.maintenance_version("audio_duration_backfill") {
+        Ok(version) if version >= AUDIO_DURATION_BACKFILL_VERSION => return,
+        Ok(_) => {}
+        Err(err) => {
+            error!("Failed to read audio duration backfill marker: {err}");
+            return;
+        }
+    }
+
+    match storage.backfill_audio_durations(base_dir) {
+        Ok(fixed) => {
+            if fixed > 0 {
+                info!("Backfilled audio durations for {fixed} transcriptions");
+            }
+            if let Err(err) = settings_store
+                .set_maintenance_version("audio_duration_backfill", AUDIO_DURATION_BACKFILL_VERSION)
+            {
+                error!("Failed to record audio duration backfill: {err}");
+            }
+        }
+        Err(err) => error!("Failed to backfill audio durations: {err}"),
+    }
+}
+
+#[tauri::command]
+fn get_transcription_streak(state: tauri::State<AppState>) -> Result<u32, String> {
+    state
+        .storage()
+        .get_transcription_streak()
+        .map_err(|err| format!("Failed to compute streak: {err}"))
+}
+
+#[derive(Serialize)]
+struct DailyProgress {
+    words_today: u32,
+    goal: Option<u32>,
+    /// Share of the goal reached so far, capped at 100.
+    percent: Option<f32>,
+}
+
+#[tauri::command]
+fn get_daily_progress(state: tauri::State<AppState>) -> Result<DailyProgress, String> {
+    let words_today = state
+        .storage()
+        .get_words_today()
+        .map_err(|err| format!("Failed to count today's words: {err}"))?;
+    let goal = state.current_settings().daily_word_goal;
+
+    Ok(DailyProgress {
+        words_today,
+        goal,
+        percent: goal.map(|goal| (words_today as f32 / goal as f32 * 100.0).min(100.0)),
+    })
+}
+
+#[tauri::command]
+fn get_longest_streak(state: tauri::State<AppState>) -> Result<u32, String> {
+    state
+        .storage()
+        .get_longest_streak()
+        .map_err(|err| format!("Failed to compute longest streak: {err}"))
+}
+
+#[tauri::command]
+fn tag_transcription(
+    id: String,
+    tags: Vec<String>,
+    state: tauri::State<AppState>,
+) -> Result<storage::TranscriptionRecord, String> {
+    state
+        .storage()
+        .set_tags(&id, sanitize_tags(&tags))
+        .map_err(|err| format!("Failed to tag transcription: {err}"))?
+        .ok_or_else(|| "Transcription not found".to_string())
+}
+
+#[tauri::command]
+fn get_transcriptions_by_tag(
+    tag: String,
+    state: tauri::State<AppState>,
+) -> Result<Vec<storage::TranscriptionRecord>, String> {
+    state
+        .storage()
+        .get_by_tag(&tag)
+        .map_err(|err| format!("Failed to load tagged transcriptions: {err}"))
+}
+
+#[tauri::command]
+fn search_transcriptions(
+    query: String,
+    max_snippet_chars: usize,
+    state: tauri::State<AppState>,
+) -> Result<Vec<storage::SearchResult>, String> {
+    state
+        .storage()
+        .full_text_search_with_highlight(&query, max_snippet_chars)
+        .map_err(|err| format!("Failed to search transcriptions: {err}"))
+}
+
+#[tauri::command]
+fn import_transcription_from_cloud(
+    record: storage::TranscriptionRecord,
+    state: tauri::State<AppState>,
+) -> Result<bool, String> {
+    state
+        .storage()
+        .import_transcription(record)
+        .map_err(|err| format!("Failed to import transcription: {err}"))
+}
+
+#[tauri::command]
+fn mark_transcription_synced(id: String, state: tauri::State<AppState>) -> Result<(), String> {
+    state
+        .storage()
+        .mark_as_synced(&id)
+        .map_err(|err| format!("Failed to mark transcription as synced: {err}"))
+}
+
+/// Move a transcription to the recently deleted list. Its recording is kept until the
+/// transcription is purged, so it can still be restored.
+#[tauri::command]
+fn delete_transcription(id: String, state: tauri::State<AppState>) -> Result<bool, String> {
+    let storage = state.storage();
+    let audio_path = storage.get_by_id(&id).map(|record| record.audio_path);
+    match storage.delete(&id) {
+        Ok(deleted) => {
+            if let (true, Some(audio_path)) = (deleted, audio_path) {
+                // Keep deleted transcripts out of Spotlight while they wait to be purged
+                platform::spotlight::remove_index(Path::new(&audio_path));
+            }
+            Ok(deleted)
+        }
+        Err(err) => Err(format!("Failed to delete transcription: {err}")),
+    }
+}
+
+#[tauri::command]
+fn restore_transcription(id: String, state: tauri::State<AppState>) -> Result<bool, String> {
+    let storage = state.storage();
+    let restored = storage
+        .restore(&id)
+        .map_err(|err| format!("Failed to restore transcription: {err}"))?;
+    if !restored || !state.current_settings().spotlight_indexing_enabled {
+        return Ok(restored);
+    }
+    if let Some(record) = storage.get_by_id(&id) {
+        let path = Path::new(&record.audio_path);
+        if path.exists() {
+            platform::spotlight::index_audio_file(path, &record.text);
+        }
+    }
+    Ok(restored)
+}
+
+#[tauri::command]
+fn get_deleted_transcriptions(
+    state: tauri::State<AppState>,
+) -> Result<Vec<storage::TranscriptionRecord>, String> {
+    state
+        .storage()
+        .get_deleted()
+        .map_err(|err| format!("Failed to load deleted transcriptions: {err}"))
+}
+
+/// Redact a transcription's text and delete its audio, keeping the row for cloud sync.
+#[tauri::command]
+fn anonymize_transcription(id: String, state: tauri::State<AppState>) -> Result<bool, String> {
+    match state.storage().anonymize_transcription(&id) {
+        Ok(Some(audio_path)) => {
+            let path = PathBuf::from(audio_path);
+            if path.exists() {
+                platform::spotlight::remove_index(&path);
+                let _ = std::fs::remove_file(path);
             }
             Ok(true)
         }
         Ok(None) => Ok(false),
-        Err(err) => Err(format!("Failed to delete transcription: {err}")),
+        Err(err) => Err(format!("Failed to redact transcription: {err}")),
     }
 }
 
 #[tauri::command]
-fn delete_all_transcriptions(state: tauri::State<AppState>) -> Result<u32, String> {
-    let audio_paths = state
+fn merge_transcriptions(
+    ids: Vec<String>,
+    state: tauri::State<AppState>,
+) -> Result<storage::TranscriptionRecord, String> {
+    // The parts stay restorable, so their recordings are left for the purge to remove
+    state
         .storage()
-        .delete_all()
-        .map_err(|err| format!("Failed to delete all transcriptions: {err}"))?;
+        .merge_transcriptions(ids)
+        .map_err(|err| format!("Failed to merge transcriptions: {err}"))
+}
+
+#[derive(Serialize)]
+struct DuplicatePair {
+    id: String,
+    duplicate_id: String,
+    similarity: f32,
+}
 
-    let deleted_count = audio_paths.len() as u32;
-    for audio_path in audio_paths {
-        let _ = std::fs::remove_file(audio_path);
+#[tauri::command]
+fn get_duplicate_transcriptions(
+    threshold: f32,
+    state: tauri::State<AppState>,
+) -> Result<Vec<DuplicatePair>, String> {
+    if !(0.0..=1.0).contains(&threshold) {
+        return Err("Threshold must be between 0 and 1".into());
     }
 
-    Ok(deleted_count)
+    let pairs = state
+        .storage()
+        .find_near_duplicates(threshold)
+        .map_err(|err| format!("Failed to find duplicate transcriptions: {err}"))?;
+    Ok(pairs
+        .into_iter()
+        .map(|(id, duplicate_id, similarity)| DuplicatePair {
+            id,
+            duplicate_id,
+            similarity,
+        })
+        .collect())
+}
+
+#[tauri::command]
+fn delete_duplicate_keeping_longer(
+    id1: String,
+    id2: String,
+    state: tauri::State<AppState>,
+) -> Result<storage::TranscriptionRecord, String> {
+    let (survivor, _) = state
+        .storage()
+        .delete_duplicate_keeping_longer(&id1, &id2)
+        .map_err(|err| format!("Failed to delete duplicate transcription: {err}"))?;
+    Ok(survivor)
+}
+
+#[tauri::command]
+fn delete_all_transcriptions(state: tauri::State<AppState>) -> Result<u32, String> {
+    state
+        .storage()
+        .delete_all()
+        .map_err(|err| format!("Failed to delete all transcriptions: {err}"))
 }
 
 #[tauri::command]
@@ -881,162 +2886,260 @@ async fn retry_transcription(
         ended_at: record.timestamp,
     };
 
-    let _ = state.storage().delete(&id);
+    // The retry saves a new record for the same recording, so the old one goes for good
+    let _ = state.storage().delete_permanently(&id);
 
     emit_transcription_start(&app, &saved);
+    async_runtime::spawn(retry_transcription_async(app, state.http(), saved, true));
 
-    let http = state.http();
-    let app_handle = app.clone();
-    let saved_for_task = saved.clone();
+    Ok(())
+}
+
+/// Gap between batch retries, so an API that has just come back isn't hammered.
+const BATCH_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// Retry every failed transcription that still has its recording, one at a time in the
+/// background. Returns how many were queued. Only one batch runs at a time, so a record
+/// can't be picked up twice.
+#[tauri::command]
+fn retry_all_failed_transcriptions(
+    app: AppHandle<AppRuntime>,
+    state: tauri::State<AppState>,
+) -> Result<u32, String> {
+    if state.retrying_failed.swap(true, Ordering::SeqCst) {
+        return Err("Failed transcriptions are already being retried".to_string());
+    }
+    let failed = match state.storage().get_all_failed() {
+        Ok(failed) if !failed.is_empty() => failed,
+        result => {
+            state.retrying_failed.store(false, Ordering::SeqCst);
+            return result
+                .map(|_| 0)
+                .map_err(|err| format!("Failed to load failed transcriptions: {err}"));
+        }
+    };
+
+    let count = failed.len() as u32;
+    toast::show(
+        &app,
+        "info",
+        None,
+        &format!("Retrying {count} failed transcriptions"),
+    );
 
+    let http = state.http();
     async_runtime::spawn(async move {
-        let settings = app_handle.state::<AppState>().current_settings();
-        let config = transcription::TranscriptionConfig::from_settings(&settings);
-        let use_local = matches!(settings.transcription_mode, TranscriptionMode::Local);
-
-        let result = if use_local {
-            match load_audio_for_transcription(&saved_for_task.path) {
-                Ok((samples, sample_rate)) => {
-                    let model_key = settings.local_model.clone();
-                    match model_manager::ensure_model_ready(&app_handle, &model_key) {
-                        Ok(ready_model) => {
-                            let dictionary_prompt =
-                                dictionary_prompt_for_model(&ready_model, &settings);
-                            let language = settings.language.clone();
-                            let transcriber = app_handle.state::<AppState>().local_transcriber();
-                            match async_runtime::spawn_blocking(move || {
-                                transcriber.transcribe(
-                                    &ready_model,
-                                    &samples,
-                                    sample_rate,
-                                    dictionary_prompt.as_deref(),
-                                    Some(&language),
-                                )
-                            })
-                            .await
-                            {
-                                Ok(inner) => inner,
-                                Err(err) => Err(anyhow!("Local transcription task failed: {err}")),
-                            }
-                        }
-                        Err(err) => Err(err),
-                    }
-                }
-                Err(err) => Err(err),
+        for (index, record) in failed.into_iter().enumerate() {
+            if index > 0 {
+                tokio::time::sleep(BATCH_RETRY_DELAY).await;
             }
-        } else {
-            transcription::request_transcription(&http, &saved_for_task, &config).await
-        };
+            let saved = RecordingSaved {
+                path: PathBuf::from(&record.audio_path),
+                started_at: record.timestamp,
+                ended_at: record.timestamp,
+            };
+            if let Err(err) = app
+                .state::<AppState>()
+                .storage()
+                .delete_permanently(&record.id)
+            {
+                warn!("Skipping retry of {}: {err}", record.id);
+                continue;
+            }
+            emit_transcription_start(&app, &saved);
+            retry_transcription_async(app.clone(), http.clone(), saved, false).await;
+        }
+        app.state::<AppState>()
+            .retrying_failed
+            .store(false, Ordering::SeqCst);
+    });
 
-        match result {
-            Ok(result) => {
-                let raw_transcript = result.transcript.clone();
-                let reported_model = result.speech_model.clone();
+    Ok(count)
+}
 
-                if count_words(&raw_transcript) == 0 {
-                    handle_empty_transcription(&app_handle, &saved_for_task.path);
-                    return;
-                }
+/// Transcribe a saved recording again and report it like a fresh one. Batch retries pass
+/// `auto_paste: false` so a pile of old transcripts doesn't land in the focused app.
+async fn retry_transcription_async(
+    app_handle: AppHandle<AppRuntime>,
+    http: Client,
+    saved_for_task: RecordingSaved,
+    auto_paste: bool,
+) {
+    let started = Instant::now();
+    let settings = app_handle.state::<AppState>().current_settings();
+    let config = transcription::TranscriptionConfig::from_settings(&settings);
+    let Some((result, use_local)) = transcribe_for_mode(
+        &app_handle,
+        &http,
+        &settings,
+        &saved_for_task,
+        || {
+            let (samples, sample_rate) = load_audio_for_transcription(&saved_for_task.path)?;
+            Ok(CompletedRecording {
+                samples,
+                sample_rate,
+                channels: 1,
+                started_at: saved_for_task.started_at,
+                ended_at: saved_for_task.ended_at,
+            })
+        },
+        || false,
+    )
+    .await
+    else {
+        return;
+    };
 
-                let (final_transcript, llm_cleaned) =
-                    if llm_cleanup::is_cleanup_available(&settings) {
-                        match llm_cleanup::cleanup_transcription(&http, &raw_transcript, &settings)
-                            .await
-                        {
-                            Ok(cleaned) => (cleaned, true),
-                            Err(err) => {
-                                eprintln!(
-                                    "LLM cleanup failed during retry, using raw transcript: {err}"
-                                );
-                                (raw_transcript.clone(), false)
-                            }
-                        }
-                    } else {
-                        (raw_transcript.clone(), false)
-                    };
+    match result {
+        Ok(result) => {
+            let raw_transcript = result.transcript.clone();
 
-                let final_transcript =
-                    apply_replacements(&final_transcript, &settings.replacements);
+            if count_words(&raw_transcript) == 0 {
+                handle_empty_transcription(&app_handle, &saved_for_task.path);
+                return;
+            }
 
-                if count_words(&final_transcript) == 0 {
-                    handle_empty_transcription(&app_handle, &saved_for_task.path);
-                    return;
+            let (final_transcript, llm_cleaned) = if llm_cleanup::is_cleanup_available(&settings)
+                && meets_cleanup_min_words(&raw_transcript, &settings)
+            {
+                match llm_cleanup::cleanup_transcription(
+                    &http,
+                    &raw_transcript,
+                    &settings,
+                    |chunk| emit_llm_chunk(&app_handle, chunk),
+                )
+                .await
+                {
+                    Ok(cleaned) => (cleaned, true),
+                    Err(err) => {
+                        warn!("LLM cleanup failed during retry, using raw transcript: {err}");
+                        (raw_transcript.clone(), false)
+                    }
                 }
+            } else {
+                (raw_transcript.clone(), false)
+            };
+            let final_transcript =
+                punctuate_if_needed(final_transcript, &settings, use_local, llm_cleaned);
+            let final_transcript = paragraphize_if_needed(final_transcript, &settings);
+
+            let replaced = apply_replacements(&final_transcript, settings.active_replacements());
+            let (final_transcript, pre_replacement_transcript) = if replaced == final_transcript {
+                (final_transcript, None)
+            } else {
+                (replaced, Some(final_transcript))
+            };
+            let final_transcript = finalize_transcript(final_transcript, &settings);
 
-                let mut pasted = false;
-                if config.auto_paste && !final_transcript.trim().is_empty() {
-                    let text = final_transcript.clone();
-                    match async_runtime::spawn_blocking(move || assistive::paste_text(&text)).await
-                    {
-                        Ok(Ok(())) => pasted = true,
-                        Ok(Err(err)) => {
-                            let err_str = err.to_string();
-                            let is_accessibility_issue =
-                                err_str.to_lowercase().contains("accessibility")
-                                    || err_str.to_lowercase().contains("permission")
-                                    || err_str.to_lowercase().contains("not allowed")
-                                    || err_str.to_lowercase().contains("assistive");
-
-                            if is_accessibility_issue {
-                                toast::show(
-                                    &app_handle,
-                                    "warning",
-                                    Some("Accessibility Required"),
-                                    "Enable accessibility access in System Settings to auto-paste transcriptions.",
-                                );
-                            } else {
-                                toast::show(
-                                    &app_handle,
-                                    "error",
-                                    None,
-                                    &format!("Auto paste failed: {err}"),
-                                );
-                            }
-                            eprintln!("Auto paste failed: {err}");
-                        }
-                        Err(err) => {
-                            toast::show(&app_handle, "error", None, "Auto paste failed");
-                            eprintln!("Auto paste task error: {err}");
+            if count_words(&final_transcript) == 0 {
+                handle_empty_transcription(&app_handle, &saved_for_task.path);
+                return;
+            }
+
+            let mut pasted = false;
+            if auto_paste
+                && config.auto_paste
+                && !settings.focus_mode
+                && !final_transcript.trim().is_empty()
+            {
+                let text = final_transcript.clone();
+                let paste_app = app_handle.clone();
+                let paste_mode = settings.paste_mode.clone();
+                match async_runtime::spawn_blocking(move || {
+                    paste_transcript(&paste_app, &text, &paste_mode)
+                })
+                .await
+                {
+                    Ok(Ok(())) => pasted = true,
+                    Ok(Err(err)) => {
+                        let err_str = err.to_string();
+                        let is_accessibility_issue =
+                            err_str.to_lowercase().contains("accessibility")
+                                || err_str.to_lowercase().contains("permission")
+                                || err_str.to_lowercase().contains("not allowed")
+                                || err_str.to_lowercase().contains("assistive");
+
+                        if is_accessibility_issue {
+                            toast::show(
+                                &app_handle,
+                                "warning",
+                                Some("Accessibility Required"),
+                                "Enable accessibility access in System Settings to auto-paste transcriptions.",
+                            );
+                        } else {
+                            toast::show(
+                                &app_handle,
+                                "error",
+                                None,
+                                &format!("Auto paste failed: {err}"),
+                            );
                         }
+                        error!("Auto paste failed: {err}");
+                    }
+                    Err(err) => {
+                        toast::show(&app_handle, "error", None, "Auto paste failed");
+                        error!("Auto paste task error: {err}");
                     }
                 }
+            }
 
-                let metadata = build_transcription_metadata(
-                    &saved_for_task,
-                    &settings,
-                    use_local,
-                    reported_model.as_deref(),
-                    &final_transcript,
-                    llm_cleaned,
-                );
+            let metadata = build_transcription_metadata(
+                &saved_for_task,
+                &settings,
+                use_local,
+                &result,
+                &final_transcript,
+                llm_cleaned,
+            );
 
-                emit_transcription_complete_with_cleanup(
-                    &app_handle,
-                    raw_transcript,
-                    final_transcript,
-                    pasted,
-                    saved_for_task.path.display().to_string(),
-                    llm_cleaned,
-                    metadata,
-                    "unknown",
-                    if use_local { "local" } else { "cloud" },
-                );
+            app_handle.state::<AppState>().record_session_transcription(
+                metadata.audio_duration_seconds,
+                metadata.word_count,
+                started.elapsed(),
+            );
+            info!(
+                engine = if use_local { "local" } else { "cloud" },
+                model = %metadata.speech_model,
+                words = metadata.word_count,
+                duration_ms = started.elapsed().as_millis() as u64,
+                "Transcription finished"
+            );
 
-                hide_overlay(&app_handle);
-            }
-            Err(err) => {
-                let stage = if use_local { "local" } else { "api" };
-                emit_transcription_error(
-                    &app_handle,
-                    format!("Transcription failed: {err}"),
-                    stage,
-                    saved_for_task.path.display().to_string(),
-                );
-            }
-        }
-    });
+            emit_transcription_complete_with_cleanup(
+                &app_handle,
+                raw_transcript,
+                final_transcript,
+                pre_replacement_transcript,
+                pasted,
+                saved_for_task.path.display().to_string(),
+                llm_cleaned,
+                metadata,
+                "unknown",
+                if use_local { "local" } else { "cloud" },
+            );
 
-    Ok(())
+            hide_overlay(&app_handle);
+        }
+        Err(err) if err.is::<local_transcription::TranscriptionTimedOut>() => {
+            error!(error = %err, "Local transcription timed out");
+            emit_transcription_error(
+                &app_handle,
+                err.to_string(),
+                "local_timeout",
+                saved_for_task.path.display().to_string(),
+            );
+        }
+        Err(err) => {
+            let stage = if use_local { "local" } else { "api" };
+            error!(stage, error = %err, "Transcription failed");
+            emit_transcription_error(
+                &app_handle,
+                format!("Transcription failed: {err}"),
+                stage,
+                saved_for_task.path.display().to_string(),
+            );
+        }
+    }
 }
 
 #[tauri::command]
@@ -1060,6 +3163,7 @@ async fn retry_llm_cleanup(
     }
     let llm_model = llm_cleanup::resolved_model_name(&settings);
 
+    let was_flagged = record.flagged;
     let text_to_clean = record.raw_text.unwrap_or(record.text);
 
     let http = state.http();
@@ -1067,23 +3171,33 @@ async fn retry_llm_cleanup(
     let record_id = id.clone();
 
     async_runtime::spawn(async move {
-        match llm_cleanup::cleanup_transcription(&http, &text_to_clean, &settings).await {
+        match llm_cleanup::cleanup_transcription(&http, &text_to_clean, &settings, |chunk| {
+            emit_llm_chunk(&app, chunk)
+        })
+        .await
+        {
             Ok(cleaned) => {
-                if let Err(err) =
-                    storage.update_with_llm_cleanup(&record_id, cleaned, llm_model.clone())
-                {
-                    eprintln!("Failed to save LLM cleanup: {err}");
+                match storage.update_with_llm_cleanup(&record_id, cleaned, llm_model.clone()) {
+                    // The cleanup is taken as the review the flag asked for
+                    Ok(Some(_)) if was_flagged => {
+                        if let Err(err) = storage.set_flagged(&record_id, false) {
+                            error!("Failed to unflag cleaned transcription: {err}");
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(err) => error!("Failed to save LLM cleanup: {err}"),
                 }
                 let _ = app.emit(
                     EVENT_TRANSCRIPTION_COMPLETE,
                     TranscriptionCompletePayload {
                         transcript: String::new(),
                         auto_paste: false,
+                        confidence: None,
                     },
                 );
             }
             Err(err) => {
-                eprintln!("LLM cleanup failed: {err}");
+                error!("LLM cleanup failed: {err}");
                 let _ = app.emit(
                     EVENT_TRANSCRIPTION_ERROR,
                     TranscriptionErrorPayload {
@@ -1113,6 +3227,7 @@ async fn undo_llm_cleanup(
                 TranscriptionCompletePayload {
                     transcript: String::new(),
                     auto_paste: false,
+                    confidence: None,
                 },
             );
             Ok(())
@@ -1122,25 +3237,58 @@ async fn undo_llm_cleanup(
     }
 }
 
+#[tauri::command]
+async fn undo_replacement(
+    id: String,
+    app: AppHandle<AppRuntime>,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    let storage = state.storage();
+
+    match storage.revert_to_pre_replacement(&id) {
+        Ok(Some(_)) => {
+            let _ = app.emit(
+                EVENT_TRANSCRIPTION_COMPLETE,
+                TranscriptionCompletePayload {
+                    transcript: String::new(),
+                    auto_paste: false,
+                    confidence: None,
+                },
+            );
+            Ok(())
+        }
+        Ok(None) => Err("No pre-replacement text available to revert to".to_string()),
+        Err(err) => Err(format!("Failed to undo replacements: {err}")),
+    }
+}
+
 pub(crate) fn hide_overlay(app: &AppHandle<AppRuntime>) {
-    app.state::<AppState>().pill().reset(app);
+    let state = app.state::<AppState>();
+    // Earlier chunks finish transcribing while a continuous session keeps recording, and a
+    // stopped session's overlay stays up until its last chunk is done
+    if state.pill().continuous_session_active() {
+        return;
+    }
+    state.pill().reset(app);
 }
 
 pub(crate) fn stop_active_recording(app: &AppHandle<AppRuntime>) {
     app.state::<AppState>().pill().cancel(app);
 }
 
+/// Returns whether a queued toast took the dismissed one's place, in which case the
+/// toast window should stay visible.
 #[tauri::command]
-fn toast_dismissed(app: AppHandle<AppRuntime>) {
+fn toast_dismissed(app: AppHandle<AppRuntime>) -> bool {
     stop_active_recording(&app);
     hide_overlay(&app);
-    toast::hide(&app);
+    toast::show_next(&app)
 }
 
 #[tauri::command]
 fn cancel_recording(app: AppHandle<AppRuntime>) {
     let state = app.state::<AppState>();
-    if state.pill().status() == pill::PillStatus::Processing {
+    if state.pill().is_processing() {
         state.pill().cancel_processing(&app);
     } else {
         stop_active_recording(&app);
@@ -1148,7 +3296,31 @@ fn cancel_recording(app: AppHandle<AppRuntime>) {
     }
 }
 
-pub(crate) fn persist_recording_async(app: AppHandle<AppRuntime>, recording: CompletedRecording) {
+/// Ends the continuous recording session, transcribing the chunk in progress.
+#[tauri::command]
+fn stop_continuous_recording(app: AppHandle<AppRuntime>) -> Result<(), String> {
+    if !app.state::<AppState>().pill().stop_continuous(&app) {
+        return Err("No continuous recording in progress".into());
+    }
+    Ok(())
+}
+
+/// Transcriptions of the latest continuous session's chunks transcribed so far, ready to
+/// pass to `merge_transcriptions`.
+#[tauri::command]
+fn get_continuous_session_ids(state: tauri::State<AppState>) -> Vec<String> {
+    state.continuous_session_ids()
+}
+
+/// Save `recording` and queue it for transcription. `chunk` is set when it is one chunk of
+/// a continuous session. With `report_level`, its loudness is reported as soon as it has
+/// been analyzed, before saving finishes.
+pub(crate) fn persist_recording_async(
+    app: AppHandle<AppRuntime>,
+    recording: CompletedRecording,
+    chunk: Option<ChunkInFlight>,
+    report_level: bool,
+) {
     let base_dir = match recordings_root(&app) {
         Ok(path) => path,
         Err(err) => {
@@ -1161,22 +3333,66 @@ pub(crate) fn persist_recording_async(app: AppHandle<AppRuntime>, recording: Com
     };
 
     let recording_for_transcription = recording.clone();
+    let bitrate_kbps = app
+        .state::<AppState>()
+        .current_settings()
+        .audio_bitrate_kbps;
 
     async_runtime::spawn(async move {
-        let task =
-            async_runtime::spawn_blocking(move || recorder::persist_recording(base_dir, recording));
+        let task = async_runtime::spawn_blocking(move || {
+            recorder::persist_recording(base_dir, recording, bitrate_kbps)
+        });
+        // Converted once, while the MP3 encodes, for the level report, validation and
+        // duplicate detection
+        let analysis = async_runtime::spawn_blocking(move || {
+            let samples = recorder::normalized_samples(&recording_for_transcription.samples);
+            (recording_for_transcription, samples)
+        });
+        let (recording, samples) = match analysis.await {
+            Ok(analyzed) => analyzed,
+            Err(err) => {
+                emit_error(&app, format!("Recording task failed: {err}"));
+                return;
+            }
+        };
+        if report_level {
+            emit_recording_peak_db(&app, &samples);
+        }
+
         match task.await {
-            Ok(Ok(saved)) => emit_complete(&app, saved, recording_for_transcription),
+            Ok(Ok(saved)) => emit_complete(&app, saved, recording, &samples, chunk),
             Ok(Err(err)) => emit_error(&app, format!("Unable to save recording: {err}")),
             Err(err) => emit_error(&app, format!("Recording task failed: {err}")),
         }
     });
 }
 
+/// Report how loud a recording was as soon as it stops, so the UI can warn about a quiet
+/// microphone without waiting for it to be saved and validated. `passed` says whether it
+/// clears the "too quiet" check.
+fn emit_recording_peak_db(app: &AppHandle<AppRuntime>, samples: &[f32]) {
+    let rms_db = recorder::calculate_rms_db(samples);
+    let min_rms_energy = app
+        .state::<AppState>()
+        .current_settings()
+        .validation_min_rms_energy;
+
+    emit_event(
+        app,
+        EVENT_RECORDING_PEAK_DB,
+        RecordingPeakDbPayload {
+            rms_db,
+            passed: rms_db >= 20.0 * min_rms_energy.log10(),
+        },
+    );
+}
+
 fn emit_complete(
     app: &AppHandle<AppRuntime>,
     saved: RecordingSaved,
     recording: CompletedRecording,
+    samples: &[f32],
+    chunk: Option<ChunkInFlight>,
 ) {
     emit_event(
         app,
@@ -1188,8 +3404,27 @@ fn emit_complete(
             duration_ms: (saved.ended_at - saved.started_at).num_milliseconds(),
         },
     );
+    if let Some(chunk) = &chunk {
+        emit_event(
+            app,
+            EVENT_RECORDING_CHUNK_SAVED,
+            RecordingChunkSavedPayload {
+                chunk_index: chunk.chunk_index,
+                session_id: chunk.session_id.clone(),
+            },
+        );
+    }
+
+    let state = app.state::<AppState>();
+    let settings = state.current_settings();
+    let validation = ValidationConfig {
+        min_duration_ms: settings.validation_min_duration_ms,
+        min_rms_energy: settings.validation_min_rms_energy,
+        min_speech_percentage: settings.validation_min_speech_percentage,
+        max_spectral_flatness: settings.spectral_flatness_threshold,
+    };
 
-    if let Err(rejection) = validate_recording(&recording) {
+    if let Err(rejection) = validate_recording_with_config(&recording, samples, &validation) {
         let reason = match rejection {
             RecordingRejectionReason::TooShort {
                 duration_ms,
@@ -1203,19 +3438,87 @@ fn emit_complete(
             RecordingRejectionReason::NoSpeechDetected => {
                 "No speech detected in recording".to_string()
             }
+            RecordingRejectionReason::BackgroundNoise { spectral_flatness } => {
+                format!(
+                    "Recording is only background noise (spectral flatness {spectral_flatness:.2})"
+                )
+            }
             RecordingRejectionReason::EmptyBuffer => "Recording buffer is empty".to_string(),
         };
-        eprintln!("Recording rejected: {reason}");
+        info!(%reason, "Recording rejected");
 
         if let Err(err) = std::fs::remove_file(&saved.path) {
-            eprintln!("Failed to remove rejected recording file: {err}");
+            error!("Failed to remove rejected recording file: {err}");
         }
 
         hide_overlay(app);
         return;
     }
 
-    queue_transcription(app, saved, recording);
+    if settings.duplicate_detection_enabled {
+        if let Some(fingerprint) = AudioFingerprint::compute(samples, recording.sample_rate) {
+            let mut recent = state.recent_fingerprints.lock();
+            let duplicate = recent.find_duplicate(&fingerprint, recording.ended_at);
+            recent.push(fingerprint, recording.ended_at);
+            drop(recent);
+            if let Some(distance) = duplicate {
+                if hold_duplicate_recording(app, &saved, u64::BITS - distance) {
+                    return;
+                }
+            }
+        }
+    }
+
+    queue_transcription(app, saved, recording, chunk);
+}
+
+/// Keep a recording that sounds like one made moments earlier as a failed transcription
+/// instead of transcribing it, and offer to transcribe it anyway from the toast. Returns
+/// false if it couldn't be saved, in which case it should be transcribed as usual.
+fn hold_duplicate_recording(
+    app: &AppHandle<AppRuntime>,
+    saved: &RecordingSaved,
+    similarity: u32,
+) -> bool {
+    info!(similarity, "Recording held as a possible duplicate");
+    let message = "This recording sounds like the one you just made, so it wasn't transcribed.";
+    let record = app.state::<AppState>().storage().save_transcription(
+        String::new(),
+        saved.path.display().to_string(),
+        storage::TranscriptionStatus::Error,
+        Some(message.to_string()),
+        None,
+        storage::TranscriptionMetadata {
+            audio_duration_seconds: (saved.ended_at - saved.started_at).num_milliseconds() as f32
+                / 1000.0,
+            ..Default::default()
+        },
+    );
+
+    let record = match record {
+        Ok(record) => record,
+        Err(err) => {
+            error!("Failed to hold duplicate recording: {err}");
+            return false;
+        }
+    };
+
+    hide_overlay(app);
+    toast::emit_toast(
+        app,
+        toast::Payload {
+            toast_type: "warning".to_string(),
+            title: None,
+            message: message.to_string(),
+            auto_dismiss: None,
+            duration: None,
+            retry_id: Some(record.id),
+            mode: Some("duplicate".into()),
+            action: None,
+            action_label: None,
+        },
+    );
+    true
 }
 
 pub(crate) fn emit_error(app: &AppHandle<AppRuntime>, message: String) {
@@ -1226,9 +3529,23 @@ pub(crate) fn emit_error(app: &AppHandle<AppRuntime>, message: String) {
             message: message.clone(),
         },
     );
-    app.state::<AppState>()
-        .pill()
-        .transition_to_error(app, &message);
+    let state = app.state::<AppState>();
+    // A failed chunk shouldn't end the continuous session still recording
+    if state.pill().is_continuous() {
+        toast::show(app, "error", None, &message);
+    } else {
+        state.pill().transition_to_error(app, &message);
+    }
+}
+
+fn emit_llm_chunk(app: &AppHandle<AppRuntime>, chunk: &str) {
+    emit_event(
+        app,
+        EVENT_TRANSCRIPTION_LLM_STREAMING,
+        LlmStreamingPayload {
+            chunk: chunk.to_string(),
+        },
+    );
 }
 
 pub(crate) fn emit_event<T: Serialize + Clone>(
@@ -1237,7 +3554,7 @@ pub(crate) fn emit_event<T: Serialize + Clone>(
     payload: T,
 ) {
     if let Err(err) = app.emit(event, payload) {
-        eprintln!("Failed to emit {event}: {err}");
+        error!("Failed to emit {event}: {err}");
     }
 }
 
@@ -1245,6 +3562,7 @@ fn queue_transcription(
     app: &AppHandle<AppRuntime>,
     saved: RecordingSaved,
     recording: CompletedRecording,
+    chunk: Option<ChunkInFlight>,
 ) {
     emit_transcription_start(app, &saved);
 
@@ -1255,41 +3573,33 @@ fn queue_transcription(
     let http = state.http();
     let app_handle = app.clone();
     let saved_for_task = saved.clone();
-    let recording_for_task = recording.clone();
 
     async_runtime::spawn(async move {
+        let _finished = TranscriptionTaskGuard {
+            app: app_handle.clone(),
+            path: saved_for_task.path.clone(),
+        };
+        let started = Instant::now();
         let is_cancelled = || app_handle.state::<AppState>().is_cancelled();
 
         let settings = app_handle.state::<AppState>().current_settings();
         let config = transcription::TranscriptionConfig::from_settings(&settings);
-        let use_local = matches!(settings.transcription_mode, TranscriptionMode::Local);
-        let result = if use_local {
-            let model_key = settings.local_model.clone();
-            match model_manager::ensure_model_ready(&app_handle, &model_key) {
-                Ok(ready_model) => {
-                    let dictionary_prompt = dictionary_prompt_for_model(&ready_model, &settings);
-                    let language = settings.language.clone();
-                    let transcriber = app_handle.state::<AppState>().local_transcriber();
-                    let local_recording = recording_for_task.clone();
-                    match async_runtime::spawn_blocking(move || {
-                        transcriber.transcribe(
-                            &ready_model,
-                            &local_recording.samples,
-                            local_recording.sample_rate,
-                            dictionary_prompt.as_deref(),
-                            Some(&language),
-                        )
-                    })
-                    .await
-                    {
-                        Ok(inner) => inner,
-                        Err(err) => Err(anyhow!("Local transcription task failed: {err}")),
-                    }
-                }
-                Err(err) => Err(err),
-            }
-        } else {
-            transcription::request_transcription(&http, &saved_for_task, &config).await
+        info!(
+            mode = ?settings.transcription_mode,
+            model = %settings.local_model,
+            "Transcription started"
+        );
+        let Some((result, use_local)) = transcribe_for_mode(
+            &app_handle,
+            &http,
+            &settings,
+            &saved_for_task,
+            || Ok(recording),
+            is_cancelled,
+        )
+        .await
+        else {
+            return;
         };
 
         match result {
@@ -1297,7 +3607,6 @@ fn queue_transcription(
                 if is_cancelled() { return; }
 
                 let raw_transcript = result.transcript.clone();
-                let reported_model = result.speech_model.clone();
 
                 if count_words(&raw_transcript) == 0 {
                     handle_empty_transcription(&app_handle, &saved_for_task.path);
@@ -1307,22 +3616,39 @@ fn queue_transcription(
                 if is_cancelled() { return; }
 
                 let (final_transcript, llm_cleaned) =
-                    if llm_cleanup::is_cleanup_available(&settings) {
-                        match llm_cleanup::cleanup_transcription(&http, &raw_transcript, &settings)
-                            .await
+                    if llm_cleanup::is_cleanup_available(&settings)
+                        && meets_cleanup_min_words(&raw_transcript, &settings)
+                    {
+                        match llm_cleanup::cleanup_transcription(
+                            &http,
+                            &raw_transcript,
+                            &settings,
+                            |chunk| emit_llm_chunk(&app_handle, chunk),
+                        )
+                        .await
                         {
                             Ok(cleaned) => (cleaned, true),
                             Err(err) => {
-                                eprintln!("LLM cleanup failed, using raw transcript: {err}");
+                                warn!("LLM cleanup failed, using raw transcript: {err}");
                                 (raw_transcript.clone(), false)
                             }
                         }
                     } else {
                         (raw_transcript.clone(), false)
                     };
-
                 let final_transcript =
-                    apply_replacements(&final_transcript, &settings.replacements);
+                    punctuate_if_needed(final_transcript, &settings, use_local, llm_cleaned);
+                let final_transcript = paragraphize_if_needed(final_transcript, &settings);
+
+                let replaced =
+                    apply_replacements(&final_transcript, settings.active_replacements());
+                let (final_transcript, pre_replacement_transcript) = if replaced == final_transcript
+                {
+                    (final_transcript, None)
+                } else {
+                    (replaced, Some(final_transcript))
+                };
+                let final_transcript = finalize_transcript(final_transcript, &settings);
 
                 if count_words(&final_transcript) == 0 {
                     handle_empty_transcription(&app_handle, &saved_for_task.path);
@@ -1331,65 +3657,336 @@ fn queue_transcription(
 
                 if is_cancelled() { return; }
 
-                let mut pasted = false;
-                if config.auto_paste && !final_transcript.trim().is_empty() {
-                    let text = final_transcript.clone();
-                    match async_runtime::spawn_blocking(move || assistive::paste_text(&text)).await
-                    {
-                        Ok(Ok(())) => pasted = true,
-                        Ok(Err(err)) => {
-                            emit_transcription_error(
-                                &app_handle,
-                                format!("Auto paste failed: {err}"),
-                                "auto_paste",
-                                saved_for_task.path.display().to_string(),
-                            );
-                        }
-                        Err(err) => {
-                            emit_transcription_error(
-                                &app_handle,
-                                format!("Auto paste task error: {err}"),
-                                "auto_paste",
-                                saved_for_task.path.display().to_string(),
-                            );
-                        }
-                    }
-                }
+                let mut pasted = false;
+                // Chunks arrive while the user is still talking, so they are never pasted
+                if config.auto_paste
+                    && chunk.is_none()
+                    && !settings.focus_mode
+                    && !final_transcript.trim().is_empty()
+                {
+                    let text = final_transcript.clone();
+                    let paste_app = app_handle.clone();
+                    let paste_mode = settings.paste_mode.clone();
+                    match async_runtime::spawn_blocking(move || {
+                        paste_transcript(&paste_app, &text, &paste_mode)
+                    })
+                    .await
+                    {
+                        Ok(Ok(())) => pasted = true,
+                        Ok(Err(err)) => {
+                            emit_transcription_error(
+                                &app_handle,
+                                format!("Auto paste failed: {err}"),
+                                "auto_paste",
+                                saved_for_task.path.display().to_string(),
+                            );
+                        }
+                        Err(err) => {
+                            emit_transcription_error(
+                                &app_handle,
+                                format!("Auto paste task error: {err}"),
+                                "auto_paste",
+                                saved_for_task.path.display().to_string(),
+                            );
+                        }
+                    }
+                }
+
+                let metadata = build_transcription_metadata(
+                    &saved_for_task,
+                    &settings,
+                    use_local,
+                    &result,
+                    &final_transcript,
+                    llm_cleaned,
+                );
+
+                app_handle.state::<AppState>().record_session_transcription(
+                    metadata.audio_duration_seconds,
+                    metadata.word_count,
+                    started.elapsed(),
+                );
+                info!(
+                    engine = if use_local { "local" } else { "cloud" },
+                    model = %metadata.speech_model,
+                    words = metadata.word_count,
+                    duration_ms = started.elapsed().as_millis() as u64,
+                    "Transcription finished"
+                );
+
+                let transcription_id = emit_transcription_complete_with_cleanup(
+                    &app_handle,
+                    raw_transcript,
+                    final_transcript,
+                    pre_replacement_transcript,
+                    pasted,
+                    saved_for_task.path.display().to_string(),
+                    llm_cleaned,
+                    metadata,
+                    "unknown",
+                    if use_local { "local" } else { "cloud" },
+                );
+                if let (Some(chunk), Some(id)) = (&chunk, transcription_id) {
+                    app_handle
+                        .state::<AppState>()
+                        .record_continuous_chunk(chunk, id);
+                }
+
+                hide_overlay(&app_handle);
+            }
+            Err(err) if err.is::<local_transcription::TranscriptionTimedOut>() => {
+                error!(error = %err, "Local transcription timed out");
+                emit_transcription_error(
+                    &app_handle,
+                    err.to_string(),
+                    "local_timeout",
+                    saved_for_task.path.display().to_string(),
+                );
+            }
+            Err(err) => {
+                let stage = if use_local { "local" } else { "api" };
+                error!(stage, error = %err, "Transcription failed");
+                emit_transcription_error(
+                    &app_handle,
+                    format!("Transcription failed: {err}"),
+                    stage,
+                    saved_for_task.path.display().to_string(),
+                );
+            }
+        }
+    });
+}
+
+/// Transcribe with the engine the transcription mode picks. Hybrid tries the local model
+/// first and falls back to the cloud when it fails, runs past its timeout, or is still busy
+/// with a run abandoned earlier. `recording` is only called when the local model is used.
+/// Returns the result and whether the local model produced it, or `None` if cancelled
+/// before falling back.
+async fn transcribe_for_mode(
+    app: &AppHandle<AppRuntime>,
+    http: &Client,
+    settings: &UserSettings,
+    saved: &RecordingSaved,
+    recording: impl FnOnce() -> Result<CompletedRecording>,
+    is_cancelled: impl Fn() -> bool,
+) -> Option<(Result<transcription::TranscriptionSuccess>, bool)> {
+    let config = transcription::TranscriptionConfig::from_settings(settings);
+    let timeout = match settings.transcription_mode {
+        TranscriptionMode::Local => {
+            let result = match recording() {
+                Ok(recording) => transcribe_recording_locally(app, settings, recording).await,
+                Err(err) => Err(err),
+            };
+            return Some((result, true));
+        }
+        TranscriptionMode::Cloud => {
+            let result = transcription::request_transcription(http, saved, &config).await;
+            return Some((result, false));
+        }
+        TranscriptionMode::Hybrid => {
+            Duration::from_secs(u64::from(settings.hybrid_local_timeout_seconds))
+        }
+    };
+
+    let reason = if app.state::<AppState>().local_transcriber().is_busy() {
+        warn!("Local model is still busy, falling back to cloud");
+        "busy"
+    } else {
+        let local = match recording() {
+            Ok(recording) => {
+                tokio::time::timeout(
+                    timeout,
+                    transcribe_recording_locally(app, settings, recording),
+                )
+                .await
+            }
+            Err(err) => Ok(Err(err)),
+        };
+        match local {
+            Ok(Ok(result)) => return Some((Ok(result), true)),
+            Ok(Err(err)) => {
+                warn!(error = %err, "Local transcription failed, falling back to cloud");
+                "error"
+            }
+            Err(_) => {
+                warn!(
+                    timeout_secs = timeout.as_secs(),
+                    "Local transcription timed out, falling back to cloud"
+                );
+                "timeout"
+            }
+        }
+    };
+    emit_event(
+        app,
+        EVENT_TRANSCRIPTION_FALLBACK,
+        TranscriptionFallbackPayload {
+            reason: reason.to_string(),
+            stage: "local".to_string(),
+        },
+    );
+
+    if is_cancelled() {
+        return None;
+    }
+    Some((
+        transcription::request_transcription(http, saved, &config).await,
+        false,
+    ))
+}
+
+/// Marks the transcription task as done when dropped, which covers every early return.
+struct TranscriptionTaskGuard {
+    app: AppHandle<AppRuntime>,
+    path: PathBuf,
+}
+
+impl Drop for TranscriptionTaskGuard {
+    fn drop(&mut self) {
+        self.app
+            .state::<AppState>()
+            .finish_transcription_task(&self.path);
+    }
+}
+
+async fn transcribe_recording_locally(
+    app: &AppHandle<AppRuntime>,
+    settings: &UserSettings,
+    recording: CompletedRecording,
+) -> Result<transcription::TranscriptionSuccess> {
+    // The first load of a model hashes its files, which can take a while for large ones
+    let ready_model = {
+        let app = app.clone();
+        let model_key = settings.local_model.clone();
+        async_runtime::spawn_blocking(move || model_manager::ensure_model_ready(&app, &model_key))
+            .await
+            .map_err(|err| anyhow!("Model check task failed: {err}"))??
+    };
+    load_local_model(app, &ready_model).await?;
+    let dictionary_prompt = dictionary_prompt_for_model(&ready_model, settings);
+    let language = settings.language.code().map(str::to_string);
+    let transcriber = app.state::<AppState>().local_transcriber();
+    let limit = local_transcription_limit(settings);
+
+    let audio_duration_seconds = recording.samples.len() as f32
+        / (recording.sample_rate as f32 * f32::from(recording.channels.max(1)));
+    if let Some(estimate) =
+        model_manager::estimate_transcription_time(app, &ready_model.key, audio_duration_seconds)
+    {
+        emit_event(
+            app,
+            EVENT_TRANSCRIPTION_ESTIMATED_DURATION,
+            EstimatedDurationPayload {
+                ms: (estimate * 1000.0).round() as u32,
+            },
+        );
+    }
+    let model_key = ready_model.key.clone();
+    let started = Instant::now();
+
+    let result = local_transcription::run_with_time_limit(limit, move || {
+        transcriber.transcribe(
+            &ready_model,
+            &recording.samples,
+            recording.sample_rate,
+            dictionary_prompt.as_deref(),
+            language.as_deref(),
+        )
+    })
+    .await;
+    unload_if_stalled(app, &result);
+    if result.is_ok() {
+        let elapsed_seconds = started.elapsed().as_secs_f32();
+        if let Err(err) = model_manager::record_transcription_benchmark(
+            app,
+            &model_key,
+            audio_duration_seconds,
+            elapsed_seconds,
+        ) {
+            warn!("Failed to record transcription benchmark: {err}");
+        }
+    }
+    result
+}
+
+fn local_transcription_limit(settings: &UserSettings) -> Duration {
+    Duration::from_secs(u64::from(settings.local_transcription_timeout_seconds))
+}
+
+/// Drop a model that timed out, in case its engine is wedged; the next recording then
+/// loads a fresh one.
+fn unload_if_stalled<T>(app: &AppHandle<AppRuntime>, result: &Result<T>) {
+    let Err(err) = result else {
+        return;
+    };
+    if !err.is::<local_transcription::TranscriptionTimedOut>() {
+        return;
+    }
+
+    match app.state::<AppState>().local_transcriber().unload() {
+        Some(model_key) => {
+            warn!(%model_key, "Unloaded local model after transcription timed out");
+            emit_event(
+                app,
+                EVENT_MODEL_UNLOADED,
+                ModelUnloadedPayload { model_key },
+            );
+        }
+        None => warn!("Timed out local model is still busy, leaving it loaded"),
+    }
+}
+
+const MODEL_LOADING_TOAST_DELAY: Duration = Duration::from_secs(2);
 
-                let metadata = build_transcription_metadata(
-                    &saved_for_task,
-                    &settings,
-                    use_local,
-                    reported_model.as_deref(),
-                    &final_transcript,
-                    llm_cleaned,
-                );
+/// Load a local model that isn't in memory yet, showing the pill's loading state meanwhile.
+async fn load_local_model(
+    app: &AppHandle<AppRuntime>,
+    model: &model_manager::ReadyModel,
+) -> Result<()> {
+    let transcriber = app.state::<AppState>().local_transcriber();
+    if transcriber.is_loaded(model) {
+        return Ok(());
+    }
 
-                emit_transcription_complete_with_cleanup(
-                    &app_handle,
-                    raw_transcript,
-                    final_transcript,
-                    pasted,
-                    saved_for_task.path.display().to_string(),
-                    llm_cleaned,
-                    metadata,
-                    "unknown",
-                    if use_local { "local" } else { "cloud" },
-                );
+    let state = app.state::<AppState>();
+    state.pill().transition_from(
+        app,
+        pill::PillStatus::Processing,
+        pill::PillStatus::ModelLoading,
+    );
+    emit_event(
+        app,
+        EVENT_TRANSCRIPTION_MODEL_LOADING,
+        ModelLoadingPayload {
+            model_key: model.key.clone(),
+        },
+    );
 
-                hide_overlay(&app_handle);
-            }
-            Err(err) => {
-                let stage = if use_local { "local" } else { "api" };
-                emit_transcription_error(
-                    &app_handle,
-                    format!("Transcription failed: {err}"),
-                    stage,
-                    saved_for_task.path.display().to_string(),
-                );
-            }
+    let ready_model = model.clone();
+    let mut load = async_runtime::spawn_blocking(move || transcriber.ensure_engine(&ready_model));
+    let loaded = match tokio::time::timeout(MODEL_LOADING_TOAST_DELAY, &mut load).await {
+        Ok(loaded) => loaded,
+        Err(_) => {
+            toast::show(app, "info", None, "Loading model…");
+            load.await
         }
-    });
+    };
+
+    state.pill().transition_from(
+        app,
+        pill::PillStatus::ModelLoading,
+        pill::PillStatus::Processing,
+    );
+    loaded.map_err(|err| anyhow!("Model load task failed: {err}"))??;
+
+    emit_event(
+        app,
+        EVENT_TRANSCRIPTION_MODEL_READY,
+        ModelLoadingPayload {
+            model_key: model.key.clone(),
+        },
+    );
+    Ok(())
 }
 
 fn emit_transcription_start(app: &AppHandle<AppRuntime>, saved: &RecordingSaved) {
@@ -1402,25 +3999,30 @@ fn emit_transcription_start(app: &AppHandle<AppRuntime>, saved: &RecordingSaved)
     );
 }
 
+/// Returns the id of the saved transcription.
 #[allow(dead_code)]
 fn emit_transcription_complete_with_cleanup(
     app: &AppHandle<AppRuntime>,
     raw_transcript: String,
     final_transcript: String,
+    pre_replacement_transcript: Option<String>,
     auto_paste: bool,
     audio_path: String,
     llm_cleaned: bool,
     metadata: storage::TranscriptionMetadata,
     mode: &str,
     engine: &str,
-) {
-    analytics::track_transcription_completed(
+) -> Option<String> {
+    analytics::track(
         app,
-        mode,
-        engine,
-        Some(&metadata.speech_model),
-        llm_cleaned,
-        metadata.audio_duration_seconds as f64,
+        AnalyticsEvent::TranscriptionCompleted {
+            mode: mode.to_string(),
+            engine: engine.to_string(),
+            model: metadata.speech_model.clone(),
+            word_count: metadata.word_count,
+            duration_seconds: metadata.audio_duration_seconds,
+            llm_cleaned,
+        },
     );
 
     emit_event(
@@ -1429,27 +4031,169 @@ fn emit_transcription_complete_with_cleanup(
         TranscriptionCompletePayload {
             transcript: final_transcript.clone(),
             auto_paste,
+            confidence: metadata.confidence,
+            detected_language: metadata.detected_language.clone(),
         },
     );
 
-    if llm_cleaned {
-        let _ = app
-            .state::<AppState>()
+    let pasted_text = auto_paste.then(|| final_transcript.clone());
+    let saved = if llm_cleaned {
+        app.state::<AppState>()
             .storage()
             .save_transcription_with_cleanup(
                 raw_transcript,
                 final_transcript,
                 audio_path,
+                pre_replacement_transcript,
                 metadata,
-            );
+            )
     } else {
-        let _ = app.state::<AppState>().storage().save_transcription(
+        app.state::<AppState>().storage().save_transcription(
             final_transcript,
             audio_path,
             storage::TranscriptionStatus::Success,
             None,
+            pre_replacement_transcript,
             metadata,
-        );
+        )
+    };
+
+    let settings = app.state::<AppState>().current_settings();
+    if saved.is_ok() {
+        schedule_auto_backup(app, &settings);
+    }
+    if let (Some(text), true) = (pasted_text, settings.clipboard_history_enabled) {
+        let transcription_id = saved.as_ref().ok().map(|record| record.id.clone());
+        app.state::<AppState>()
+            .record_pasted_text(text, transcription_id);
+    }
+    if let Ok(record) = &saved {
+        if settings.spotlight_indexing_enabled && !record.audio_path.is_empty() {
+            platform::spotlight::index_audio_file(Path::new(&record.audio_path), &record.text);
+        }
+    }
+    if let (Ok(record), Some(goal)) = (&saved, settings.daily_word_goal) {
+        check_daily_goal(app, record.word_count, goal);
+    }
+    let transcription_id = saved.as_ref().ok().map(|record| record.id.clone());
+    if let (Ok(record), Some(url)) = (saved, settings.webhook_url) {
+        if settings.webhook_on_every_transcription {
+            let app_handle = app.clone();
+            async_runtime::spawn(async move {
+                if let Err(err) = send_to_webhook(&app_handle, &url, &record).await {
+                    error!("Webhook push failed: {err}");
+                }
+            });
+        }
+    }
+    transcription_id
+}
+
+/// Celebrate when the words just saved carried today's total past the daily goal.
+fn check_daily_goal(app: &AppHandle<AppRuntime>, added_words: u32, goal: u32) {
+    let words_today = match app.state::<AppState>().storage().get_words_today() {
+        Ok(words) => words,
+        Err(err) => {
+            error!("Failed to count today's words: {err}");
+            return;
+        }
+    };
+
+    if !goal_crossed(words_today.saturating_sub(added_words), words_today, goal) {
+        return;
+    }
+    emit_event(app, EVENT_GOAL_REACHED, GoalReachedPayload { words_today });
+    toast::show(app, "success", None, "Daily goal reached!");
+}
+
+fn goal_crossed(words_before: u32, words_after: u32, goal: u32) -> bool {
+    goal > 0 && words_before < goal && goal <= words_after
+}
+
+#[tauri::command]
+async fn push_transcription_to_webhook(
+    id: String,
+    webhook_url: String,
+    app: AppHandle<AppRuntime>,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    let record = state
+        .storage()
+        .get_by_id(&id)
+        .ok_or_else(|| "Transcription not found".to_string())?;
+
+    let webhook_url = webhook_url.trim();
+    webhook::validate_url(webhook_url)?;
+    send_to_webhook(&app, webhook_url, &record).await
+}
+
+async fn send_to_webhook(
+    app: &AppHandle<AppRuntime>,
+    url: &str,
+    record: &storage::TranscriptionRecord,
+) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let storage = state.storage();
+
+    let secret = state.current_settings().webhook_secret;
+
+    match webhook::push_transcription(&state.http(), url, secret.as_deref(), record).await {
+        Ok(()) => {
+            if let Err(err) = storage.mark_as_synced(&record.id) {
+                error!("Failed to mark transcription as synced: {err}");
+            }
+            if let Err(err) = storage.mark_webhook_pending(&record.id, false) {
+                error!("Failed to clear pending webhook send: {err}");
+            }
+            Ok(())
+        }
+        Err(webhook::WebhookError::Permanent(err)) => {
+            if let Err(store_err) = storage.mark_webhook_pending(&record.id, false) {
+                error!("Failed to clear pending webhook send: {store_err}");
+            }
+            toast::show(app, "error", Some("Webhook failed"), &err.to_string());
+            Err(format!("Webhook rejected transcription: {err}"))
+        }
+        Err(webhook::WebhookError::Retriable(err)) => {
+            if let Err(store_err) = storage.mark_webhook_pending(&record.id, true) {
+                error!("Failed to store pending webhook send: {store_err}");
+            }
+            Err(format!("Webhook unavailable, will retry later: {err}"))
+        }
+    }
+}
+
+const WEBHOOK_RETRY_STARTUP_DELAY: Duration = Duration::from_secs(30);
+const WEBHOOK_RETRY_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Resend transcriptions whose webhook push failed with a retriable error: once shortly
+/// after launch, then periodically while the app is running.
+fn start_webhook_retry_loop(app: &AppHandle<AppRuntime>) {
+    let app = app.clone();
+    async_runtime::spawn(async move {
+        tokio::time::sleep(WEBHOOK_RETRY_STARTUP_DELAY).await;
+        loop {
+            retry_pending_webhooks(&app).await;
+            tokio::time::sleep(WEBHOOK_RETRY_INTERVAL).await;
+        }
+    });
+}
+
+async fn retry_pending_webhooks(app: &AppHandle<AppRuntime>) {
+    let Some(url) = app.state::<AppState>().current_settings().webhook_url else {
+        return;
+    };
+    let pending = match app.state::<AppState>().storage().get_pending_webhooks() {
+        Ok(records) => records,
+        Err(err) => {
+            error!("Failed to load pending webhook sends: {err}");
+            return;
+        }
+    };
+    for record in pending {
+        if let Err(err) = send_to_webhook(app, &url, &record).await {
+            warn!(id = %record.id, "Pending webhook send failed: {err}");
+        }
     }
 }
 
@@ -1460,6 +4204,7 @@ fn handle_empty_transcription(app: &AppHandle<AppRuntime>, audio_path: &Path) {
         TranscriptionCompletePayload {
             transcript: String::new(),
             auto_paste: false,
+            confidence: None,
         },
     );
 
@@ -1480,7 +4225,7 @@ fn handle_empty_transcription(app: &AppHandle<AppRuntime>, audio_path: &Path) {
 
     if audio_path.exists() {
         if let Err(err) = std::fs::remove_file(audio_path) {
-            eprintln!(
+            warn!(
                 "Failed to remove empty transcription audio {}: {err}",
                 audio_path.display()
             );
@@ -1496,7 +4241,14 @@ fn emit_transcription_error(
     stage: &str,
     audio_path: String,
 ) {
-    let engine = if stage == "local" { "local" } else { "cloud" };
+    let settings = app.state::<AppState>().current_settings();
+    let is_local = match stage {
+        "local" | "local_timeout" => true,
+        "api" => false,
+        // Later stages don't say which engine ran; hybrid tries the local model first
+        _ => !matches!(settings.transcription_mode, TranscriptionMode::Cloud),
+    };
+    let engine = if is_local { "local" } else { "cloud" };
     let reason = if message.contains("No speech") || message.contains("empty") {
         "no_speech"
     } else if message.contains("Model") || message.contains("model") {
@@ -1504,7 +4256,14 @@ fn emit_transcription_error(
     } else {
         "api_error"
     };
-    analytics::track_transcription_failed(app, stage, engine, reason);
+    analytics::track(
+        app,
+        AnalyticsEvent::TranscriptionFailed {
+            stage: stage.to_string(),
+            engine: engine.to_string(),
+            reason: reason.to_string(),
+        },
+    );
 
     emit_event(
         app,
@@ -1515,12 +4274,11 @@ fn emit_transcription_error(
         },
     );
 
-    app.state::<AppState>()
-        .pill()
-        .transition_to_error(app, &message);
-
-    let settings = app.state::<AppState>().current_settings();
-    let is_local = matches!(settings.transcription_mode, TranscriptionMode::Local);
+    let state = app.state::<AppState>();
+    // A continuous session keeps recording; the toast below covers the failed chunk
+    if !state.pill().continuous_session_active() {
+        state.pill().transition_to_error(app, &message);
+    }
 
     let toast_message = format_transcription_error(&message, is_local);
     let metadata = storage::TranscriptionMetadata {
@@ -1533,6 +4291,7 @@ fn emit_transcription_error(
         audio_path.clone(),
         storage::TranscriptionStatus::Error,
         Some(toast_message.clone()),
+        None,
         metadata,
     );
 
@@ -1540,13 +4299,13 @@ fn emit_transcription_error(
         match record_result {
             Ok(record) => Some(record.id),
             Err(err) => {
-                eprintln!("Failed to persist failed transcription: {err}");
+                error!("Failed to persist failed transcription: {err}");
                 None
             }
         }
     } else {
         if let Err(err) = record_result {
-            eprintln!("Failed to persist failed transcription: {err}");
+            error!("Failed to persist failed transcription: {err}");
         }
         None
     };
@@ -1582,6 +4341,9 @@ fn format_transcription_error(message: &str, is_local: bool) -> String {
         if msg_lower.contains("model not found") || msg_lower.contains("no model") {
             return "No transcription model selected".to_string();
         }
+        if msg_lower.contains("timed out") {
+            return "Transcription timed out. Try a smaller model in Settings.".to_string();
+        }
     } else {
         if msg_lower.contains("network") || msg_lower.contains("connection") {
             return "Network error. Recording saved. Tap Retry to send again.".to_string();
@@ -1615,12 +4377,16 @@ fn build_transcription_metadata(
     saved: &RecordingSaved,
     settings: &UserSettings,
     use_local: bool,
-    reported_model: Option<&str>,
+    result: &transcription::TranscriptionSuccess,
     final_text: &str,
     llm_cleaned: bool,
 ) -> storage::TranscriptionMetadata {
     storage::TranscriptionMetadata {
-        speech_model: resolve_speech_model_label(settings, use_local, reported_model),
+        speech_model: resolve_speech_model_label(
+            settings,
+            use_local,
+            result.speech_model.as_deref(),
+        ),
         llm_model: if llm_cleaned {
             llm_cleanup::resolved_model_name(settings)
         } else {
@@ -1628,6 +4394,8 @@ fn build_transcription_metadata(
         },
         word_count: count_words(final_text),
         audio_duration_seconds: compute_audio_duration_seconds(saved),
+        confidence: result.avg_confidence,
+        detected_language: result.detected_language.clone(),
     }
 }
 
@@ -1652,6 +4420,37 @@ fn compute_audio_duration_seconds(saved: &RecordingSaved) -> f32 {
     (duration_ms.max(0) as f32) / 1000.0
 }
 
+fn meets_cleanup_min_words(transcript: &str, settings: &UserSettings) -> bool {
+    let words = count_words(transcript);
+    if words < settings.llm_cleanup_min_words {
+        info!(
+            words,
+            threshold = settings.llm_cleanup_min_words,
+            "LLM cleanup skipped: transcript too short"
+        );
+        return false;
+    }
+    true
+}
+
+/// Insert a transcription at the cursor using the configured paste mode. Typing only
+/// handles ASCII reliably, so anything else goes through the clipboard instead.
+fn paste_transcript(app: &AppHandle<AppRuntime>, text: &str, mode: &PasteMode) -> Result<()> {
+    match mode {
+        PasteMode::InputSimulation if text.is_ascii() => assistive::paste_via_key_events(text),
+        PasteMode::InputSimulation => {
+            toast::show(
+                app,
+                "warning",
+                None,
+                "Typing only supports plain text, so this transcription was pasted via the clipboard.",
+            );
+            assistive::paste_text(text)
+        }
+        PasteMode::Clipboard => assistive::paste_text(text),
+    }
+}
+
 fn count_words(text: &str) -> u32 {
     text.split_whitespace()
         .filter(|word| !word.is_empty())
@@ -1662,6 +4461,15 @@ fn load_audio_for_transcription(path: &PathBuf) -> Result<(Vec<i16>, u32)> {
     use minimp3::{Decoder, Frame};
     use std::io::Read;
 
+    let is_wav = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.eq_ignore_ascii_case("wav"))
+        .unwrap_or(false);
+    if is_wav {
+        return load_wav(path);
+    }
+
     let mut file = std::fs::File::open(path)
         .with_context(|| format!("Failed to open audio file at {}", path.display()))?;
     let mut mp3_data = Vec::new();
@@ -1703,6 +4511,51 @@ fn load_audio_for_transcription(path: &PathBuf) -> Result<(Vec<i16>, u32)> {
     Ok((samples, sample_rate))
 }
 
+fn load_wav(path: &PathBuf) -> Result<(Vec<i16>, u32)> {
+    let mut reader = hound::WavReader::open(path)
+        .with_context(|| format!("Failed to open WAV file at {}", path.display()))?;
+    let spec = reader.spec();
+    let channels = spec.channels.max(1) as usize;
+
+    let interleaved: Vec<i16> = match spec.sample_format {
+        hound::SampleFormat::Int if spec.bits_per_sample <= 16 => reader
+            .samples::<i16>()
+            .collect::<std::result::Result<_, _>>()
+            .context("Failed to read WAV samples")?,
+        hound::SampleFormat::Int => {
+            let shift = spec.bits_per_sample.saturating_sub(16);
+            reader
+                .samples::<i32>()
+                .map(|sample| sample.map(|s| (s >> shift) as i16))
+                .collect::<std::result::Result<_, _>>()
+                .context("Failed to read WAV samples")?
+        }
+        hound::SampleFormat::Float => reader
+            .samples::<f32>()
+            .map(|sample| sample.map(|s| (s.clamp(-1.0, 1.0) * i16::MAX as f32) as i16))
+            .collect::<std::result::Result<_, _>>()
+            .context("Failed to read WAV samples")?,
+    };
+
+    let samples: Vec<i16> = if channels == 1 {
+        interleaved
+    } else {
+        interleaved
+            .chunks(channels)
+            .map(|chunk| {
+                let sum: i32 = chunk.iter().map(|&s| s as i32).sum();
+                (sum / chunk.len() as i32) as i16
+            })
+            .collect()
+    };
+
+    if samples.is_empty() {
+        return Err(anyhow!("No audio data in WAV file"));
+    }
+
+    Ok((samples, spec.sample_rate))
+}
+
 fn recordings_root(app: &AppHandle<AppRuntime>) -> GlimpseResult<PathBuf> {
     let mut data_dir = app
         .path()
@@ -1717,11 +4570,28 @@ pub(crate) struct RecordingStartPayload {
     started_at: String,
 }
 
+#[derive(Serialize, Clone)]
+pub(crate) struct RecordingAmplitudePayload {
+    rms: f32,
+}
+
 #[derive(Serialize, Clone)]
 pub(crate) struct RecordingStopPayload {
     ended_at: String,
 }
 
+#[derive(Serialize, Clone)]
+struct RecordingChunkSavedPayload {
+    chunk_index: u32,
+    session_id: String,
+}
+
+#[derive(Serialize, Clone)]
+struct RecordingPeakDbPayload {
+    rms_db: f32,
+    passed: bool,
+}
+
 #[derive(Serialize, Clone)]
 struct RecordingCompletePayload {
     path: String,
@@ -1735,15 +4605,69 @@ struct RecordingErrorPayload {
     message: String,
 }
 
+#[derive(Serialize, Clone)]
+struct RecordingDeviceReconnectedPayload {
+    device: String,
+}
+
+#[derive(Serialize, Clone)]
+struct RecordingCalibratingPayload {
+    percent: u32,
+}
+
+#[derive(Serialize, Clone)]
+struct RecordingTickPayload {
+    duration_ms: i64,
+}
+
+#[derive(Serialize, Clone)]
+struct AudioLevelPayload {
+    db: f32,
+    peak_db: f32,
+}
+
 #[derive(Serialize, Clone)]
 struct TranscriptionStartPayload {
     path: String,
 }
 
+#[derive(Serialize, Clone)]
+struct EstimatedDurationPayload {
+    ms: u32,
+}
+
+#[derive(Serialize, Clone)]
+struct LlmStreamingPayload {
+    chunk: String,
+}
+
+#[derive(Serialize, Clone)]
+struct ModelLoadingPayload {
+    model_key: String,
+}
+
+#[derive(Serialize, Clone)]
+struct ModelUnloadedPayload {
+    model_key: String,
+}
+
+#[derive(Serialize, Clone)]
+struct GoalReachedPayload {
+    words_today: u32,
+}
+
+#[derive(Serialize, Clone)]
+struct TranscriptionFallbackPayload {
+    reason: String,
+    stage: String,
+}
+
 #[derive(Serialize, Clone)]
 struct TranscriptionCompletePayload {
     transcript: String,
     auto_paste: bool,
+    confidence: Option<f32>,
+    detected_language: Option<String>,
 }
 
 #[derive(Serialize, Clone)]
@@ -1751,3 +4675,161 @@ struct TranscriptionErrorPayload {
     message: String,
     stage: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn replacement(from: &str, to: &str, priority: i32) -> Replacement {
+        Replacement {
+            from: from.to_string(),
+            to: to.to_string(),
+            priority,
+        }
+    }
+
+    #[test]
+    fn test_apply_replacements_respects_priority() {
+        let replacements = vec![
+            replacement("AI", "A.I.", 0),
+            replacement("Open AI", "OpenAI", 10),
+        ];
+
+        assert_eq!(
+            apply_replacements("I use Open AI and AI tools", &replacements),
+            "I use OpenAI and A.I. tools"
+        );
+    }
+
+    #[test]
+    fn test_apply_replacements_prefers_longer_patterns_on_tie() {
+        let replacements = vec![
+            replacement("AI", "A.I.", 0),
+            replacement("Open AI", "OpenAI", 0),
+        ];
+
+        assert_eq!(apply_replacements("Open AI", &replacements), "OpenAI");
+    }
+
+    #[test]
+    fn test_merge_dictionary_import() {
+        let existing = vec!["Kubernetes".to_string()];
+        let text = "kubernetes\n  PostgreSQL \n\nPOSTGRESQL\r\nGlimpse\n";
+        let (merged, dropped) = merge_dictionary_import(&existing, text);
+        assert_eq!(merged, vec!["Kubernetes", "PostgreSQL", "Glimpse"]);
+        assert_eq!(dropped, 0);
+
+        let long_list: String = (0..70).map(|i| format!("term{i}\n")).collect();
+        let (merged, dropped) = merge_dictionary_import(&existing, &long_list);
+        assert_eq!(merged.len(), MAX_DICTIONARY_ENTRIES);
+        assert_eq!(merged[0], "Kubernetes");
+        assert_eq!(dropped, 7);
+    }
+
+    #[test]
+    fn test_validate_settings() {
+        let defaults = UserSettings::default();
+        assert_eq!(validate_settings(&defaults), Ok(()));
+
+        let invalid: [fn(&mut UserSettings); 8] = [
+            |settings| settings.audio_bitrate_kbps = 100,
+            |settings| settings.min_word_confidence = 1.5,
+            |settings| settings.hybrid_local_timeout_seconds = 0,
+            |settings| settings.local_transcription_timeout_seconds = 10,
+            |settings| settings.sentences_per_paragraph = 0,
+            |settings| settings.reconnect_grace_seconds = Some(600),
+            |settings| settings.webhook_url = Some("http://example.com/hook".to_string()),
+            |settings| {
+                settings.hold_enabled = true;
+                settings.hold_shortcut = settings.smart_shortcut.to_lowercase();
+            },
+        ];
+        for change in invalid {
+            let mut settings = defaults.clone();
+            change(&mut settings);
+            assert!(validate_settings(&settings).is_err(), "{settings:?}");
+        }
+    }
+
+    #[test]
+    fn test_finalize_transcript() {
+        let mut settings = UserSettings::default();
+        assert_eq!(
+            finalize_transcript("Hello  there,\n  friend. ".to_string(), &settings),
+            "Hello there, friend."
+        );
+        assert_eq!(
+            finalize_transcript("One  two.\n\n\n\nThree. ".to_string(), &settings),
+            "One two.\n\nThree."
+        );
+
+        settings.strip_trailing_period = true;
+        assert_eq!(
+            finalize_transcript("weather tomorrow.".to_string(), &settings),
+            "weather tomorrow"
+        );
+        assert_eq!(
+            finalize_transcript("and then...".to_string(), &settings),
+            "and then..."
+        );
+
+        settings.replace_consecutive_spaces = false;
+        assert_eq!(
+            finalize_transcript("keep  spacing. ".to_string(), &settings),
+            "keep  spacing"
+        );
+    }
+
+    #[test]
+    fn test_goal_crossed() {
+        assert!(goal_crossed(900, 1000, 1000));
+        assert!(goal_crossed(0, 1500, 1000));
+        assert!(!goal_crossed(900, 999, 1000));
+        // Already reached earlier today
+        assert!(!goal_crossed(1000, 1200, 1000));
+        assert!(!goal_crossed(0, 10, 0));
+    }
+
+    #[test]
+    fn test_validate_report_range() {
+        let now = chrono::Local::now();
+        let days = |n: u32| chrono::Duration::days(i64::from(n));
+
+        assert!(validate_report_range(now, now).is_ok());
+        assert!(validate_report_range(now - days(MAX_REPORT_DAYS), now).is_ok());
+        assert!(validate_report_range(now - days(MAX_REPORT_DAYS + 1), now).is_err());
+        assert!(validate_report_range(now, now - days(1)).is_err());
+    }
+
+    #[test]
+    fn test_resolve_export_path_stays_in_recordings() {
+        let root = std::env::temp_dir().join(format!("glimpse-export-{}", uuid::Uuid::new_v4()));
+        let recordings = root.join("recordings");
+        let day = recordings.join("2026-10-17");
+        let outside = root.join("elsewhere");
+        std::fs::create_dir_all(&day).unwrap();
+        std::fs::create_dir_all(&outside).unwrap();
+
+        let resolved = resolve_export_path(&day.join("memo.wav"), &recordings).unwrap();
+        assert_eq!(resolved, day.canonicalize().unwrap().join("memo.wav"));
+
+        assert!(resolve_export_path(&outside.join("memo.wav"), &recordings).is_err());
+        assert!(resolve_export_path(&day.join("../../elsewhere/memo.wav"), &recordings).is_err());
+        assert!(resolve_export_path(&day.join("missing/memo.wav"), &recordings).is_err());
+        assert!(resolve_export_path(Path::new("memo.wav"), &recordings).is_err());
+
+        #[cfg(unix)]
+        {
+            let link = recordings.join("escape");
+            std::os::unix::fs::symlink(&outside, &link).unwrap();
+            assert!(resolve_export_path(&link.join("memo.wav"), &recordings).is_err());
+
+            let file_link = day.join("linked.wav");
+            std::os::unix::fs::symlink(outside.join("target.wav"), &file_link).unwrap();
+            std::fs::write(outside.join("target.wav"), b"riff").unwrap();
+            assert!(resolve_export_path(&file_link, &recordings).is_err());
+        }
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -1,6 +1,8 @@
 use anyhow::{anyhow, Context, Result};
-use reqwest::Client;
+use reqwest::header::CONTENT_TYPE;
+use reqwest::{Client, Response};
 use serde::{Deserialize, Serialize};
+use tracing::debug;
 
 use crate::settings::{LlmProvider, UserSettings};
 
@@ -29,12 +31,26 @@ User: My favorite color is red... actually wait wait wait its blue.
 Assistant: <output>My favorite color is blue.</output>
 "#;
 
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+pub const MAX_SYSTEM_PROMPT_CHARS: usize = 2000;
+
+/// Anthropic's models endpoint uses its own schema, so the picker offers a fixed list.
+const ANTHROPIC_MODELS: &[&str] = &[
+    "claude-3-5-sonnet-20241022",
+    "claude-3-5-haiku-20241022",
+    "claude-3-opus-20240229",
+    "claude-3-haiku-20240307",
+];
+
 #[derive(Debug, Serialize)]
 struct ChatRequest {
     model: String,
     messages: Vec<Message>,
     temperature: f32,
     max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -58,6 +74,140 @@ struct MessageContent {
     content: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct ChatStreamChunk {
+    choices: Vec<StreamChoice>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StreamChoice {
+    #[serde(default)]
+    delta: StreamDelta,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct StreamDelta {
+    #[serde(default)]
+    content: Option<String>,
+}
+
+/// Splits an OpenAI-style `text/event-stream` body into lines as it arrives and pulls the
+/// `delta.content` out of each `data:` event, up to `data: [DONE]`.
+#[derive(Debug, Default)]
+struct SseParser {
+    buffer: Vec<u8>,
+    done: bool,
+}
+
+impl SseParser {
+    /// Add bytes from the body and return the content of every event completed by them.
+    fn push(&mut self, bytes: &[u8]) -> Vec<String> {
+        self.buffer.extend_from_slice(bytes);
+        let mut deltas = Vec::new();
+        // Lines are split as bytes so a character cut between chunks is decoded whole
+        while let Some(newline) = self.buffer.iter().position(|byte| *byte == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=newline).collect();
+            deltas.extend(self.parse_line(&line));
+        }
+        deltas
+    }
+
+    /// Content of a final event the body didn't end with a newline.
+    fn finish(&mut self) -> Option<String> {
+        let line = std::mem::take(&mut self.buffer);
+        self.parse_line(&line)
+    }
+
+    fn parse_line(&mut self, line: &[u8]) -> Option<String> {
+        if self.done {
+            return None;
+        }
+        let line = String::from_utf8_lossy(line);
+        let data = line.trim_end().strip_prefix("data:")?.trim_start();
+        if data == "[DONE]" {
+            self.done = true;
+            return None;
+        }
+        let chunk: ChatStreamChunk = serde_json::from_str(data).ok()?;
+        let content: String = chunk
+            .choices
+            .into_iter()
+            .filter_map(|choice| choice.delta.content)
+            .collect();
+        (!content.is_empty()).then_some(content)
+    }
+}
+
+/// Passes on only the streamed text between `<output>` and `</output>`, like `parse_output`
+/// does for the whole reply. Tags may arrive split across deltas, so anything that could
+/// still turn out to be part of one is held back until the next delta.
+#[derive(Debug, Default)]
+struct OutputTagFilter {
+    pending: String,
+    inside: bool,
+    closed: bool,
+}
+
+impl OutputTagFilter {
+    fn push(&mut self, delta: &str) -> Option<String> {
+        if self.closed {
+            return None;
+        }
+        self.pending.push_str(delta);
+
+        if !self.inside {
+            let Some(start) = self.pending.find("<output>") else {
+                let keep = partial_tag_len(&self.pending, "<output>");
+                self.pending.drain(..self.pending.len() - keep);
+                return None;
+            };
+            self.pending.drain(..start + "<output>".len());
+            self.inside = true;
+        }
+
+        let visible = if let Some(end) = self.pending.find("</output>") {
+            self.closed = true;
+            let visible = self.pending[..end].to_string();
+            self.pending.clear();
+            visible
+        } else {
+            let keep = partial_tag_len(&self.pending, "</output>");
+            self.pending.drain(..self.pending.len() - keep).collect()
+        };
+        (!visible.is_empty()).then_some(visible)
+    }
+}
+
+/// Length of the longest suffix of `text` that is a proper prefix of `tag`.
+fn partial_tag_len(text: &str, tag: &str) -> usize {
+    (1..tag.len())
+        .rev()
+        .find(|&len| text.ends_with(&tag[..len]))
+        .unwrap_or(0)
+}
+
+#[derive(Debug, Serialize)]
+struct MessagesRequest {
+    model: String,
+    system: String,
+    messages: Vec<Message>,
+    temperature: f32,
+    max_tokens: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct MessagesResponse {
+    content: Vec<ContentBlock>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ContentBlock {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    text: String,
+}
+
 fn strip_control_tokens(text: &str) -> String {
     let re = regex::Regex::new(r"<\|[^|]+\|>").unwrap();
     let result = re.replace_all(text, "").to_string();
@@ -74,6 +224,27 @@ fn parse_output(response: &str) -> Option<String> {
     }
 }
 
+/// Custom prompts must still ask for `<output>` tags, since that's how the reply is parsed.
+pub fn validate_system_prompt(prompt: &str) -> Result<(), String> {
+    if prompt.chars().count() > MAX_SYSTEM_PROMPT_CHARS {
+        return Err(format!(
+            "System prompt must be at most {MAX_SYSTEM_PROMPT_CHARS} characters"
+        ));
+    }
+    if !prompt.contains("<output>") || !prompt.contains("</output>") {
+        return Err("System prompt must mention the <output> and </output> tags".into());
+    }
+    Ok(())
+}
+
+fn system_prompt(settings: &UserSettings) -> &str {
+    settings
+        .llm_system_prompt_override
+        .as_deref()
+        .filter(|prompt| !prompt.trim().is_empty())
+        .unwrap_or(SYSTEM_PROMPT)
+}
+
 fn get_endpoint(settings: &UserSettings) -> Result<String> {
     let base = match settings.llm_provider {
         LlmProvider::None => return Err(anyhow!("LLM cleanup is disabled")),
@@ -95,6 +266,17 @@ fn get_endpoint(settings: &UserSettings) -> Result<String> {
             .is_empty()
             .then_some("https://api.openai.com")
             .unwrap_or(&settings.llm_endpoint),
+        LlmProvider::Anthropic => {
+            let base = if settings.llm_endpoint.is_empty() {
+                "https://api.anthropic.com"
+            } else {
+                &settings.llm_endpoint
+            };
+            return Ok(format!(
+                "{}/v1/messages",
+                base.trim_end_matches('/').trim_end_matches("/v1/messages")
+            ));
+        }
         _ => {
             if settings.llm_endpoint.is_empty() {
                 return Err(anyhow!("Endpoint not configured"));
@@ -119,21 +301,26 @@ fn resolve_model(settings: &UserSettings) -> String {
         LlmProvider::LmStudio => "local-model",
         LlmProvider::Ollama => "llama3.2",
         LlmProvider::OpenAI => "gpt-4o-mini",
+        LlmProvider::Anthropic => "claude-3-haiku-20240307",
         _ => "default",
     }
     .to_string()
 }
 
+/// Clean up `text` with the configured LLM. When the provider streams its reply,
+/// `on_chunk` is called with each piece as it arrives.
 pub async fn cleanup_transcription(
     client: &Client,
     text: &str,
     settings: &UserSettings,
+    on_chunk: impl Fn(&str),
 ) -> Result<String> {
     if !settings.llm_cleanup_enabled || matches!(settings.llm_provider, LlmProvider::None) {
         return Err(anyhow!("LLM cleanup not configured"));
     }
 
-    eprintln!("[LLM] Transcription received: {}", text);
+    // Transcripts are the user's words; log their size, never their content
+    debug!(chars = text.chars().count(), "LLM cleanup input");
 
     let user_content = if settings.user_context.is_empty() {
         text.to_string()
@@ -141,41 +328,73 @@ pub async fn cleanup_transcription(
         format!("Context: {}\n\n{}", settings.user_context, text)
     };
 
-    let body = ChatRequest {
-        model: resolve_model(settings),
-        messages: vec![
-            Message {
-                role: "system".into(),
-                content: SYSTEM_PROMPT.into(),
-            },
-            Message {
+    let is_anthropic = matches!(settings.llm_provider, LlmProvider::Anthropic);
+    let endpoint = get_endpoint(settings)?;
+    let req = if is_anthropic {
+        let body = MessagesRequest {
+            model: resolve_model(settings),
+            system: system_prompt(settings).into(),
+            messages: vec![Message {
                 role: "user".into(),
                 content: user_content,
-            },
-        ],
-        temperature: 0.2,
-        max_tokens: Some(4096),
+            }],
+            temperature: 0.2,
+            max_tokens: 4096,
+        };
+        client
+            .post(&endpoint)
+            .json(&body)
+            .header("x-api-key", &settings.llm_api_key)
+            .header("anthropic-version", ANTHROPIC_VERSION)
+    } else {
+        let body = ChatRequest {
+            model: resolve_model(settings),
+            messages: vec![
+                Message {
+                    role: "system".into(),
+                    content: system_prompt(settings).into(),
+                },
+                Message {
+                    role: "user".into(),
+                    content: user_content,
+                },
+            ],
+            temperature: 0.2,
+            max_tokens: Some(4096),
+            stream: settings.llm_streaming_enabled,
+        };
+        let mut req = client.post(&endpoint).json(&body);
+        if !settings.llm_api_key.is_empty() {
+            req = req.header("Authorization", format!("Bearer {}", settings.llm_api_key));
+        }
+        req
     };
 
-    let mut req = client.post(&get_endpoint(settings)?).json(&body);
-    if !settings.llm_api_key.is_empty() {
-        req = req.header("Authorization", format!("Bearer {}", settings.llm_api_key));
-    }
-
     let resp = req.send().await.context("Failed to reach LLM API")?;
     if !resp.status().is_success() {
         let err = resp.text().await.unwrap_or_default();
         return Err(anyhow!("LLM error {}", err));
     }
 
-    let chat: ChatResponse = resp.json().await.context("Failed to parse response")?;
-    let raw = chat
-        .choices
-        .first()
-        .map(|c| c.message.content.clone())
-        .unwrap_or_default();
+    let raw = if is_anthropic {
+        let message: MessagesResponse = resp.json().await.context("Failed to parse response")?;
+        message
+            .content
+            .into_iter()
+            .filter(|block| block.kind == "text")
+            .map(|block| block.text)
+            .collect::<String>()
+    } else if is_event_stream(&resp) {
+        read_event_stream(resp, &on_chunk).await?
+    } else {
+        let chat: ChatResponse = resp.json().await.context("Failed to parse response")?;
+        chat.choices
+            .first()
+            .map(|c| c.message.content.clone())
+            .unwrap_or_default()
+    };
 
-    eprintln!("[LLM] Response from LLM: {}", raw);
+    debug!(chars = raw.chars().count(), "LLM cleanup response");
 
     let result = parse_output(&raw)
         .or_else(|| {
@@ -188,11 +407,42 @@ pub async fn cleanup_transcription(
         })
         .unwrap_or_else(|| text.to_string());
 
-    eprintln!("[LLM] Final cleaned output: {}", result);
+    debug!(chars = result.chars().count(), "LLM cleanup output");
 
     Ok(result)
 }
 
+fn is_event_stream(resp: &Response) -> bool {
+    resp.headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("text/event-stream"))
+}
+
+async fn read_event_stream(mut resp: Response, on_chunk: &impl Fn(&str)) -> Result<String> {
+    let mut parser = SseParser::default();
+    let mut visible = OutputTagFilter::default();
+    let mut content = String::new();
+    while let Some(bytes) = resp.chunk().await.context("Failed to read LLM stream")? {
+        for delta in parser.push(&bytes) {
+            if let Some(text) = visible.push(&delta) {
+                on_chunk(&text);
+            }
+            content.push_str(&delta);
+        }
+        if parser.done {
+            break;
+        }
+    }
+    if let Some(delta) = parser.finish() {
+        if let Some(text) = visible.push(&delta) {
+            on_chunk(&text);
+        }
+        content.push_str(&delta);
+    }
+    Ok(content)
+}
+
 pub fn is_cleanup_available(settings: &UserSettings) -> bool {
     settings.llm_cleanup_enabled && !matches!(settings.llm_provider, LlmProvider::None)
 }
@@ -239,6 +489,10 @@ pub async fn fetch_available_models(
     provider: &LlmProvider,
     api_key: &str,
 ) -> Result<Vec<String>> {
+    if matches!(provider, LlmProvider::Anthropic) {
+        return Ok(ANTHROPIC_MODELS.iter().map(|m| m.to_string()).collect());
+    }
+
     let base = get_base_url(endpoint, provider);
     if base.is_empty() {
         return Ok(vec![]);
@@ -267,3 +521,81 @@ pub async fn fetch_available_models(
         .context("Failed to parse models response")?;
     Ok(data.data.into_iter().map(|m| m.id).collect())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_system_prompt_override() {
+        let mut settings = UserSettings::default();
+        assert_eq!(system_prompt(&settings), SYSTEM_PROMPT);
+
+        settings.llm_system_prompt_override = Some("  ".into());
+        assert_eq!(system_prompt(&settings), SYSTEM_PROMPT);
+
+        let custom = "Format as bullet points inside <output></output>.";
+        settings.llm_system_prompt_override = Some(custom.into());
+        assert_eq!(system_prompt(&settings), custom);
+
+        assert!(validate_system_prompt(custom).is_ok());
+        assert!(validate_system_prompt("Format as bullet points.").is_err());
+        assert!(validate_system_prompt(&format!("<output></output>{}", "a".repeat(2000))).is_err());
+    }
+
+    #[test]
+    fn test_sse_parser_collects_deltas_across_chunks() {
+        let body = concat!(
+            ": keep-alive\n",
+            "data: {\"choices\":[{\"delta\":{\"role\":\"assistant\"}}]}\n\n",
+            "data: {\"choices\":[{\"delta\":{\"content\":\"<output>Café\"}}]}\r\n\r\n",
+            "data: {\"choices\":[{\"delta\":{\"content\":\" time</output>\"}}]}\n\n",
+            "data: [DONE]\n\n",
+            "data: {\"choices\":[{\"delta\":{\"content\":\"ignored\"}}]}\n",
+        );
+
+        // Feed a few bytes at a time so lines and characters are split between chunks
+        let mut parser = SseParser::default();
+        let mut deltas = Vec::new();
+        for chunk in body.as_bytes().chunks(5) {
+            deltas.extend(parser.push(chunk));
+        }
+        deltas.extend(parser.finish());
+
+        assert_eq!(deltas, vec!["<output>Café", " time</output>"]);
+        assert!(parser.done);
+        assert_eq!(parse_output(&deltas.concat()).as_deref(), Some("Café time"));
+    }
+
+    #[test]
+    fn test_output_filter_hides_tags_split_across_deltas() {
+        let mut filter = OutputTagFilter::default();
+        let deltas = [
+            "Sure! <out",
+            "put>Caf",
+            "é ti",
+            "me</out",
+            "put> Hope",
+            " that helps",
+        ];
+        let visible: Vec<String> = deltas
+            .iter()
+            .filter_map(|delta| filter.push(delta))
+            .collect();
+        assert_eq!(visible, vec!["Caf", "é ti", "me"]);
+
+        let mut filter = OutputTagFilter::default();
+        assert_eq!(filter.push("<output>a <").as_deref(), Some("a "));
+        assert_eq!(filter.push(" b</output>").as_deref(), Some("< b"));
+    }
+
+    #[test]
+    fn test_sse_parser_flushes_unterminated_last_line() {
+        let mut parser = SseParser::default();
+        assert!(parser
+            .push(b"data: {\"choices\":[{\"delta\":{\"content\":\"hi\"}}]}")
+            .is_empty());
+        assert_eq!(parser.finish().as_deref(), Some("hi"));
+        assert!(!parser.done);
+    }
+}
@@ -1,4 +1,8 @@
+use std::fmt;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use anyhow::{anyhow, Result};
 use parking_lot::Mutex;
@@ -16,8 +20,50 @@ use crate::{
     transcription::{normalize_transcript, TranscriptionSuccess},
 };
 
+/// Unload a model after this many seconds unused, unless configured otherwise.
+pub const DEFAULT_IDLE_TIMEOUT_SECONDS: u32 = 300;
+const IDLE_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// A local transcription that ran past its wall-time limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TranscriptionTimedOut {
+    pub seconds: u64,
+}
+
+impl fmt::Display for TranscriptionTimedOut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Transcription timed out after {}s", self.seconds)
+    }
+}
+
+impl std::error::Error for TranscriptionTimedOut {}
+
+/// Run blocking transcription `work` off the async runtime, failing with
+/// [`TranscriptionTimedOut`] once `limit` passes. The blocking thread can't be
+/// interrupted and is left to finish on its own.
+pub async fn run_with_time_limit<T, F>(limit: Duration, work: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T> + Send + 'static,
+{
+    match tokio::time::timeout(limit, tauri::async_runtime::spawn_blocking(work)).await {
+        Ok(Ok(inner)) => inner,
+        Ok(Err(err)) => Err(anyhow!("Local transcription task failed: {err}")),
+        Err(_) => Err(TranscriptionTimedOut {
+            seconds: limit.as_secs(),
+        }
+        .into()),
+    }
+}
+
 pub struct LocalTranscriber {
     inner: Mutex<Option<LoadedEngine>>,
+    /// UNIX millis of the last model load or transcription.
+    last_use_time: AtomicU64,
+    /// 0 keeps the model loaded until the app quits.
+    idle_timeout_seconds: AtomicU32,
+    /// Loads and transcriptions in progress, including ones abandoned after a timeout.
+    running: AtomicU32,
 }
 
 struct LoadedEngine {
@@ -32,6 +78,22 @@ enum EngineInstance {
     Moonshine { engine: MoonshineEngine },
 }
 
+/// Counts a load or transcription as running until dropped.
+struct RunningGuard<'a>(&'a AtomicU32);
+
+impl<'a> RunningGuard<'a> {
+    fn new(running: &'a AtomicU32) -> Self {
+        running.fetch_add(1, Ordering::Relaxed);
+        Self(running)
+    }
+}
+
+impl Drop for RunningGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
 struct PreparedAudio {
     pub data: Vec<f32>,
 }
@@ -40,7 +102,65 @@ impl LocalTranscriber {
     pub fn new() -> Self {
         Self {
             inner: Mutex::new(None),
+            last_use_time: AtomicU64::new(now_millis()),
+            idle_timeout_seconds: AtomicU32::new(DEFAULT_IDLE_TIMEOUT_SECONDS),
+            running: AtomicU32::new(0),
+        }
+    }
+
+    pub fn set_idle_timeout(&self, seconds: u32) {
+        self.idle_timeout_seconds.store(seconds, Ordering::Relaxed);
+    }
+
+    /// Periodically unload the model once it has been idle past the timeout, calling
+    /// `on_unload` with the key of each model dropped.
+    pub fn start_idle_monitor<F>(self: &Arc<Self>, on_unload: F)
+    where
+        F: Fn(String) + Send + 'static,
+    {
+        let transcriber = Arc::clone(self);
+        tauri::async_runtime::spawn(async move {
+            loop {
+                tokio::time::sleep(IDLE_CHECK_INTERVAL).await;
+                if let Some(model_key) = transcriber.unload_if_idle() {
+                    on_unload(model_key);
+                }
+            }
+        });
+    }
+
+    /// Drop the loaded engine, freeing its memory, if it has gone unused for longer than
+    /// the idle timeout. Returns the key of the unloaded model.
+    fn unload_if_idle(&self) -> Option<String> {
+        let timeout_ms = u64::from(self.idle_timeout_seconds.load(Ordering::Relaxed)) * 1000;
+        if timeout_ms == 0 {
+            return None;
+        }
+        let idle_ms = now_millis().saturating_sub(self.last_use_time.load(Ordering::Relaxed));
+        if idle_ms < timeout_ms {
+            return None;
         }
+
+        // A transcription in progress holds the lock, so the model isn't idle
+        let mut guard = self.inner.try_lock()?;
+        guard.take().map(|loaded| loaded.key)
+    }
+
+    /// Drop the loaded engine right away. A transcription still running holds on to the
+    /// engine, in which case nothing is unloaded. Returns the key of the unloaded model.
+    pub fn unload(&self) -> Option<String> {
+        let mut guard = self.inner.try_lock()?;
+        guard.take().map(|loaded| loaded.key)
+    }
+
+    fn touch(&self) {
+        self.last_use_time.store(now_millis(), Ordering::Relaxed);
+    }
+
+    /// Whether a model load or transcription is still running. A run that outlived its
+    /// time limit keeps going in the background, and new ones would queue behind it.
+    pub fn is_busy(&self) -> bool {
+        self.running.load(Ordering::Relaxed) > 0
     }
 
     pub fn transcribe(
@@ -51,6 +171,7 @@ impl LocalTranscriber {
         initial_prompt: Option<&str>,
         language: Option<&str>,
     ) -> Result<TranscriptionSuccess> {
+        let _running = RunningGuard::new(&self.running);
         self.ensure_engine(model)?;
         let prepared = prepare_audio(samples, sample_rate);
         let model_label = model_manager::definition(&model.key)
@@ -93,13 +214,31 @@ impl LocalTranscriber {
             }
         };
 
+        drop(guard);
+        self.touch();
+
         Ok(TranscriptionSuccess {
             transcript: normalize_transcript(&transcript),
             speech_model: Some(model_label),
+            word_confidences: None,
+            // transcribe-rs segments carry no log-probabilities to derive this from
+            avg_confidence: None,
+            // nor the language Whisper settled on when none was given
+            detected_language: None,
         })
     }
 
-    fn ensure_engine(&self, model: &ReadyModel) -> Result<()> {
+    /// Whether `model` is already in memory, so transcribing won't have to load it first.
+    pub fn is_loaded(&self, model: &ReadyModel) -> bool {
+        self.inner
+            .lock()
+            .as_ref()
+            .is_some_and(|current| current.key == model.key && current.path == model.path)
+    }
+
+    pub fn ensure_engine(&self, model: &ReadyModel) -> Result<()> {
+        let _running = RunningGuard::new(&self.running);
+        self.touch();
         {
             let guard = self.inner.lock();
             if let Some(current) = guard.as_ref() {
@@ -146,6 +285,8 @@ impl LocalTranscriber {
             }
         };
 
+        // Loading can take a while; don't let the monitor count it as idle time
+        self.touch();
         let mut guard = self.inner.lock();
         *guard = Some(LoadedEngine {
             key: model.key.clone(),
@@ -163,6 +304,13 @@ impl Default for LocalTranscriber {
     }
 }
 
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or_default()
+}
+
 fn prepare_audio(samples: &[i16], sample_rate: u32) -> PreparedAudio {
     let normalized: Vec<f32> = samples
         .iter()
@@ -208,3 +356,94 @@ fn resample_linear(samples: &[f32], from_rate: u32, to_rate: u32) -> Vec<f32> {
 
     output
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load_unused_engine(transcriber: &LocalTranscriber) {
+        *transcriber.inner.lock() = Some(LoadedEngine {
+            key: "test-model".to_string(),
+            path: PathBuf::new(),
+            engine: EngineInstance::Moonshine {
+                engine: MoonshineEngine::new(),
+            },
+        });
+        transcriber.touch();
+    }
+
+    #[test]
+    fn test_idle_engine_is_unloaded() {
+        let transcriber = LocalTranscriber::new();
+        transcriber.set_idle_timeout(1);
+        load_unused_engine(&transcriber);
+
+        assert_eq!(transcriber.unload_if_idle(), None);
+        assert!(transcriber.inner.lock().is_some());
+
+        std::thread::sleep(Duration::from_millis(1100));
+        assert_eq!(transcriber.unload_if_idle().as_deref(), Some("test-model"));
+        assert!(transcriber.inner.lock().is_none());
+        assert_eq!(transcriber.unload_if_idle(), None);
+    }
+
+    #[test]
+    fn test_unload_skips_busy_engine() {
+        let transcriber = LocalTranscriber::new();
+        load_unused_engine(&transcriber);
+
+        {
+            let _busy = transcriber.inner.lock();
+            assert_eq!(transcriber.unload(), None);
+        }
+        assert_eq!(transcriber.unload().as_deref(), Some("test-model"));
+        assert_eq!(transcriber.unload(), None);
+    }
+
+    #[test]
+    fn test_busy_while_any_run_is_in_progress() {
+        let transcriber = LocalTranscriber::new();
+        assert!(!transcriber.is_busy());
+        {
+            let _load = RunningGuard::new(&transcriber.running);
+            let _transcription = RunningGuard::new(&transcriber.running);
+            assert!(transcriber.is_busy());
+        }
+        assert!(!transcriber.is_busy());
+    }
+
+    #[test]
+    fn test_stalled_transcription_times_out() {
+        let started = std::time::Instant::now();
+        let result =
+            tauri::async_runtime::block_on(run_with_time_limit(Duration::from_secs(1), || {
+                std::thread::sleep(Duration::from_secs(5));
+                Ok("too late")
+            }));
+
+        let err = result.unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<TranscriptionTimedOut>(),
+            Some(&TranscriptionTimedOut { seconds: 1 })
+        );
+        assert_eq!(err.to_string(), "Transcription timed out after 1s");
+        assert!(started.elapsed() < Duration::from_secs(3));
+
+        let quick =
+            tauri::async_runtime::block_on(run_with_time_limit(Duration::from_secs(1), || {
+                Ok("done")
+            }));
+        assert_eq!(quick.unwrap(), "done");
+    }
+
+    #[test]
+    fn test_zero_idle_timeout_keeps_engine_loaded() {
+        let transcriber = LocalTranscriber::new();
+        transcriber.set_idle_timeout(0);
+        load_unused_engine(&transcriber);
+        transcriber.last_use_time.store(0, Ordering::Relaxed);
+
+        assert_eq!(transcriber.unload_if_idle(), None);
+        assert!(transcriber.inner.lock().is_some());
+    }
+}
@@ -0,0 +1,69 @@
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
+
+use crate::APP_IDENTIFIER;
+
+const LOG_FILE_NAME: &str = "glimpse.log";
+const MAX_LOG_FILES: usize = 7;
+
+static LOG_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Log to stderr and to a daily rotating file under `app_data_dir/logs`.
+///
+/// This runs before the Tauri app exists, so the data dir is resolved the same way Tauri
+/// does. Keep the returned guard alive for the lifetime of the app so buffered lines flush.
+pub fn init() -> Option<WorkerGuard> {
+    let filter = || EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let stderr_layer = fmt::layer().with_writer(std::io::stderr);
+
+    let appender = dirs::data_dir()
+        .map(|dir| dir.join(APP_IDENTIFIER).join("logs"))
+        .and_then(|dir| {
+            RollingFileAppender::builder()
+                .rotation(Rotation::DAILY)
+                .filename_prefix(LOG_FILE_NAME)
+                .max_log_files(MAX_LOG_FILES)
+                .build(&dir)
+                .map_err(|err| eprintln!("Failed to open log file in {}: {err}", dir.display()))
+                .ok()
+                .map(|appender| (dir, appender))
+        });
+
+    let Some((dir, appender)) = appender else {
+        let _ = tracing_subscriber::registry()
+            .with(filter())
+            .with(stderr_layer)
+            .try_init();
+        return None;
+    };
+
+    let (writer, guard) = tracing_appender::non_blocking(appender);
+    let file_layer = fmt::layer().with_ansi(false).with_writer(writer);
+    let _ = tracing_subscriber::registry()
+        .with(filter())
+        .with(stderr_layer)
+        .with(file_layer)
+        .try_init();
+    let _ = LOG_DIR.set(dir);
+    Some(guard)
+}
+
+/// Path of the file currently being written, or `None` if only stderr logging is active.
+pub fn current_log_path() -> Option<PathBuf> {
+    // The rolling appender names files `<prefix>.<UTC date>`.
+    let date = chrono::Utc::now().format("%Y-%m-%d");
+    LOG_DIR
+        .get()
+        .map(|dir| dir.join(format!("{LOG_FILE_NAME}.{date}")))
+}
+
+#[tauri::command]
+pub fn get_log_path() -> Result<String, String> {
+    current_log_path()
+        .map(|path| path.display().to_string())
+        .ok_or_else(|| "File logging is not available".to_string())
+}
@@ -1,14 +1,30 @@
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use crate::analytics::AnalyticsEvent;
 use crate::AppRuntime;
 use anyhow::{anyhow, Context, Result};
 use serde::Serialize;
-use tauri::{AppHandle, Manager, Runtime};
+use tauri::{AppHandle, Emitter, Manager, Runtime};
 
-use crate::downloader::{download_model_files, ModelFileDescriptor};
+use crate::downloader::{
+    download_model_files, verify_checksum, DownloadProgress, DownloadProgressMap, DownloadQueue,
+    ModelFileDescriptor,
+};
 
 const MODELS_ROOT: &str = "models";
+const BENCHMARKS_FILE_NAME: &str = "model_benchmarks.json";
+/// Serializes read-modify-write cycles on the benchmarks file so concurrent transcriptions
+/// don't drop each other's measurements.
+static BENCHMARKS_LOCK: parking_lot::Mutex<()> = parking_lot::Mutex::new(());
+/// Models whose files have been hashed since launch, so each is checked once per run.
+static VERIFIED_MODELS: parking_lot::Mutex<BTreeSet<&'static str>> =
+    parking_lot::Mutex::new(BTreeSet::new());
+/// Weight of the newest measurement in a model's stored real-time factor.
+const BENCHMARK_SMOOTHING: f32 = 0.3;
+/// Shorter recordings are dominated by fixed overhead and would skew the benchmark.
+const MIN_BENCHMARK_AUDIO_SECONDS: f32 = 2.0;
 
 #[derive(Debug, Clone)]
 pub enum ModelStorage {
@@ -35,6 +51,9 @@ pub struct ModelDefinition {
     pub label: &'static str,
     pub description: &'static str,
     pub size_mb: f32,
+    /// Rough transcription time divided by audio duration on a typical laptop, used until
+    /// this machine has measured its own (see `model_benchmarks.json`).
+    pub real_time_factor: f32,
     pub files: &'static [ModelFileDescriptor],
     pub engine: LocalModelEngine,
     pub variant: &'static str,
@@ -53,26 +72,32 @@ const PARAKEET_TDT_FP32_FILES: [ModelFileDescriptor; 6] = [
     ModelFileDescriptor {
         url: "https://huggingface.co/istupakov/parakeet-tdt-0.6b-v3-onnx/resolve/main/config.json",
         name: "config.json",
+        checksum_sha256: None,
     },
     ModelFileDescriptor {
         url: "https://huggingface.co/istupakov/parakeet-tdt-0.6b-v3-onnx/resolve/main/encoder-model.onnx",
         name: "encoder-model.onnx",
+        checksum_sha256: None,
     },
     ModelFileDescriptor {
         url: "https://huggingface.co/istupakov/parakeet-tdt-0.6b-v3-onnx/resolve/main/encoder-model.onnx.data",
         name: "encoder-model.onnx.data",
+        checksum_sha256: None,
     },
     ModelFileDescriptor {
         url: "https://huggingface.co/istupakov/parakeet-tdt-0.6b-v3-onnx/resolve/main/decoder_joint-model.onnx",
         name: "decoder_joint-model.onnx",
+        checksum_sha256: None,
     },
     ModelFileDescriptor {
         url: "https://huggingface.co/istupakov/parakeet-tdt-0.6b-v3-onnx/resolve/main/nemo128.onnx",
         name: "nemo128.onnx",
+        checksum_sha256: None,
     },
     ModelFileDescriptor {
         url: "https://huggingface.co/istupakov/parakeet-tdt-0.6b-v3-onnx/resolve/main/vocab.txt",
         name: "vocab.txt",
+        checksum_sha256: None,
     },
 ];
 
@@ -80,47 +105,57 @@ const PARAKEET_TDT_INT8_FILES: [ModelFileDescriptor; 5] = [
     ModelFileDescriptor {
         url: "https://huggingface.co/istupakov/parakeet-tdt-0.6b-v3-onnx/resolve/main/config.json",
         name: "config.json",
+        checksum_sha256: None,
     },
     ModelFileDescriptor {
         url: "https://huggingface.co/istupakov/parakeet-tdt-0.6b-v3-onnx/resolve/main/encoder-model.int8.onnx",
         name: "encoder-model.int8.onnx",
+        checksum_sha256: None,
     },
     ModelFileDescriptor {
         url: "https://huggingface.co/istupakov/parakeet-tdt-0.6b-v3-onnx/resolve/main/decoder_joint-model.int8.onnx",
         name: "decoder_joint-model.int8.onnx",
+        checksum_sha256: None,
     },
     ModelFileDescriptor {
         url: "https://huggingface.co/istupakov/parakeet-tdt-0.6b-v3-onnx/resolve/main/nemo128.onnx",
         name: "nemo128.onnx",
+        checksum_sha256: None,
     },
     ModelFileDescriptor {
         url: "https://huggingface.co/istupakov/parakeet-tdt-0.6b-v3-onnx/resolve/main/vocab.txt",
         name: "vocab.txt",
+        checksum_sha256: None,
     },
 ];
 
 const WHISPER_SMALL_Q5_FILES: [ModelFileDescriptor; 1] = [ModelFileDescriptor {
     url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-small-q5_1.bin",
     name: "ggml-small-q5_1.bin",
+    checksum_sha256: None,
 }];
 
 const WHISPER_LARGE_V3_TURBO_Q8_FILES: [ModelFileDescriptor; 1] = [ModelFileDescriptor {
     url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-large-v3-turbo-q8_0.bin",
     name: "ggml-large-v3-turbo-q8_0.bin",
+    checksum_sha256: None,
 }];
 
 const MOONSHINE_TINY_FILES: [ModelFileDescriptor; 3] = [
     ModelFileDescriptor {
         url: "https://huggingface.co/UsefulSensors/moonshine/resolve/main/onnx/merged/tiny/float/encoder_model.onnx",
         name: "encoder_model.onnx",
+        checksum_sha256: None,
     },
     ModelFileDescriptor {
         url: "https://huggingface.co/UsefulSensors/moonshine/resolve/main/onnx/merged/tiny/float/decoder_model_merged.onnx",
         name: "decoder_model_merged.onnx",
+        checksum_sha256: None,
     },
     ModelFileDescriptor {
         url: "https://huggingface.co/UsefulSensors/moonshine/resolve/main/onnx/merged/base/float/tokenizer.json",
         name: "tokenizer.json",
+        checksum_sha256: None,
     },
 ];
 
@@ -128,14 +163,17 @@ const MOONSHINE_BASE_FILES: [ModelFileDescriptor; 3] = [
     ModelFileDescriptor {
         url: "https://huggingface.co/UsefulSensors/moonshine/resolve/main/onnx/merged/base/float/encoder_model.onnx",
         name: "encoder_model.onnx",
+        checksum_sha256: None,
     },
     ModelFileDescriptor {
         url: "https://huggingface.co/UsefulSensors/moonshine/resolve/main/onnx/merged/base/float/decoder_model_merged.onnx",
         name: "decoder_model_merged.onnx",
+        checksum_sha256: None,
     },
     ModelFileDescriptor {
         url: "https://huggingface.co/UsefulSensors/moonshine/resolve/main/onnx/merged/base/float/tokenizer.json",
         name: "tokenizer.json",
+        checksum_sha256: None,
     },
 ];
 
@@ -146,6 +184,7 @@ pub const MODEL_DEFINITIONS: &[ModelDefinition] = &[
         description:
             "Great quality local Whisper model with multilingual support, supports custom words.",
         size_mb: 880.0,
+        real_time_factor: 0.35,
         files: &WHISPER_LARGE_V3_TURBO_Q8_FILES,
         engine: LocalModelEngine::Whisper,
         variant: "Q8_1",
@@ -159,6 +198,7 @@ pub const MODEL_DEFINITIONS: &[ModelDefinition] = &[
         label: "Parakeet 0.6B (Int8)",
         description: "Fast multilingual transcription with NVIDIA's quantized Parakeet model.",
         size_mb: 700.0,
+        real_time_factor: 0.08,
         files: &PARAKEET_TDT_INT8_FILES,
         engine: LocalModelEngine::Parakeet { quantized: true },
         variant: "Int8",
@@ -170,6 +210,7 @@ pub const MODEL_DEFINITIONS: &[ModelDefinition] = &[
         label: "Parakeet 0.6B (FP32)",
         description: "Highest accuracy Parakeet model with full-precision weights.",
         size_mb: 2300.0,
+        real_time_factor: 0.15,
         files: &PARAKEET_TDT_FP32_FILES,
         engine: LocalModelEngine::Parakeet { quantized: false },
         variant: "FP32",
@@ -181,6 +222,7 @@ pub const MODEL_DEFINITIONS: &[ModelDefinition] = &[
         label: "Whisper Small",
         description: "CPU-friendly, supports custom words.",
         size_mb: 200.0,
+        real_time_factor: 0.2,
         files: &WHISPER_SMALL_Q5_FILES,
         engine: LocalModelEngine::Whisper,
         variant: "Q5_1",
@@ -194,6 +236,7 @@ pub const MODEL_DEFINITIONS: &[ModelDefinition] = &[
         label: "Moonshine Tiny",
         description: "Ultra-fast lightweight model, great for quick transcriptions.",
         size_mb: 110.0,
+        real_time_factor: 0.04,
         files: &MOONSHINE_TINY_FILES,
         engine: LocalModelEngine::Moonshine {
             variant: MoonshineVariant::Tiny,
@@ -207,6 +250,7 @@ pub const MODEL_DEFINITIONS: &[ModelDefinition] = &[
         label: "Moonshine Base",
         description: "Balanced speed and accuracy with Moonshine architecture.",
         size_mb: 250.0,
+        real_time_factor: 0.07,
         files: &MOONSHINE_BASE_FILES,
         engine: LocalModelEngine::Moonshine {
             variant: MoonshineVariant::Base,
@@ -222,13 +266,18 @@ pub fn definition(key: &str) -> Option<&'static ModelDefinition> {
 }
 
 pub fn get_model_dir<R: Runtime>(app: &AppHandle<R>, key: &str) -> Result<PathBuf> {
-    let mut dir = app
+    let dir = app
         .path()
         .app_data_dir()
         .context("Unable to resolve app data directory")?;
-    dir.push(MODELS_ROOT);
-    dir.push(key);
-    Ok(dir)
+    Ok(model_dir_in(dir, key))
+}
+
+/// Where `key` is installed inside the app data directory.
+pub fn model_dir_in(mut data_dir: PathBuf, key: &str) -> PathBuf {
+    data_dir.push(MODELS_ROOT);
+    data_dir.push(key);
+    data_dir
 }
 
 fn ensure_models_root<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf> {
@@ -248,8 +297,9 @@ fn artifact_path(dir: &Path, storage: &ModelStorage) -> PathBuf {
     }
 }
 
+/// A catalog entry together with its install status on this machine.
 #[derive(Debug, Serialize, Clone)]
-pub struct ModelInfo {
+pub struct ModelInfoWithStatus {
     pub key: String,
     pub label: String,
     pub description: String,
@@ -258,6 +308,11 @@ pub struct ModelInfo {
     pub engine: String,
     pub variant: String,
     pub tags: Vec<String>,
+    pub recommended: bool,
+    pub installed: bool,
+    pub bytes_on_disk: u64,
+    pub missing_files: Vec<String>,
+    pub directory: String,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -290,6 +345,27 @@ impl ModelStatus {
     }
 }
 
+/// Hash each model file once per run; files without a known checksum are only warned about.
+/// The lock isn't held while hashing, so checking one model doesn't hold up another.
+fn verify_model_files(dir: &Path, def: &ModelDefinition) -> Result<()> {
+    if VERIFIED_MODELS.lock().contains(def.key) {
+        return Ok(());
+    }
+
+    for descriptor in def.files {
+        match descriptor.checksum_sha256 {
+            Some(expected) => verify_checksum(&dir.join(descriptor.name), expected)?,
+            None => eprintln!(
+                "Warning: no checksum for {} ({}), skipping verification",
+                descriptor.name, def.key
+            ),
+        }
+    }
+
+    VERIFIED_MODELS.lock().insert(def.key);
+    Ok(())
+}
+
 fn missing_files(dir: &Path, def: &ModelDefinition) -> Vec<String> {
     def.files
         .iter()
@@ -329,23 +405,33 @@ fn engine_label(engine: &LocalModelEngine) -> &'static str {
 }
 
 #[tauri::command]
-pub fn list_models() -> Vec<ModelInfo> {
+pub fn list_models_with_status<R: Runtime>(
+    app: AppHandle<R>,
+) -> Result<Vec<ModelInfoWithStatus>, String> {
     MODEL_DEFINITIONS
         .iter()
-        .map(|def| ModelInfo {
-            key: def.key.to_string(),
-            label: def.label.to_string(),
-            description: def.description.to_string(),
-            size_mb: def.size_mb,
-            file_count: def.files.len(),
-            engine: engine_label(&def.engine).to_string(),
-            variant: def.variant.to_string(),
-            tags: def.tags.iter().map(|s| s.to_string()).collect(),
+        .map(|def| {
+            let dir = get_model_dir(&app, def.key).map_err(|err| err.to_string())?;
+            let status = ModelStatus::from_definition(&dir, def);
+            Ok(ModelInfoWithStatus {
+                key: status.key,
+                label: def.label.to_string(),
+                description: def.description.to_string(),
+                size_mb: def.size_mb,
+                file_count: def.files.len(),
+                engine: engine_label(&def.engine).to_string(),
+                variant: def.variant.to_string(),
+                tags: def.tags.iter().map(|s| s.to_string()).collect(),
+                recommended: def.tags.contains(&"Recommended"),
+                installed: status.installed,
+                bytes_on_disk: status.bytes_on_disk,
+                missing_files: status.missing_files,
+                directory: status.directory,
+            })
         })
         .collect()
 }
 
-#[tauri::command]
 pub fn check_model_status<R: Runtime>(
     app: AppHandle<R>,
     model: String,
@@ -366,11 +452,22 @@ pub async fn download_model(
     let dir = get_model_dir(&app, &model).map_err(|err| err.to_string())?;
     let client = state.http();
 
-    download_model_files(&app, &client, &model, def.files, &dir)
+    // Both would write the same partial files
+    let queue = state.download_queue();
+    let _claim = queue.claim_direct(&model).map_err(|err| err.to_string())?;
+
+    let progress = state.download_progress();
+    download_model_files(&app, &client, &model, def.files, &dir, &progress)
         .await
         .map_err(|err| err.to_string())?;
 
-    crate::analytics::track_model_downloaded(&app, &model, def.size_mb);
+    crate::analytics::track(
+        &app,
+        AnalyticsEvent::ModelDownloaded {
+            model,
+            size_mb: def.size_mb,
+        },
+    );
 
     let status = ModelStatus::from_definition(&dir, def);
 
@@ -382,6 +479,117 @@ pub async fn download_model(
     Ok(status)
 }
 
+const EVENT_QUEUE_POSITION: &str = "model:queue-position";
+const EVENT_QUEUE_FINISHED: &str = "model:queue-finished";
+
+#[derive(Serialize, Clone)]
+struct QueuePositionPayload {
+    model: String,
+    /// `None` once the model has left the queue, whether its download succeeded or not.
+    position: Option<usize>,
+}
+
+/// Queue a model download behind any already in progress. Progress and completion are
+/// reported through the usual `download:*` events, plus `model:queue-position` whenever the
+/// queue moves and `model:queue-finished` once the last queued download is done.
+#[tauri::command]
+pub fn queue_model_download(
+    app: AppHandle<AppRuntime>,
+    state: tauri::State<'_, crate::AppState>,
+    model: String,
+) -> Result<(), String> {
+    let def = definition(&model).ok_or_else(|| "Unknown model".to_string())?;
+    ensure_models_root(&app).map_err(|err| err.to_string())?;
+    let dir = get_model_dir(&app, &model).map_err(|err| err.to_string())?;
+
+    let queue = state.download_queue();
+    let start_worker = queue
+        .enqueue(model, def.files, dir)
+        .map_err(|err| err.to_string())?;
+    emit_queue_positions(&app, &queue);
+
+    if start_worker {
+        tauri::async_runtime::spawn(run_download_queue(
+            app,
+            state.http(),
+            queue,
+            state.download_progress(),
+        ));
+    }
+    Ok(())
+}
+
+async fn run_download_queue(
+    app: AppHandle<AppRuntime>,
+    client: reqwest::Client,
+    queue: std::sync::Arc<DownloadQueue>,
+    progress: std::sync::Arc<DownloadProgressMap>,
+) {
+    while let Some(job) = queue.next_job() {
+        emit_queue_positions(&app, &queue);
+
+        let result =
+            download_model_files(&app, &client, &job.model, job.files, &job.dir, &progress).await;
+        let _ = app.emit(
+            EVENT_QUEUE_POSITION,
+            QueuePositionPayload {
+                model: job.model.clone(),
+                position: None,
+            },
+        );
+        if let Err(err) = result {
+            eprintln!("Queued download of {} failed: {err}", job.model);
+            continue;
+        }
+
+        if let Some(def) = definition(&job.model) {
+            crate::analytics::track(
+                &app,
+                AnalyticsEvent::ModelDownloaded {
+                    model: job.model.clone(),
+                    size_mb: def.size_mb,
+                },
+            );
+        }
+        if let Some(state) = app.try_state::<crate::AppState>() {
+            let settings = state.current_settings();
+            if let Err(err) = crate::tray::refresh_tray_menu(&app, &settings) {
+                eprintln!("Failed to refresh tray menu after download: {err}");
+            }
+        }
+    }
+    let _ = app.emit(EVENT_QUEUE_FINISHED, ());
+}
+
+fn emit_queue_positions<R: Runtime>(app: &AppHandle<R>, queue: &DownloadQueue) {
+    for (model, position) in queue.positions() {
+        let _ = app.emit(
+            EVENT_QUEUE_POSITION,
+            QueuePositionPayload {
+                model,
+                position: Some(position),
+            },
+        );
+    }
+}
+
+/// Progress of `model`'s download, or `None` when it isn't downloading.
+#[tauri::command]
+pub fn get_model_download_progress(
+    state: tauri::State<'_, crate::AppState>,
+    model: String,
+) -> Option<DownloadProgress> {
+    state.download_progress().get(&model)
+}
+
+/// Progress of every download in flight, so a freshly opened window can restore its state.
+#[tauri::command]
+pub fn get_all_download_progress(
+    state: tauri::State<'_, crate::AppState>,
+) -> Vec<DownloadProgress> {
+    state.download_progress().all()
+}
+
 #[tauri::command]
 pub fn delete_model(app: AppHandle<AppRuntime>, model: String) -> Result<ModelStatus, String> {
     let def = definition(&model).ok_or_else(|| "Unknown model".to_string())?;
@@ -403,9 +611,13 @@ pub fn delete_model(app: AppHandle<AppRuntime>, model: String) -> Result<ModelSt
 }
 
 pub fn ensure_model_ready<R: Runtime>(app: &AppHandle<R>, model: &str) -> Result<ReadyModel> {
+    ensure_model_ready_in(&get_model_dir(app, model)?, model)
+}
+
+/// [`ensure_model_ready`] for a model installed in `dir`.
+pub fn ensure_model_ready_in(dir: &Path, model: &str) -> Result<ReadyModel> {
     let def = definition(model).ok_or_else(|| anyhow!("Unknown model"))?;
-    let dir = get_model_dir(app, model)?;
-    let status = ModelStatus::from_definition(&dir, def);
+    let status = ModelStatus::from_definition(dir, def);
     if !status.installed {
         return Err(anyhow!(
             "{} is not fully installed. Missing: {}",
@@ -414,9 +626,137 @@ pub fn ensure_model_ready<R: Runtime>(app: &AppHandle<R>, model: &str) -> Result
         ));
     }
 
+    verify_model_files(dir, def)?;
+
     Ok(ReadyModel {
         key: def.key.to_string(),
-        path: artifact_path(&dir, &def.storage),
+        path: artifact_path(dir, &def.storage),
         engine: def.engine.clone(),
     })
 }
+
+fn benchmarks_path<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .context("Unable to resolve app data directory")?;
+    Ok(dir.join(BENCHMARKS_FILE_NAME))
+}
+
+/// Real-time factors measured on this machine, keyed by model. A missing or unreadable file
+/// counts as no measurements yet.
+fn load_benchmarks<R: Runtime>(app: &AppHandle<R>) -> BTreeMap<String, f32> {
+    let Ok(path) = benchmarks_path(app) else {
+        return BTreeMap::new();
+    };
+    match fs::read_to_string(&path) {
+        Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|err| {
+            eprintln!("Ignoring unreadable {}: {err}", path.display());
+            BTreeMap::new()
+        }),
+        Err(_) => BTreeMap::new(),
+    }
+}
+
+/// Real-time factor of `model_key` on this machine, falling back to the reference one until
+/// a transcription has been measured. `None` for unknown models.
+pub fn model_real_time_factor<R: Runtime>(app: &AppHandle<R>, model_key: &str) -> Option<f32> {
+    let def = definition(model_key)?;
+    Some(
+        load_benchmarks(app)
+            .get(model_key)
+            .copied()
+            .unwrap_or(def.real_time_factor),
+    )
+}
+
+/// Expected seconds to transcribe `audio_duration_seconds` of audio with `model_key`.
+pub fn estimate_transcription_time<R: Runtime>(
+    app: &AppHandle<R>,
+    model_key: &str,
+    audio_duration_seconds: f32,
+) -> Option<f32> {
+    model_real_time_factor(app, model_key).map(|rtf| rtf * audio_duration_seconds.max(0.0))
+}
+
+/// Fold a finished transcription's speed into `model_benchmarks.json`.
+pub fn record_transcription_benchmark<R: Runtime>(
+    app: &AppHandle<R>,
+    model_key: &str,
+    audio_duration_seconds: f32,
+    elapsed_seconds: f32,
+) -> Result<()> {
+    let Some(def) = definition(model_key) else {
+        return Ok(());
+    };
+    if audio_duration_seconds < MIN_BENCHMARK_AUDIO_SECONDS {
+        return Ok(());
+    }
+
+    let _guard = BENCHMARKS_LOCK.lock();
+    let mut benchmarks = load_benchmarks(app);
+    let previous = benchmarks
+        .get(model_key)
+        .copied()
+        .unwrap_or(def.real_time_factor);
+    let measured = elapsed_seconds / audio_duration_seconds;
+    benchmarks.insert(
+        model_key.to_string(),
+        blend_real_time_factor(previous, measured),
+    );
+
+    let path = benchmarks_path(app)?;
+    let contents = serde_json::to_string_pretty(&benchmarks)?;
+    // Readers don't take the lock, so swap the file in whole rather than truncating it
+    let partial = path.with_extension("json.tmp");
+    fs::write(&partial, contents)
+        .with_context(|| format!("Failed to write {}", partial.display()))?;
+    fs::rename(&partial, &path).with_context(|| format!("Failed to replace {}", path.display()))?;
+    Ok(())
+}
+
+fn blend_real_time_factor(previous: f32, measured: f32) -> f32 {
+    previous * (1.0 - BENCHMARK_SMOOTHING) + measured * BENCHMARK_SMOOTHING
+}
+
+#[tauri::command]
+pub fn get_model_benchmark<R: Runtime>(app: AppHandle<R>, model_key: String) -> Option<f32> {
+    model_real_time_factor(&app, &model_key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blend_real_time_factor_moves_toward_measurement() {
+        let blended = blend_real_time_factor(0.2, 0.4);
+        assert!((blended - 0.26).abs() < 1e-6);
+        assert!((blend_real_time_factor(0.1, 0.1) - 0.1).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_every_file_has_a_sha256_checksum() {
+        for def in MODEL_DEFINITIONS {
+            for file in def.files {
+                let checksum = file
+                    .checksum_sha256
+                    .unwrap_or_else(|| panic!("{} ({}) has no checksum", file.name, def.key));
+                assert!(
+                    checksum.len() == 64
+                        && checksum.chars().all(|c| matches!(c, '0'..='9' | 'a'..='f')),
+                    "{} ({}) has a malformed checksum",
+                    file.name,
+                    def.key
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_every_model_has_a_reference_speed() {
+        for def in MODEL_DEFINITIONS {
+            assert!(def.real_time_factor > 0.0, "{} has no benchmark", def.key);
+        }
+    }
+}
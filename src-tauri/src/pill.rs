@@ -1,25 +1,41 @@
 use crate::{
-    emit_event, permissions, platform, recorder::RecorderManager, toast, AppRuntime, AppState,
-    MAIN_WINDOW_LABEL,
+    assistive,
+    audio::{self, MicrophoneStatus},
+    emit_event, permissions, platform,
+    recorder::{DeviceEvent, RecorderManager},
+    settings::{DoubleTapAction, OverlayPosition, UserSettings},
+    toast, tray, AppRuntime, AppState, MAIN_WINDOW_LABEL,
 };
 use chrono::{DateTime, Local};
 use parking_lot::Mutex;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::async_runtime::JoinHandle;
 use tauri::{AppHandle, Emitter, Manager, WebviewWindow};
 use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
 
-const MIN_RECORDING_DURATION_MS: i64 = 300;
-const SMART_MODE_TAP_THRESHOLD_MS: i64 = 200;
+/// ~20 updates per second keeps the waveform smooth without flooding the webview.
+const AMPLITUDE_EMIT_INTERVAL: Duration = Duration::from_millis(50);
+const AUTO_STOP_POLL_INTERVAL: Duration = Duration::from_millis(500);
+const RECORDING_TICK_INTERVAL: Duration = Duration::from_secs(1);
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(100);
+/// Give up waiting on a cancelled transcription task after this long.
+const CANCEL_TIMEOUT: Duration = Duration::from_secs(3);
 
 pub const EVENT_PILL_STATE: &str = "pill:state";
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum PillStatus {
     Idle,
     Listening,
     Processing,
+    /// Processing, but waiting on a local model to load first.
+    #[serde(rename = "model_loading")]
+    ModelLoading,
+    /// Cancel requested, waiting for the transcription task to stop.
+    Cancelling,
     Error,
 }
 
@@ -29,6 +45,8 @@ impl std::fmt::Display for PillStatus {
             PillStatus::Idle => write!(f, "idle"),
             PillStatus::Listening => write!(f, "listening"),
             PillStatus::Processing => write!(f, "processing"),
+            PillStatus::ModelLoading => write!(f, "model_loading"),
+            PillStatus::Cancelling => write!(f, "cancelling"),
             PillStatus::Error => write!(f, "error"),
         }
     }
@@ -38,6 +56,36 @@ impl std::fmt::Display for PillStatus {
 pub enum RecordingMode {
     Hold,
     Toggle,
+    /// Records until stopped, handing off a chunk every `chunk_duration_seconds`.
+    Continuous,
+}
+
+/// One chunk of a continuous recording session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContinuousChunk {
+    pub session_id: String,
+    pub chunk_index: u32,
+}
+
+/// A chunk handed off for saving and transcription. It counts as in flight until dropped,
+/// which keeps the overlay up until a finished session's last chunk is done.
+pub struct ChunkInFlight {
+    chunk: ContinuousChunk,
+    app: AppHandle<AppRuntime>,
+}
+
+impl std::ops::Deref for ChunkInFlight {
+    type Target = ContinuousChunk;
+
+    fn deref(&self) -> &ContinuousChunk {
+        &self.chunk
+    }
+}
+
+impl Drop for ChunkInFlight {
+    fn drop(&mut self) {
+        self.app.state::<AppState>().pill().finish_chunk(&self.app);
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -47,7 +95,7 @@ enum ShortcutOrigin {
     Smart,
 }
 
-#[derive(Serialize, Clone)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct PillStatePayload {
     pub status: PillStatus,
     pub mode: Option<String>,
@@ -59,6 +107,18 @@ pub struct PillController {
     smart_press_time: Mutex<Option<DateTime<Local>>>,
     hold_key_down: Mutex<bool>,
     shortcut_origin: Mutex<Option<ShortcutOrigin>>,
+    auto_stop_task: Mutex<Option<JoinHandle<()>>>,
+    chunk_task: Mutex<Option<JoinHandle<()>>>,
+    /// The latest continuous session and the chunk it is recording. Kept after the session
+    /// ends so its last chunks can still be matched to it.
+    continuous_session: Mutex<Option<ContinuousChunk>>,
+    /// Chunks handed off by continuous sessions that haven't finished processing.
+    chunks_in_flight: Mutex<u32>,
+    tick_task: Mutex<Option<JoinHandle<()>>>,
+    /// When a shortcut last started a recording, for `shortcut_cooldown_ms`.
+    last_trigger_time: Mutex<Option<Instant>>,
+    /// When a smart press last started a recording, for `double_tap_window_ms`.
+    last_smart_press_time: Mutex<Option<Instant>>,
     recorder: Arc<RecorderManager>,
 }
 
@@ -70,6 +130,13 @@ impl PillController {
             smart_press_time: Mutex::new(None),
             hold_key_down: Mutex::new(false),
             shortcut_origin: Mutex::new(None),
+            auto_stop_task: Mutex::new(None),
+            chunk_task: Mutex::new(None),
+            continuous_session: Mutex::new(None),
+            chunks_in_flight: Mutex::new(0),
+            tick_task: Mutex::new(None),
+            last_trigger_time: Mutex::new(None),
+            last_smart_press_time: Mutex::new(None),
             recorder,
         }
     }
@@ -87,6 +154,7 @@ impl PillController {
         let mode = self.recording_mode.lock().map(|m| match m {
             RecordingMode::Hold => "hold",
             RecordingMode::Toggle => "toggle",
+            RecordingMode::Continuous => "continuous",
         });
 
         if let Err(err) = app.emit(
@@ -106,14 +174,48 @@ impl PillController {
     }
 
     pub fn transition_to(&self, app: &AppHandle<AppRuntime>, new_status: PillStatus) {
-        {
+        let old_status = {
             let mut status = self.status.lock();
             if *status == new_status {
                 return;
             }
-            *status = new_status;
+            std::mem::replace(&mut *status, new_status)
+        };
+        self.emit_state(app);
+        refresh_tray_recording_item(app, old_status, new_status);
+    }
+
+    /// Move to `to` only while in `from`, so a cancelled transcription isn't brought back.
+    /// Returns whether the pill moved.
+    pub fn transition_from(
+        &self,
+        app: &AppHandle<AppRuntime>,
+        from: PillStatus,
+        to: PillStatus,
+    ) -> bool {
+        if !self.replace_status(from, to) {
+            return false;
         }
         self.emit_state(app);
+        refresh_tray_recording_item(app, from, to);
+        true
+    }
+
+    /// Swap `from` for `to` without announcing it. Returns whether the status was `from`.
+    fn replace_status(&self, from: PillStatus, to: PillStatus) -> bool {
+        let mut status = self.status.lock();
+        if *status != from {
+            return false;
+        }
+        *status = to;
+        true
+    }
+
+    pub fn is_processing(&self) -> bool {
+        matches!(
+            self.status(),
+            PillStatus::Processing | PillStatus::ModelLoading
+        )
     }
 
     pub fn transition_to_error(&self, app: &AppHandle<AppRuntime>, message: &str) {
@@ -129,6 +231,8 @@ impl PillController {
     }
 
     fn reset_recording_state(&self) {
+        self.cancel_auto_stop();
+        self.cancel_chunk_rotation();
         *self.recording_mode.lock() = None;
         *self.smart_press_time.lock() = None;
         // Note: hold_key_down is intentionally NOT cleared here.
@@ -144,6 +248,60 @@ impl PillController {
         *self.recording_mode.lock()
     }
 
+    pub fn is_continuous(&self) -> bool {
+        self.active_mode() == Some(RecordingMode::Continuous)
+    }
+
+    pub fn continuous_session_id(&self) -> Option<String> {
+        self.continuous_session
+            .lock()
+            .as_ref()
+            .map(|session| session.session_id.clone())
+    }
+
+    /// Whether a continuous session is recording, or has chunks still being saved or
+    /// transcribed. Until both are done the overlay belongs to the session.
+    pub fn continuous_session_active(&self) -> bool {
+        self.is_continuous() || *self.chunks_in_flight.lock() > 0
+    }
+
+    /// The chunk a continuous session just finished, moving the session on to the next one
+    /// and counting the finished chunk as in flight. `None` outside continuous mode.
+    fn next_chunk(&self) -> Option<ContinuousChunk> {
+        if !self.is_continuous() {
+            return None;
+        }
+        let mut session = self.continuous_session.lock();
+        let session = session.as_mut()?;
+        let finished = session.clone();
+        session.chunk_index += 1;
+        *self.chunks_in_flight.lock() += 1;
+        Some(finished)
+    }
+
+    fn hand_off_chunk(&self, app: &AppHandle<AppRuntime>) -> Option<ChunkInFlight> {
+        self.next_chunk().map(|chunk| ChunkInFlight {
+            chunk,
+            app: app.clone(),
+        })
+    }
+
+    /// Count a chunk as done. Returns whether it was the last one of a session that has
+    /// stopped recording.
+    fn end_chunk(&self) -> bool {
+        let mut in_flight = self.chunks_in_flight.lock();
+        *in_flight = in_flight.saturating_sub(1);
+        *in_flight == 0 && !self.is_continuous()
+    }
+
+    /// Called as each chunk finishes, however it ended. Once a stopped session's last chunk
+    /// is done, the pill leaves the processing state it has been held in.
+    fn finish_chunk(&self, app: &AppHandle<AppRuntime>) {
+        if self.end_chunk() && self.is_processing() {
+            self.reset(app);
+        }
+    }
+
     fn try_start_recording(&self, mode: RecordingMode) -> bool {
         let mut current_mode = self.recording_mode.lock();
         if current_mode.is_some() {
@@ -156,6 +314,30 @@ impl PillController {
         true
     }
 
+    /// Whether a recording started less than `cooldown_ms` ago, in which case the press is
+    /// most likely a bounce of the one that started it.
+    fn within_cooldown(&self, cooldown_ms: u64) -> bool {
+        self.within_cooldown_at(cooldown_ms, Instant::now())
+    }
+
+    fn within_cooldown_at(&self, cooldown_ms: u64, now: Instant) -> bool {
+        cooldown_ms > 0
+            && self.last_trigger_time.lock().is_some_and(|at| {
+                now.saturating_duration_since(at) < Duration::from_millis(cooldown_ms)
+            })
+    }
+
+    /// Whether this press follows a recording-starting smart press within `window_ms`.
+    /// Either way the earlier press is used up, so a third press starts over.
+    fn is_double_tap(&self, window_ms: u64) -> bool {
+        let previous = self.last_smart_press_time.lock().take();
+        window_ms > 0 && previous.is_some_and(|at| at.elapsed() < Duration::from_millis(window_ms))
+    }
+
+    fn mark_triggered(&self) {
+        *self.last_trigger_time.lock() = Some(Instant::now());
+    }
+
     fn clear_hold_state(&self) -> bool {
         let mut hold_down = self.hold_key_down.lock();
         if *hold_down {
@@ -167,7 +349,10 @@ impl PillController {
     }
 
     fn handle_hold_press(&self, app: &AppHandle<AppRuntime>) {
-        if self.status() == PillStatus::Processing {
+        if self.status() == PillStatus::Cancelling {
+            return;
+        }
+        if self.is_processing() {
             if *self.shortcut_origin.lock() == Some(ShortcutOrigin::Hold) {
                 self.cancel_processing(app);
             }
@@ -179,6 +364,12 @@ impl PillController {
             return;
         }
 
+        let state = app.state::<AppState>();
+        let settings = state.current_settings();
+        if self.within_cooldown(settings.shortcut_cooldown_ms) {
+            return;
+        }
+
         if !check_mic_permission(app) {
             return;
         }
@@ -194,12 +385,32 @@ impl PillController {
             }
         }
 
-        let state = app.state::<AppState>();
-        let settings = state.current_settings();
+        self.begin_recording(app, &settings, false);
+    }
 
-        match self.recorder.start(settings.microphone_device) {
+    /// Opens the input stream for a recording whose mode `try_start_recording` already set.
+    fn begin_recording(
+        &self,
+        app: &AppHandle<AppRuntime>,
+        settings: &UserSettings,
+        auto_stop: bool,
+    ) {
+        self.recorder
+            .set_language_hint(settings.language.code().map(str::to_string));
+        self.recorder.set_trim_mode(settings.trim_mode);
+        let (device_id, fallback_warning) = resolve_microphone(settings);
+        match self.recorder.start(device_id, reconnect_grace(settings)) {
             Ok(started) => {
+                self.mark_triggered();
                 self.transition_to(app, PillStatus::Listening);
+                self.emit_duration_ticks(app);
+                self.stream_amplitude(app);
+                self.watch_input_device(app);
+                if auto_stop {
+                    self.schedule_auto_stop(app);
+                }
+                // The toast window hides itself when a recording starts
+                toast::clear_queue(app);
                 emit_event(
                     app,
                     crate::EVENT_RECORDING_START,
@@ -208,6 +419,10 @@ impl PillController {
                     },
                 );
                 check_accessibility_warning(app);
+                // Shown only now, as the toast window hides itself when a recording starts
+                if let Some(warning) = fallback_warning {
+                    toast::show(app, "warning", None, &warning);
+                }
             }
             Err(err) => {
                 self.reset_recording_state();
@@ -216,6 +431,186 @@ impl PillController {
         }
     }
 
+    /// Emit the elapsed recording time once a second until the recorder stops.
+    fn emit_duration_ticks(&self, app: &AppHandle<AppRuntime>) {
+        let recorder = Arc::clone(&self.recorder);
+        let app = app.clone();
+        let task = tauri::async_runtime::spawn(async move {
+            loop {
+                tokio::time::sleep(RECORDING_TICK_INTERVAL).await;
+                let Some(duration_ms) = recorder.get_recording_duration_ms() else {
+                    return;
+                };
+                emit_event(
+                    &app,
+                    crate::EVENT_RECORDING_TICK,
+                    crate::RecordingTickPayload { duration_ms },
+                );
+            }
+        });
+
+        // A quick stop and restart could otherwise leave two tickers running
+        if let Some(previous) = self.tick_task.lock().replace(task) {
+            previous.abort();
+        }
+    }
+
+    /// Forward recorder amplitude to the overlay until the recorder drops the channel on stop.
+    fn stream_amplitude(&self, app: &AppHandle<AppRuntime>) {
+        let amplitude = self.recorder.subscribe_amplitude();
+        let app = app.clone();
+        tauri::async_runtime::spawn_blocking(move || {
+            let mut last_emit: Option<Instant> = None;
+            let mut peak = 0.0f32;
+            while let Ok(rms) = amplitude.recv() {
+                peak = peak.max(rms);
+                if last_emit.is_some_and(|at| at.elapsed() < AMPLITUDE_EMIT_INTERVAL) {
+                    continue;
+                }
+                emit_event(
+                    &app,
+                    crate::EVENT_RECORDING_AMPLITUDE,
+                    crate::RecordingAmplitudePayload { rms: peak },
+                );
+                peak = 0.0;
+                last_emit = Some(Instant::now());
+            }
+        });
+    }
+
+    /// Surface microphone disconnects and reconnects until the recorder drops the channel on stop.
+    fn watch_input_device(&self, app: &AppHandle<AppRuntime>) {
+        let events = self.recorder.subscribe_device_events();
+        let app = app.clone();
+        tauri::async_runtime::spawn_blocking(move || {
+            while let Ok(event) = events.recv() {
+                match event {
+                    DeviceEvent::Disconnected { device } => emit_event(
+                        &app,
+                        crate::EVENT_RECORDING_ERROR,
+                        crate::RecordingErrorPayload {
+                            message: format!("Microphone \"{device}\" disconnected"),
+                        },
+                    ),
+                    DeviceEvent::Reconnected { device } => emit_event(
+                        &app,
+                        crate::EVENT_RECORDING_DEVICE_RECONNECTED,
+                        crate::RecordingDeviceReconnectedPayload { device },
+                    ),
+                    DeviceEvent::ReconnectFailed { .. } => {
+                        toast::show(
+                            &app,
+                            "warning",
+                            Some("Microphone Disconnected"),
+                            "Recording stopped. Transcribing what was captured.",
+                        );
+                        let state = app.state::<AppState>();
+                        let pill = state.pill();
+                        if pill.is_recording() {
+                            pill.stop_and_process(&app);
+                        }
+                        return;
+                    }
+                }
+            }
+        });
+    }
+
+    /// Stop a toggle recording once the input has been silent for the configured duration.
+    fn schedule_auto_stop(&self, app: &AppHandle<AppRuntime>) {
+        let settings = app.state::<AppState>().current_settings();
+        let Some(silence_seconds) = settings.auto_stop_silence_seconds else {
+            return;
+        };
+        let threshold = settings.auto_stop_silence_rms;
+        let silence_limit = Duration::from_secs(silence_seconds as u64);
+
+        let app_handle = app.clone();
+        let task = tauri::async_runtime::spawn(async move {
+            let mut silent_for = Duration::ZERO;
+            loop {
+                tokio::time::sleep(AUTO_STOP_POLL_INTERVAL).await;
+
+                let state = app_handle.state::<AppState>();
+                let pill = state.pill();
+                if pill.active_mode() != Some(RecordingMode::Toggle) {
+                    return;
+                }
+
+                if pill.recorder.current_rms() < threshold {
+                    silent_for += AUTO_STOP_POLL_INTERVAL;
+                } else {
+                    silent_for = Duration::ZERO;
+                }
+
+                if silent_for >= silence_limit {
+                    // Drop our own handle so stop_and_process does not abort this task mid-stop.
+                    pill.auto_stop_task.lock().take();
+                    pill.stop_and_process(&app_handle);
+                    return;
+                }
+            }
+        });
+
+        if let Some(previous) = self.auto_stop_task.lock().replace(task) {
+            previous.abort();
+        }
+    }
+
+    fn cancel_auto_stop(&self) {
+        if let Some(task) = self.auto_stop_task.lock().take() {
+            task.abort();
+        }
+    }
+
+    /// Start a new chunk every `chunk_duration_seconds` for as long as the continuous
+    /// session runs.
+    fn schedule_chunk_rotation(&self, app: &AppHandle<AppRuntime>, settings: &UserSettings) {
+        let chunk_length = Duration::from_secs(u64::from(settings.chunk_duration_seconds.max(1)));
+        let app_handle = app.clone();
+        let task = tauri::async_runtime::spawn(async move {
+            loop {
+                tokio::time::sleep(chunk_length).await;
+
+                let state = app_handle.state::<AppState>();
+                let pill = state.pill();
+                if !pill.is_continuous() {
+                    return;
+                }
+                pill.rotate_chunk(&app_handle);
+            }
+        });
+
+        if let Some(previous) = self.chunk_task.lock().replace(task) {
+            previous.abort();
+        }
+    }
+
+    fn cancel_chunk_rotation(&self) {
+        if let Some(task) = self.chunk_task.lock().take() {
+            task.abort();
+        }
+    }
+
+    /// Hand the chunk recorded so far to the transcription pipeline while the recorder keeps
+    /// capturing the next one, so nothing is lost at the boundary. The overlay stays in the
+    /// listening state and its timer keeps counting from the start of the session.
+    fn rotate_chunk(&self, app: &AppHandle<AppRuntime>) {
+        let recording = match self.recorder.drain() {
+            Ok(Some(recording)) => recording,
+            Ok(None) => {
+                self.reset(app);
+                return;
+            }
+            Err(err) => {
+                self.transition_to_error(app, &format!("Unable to save recording chunk: {err}"));
+                return;
+            }
+        };
+        let chunk = self.hand_off_chunk(app);
+        crate::persist_recording_async(app.clone(), recording, chunk, false);
+    }
+
     fn handle_hold_release(&self, app: &AppHandle<AppRuntime>) {
         if !self.clear_hold_state() {
             return;
@@ -228,15 +623,28 @@ impl PillController {
         self.stop_and_process(app);
     }
 
-    fn handle_toggle_press(&self, app: &AppHandle<AppRuntime>) {
-        if self.status() == PillStatus::Processing {
+    pub(crate) fn handle_toggle_press(&self, app: &AppHandle<AppRuntime>) {
+        if self.status() == PillStatus::Cancelling {
+            return;
+        }
+        if self.is_processing() {
             if *self.shortcut_origin.lock() == Some(ShortcutOrigin::Toggle) {
                 self.cancel_processing(app);
             }
             return;
         }
 
-        if self.active_mode() == Some(RecordingMode::Hold) {
+        if matches!(
+            self.active_mode(),
+            Some(RecordingMode::Hold | RecordingMode::Continuous)
+        ) {
+            return;
+        }
+
+        let state = app.state::<AppState>();
+        let settings = state.current_settings();
+        // A bounced press would otherwise stop the recording it just started
+        if self.within_cooldown(settings.shortcut_cooldown_ms) {
             return;
         }
 
@@ -252,62 +660,152 @@ impl PillController {
             }
 
             *self.shortcut_origin.lock() = Some(ShortcutOrigin::Toggle);
+            self.begin_recording(app, &settings, true);
+        }
+    }
 
-            let state = app.state::<AppState>();
-            let settings = state.current_settings();
-
-            match self.recorder.start(settings.microphone_device) {
-                Ok(started) => {
-                    self.transition_to(app, PillStatus::Listening);
-                    emit_event(
-                        app,
-                        crate::EVENT_RECORDING_START,
-                        crate::RecordingStartPayload {
-                            started_at: started.to_rfc3339(),
-                        },
-                    );
-                    check_accessibility_warning(app);
-                }
-                Err(err) => {
-                    self.reset_recording_state();
-                    self.transition_to_error(app, &format!("Unable to start recording: {err}"));
-                }
-            }
+    /// Starts a recording from the tray menu. It behaves like a toggle recording with no
+    /// shortcut behind it, so it ends from "Stop Recording", the toggle shortcut or auto-stop.
+    pub fn start_recording_from_menu(&self, app: &AppHandle<AppRuntime>) {
+        if self.status() == PillStatus::Cancelling || self.is_processing() || self.is_recording() {
+            return;
+        }
+
+        if !check_mic_permission(app) {
+            return;
+        }
+
+        if !self.try_start_recording(RecordingMode::Toggle) {
+            return;
+        }
+
+        let settings = app.state::<AppState>().current_settings();
+        self.begin_recording(app, &settings, true);
+    }
+
+    /// Starts a continuous session, or ends the running one after saving its last chunk.
+    fn handle_continuous_press(&self, app: &AppHandle<AppRuntime>) {
+        if self.status() == PillStatus::Cancelling {
+            return;
+        }
+
+        let state = app.state::<AppState>();
+        let settings = state.current_settings();
+        if self.within_cooldown(settings.shortcut_cooldown_ms) {
+            return;
+        }
+
+        if self.stop_continuous(app) || self.is_processing() || self.is_recording() {
+            return;
+        }
+
+        if !check_mic_permission(app) {
+            return;
+        }
+
+        if !self.try_start_recording(RecordingMode::Continuous) {
+            return;
+        }
+
+        state.clear_continuous_session_ids();
+        *self.continuous_session.lock() = Some(ContinuousChunk {
+            session_id: uuid::Uuid::new_v4().to_string(),
+            chunk_index: 0,
+        });
+        self.begin_recording(app, &settings, false);
+        if self.is_continuous() {
+            self.schedule_chunk_rotation(app, &settings);
         }
     }
 
+    /// Ends a running continuous session, transcribing its last chunk. Returns whether there
+    /// was one.
+    pub fn stop_continuous(&self, app: &AppHandle<AppRuntime>) -> bool {
+        if !self.is_continuous() {
+            return false;
+        }
+        self.stop_and_process(app);
+        true
+    }
+
+    pub fn stop_recording_from_menu(&self, app: &AppHandle<AppRuntime>) {
+        if !self.is_recording() {
+            return;
+        }
+        self.clear_hold_state();
+        self.stop_and_process(app);
+    }
+
     fn handle_smart_press(&self, app: &AppHandle<AppRuntime>) {
-        if self.status() == PillStatus::Processing {
+        if self.is_processing() {
             if *self.shortcut_origin.lock() == Some(ShortcutOrigin::Smart) {
                 self.cancel_processing(app);
             }
             return;
         }
 
+        let settings = app.state::<AppState>().current_settings();
+        if settings.double_tap_action != DoubleTapAction::Disabled
+            && self.is_double_tap(settings.double_tap_window_ms)
+        {
+            self.handle_double_tap(app, settings.double_tap_action);
+            return;
+        }
+
+        if self.within_cooldown(settings.shortcut_cooldown_ms) {
+            return;
+        }
+
         if self.is_recording() && self.active_mode() == Some(RecordingMode::Toggle) {
             self.handle_toggle_press(app);
             return;
         }
 
-        if self.active_mode() == Some(RecordingMode::Hold) {
+        if matches!(
+            self.active_mode(),
+            Some(RecordingMode::Hold | RecordingMode::Continuous)
+        ) {
             return;
         }
 
+        *self.last_smart_press_time.lock() = Some(Instant::now());
         *self.smart_press_time.lock() = Some(Local::now());
         *self.shortcut_origin.lock() = Some(ShortcutOrigin::Smart);
         self.handle_hold_press(app);
     }
 
+    /// Runs the double-tap action in place of the recording the first tap started.
+    fn handle_double_tap(&self, app: &AppHandle<AppRuntime>, action: DoubleTapAction) {
+        if self.is_recording() {
+            self.cancel(app);
+        }
+
+        match action {
+            DoubleTapAction::OpenSettings => {
+                if let Err(err) = tray::toggle_settings_window(app) {
+                    eprintln!("Failed to open settings: {err}");
+                }
+            }
+            DoubleTapAction::CopyLast => copy_latest_transcription(app),
+            DoubleTapAction::Disabled => {}
+        }
+    }
+
     fn handle_smart_release(&self, app: &AppHandle<AppRuntime>) {
         let press_time = self.smart_press_time.lock().take();
 
         if let Some(start_time) = press_time {
             let held_duration_ms = (Local::now() - start_time).num_milliseconds();
+            let threshold_ms = app
+                .state::<AppState>()
+                .current_settings()
+                .smart_tap_threshold_ms;
 
-            if held_duration_ms < SMART_MODE_TAP_THRESHOLD_MS {
+            if is_smart_tap(held_duration_ms, threshold_ms) {
                 if self.active_mode() == Some(RecordingMode::Hold) {
                     *self.hold_key_down.lock() = false;
                     *self.recording_mode.lock() = Some(RecordingMode::Toggle);
+                    self.schedule_auto_stop(app);
                 }
                 return;
             }
@@ -317,11 +815,18 @@ impl PillController {
     }
 
     fn stop_and_process(&self, app: &AppHandle<AppRuntime>) {
+        self.cancel_auto_stop();
+        self.cancel_chunk_rotation();
         match self.recorder.stop() {
             Ok(Some(recording)) => {
+                let chunk = self.hand_off_chunk(app);
                 let duration_ms = (recording.ended_at - recording.started_at).num_milliseconds();
+                let min_duration_ms = app
+                    .state::<AppState>()
+                    .current_settings()
+                    .min_recording_duration_ms;
 
-                if duration_ms < MIN_RECORDING_DURATION_MS {
+                if duration_ms < min_duration_ms as i64 {
                     self.reset(app);
                     return;
                 }
@@ -337,7 +842,7 @@ impl PillController {
                     },
                 );
 
-                crate::persist_recording_async(app.clone(), recording);
+                crate::persist_recording_async(app.clone(), recording, chunk, true);
             }
             Ok(None) => {
                 self.reset(app);
@@ -356,7 +861,7 @@ impl PillController {
     }
 
     pub fn cancel_processing(&self, app: &AppHandle<AppRuntime>) {
-        if self.status() != PillStatus::Processing {
+        if !self.is_processing() {
             return;
         }
 
@@ -369,10 +874,73 @@ impl PillController {
         }
 
         toast::show(app, "info", None, "Transcription cancelled");
-        self.reset(app);
+        self.reset_recording_state();
+        self.transition_to(app, PillStatus::Cancelling);
+        self.await_cancellation(app);
+    }
+
+    /// Return to idle once the cancelled transcription task has wound down, or after
+    /// `CANCEL_TIMEOUT` if it is stuck somewhere it doesn't check for cancellation.
+    fn await_cancellation(&self, app: &AppHandle<AppRuntime>) {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            let started = Instant::now();
+            loop {
+                tokio::time::sleep(CANCEL_POLL_INTERVAL).await;
+                let state = app.state::<AppState>();
+                if !state.is_cancelled() && !state.has_pending_path() {
+                    break;
+                }
+                if started.elapsed() >= CANCEL_TIMEOUT {
+                    eprintln!("Cancelled transcription still running, resetting anyway");
+                    break;
+                }
+            }
+            app.state::<AppState>().pill().transition_from(
+                &app,
+                PillStatus::Cancelling,
+                PillStatus::Idle,
+            );
+        });
     }
 }
 
+/// Updates the tray's record item when it would read differently after the transition.
+fn refresh_tray_recording_item(app: &AppHandle<AppRuntime>, from: PillStatus, to: PillStatus) {
+    if tray::recording_menu_item(from) == tray::recording_menu_item(to) {
+        return;
+    }
+    if let Err(err) = tray::update_tray_record_item(app, to) {
+        eprintln!("Failed to update tray record item: {err}");
+    }
+}
+
+/// Device to record from, falling back to the system default (without touching the saved
+/// setting) when the configured microphone is not connected. A fallback also returns the
+/// warning to show the user.
+fn resolve_microphone(settings: &UserSettings) -> (Option<String>, Option<String>) {
+    match audio::microphone_status(settings.microphone_device.as_deref()) {
+        MicrophoneStatus::Available => (settings.microphone_device.clone(), None),
+        MicrophoneStatus::NotConfigured => (None, None),
+        MicrophoneStatus::Unavailable(fallback) => {
+            let configured = settings.microphone_device.as_deref().unwrap_or_default();
+            eprintln!("Microphone '{configured}' unavailable, recording from '{fallback}'");
+            (
+                None,
+                Some(format!(
+                    "Your microphone '{configured}' is unavailable — using system default"
+                )),
+            )
+        }
+    }
+}
+
+fn reconnect_grace(settings: &UserSettings) -> Option<Duration> {
+    settings
+        .reconnect_grace_seconds
+        .map(|seconds| Duration::from_secs(u64::from(seconds)))
+}
+
 fn check_mic_permission(app: &AppHandle<AppRuntime>) -> bool {
     #[cfg(target_os = "macos")]
     {
@@ -419,6 +987,12 @@ fn check_accessibility_warning(app: &AppHandle<AppRuntime>) {
     let _ = app;
 }
 
+/// Whether a smart shortcut press held for `held_ms` was a tap (toggle mode) rather than
+/// a hold. A release exactly at the threshold counts as a hold.
+fn is_smart_tap(held_ms: i64, threshold_ms: u64) -> bool {
+    held_ms < threshold_ms as i64
+}
+
 pub fn register_shortcuts(app: &AppHandle<AppRuntime>) -> anyhow::Result<()> {
     let state = app.state::<AppState>();
     let manager = app.global_shortcut();
@@ -487,12 +1061,55 @@ pub fn register_shortcuts(app: &AppHandle<AppRuntime>) -> anyhow::Result<()> {
         })?;
     }
 
+    let copy_latest_shortcut = settings.copy_latest_shortcut.trim();
+    if settings.copy_latest_enabled && !copy_latest_shortcut.is_empty() {
+        manager.on_shortcut(copy_latest_shortcut, move |app, _shortcut, event| {
+            if event.state == ShortcutState::Pressed {
+                copy_latest_transcription(app);
+            }
+        })?;
+    }
+
+    let continuous_shortcut = settings.continuous_shortcut.trim();
+    if settings.continuous_enabled && !continuous_shortcut.is_empty() {
+        manager.on_shortcut(continuous_shortcut, move |app, _shortcut, event| {
+            if event.state == ShortcutState::Pressed {
+                let state = app.state::<AppState>();
+                state.pill().handle_continuous_press(app);
+            }
+        })?;
+    }
+
     Ok(())
 }
 
+fn copy_latest_transcription(app: &AppHandle<AppRuntime>) {
+    let record = match app.state::<AppState>().storage().get_latest_successful() {
+        Ok(Some(record)) => record,
+        Ok(None) => return,
+        Err(err) => {
+            eprintln!("Failed to load latest transcription: {err}");
+            return;
+        }
+    };
+
+    match assistive::copy_text(&record.text) {
+        Ok(()) => toast::show(app, "success", None, "Copied to clipboard"),
+        Err(err) => {
+            eprintln!("Failed to copy latest transcription: {err}");
+            toast::show(app, "error", None, "Failed to copy transcription");
+        }
+    }
+}
+
 pub fn show_overlay(app: &AppHandle<AppRuntime>) {
+    let settings = app.state::<AppState>().current_settings();
+    if settings.focus_mode {
+        return;
+    }
+
     if let Some(window) = app.get_webview_window(MAIN_WINDOW_LABEL) {
-        position_overlay_on_cursor_screen(&window);
+        position_overlay_on_cursor_screen(&window, settings.overlay_position);
         platform::overlay::show(app, &window);
     }
 }
@@ -503,22 +1120,28 @@ pub fn hide_overlay(app: &AppHandle<AppRuntime>) {
     }
 }
 
-fn position_overlay(window: &WebviewWindow<AppRuntime>) {
+fn position_overlay(window: &WebviewWindow<AppRuntime>, position: OverlayPosition) {
     if let Ok(Some(monitor)) = window.current_monitor() {
         if let Ok(size) = window.outer_size() {
-            let screen = monitor.size();
-            let x = (screen.width.saturating_sub(size.width) / 2) as i32;
-            let y = ((screen.height as f64) * 0.88) as i32;
-            let _ = window.set_position(tauri::PhysicalPosition::new(x, y));
+            let origin = platform::overlay::overlay_origin(
+                position,
+                tauri::PhysicalPosition::new(0, 0),
+                *monitor.size(),
+                size,
+            );
+            let _ = window.set_position(origin);
         }
     }
 }
 
-fn position_overlay_on_cursor_screen(window: &WebviewWindow<AppRuntime>) {
+fn position_overlay_on_cursor_screen(
+    window: &WebviewWindow<AppRuntime>,
+    position: OverlayPosition,
+) {
     let cursor_pos = match window.cursor_position() {
         Ok(pos) => pos,
         Err(_) => {
-            position_overlay(window);
+            position_overlay(window, position);
             return;
         }
     };
@@ -526,7 +1149,7 @@ fn position_overlay_on_cursor_screen(window: &WebviewWindow<AppRuntime>) {
     let monitors = match window.available_monitors() {
         Ok(m) => m,
         Err(_) => {
-            position_overlay(window);
+            position_overlay(window, position);
             return;
         }
     };
@@ -543,17 +1166,15 @@ fn position_overlay_on_cursor_screen(window: &WebviewWindow<AppRuntime>) {
     let monitor = match target_monitor {
         Some(m) => m,
         None => {
-            position_overlay(window);
+            position_overlay(window, position);
             return;
         }
     };
 
     if let Ok(size) = window.outer_size() {
-        let mon_pos = monitor.position();
-        let mon_size = monitor.size();
-        let x = mon_pos.x + ((mon_size.width.saturating_sub(size.width)) / 2) as i32;
-        let y = mon_pos.y + ((mon_size.height as f64) * 0.88) as i32;
-        let _ = window.set_position(tauri::PhysicalPosition::new(x, y));
+        let origin =
+            platform::overlay::overlay_origin(position, *monitor.position(), *monitor.size(), size);
+        let _ = window.set_position(origin);
     }
 }
 
@@ -584,3 +1205,166 @@ fn simplify_recording_error(message: &str) -> String {
 
     "Recording failed".to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cooldown_ignores_rapid_second_press() {
+        let pill = PillController::new(Arc::new(RecorderManager::new()));
+        let pressed_at = Instant::now();
+        assert!(!pill.within_cooldown_at(100, pressed_at));
+
+        pill.mark_triggered();
+        let triggered_at = pill.last_trigger_time.lock().unwrap();
+        let after = |ms: u64| triggered_at + Duration::from_millis(ms);
+        assert!(pill.within_cooldown_at(100, after(0)));
+        assert!(pill.within_cooldown_at(100, after(99)));
+        assert!(!pill.within_cooldown_at(100, after(100)));
+        assert!(!pill.within_cooldown_at(100, after(150)));
+        // A zero cooldown never blocks
+        assert!(!pill.within_cooldown_at(0, after(0)));
+    }
+
+    #[test]
+    fn test_double_tap_window() {
+        let pill = PillController::new(Arc::new(RecorderManager::new()));
+        assert!(!pill.is_double_tap(300));
+
+        *pill.last_smart_press_time.lock() = Some(Instant::now());
+        assert!(pill.is_double_tap(300));
+        // The first tap is consumed, so a third press isn't another double tap
+        assert!(!pill.is_double_tap(300));
+
+        *pill.last_smart_press_time.lock() = Some(Instant::now());
+        assert!(!pill.is_double_tap(0));
+
+        // Backdated rather than slept on, so the test doesn't depend on timing
+        *pill.last_smart_press_time.lock() = Instant::now().checked_sub(Duration::from_millis(60));
+        assert!(!pill.is_double_tap(50));
+    }
+
+    #[test]
+    fn test_continuous_chunks_are_numbered_in_order() {
+        let pill = PillController::new(Arc::new(RecorderManager::new()));
+        *pill.continuous_session.lock() = Some(ContinuousChunk {
+            session_id: "session".to_string(),
+            chunk_index: 0,
+        });
+        // Only a running continuous recording hands off chunks
+        assert_eq!(pill.next_chunk(), None);
+
+        assert!(pill.try_start_recording(RecordingMode::Continuous));
+        let indices: Vec<u32> = (0..3)
+            .filter_map(|_| pill.next_chunk())
+            .map(|chunk| chunk.chunk_index)
+            .collect();
+        assert_eq!(indices, vec![0, 1, 2]);
+
+        pill.reset_recording_state();
+        assert_eq!(pill.next_chunk(), None);
+        assert_eq!(pill.continuous_session_id().as_deref(), Some("session"));
+    }
+
+    fn start_continuous_session(pill: &PillController) {
+        *pill.continuous_session.lock() = Some(ContinuousChunk {
+            session_id: "session".to_string(),
+            chunk_index: 0,
+        });
+        assert!(pill.try_start_recording(RecordingMode::Continuous));
+    }
+
+    #[test]
+    fn test_chunks_finishing_during_rotation_keep_the_session() {
+        let pill = PillController::new(Arc::new(RecorderManager::new()));
+        assert!(!pill.continuous_session_active());
+        start_continuous_session(&pill);
+
+        // Two rotations hand off two chunks while recording carries on
+        assert!(pill.next_chunk().is_some());
+        assert!(pill.next_chunk().is_some());
+        assert!(!pill.end_chunk());
+        assert!(!pill.end_chunk());
+        assert!(pill.continuous_session_active());
+    }
+
+    #[test]
+    fn test_stopping_waits_for_chunks_still_processing() {
+        let pill = PillController::new(Arc::new(RecorderManager::new()));
+        start_continuous_session(&pill);
+
+        // A rotated chunk is still transcribing when the session stops with its last chunk
+        assert!(pill.next_chunk().is_some());
+        assert!(pill.next_chunk().is_some());
+        pill.reset_recording_state();
+        assert!(pill.continuous_session_active());
+
+        // The earlier chunk finishing late must not end the session under the last one
+        assert!(!pill.end_chunk());
+        assert!(pill.continuous_session_active());
+        assert!(pill.end_chunk());
+        assert!(!pill.continuous_session_active());
+    }
+
+    #[test]
+    fn test_only_one_caller_claims_an_idle_pill() {
+        let pill = PillController::new(Arc::new(RecorderManager::new()));
+        assert!(pill.replace_status(PillStatus::Idle, PillStatus::Processing));
+        assert!(!pill.replace_status(PillStatus::Idle, PillStatus::Processing));
+        assert_eq!(pill.status(), PillStatus::Processing);
+
+        // Releasing the claim only works from the claimed state
+        assert!(!pill.replace_status(PillStatus::Listening, PillStatus::Idle));
+        assert!(pill.replace_status(PillStatus::Processing, PillStatus::Idle));
+        assert_eq!(pill.status(), PillStatus::Idle);
+
+        *pill.status.lock() = PillStatus::Listening;
+        assert!(!pill.replace_status(PillStatus::Idle, PillStatus::Processing));
+        assert_eq!(pill.status(), PillStatus::Listening);
+    }
+
+    #[test]
+    fn test_status_serializes_like_display() {
+        for status in [
+            PillStatus::Idle,
+            PillStatus::Processing,
+            PillStatus::ModelLoading,
+            PillStatus::Cancelling,
+        ] {
+            assert_eq!(
+                serde_json::to_string(&status).unwrap(),
+                format!("\"{status}\"")
+            );
+        }
+    }
+
+    #[test]
+    fn test_smart_tap_threshold_boundary() {
+        assert!(is_smart_tap(199, 200));
+        assert!(!is_smart_tap(200, 200));
+        assert!(!is_smart_tap(201, 200));
+        assert!(is_smart_tap(0, 100));
+        assert!(!is_smart_tap(800, 800));
+    }
+
+    #[test]
+    fn test_tray_record_item_follows_status() {
+        assert_eq!(
+            tray::recording_menu_item(PillStatus::Idle),
+            ("Start Recording", true)
+        );
+        assert_eq!(
+            tray::recording_menu_item(PillStatus::Listening),
+            ("Stop Recording", true)
+        );
+        for status in [PillStatus::Processing, PillStatus::Cancelling] {
+            assert!(!tray::recording_menu_item(status).1);
+        }
+        // Moving between error and idle leaves the menu alone
+        assert_eq!(
+            tray::recording_menu_item(PillStatus::Error),
+            tray::recording_menu_item(PillStatus::Idle)
+        );
+    }
+}
@@ -1,2 +1,3 @@
 pub mod overlay;
+pub mod spotlight;
 pub mod toast;
@@ -1,5 +1,6 @@
 use crate::AppRuntime;
 use anyhow::{anyhow, Context, Result};
+use tauri::window::{Effect, EffectState, EffectsBuilder};
 use tauri::Manager;
 use tauri::{AppHandle, WebviewWindow};
 use tauri_nspanel::{
@@ -73,3 +74,21 @@ pub fn hide(
     });
     Ok(())
 }
+
+/// Put an `NSVisualEffectView` with `radius` rounded corners behind the pill, or remove it
+/// when `radius` is 0. The view's blur strength is fixed by the system.
+pub fn set_backdrop_corner_radius(
+    overlay_window: &WebviewWindow<AppRuntime>,
+    radius: f32,
+) -> Result<()> {
+    let effects = (radius > 0.0).then(|| {
+        EffectsBuilder::new()
+            .effect(Effect::HudWindow)
+            .state(EffectState::Active)
+            .radius(f64::from(radius))
+            .build()
+    });
+    overlay_window
+        .set_effects(effects)
+        .context("set overlay window effects")
+}
@@ -0,0 +1,24 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+/// Spotlight only reads `com.apple.metadata:` attributes stored as binary plists.
+const TEXT_CONTENT_ATTR: &str = "com.apple.metadata:kMDItemTextContent";
+
+pub fn index_audio_file(audio_path: &Path, text: &str) -> Result<()> {
+    let mut value = Vec::new();
+    plist::Value::String(text.to_string())
+        .to_writer_binary(&mut value)
+        .context("encode transcript as plist")?;
+    xattr::set(audio_path, TEXT_CONTENT_ATTR, &value)
+        .with_context(|| format!("set {TEXT_CONTENT_ATTR} on {}", audio_path.display()))
+}
+
+pub fn remove_index(audio_path: &Path) -> Result<()> {
+    // Recordings saved while indexing was off never got the attribute
+    if xattr::get(audio_path, TEXT_CONTENT_ATTR)?.is_none() {
+        return Ok(());
+    }
+    xattr::remove(audio_path, TEXT_CONTENT_ATTR)
+        .with_context(|| format!("remove {TEXT_CONTENT_ATTR} from {}", audio_path.display()))
+}
@@ -1,4 +1,5 @@
 pub mod overlay;
+pub mod spotlight;
 pub mod toast;
 
 pub mod default;
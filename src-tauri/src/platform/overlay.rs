@@ -1,7 +1,19 @@
+use crate::settings::{OverlayPosition, UserSettings};
 use crate::AppRuntime;
-use tauri::{AppHandle, WebviewWindow};
+use tauri::{AppHandle, PhysicalPosition, PhysicalSize, WebviewWindow};
 
-pub fn init(app: &AppHandle<AppRuntime>, overlay_window: &WebviewWindow<AppRuntime>) {
+/// Lowest pill opacity allowed, so it can't be faded out of sight entirely.
+pub const MIN_OPACITY: f32 = 0.3;
+/// Top of the pill in `Auto` mode, as a fraction of the screen height.
+const AUTO_VERTICAL_POSITION: f64 = 0.88;
+/// Gap between a pinned pill and the screen edges, as a fraction of the screen size.
+const EDGE_MARGIN: f64 = 0.03;
+
+pub fn init(
+    app: &AppHandle<AppRuntime>,
+    overlay_window: &WebviewWindow<AppRuntime>,
+    settings: &UserSettings,
+) {
     #[cfg(target_os = "macos")]
     {
         if let Err(err) = crate::platform::macos::overlay::init(app, overlay_window) {
@@ -14,6 +26,70 @@ pub fn init(app: &AppHandle<AppRuntime>, overlay_window: &WebviewWindow<AppRunti
         let _ = app;
         crate::platform::default::overlay::init(overlay_window);
     }
+
+    set_backdrop_corner_radius(overlay_window, settings.overlay_corner_radius);
+}
+
+/// Keep `opacity` between [`MIN_OPACITY`] and fully opaque. Values that aren't numbers
+/// count as fully opaque.
+pub fn clamp_opacity(opacity: f32) -> f32 {
+    if opacity.is_nan() {
+        1.0
+    } else {
+        opacity.clamp(MIN_OPACITY, 1.0)
+    }
+}
+
+/// Where to put the top-left corner of a `window` sized pill on the monitor at `origin` of
+/// size `screen`, all in physical pixels. The pill is kept on that monitor.
+pub fn overlay_origin(
+    position: OverlayPosition,
+    origin: PhysicalPosition<i32>,
+    screen: PhysicalSize<u32>,
+    window: PhysicalSize<u32>,
+) -> PhysicalPosition<i32> {
+    let (width, height) = (screen.width as f64, screen.height as f64);
+    let free_width = screen.width.saturating_sub(window.width) as f64;
+    let free_height = screen.height.saturating_sub(window.height) as f64;
+    let (margin_x, margin_y) = (
+        (width * EDGE_MARGIN).round(),
+        (height * EDGE_MARGIN).round(),
+    );
+
+    let (x, y) = match position {
+        OverlayPosition::Auto => (free_width / 2.0, height * AUTO_VERTICAL_POSITION),
+        OverlayPosition::TopCenter => (free_width / 2.0, margin_y),
+        OverlayPosition::BottomCenter => (free_width / 2.0, free_height - margin_y),
+        OverlayPosition::TopLeft => (margin_x, margin_y),
+        OverlayPosition::TopRight => (free_width - margin_x, margin_y),
+        OverlayPosition::BottomLeft => (margin_x, free_height - margin_y),
+        OverlayPosition::BottomRight => (free_width - margin_x, free_height - margin_y),
+        OverlayPosition::Custom {
+            x_percent,
+            y_percent,
+        } => (
+            (x_percent as f64 * width).min(free_width),
+            (y_percent as f64 * height).min(free_height),
+        ),
+    };
+
+    PhysicalPosition::new(origin.x + x.max(0.0) as i32, origin.y + y.max(0.0) as i32)
+}
+
+/// Put a frosted backdrop with `radius` rounded corners behind the pill; 0 removes it.
+/// Only macOS supports this.
+pub fn set_backdrop_corner_radius(overlay_window: &WebviewWindow<AppRuntime>, radius: f32) {
+    #[cfg(target_os = "macos")]
+    {
+        if let Err(err) =
+            crate::platform::macos::overlay::set_backdrop_corner_radius(overlay_window, radius)
+        {
+            eprintln!("Failed to set overlay backdrop: {err}");
+        }
+    }
+
+    let _ = overlay_window;
+    let _ = radius;
 }
 
 pub fn show(app: &AppHandle<AppRuntime>, overlay_window: &WebviewWindow<AppRuntime>) {
@@ -39,3 +115,57 @@ pub fn hide(app: &AppHandle<AppRuntime>, overlay_window: &WebviewWindow<AppRunti
     let _ = app;
     crate::platform::default::overlay::hide(overlay_window);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clamp_opacity() {
+        assert_eq!(clamp_opacity(0.6), 0.6);
+        assert_eq!(clamp_opacity(1.0), 1.0);
+        assert_eq!(clamp_opacity(MIN_OPACITY), MIN_OPACITY);
+        assert_eq!(clamp_opacity(0.1), MIN_OPACITY);
+        assert_eq!(clamp_opacity(-2.0), MIN_OPACITY);
+        assert_eq!(clamp_opacity(1.5), 1.0);
+        assert_eq!(clamp_opacity(f32::INFINITY), 1.0);
+        assert_eq!(clamp_opacity(f32::NAN), 1.0);
+    }
+
+    #[test]
+    fn test_overlay_origin_for_each_position() {
+        // A 1000x500 screen to the right of a 1920 pixel wide one, with a 200x50 pill
+        let place = |position| {
+            let origin = overlay_origin(
+                position,
+                PhysicalPosition::new(1920, 0),
+                PhysicalSize::new(1000, 500),
+                PhysicalSize::new(200, 50),
+            );
+            (origin.x, origin.y)
+        };
+
+        assert_eq!(place(OverlayPosition::Auto), (2320, 440));
+        assert_eq!(place(OverlayPosition::TopCenter), (2320, 15));
+        assert_eq!(place(OverlayPosition::BottomCenter), (2320, 435));
+        assert_eq!(place(OverlayPosition::TopLeft), (1950, 15));
+        assert_eq!(place(OverlayPosition::TopRight), (2690, 15));
+        assert_eq!(place(OverlayPosition::BottomLeft), (1950, 435));
+        assert_eq!(place(OverlayPosition::BottomRight), (2690, 435));
+        assert_eq!(
+            place(OverlayPosition::Custom {
+                x_percent: 0.25,
+                y_percent: 0.5
+            }),
+            (2170, 250)
+        );
+        // The pill stays on screen even when pinned to the far corner
+        assert_eq!(
+            place(OverlayPosition::Custom {
+                x_percent: 1.0,
+                y_percent: 1.0
+            }),
+            (2720, 450)
+        );
+    }
+}
@@ -0,0 +1,27 @@
+use std::path::Path;
+
+/// Attach a transcript to its recording so Spotlight finds the file by what was said.
+/// Does nothing outside macOS.
+pub fn index_audio_file(audio_path: &Path, text: &str) {
+    #[cfg(target_os = "macos")]
+    {
+        if let Err(err) = crate::platform::macos::spotlight::index_audio_file(audio_path, text) {
+            eprintln!("Failed to index recording for Spotlight: {err}");
+        }
+    }
+
+    let _ = audio_path;
+    let _ = text;
+}
+
+/// Remove the transcript attached by [`index_audio_file`], if any.
+pub fn remove_index(audio_path: &Path) {
+    #[cfg(target_os = "macos")]
+    {
+        if let Err(err) = crate::platform::macos::spotlight::remove_index(audio_path) {
+            eprintln!("Failed to remove Spotlight text from recording: {err}");
+        }
+    }
+
+    let _ = audio_path;
+}
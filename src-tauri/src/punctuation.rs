@@ -0,0 +1,122 @@
+//! Rule-based sentence punctuation for speech models that return bare text.
+
+/// Lowercase abbreviations whose trailing period does not end a sentence.
+const ABBREVIATIONS: &[&str] = &[
+    "mr.", "mrs.", "ms.", "dr.", "prof.", "sr.", "jr.", "st.", "vs.", "e.g.", "i.e.", "approx.",
+    "no.",
+];
+
+/// Closing characters that may follow a sentence's final punctuation.
+const CLOSERS: &[char] = &['"', '\'', ')', ']', '”', '’'];
+
+/// Capitalize the first word and every word after `.`, `?` or `!` (unless the period belongs
+/// to an abbreviation), and end the text with a period when it has no final punctuation.
+/// Whitespace between words is kept as is.
+pub fn add_punctuation(text: &str) -> String {
+    let text = text.trim();
+    if text.is_empty() {
+        return String::new();
+    }
+
+    let mut result = String::with_capacity(text.len() + 1);
+    let mut capitalize_next = true;
+    for piece in text.split_inclusive(char::is_whitespace) {
+        let word = piece.trim_end();
+        if word.is_empty() {
+            result.push_str(piece);
+            continue;
+        }
+
+        if capitalize_next {
+            result.push_str(&capitalize(word));
+        } else {
+            result.push_str(word);
+        }
+        result.push_str(&piece[word.len()..]);
+        capitalize_next = ends_sentence(word);
+    }
+
+    let body = result.trim_end_matches(CLOSERS);
+    match body.chars().last() {
+        Some('.' | '?' | '!' | '…') => {}
+        Some(',' | ';' | ':') => {
+            let closers = result[body.len()..].to_string();
+            result.truncate(body.len() - 1);
+            result.push('.');
+            result.push_str(&closers);
+        }
+        _ => result.push('.'),
+    }
+    result
+}
+
+/// Whether `word` ends a sentence: it ends in `?` or `!`, or in a period that isn't part of an
+/// abbreviation, ignoring closing quotes and brackets.
+pub(crate) fn ends_sentence(word: &str) -> bool {
+    let word = word.trim_end_matches(CLOSERS);
+    if word.ends_with('?') || word.ends_with('!') {
+        return true;
+    }
+    if !word.ends_with('.') {
+        return false;
+    }
+    let bare = word.trim_start_matches(|c: char| !c.is_alphanumeric());
+    !ABBREVIATIONS.contains(&bare.to_lowercase().as_str())
+}
+
+/// Uppercase the first letter of `word`, skipping leading quotes or brackets. Words that
+/// already contain a capital, such as "iPhone", are left alone.
+fn capitalize(word: &str) -> String {
+    if word.chars().any(char::is_uppercase) {
+        return word.to_string();
+    }
+    match word.char_indices().find(|(_, c)| c.is_alphanumeric()) {
+        Some((index, first)) if first.is_alphabetic() => {
+            let rest = &word[index + first.len_utf8()..];
+            format!("{}{}{}", &word[..index], first.to_uppercase(), rest)
+        }
+        _ => word.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_punctuation_pairs() {
+        let cases = [
+            ("hello world", "Hello world."),
+            ("hello world. how are you", "Hello world. How are you."),
+            (
+                "is it ready? yes it is! great",
+                "Is it ready? Yes it is! Great.",
+            ),
+            ("we saw dr. jones at noon", "We saw dr. jones at noon."),
+            ("already done.", "Already done."),
+            ("wait for it,", "Wait for it."),
+            (
+                "it costs 3.5 dollars. that is fine",
+                "It costs 3.5 dollars. That is fine.",
+            ),
+            (
+                "i use an iPhone. iPhone is nice",
+                "I use an iPhone. iPhone is nice.",
+            ),
+            (
+                "she said \"go home.\" (maybe) later",
+                "She said \"go home.\" (Maybe) later.",
+            ),
+            ("  one.  two  ", "One.  Two."),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(add_punctuation(input), expected, "input: {input:?}");
+        }
+    }
+
+    #[test]
+    fn test_add_punctuation_empty_input() {
+        assert_eq!(add_punctuation(""), "");
+        assert_eq!(add_punctuation("   "), "");
+    }
+}
@@ -1,27 +1,133 @@
-use std::{borrow::Cow, f32::consts::PI, fs, path::PathBuf, sync::Arc};
+use std::{
+    borrow::Cow,
+    f32::consts::PI,
+    fs,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
 
 use anyhow::{anyhow, Context, Result};
 use chrono::{DateTime, Local};
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use cpal::{SampleFormat, Stream};
-use crossbeam_channel::{bounded, unbounded, Sender};
+use crossbeam_channel::{bounded, unbounded, Receiver, RecvTimeoutError, Sender, TrySendError};
 use mp3lame_encoder::{
     Bitrate, Builder as LameBuilder, FlushNoGap, InterleavedPcm, MonoPcm, Quality,
 };
 use parking_lot::Mutex;
+use tracing::{error, info, warn};
 use webrtc_vad::{Vad, VadMode};
 
+use crate::settings::TrimMode;
+
+/// How often an active recording checks that its input device is still connected.
+const DEVICE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// MP3 bitrates offered for saved recordings, in kbps.
+pub const SUPPORTED_BITRATES_KBPS: [u32; 5] = [32, 48, 64, 96, 128];
+
 /// Reason why a recording was rejected
 #[derive(Debug, Clone)]
 pub enum RecordingRejectionReason {
     TooShort { duration_ms: i64, min_ms: i64 },
     TooQuiet { rms: f32, threshold: f32 },
     NoSpeechDetected,
+    BackgroundNoise { spectral_flatness: f32 },
     EmptyBuffer,
 }
 
+/// Shares per-buffer RMS from the input stream with the rest of the app.
+#[derive(Default)]
+struct AmplitudeTap {
+    subscribers: Mutex<Vec<Sender<f32>>>,
+    /// `f32` bits of the most recent buffer RMS.
+    latest_rms: AtomicU32,
+}
+
+/// Input device changes observed while a recording is in progress. After
+/// `ReconnectFailed` the audio captured before the disconnect is still returned by `stop`.
+#[derive(Debug, Clone)]
+pub enum DeviceEvent {
+    Disconnected { device: String },
+    Reconnected { device: String },
+    ReconnectFailed { device: String },
+}
+
 pub struct RecorderManager {
     tx: Sender<RecorderCommand>,
+    amplitude: Arc<AmplitudeTap>,
+    device_subscribers: Arc<Mutex<Vec<Sender<DeviceEvent>>>>,
+    language_hint: Arc<Mutex<Option<String>>>,
+    trim_mode: Arc<Mutex<TrimMode>>,
+    recording_started_at: Arc<Mutex<Option<DateTime<Local>>>>,
+}
+
+/// Band-pass cutoffs applied to a finished recording.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LanguageFilterProfile {
+    pub high_pass_hz: f32,
+    pub low_pass_hz: f32,
+}
+
+const DEFAULT_FILTER_PROFILE: LanguageFilterProfile = LanguageFilterProfile {
+    high_pass_hz: 120.0,
+    low_pass_hz: 8_000.0,
+};
+
+/// Tonal languages keep more low-frequency pitch information; languages rich in
+/// fricatives and pharyngeals keep more of the upper band.
+const LANGUAGE_FILTER_PROFILES: &[(&str, LanguageFilterProfile)] = &[
+    ("zh", profile(80.0, 8_000.0)),
+    ("vi", profile(80.0, 8_000.0)),
+    ("th", profile(80.0, 8_000.0)),
+    ("ja", profile(100.0, 8_000.0)),
+    ("ko", profile(100.0, 8_000.0)),
+    ("hi", profile(100.0, 8_000.0)),
+    ("bn", profile(100.0, 8_000.0)),
+    ("id", profile(110.0, 8_000.0)),
+    ("ar", profile(100.0, 10_000.0)),
+    ("he", profile(100.0, 10_000.0)),
+    ("fa", profile(100.0, 10_000.0)),
+    ("ru", profile(110.0, 10_000.0)),
+    ("uk", profile(110.0, 10_000.0)),
+    ("pl", profile(120.0, 10_000.0)),
+    ("de", profile(120.0, 10_000.0)),
+    ("tr", profile(110.0, 9_000.0)),
+    ("es", profile(120.0, 9_000.0)),
+    ("pt", profile(120.0, 9_000.0)),
+    ("fr", profile(120.0, 9_000.0)),
+    ("it", profile(120.0, 9_000.0)),
+    ("nl", profile(120.0, 9_000.0)),
+];
+
+const fn profile(high_pass_hz: f32, low_pass_hz: f32) -> LanguageFilterProfile {
+    LanguageFilterProfile {
+        high_pass_hz,
+        low_pass_hz,
+    }
+}
+
+/// Filter profile for an ISO-639-1 code (region subtags like `zh-CN` are ignored).
+pub fn filter_profile_for_language(language: Option<&str>) -> LanguageFilterProfile {
+    let Some(language) = language else {
+        return DEFAULT_FILTER_PROFILE;
+    };
+    let code = language
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+
+    LANGUAGE_FILTER_PROFILES
+        .iter()
+        .find(|(key, _)| *key == code)
+        .map(|(_, profile)| *profile)
+        .unwrap_or(DEFAULT_FILTER_PROFILE)
 }
 
 struct ActiveRecording {
@@ -29,7 +135,20 @@ struct ActiveRecording {
     buffer: Arc<Mutex<Vec<i16>>>,
     sample_rate: u32,
     channels: u16,
+    /// When the audio in `buffer` began. Moves forward each time a chunk is drained.
+    started_at: DateTime<Local>,
+    device_name: String,
+}
+
+/// Audio captured before the input device disappeared, held until it reconnects or we stop.
+struct SuspendedRecording {
+    samples: Vec<i16>,
+    sample_rate: u32,
+    channels: u16,
     started_at: DateTime<Local>,
+    device_name: String,
+    lost_at: Instant,
+    gave_up: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -51,32 +170,110 @@ pub struct RecordingSaved {
 impl RecorderManager {
     pub fn new() -> Self {
         let (tx, rx) = unbounded();
+        let amplitude = Arc::new(AmplitudeTap::default());
+        let core_amplitude = amplitude.clone();
+        let device_subscribers = Arc::new(Mutex::new(Vec::new()));
+        let core_device_subscribers = device_subscribers.clone();
+        let language_hint = Arc::new(Mutex::new(None));
+        let core_language_hint = language_hint.clone();
+        let trim_mode = Arc::new(Mutex::new(TrimMode::default()));
+        let core_trim_mode = trim_mode.clone();
+        let recording_started_at = Arc::new(Mutex::new(None));
+        let core_recording_started_at = recording_started_at.clone();
 
         std::thread::Builder::new()
             .name("glimpse-recorder".into())
             .spawn(move || {
-                let mut core = RecorderCore::default();
-                while let Ok(cmd) = rx.recv() {
-                    match cmd {
-                        RecorderCommand::Start { device_id, respond } => {
-                            let _ = respond.send(core.start(device_id));
+                let mut core = RecorderCore {
+                    active: None,
+                    suspended: None,
+                    reconnect_grace: None,
+                    amplitude: core_amplitude,
+                    device_subscribers: core_device_subscribers,
+                    language_hint: core_language_hint,
+                    trim_mode: core_trim_mode,
+                    recording_started_at: core_recording_started_at,
+                };
+                loop {
+                    match rx.recv_timeout(DEVICE_POLL_INTERVAL) {
+                        Ok(RecorderCommand::Start {
+                            device_id,
+                            reconnect_grace,
+                            respond,
+                        }) => {
+                            let _ = respond.send(core.start(device_id, reconnect_grace));
                         }
-                        RecorderCommand::Stop { respond } => {
+                        Ok(RecorderCommand::Stop { respond }) => {
                             let _ = respond.send(core.stop());
                         }
+                        Ok(RecorderCommand::Drain { respond }) => {
+                            let _ = respond.send(core.drain());
+                        }
+                        Err(RecvTimeoutError::Timeout) => core.device_poll(),
+                        Err(RecvTimeoutError::Disconnected) => break,
                     }
                 }
             })
             .expect("failed to spawn recorder thread");
 
-        Self { tx }
+        Self {
+            tx,
+            amplitude,
+            device_subscribers,
+            language_hint,
+            trim_mode,
+            recording_started_at,
+        }
+    }
+
+    /// Receive input device changes until the current recording stops.
+    pub fn subscribe_device_events(&self) -> Receiver<DeviceEvent> {
+        let (tx, rx) = unbounded();
+        self.device_subscribers.lock().push(tx);
+        rx
+    }
+
+    /// Language of the upcoming speech, used to pick filter cutoffs when recording stops.
+    pub fn set_language_hint(&self, language: Option<String>) {
+        *self.language_hint.lock() = language;
     }
 
-    pub fn start(&self, device_id: Option<String>) -> Result<DateTime<Local>> {
+    /// Silence trimming applied when the current recording stops.
+    pub fn set_trim_mode(&self, mode: TrimMode) {
+        *self.trim_mode.lock() = mode;
+    }
+
+    /// Receive the RMS of each captured input buffer until the current recording stops.
+    pub fn subscribe_amplitude(&self) -> Receiver<f32> {
+        let (tx, rx) = bounded(64);
+        self.amplitude.subscribers.lock().push(tx);
+        rx
+    }
+
+    /// Milliseconds since the current recording started, or `None` when not recording.
+    pub fn get_recording_duration_ms(&self) -> Option<i64> {
+        self.recording_started_at
+            .lock()
+            .map(|started_at| (Local::now() - started_at).num_milliseconds())
+    }
+
+    /// RMS of the most recently captured input buffer, or 0 when not recording.
+    pub fn current_rms(&self) -> f32 {
+        f32::from_bits(self.amplitude.latest_rms.load(Ordering::Relaxed))
+    }
+
+    /// Start capturing. If the device drops out, the recording resumes when it comes back
+    /// within `reconnect_grace`; `None` gives up immediately.
+    pub fn start(
+        &self,
+        device_id: Option<String>,
+        reconnect_grace: Option<Duration>,
+    ) -> Result<DateTime<Local>> {
         let (respond_tx, respond_rx) = bounded(1);
         self.tx
             .send(RecorderCommand::Start {
                 device_id,
+                reconnect_grace,
                 respond: respond_tx,
             })
             .map_err(|err| anyhow!("Recorder channel closed: {err}"))?;
@@ -96,26 +293,54 @@ impl RecorderManager {
             .recv()
             .map_err(|err| anyhow!("Recorder not responding: {err}"))?
     }
+
+    /// Take the audio captured so far as a finished recording while capture carries on
+    /// into an empty buffer. Subscriptions and the elapsed recording time are kept.
+    pub fn drain(&self) -> Result<Option<CompletedRecording>> {
+        let (respond_tx, respond_rx) = bounded(1);
+        self.tx
+            .send(RecorderCommand::Drain {
+                respond: respond_tx,
+            })
+            .map_err(|err| anyhow!("Recorder channel closed: {err}"))?;
+        respond_rx
+            .recv()
+            .map_err(|err| anyhow!("Recorder not responding: {err}"))?
+    }
 }
 
 enum RecorderCommand {
     Start {
         device_id: Option<String>,
+        reconnect_grace: Option<Duration>,
         respond: Sender<Result<DateTime<Local>>>,
     },
     Stop {
         respond: Sender<Result<Option<CompletedRecording>>>,
     },
+    Drain {
+        respond: Sender<Result<Option<CompletedRecording>>>,
+    },
 }
 
-#[derive(Default)]
 struct RecorderCore {
     active: Option<ActiveRecording>,
+    suspended: Option<SuspendedRecording>,
+    reconnect_grace: Option<Duration>,
+    amplitude: Arc<AmplitudeTap>,
+    device_subscribers: Arc<Mutex<Vec<Sender<DeviceEvent>>>>,
+    language_hint: Arc<Mutex<Option<String>>>,
+    trim_mode: Arc<Mutex<TrimMode>>,
+    recording_started_at: Arc<Mutex<Option<DateTime<Local>>>>,
 }
 
 impl RecorderCore {
-    fn start(&mut self, device_id: Option<String>) -> Result<DateTime<Local>> {
-        if self.active.is_some() {
+    fn start(
+        &mut self,
+        device_id: Option<String>,
+        reconnect_grace: Option<Duration>,
+    ) -> Result<DateTime<Local>> {
+        if self.active.is_some() || self.suspended.is_some() {
             return Err(anyhow!("Recording is already in progress"));
         }
 
@@ -130,100 +355,324 @@ impl RecorderCore {
             host.default_input_device()
                 .context("No default input device found")?
         };
-        let config = device
-            .default_input_config()
-            .context("No supported input configuration found")?;
-        let format = config.sample_format();
-        let stream_config: cpal::StreamConfig = config.clone().into();
-        let sample_rate = stream_config.sample_rate.0;
-        let channels = stream_config.channels;
-
-        let buffer = Arc::new(Mutex::new(Vec::with_capacity(
-            (sample_rate as usize * channels as usize).max(48_000),
-        )));
-        let buffer_ref = buffer.clone();
-
-        let err_fn = |err| {
-            eprintln!("Microphone stream error: {err}");
-        };
+        let device_name = device.name().unwrap_or_default();
 
-        let stream = match format {
-            SampleFormat::F32 => device.build_input_stream(
-                &stream_config,
-                move |data: &[f32], _| push_f32_samples(data, &buffer_ref),
-                err_fn,
-                None,
-            )?,
-            SampleFormat::I16 => device.build_input_stream(
-                &stream_config,
-                move |data: &[i16], _| push_i16_samples(data, &buffer_ref),
-                err_fn,
-                None,
-            )?,
-            SampleFormat::U16 => device.build_input_stream(
-                &stream_config,
-                move |data: &[u16], _| push_u16_samples(data, &buffer_ref),
-                err_fn,
-                None,
-            )?,
-            _ => return Err(anyhow!("Unsupported sample format")),
-        };
+        let buffer = Arc::new(Mutex::new(Vec::new()));
+        let (stream, sample_rate, channels) =
+            open_input_stream(&device, buffer.clone(), self.amplitude.clone())?;
+        buffer
+            .lock()
+            .reserve(buffer_capacity(sample_rate, channels));
 
         stream.play()?;
 
         let started_at = Local::now();
+        *self.recording_started_at.lock() = Some(started_at);
+        self.reconnect_grace = reconnect_grace;
         self.active = Some(ActiveRecording {
             stream,
             buffer,
             sample_rate,
             channels,
             started_at,
+            device_name,
         });
 
         Ok(started_at)
     }
 
     fn stop(&mut self) -> Result<Option<CompletedRecording>> {
-        if let Some(active) = self.active.take() {
-            drop(active.stream);
-            let raw_samples = Arc::try_unwrap(active.buffer)
-                .map(|mutex| mutex.into_inner())
-                .unwrap_or_else(|arc| arc.lock().clone());
+        // Dropping the senders disconnects every amplitude and device receiver.
+        self.amplitude.subscribers.lock().clear();
+        self.amplitude.latest_rms.store(0, Ordering::Relaxed);
+        self.device_subscribers.lock().clear();
+        *self.recording_started_at.lock() = None;
+
+        let (raw_samples, sample_rate, channels, started_at) =
+            if let Some(active) = self.active.take() {
+                drop(active.stream);
+                let raw_samples = Arc::try_unwrap(active.buffer)
+                    .map(|mutex| mutex.into_inner())
+                    .unwrap_or_else(|arc| arc.lock().clone());
+                (
+                    raw_samples,
+                    active.sample_rate,
+                    active.channels,
+                    active.started_at,
+                )
+            } else if let Some(suspended) = self.suspended.take() {
+                (
+                    suspended.samples,
+                    suspended.sample_rate,
+                    suspended.channels,
+                    suspended.started_at,
+                )
+            } else {
+                return Ok(None);
+            };
 
-            let mut mono = samples_to_mono_f32(&raw_samples, active.channels as usize);
-            if mono.is_empty() {
-                return Ok(Some(CompletedRecording {
-                    samples: raw_samples,
-                    sample_rate: active.sample_rate,
-                    channels: active.channels,
-                    started_at: active.started_at,
-                    ended_at: Local::now(),
-                }));
-            }
+        let recording = self.finish(raw_samples, sample_rate, channels, started_at);
+        Ok(Some(recording))
+    }
 
-            apply_filters(&mut mono, active.sample_rate);
-            let trimmed = trim_silence(&mono, active.sample_rate);
-            let mut processed = if trimmed.is_empty() { mono } else { trimmed };
+    /// Hand over the audio captured so far and keep recording into an empty buffer.
+    fn drain(&mut self) -> Result<Option<CompletedRecording>> {
+        let now = Local::now();
+        let drained = if let Some(active) = self.active.as_mut() {
+            let fresh = Vec::with_capacity(buffer_capacity(active.sample_rate, active.channels));
+            (
+                std::mem::replace(&mut *active.buffer.lock(), fresh),
+                active.sample_rate,
+                active.channels,
+                std::mem::replace(&mut active.started_at, now),
+            )
+        } else if let Some(suspended) = self.suspended.as_mut() {
+            (
+                std::mem::take(&mut suspended.samples),
+                suspended.sample_rate,
+                suspended.channels,
+                std::mem::replace(&mut suspended.started_at, now),
+            )
+        } else {
+            return Ok(None);
+        };
 
-            apply_compression(&mut processed);
-            apply_frame_normalization(&mut processed, active.sample_rate);
+        let (raw_samples, sample_rate, channels, started_at) = drained;
+        let recording = self.finish(raw_samples, sample_rate, channels, started_at);
+        Ok(Some(recording))
+    }
 
-            let samples: Vec<i16> = processed
-                .into_iter()
-                .map(|sample| (sample.clamp(-1.0, 1.0) * i16::MAX as f32).round() as i16)
-                .collect();
+    /// Filter, trim and level captured audio into a mono recording ending now.
+    fn finish(
+        &self,
+        raw_samples: Vec<i16>,
+        sample_rate: u32,
+        channels: u16,
+        started_at: DateTime<Local>,
+    ) -> CompletedRecording {
+        let mut mono = samples_to_mono_f32(&raw_samples, channels as usize);
+        if mono.is_empty() {
+            return CompletedRecording {
+                samples: raw_samples,
+                sample_rate,
+                channels,
+                started_at,
+                ended_at: Local::now(),
+            };
+        }
+
+        let profile = filter_profile_for_language(self.language_hint.lock().as_deref());
+        apply_filters(&mut mono, sample_rate, profile);
+        let trimmed = trim_silence(&mono, sample_rate, *self.trim_mode.lock());
+        let mut processed = if trimmed.is_empty() { mono } else { trimmed };
+
+        apply_compression(&mut processed);
+        apply_frame_normalization(&mut processed, sample_rate);
+
+        let samples: Vec<i16> = processed
+            .into_iter()
+            .map(|sample| (sample.clamp(-1.0, 1.0) * i16::MAX as f32).round() as i16)
+            .collect();
 
-            Ok(Some(CompletedRecording {
+        CompletedRecording {
+            samples,
+            sample_rate,
+            channels: 1,
+            started_at,
+            ended_at: Local::now(),
+        }
+    }
+
+    /// Suspend the recording when its device disappears and resume it if the device
+    /// returns within the grace period.
+    fn device_poll(&mut self) {
+        if let Some(active) = &self.active {
+            if find_input_device(&active.device_name).is_some() {
+                return;
+            }
+
+            let Some(active) = self.active.take() else {
+                return;
+            };
+            drop(active.stream);
+            warn!(device = %active.device_name, "Input device disconnected");
+            let samples = Arc::try_unwrap(active.buffer)
+                .map(|mutex| mutex.into_inner())
+                .unwrap_or_else(|arc| arc.lock().clone());
+            self.amplitude.latest_rms.store(0, Ordering::Relaxed);
+            self.notify_device_event(DeviceEvent::Disconnected {
+                device: active.device_name.clone(),
+            });
+            self.suspended = Some(SuspendedRecording {
                 samples,
                 sample_rate: active.sample_rate,
-                channels: 1,
+                channels: active.channels,
                 started_at: active.started_at,
-                ended_at: Local::now(),
-            }))
+                device_name: active.device_name,
+                lost_at: Instant::now(),
+                gave_up: false,
+            });
+            return;
+        }
+
+        let Some(suspended) = &self.suspended else {
+            return;
+        };
+        if suspended.gave_up {
+            return;
+        }
+
+        let within_grace = self
+            .reconnect_grace
+            .is_some_and(|grace| suspended.lost_at.elapsed() <= grace);
+        let resumed = if within_grace {
+            match find_input_device(&suspended.device_name) {
+                Some(device) => self.resume(&device),
+                None => return,
+            }
         } else {
-            Ok(None)
+            Err(anyhow!("grace period elapsed"))
+        };
+
+        match resumed {
+            Ok(device) => {
+                info!(device = %device, "Input device reconnected, recording resumed");
+                self.notify_device_event(DeviceEvent::Reconnected { device });
+            }
+            Err(err) => {
+                let Some(suspended) = self.suspended.as_mut() else {
+                    return;
+                };
+                warn!(
+                    device = %suspended.device_name,
+                    error = %err,
+                    "Could not resume recording"
+                );
+                suspended.gave_up = true;
+                let device = suspended.device_name.clone();
+                self.notify_device_event(DeviceEvent::ReconnectFailed { device });
+            }
         }
     }
+
+    /// Reopen the suspended recording on `device`, appending to the audio captured so far.
+    fn resume(&mut self, device: &cpal::Device) -> Result<String> {
+        let Some(suspended) = self.suspended.take() else {
+            return Err(anyhow!("No suspended recording"));
+        };
+
+        let buffer = Arc::new(Mutex::new(Vec::new()));
+        let opened = open_input_stream(device, buffer.clone(), self.amplitude.clone());
+        let opened = opened.and_then(|(stream, sample_rate, channels)| {
+            if sample_rate != suspended.sample_rate || channels != suspended.channels {
+                return Err(anyhow!(
+                    "Device format changed ({sample_rate} Hz, {channels} ch)"
+                ));
+            }
+            stream.play()?;
+            Ok(stream)
+        });
+
+        let stream = match opened {
+            Ok(stream) => stream,
+            Err(err) => {
+                self.suspended = Some(suspended);
+                return Err(err);
+            }
+        };
+
+        // Anything captured between play() and now lands after the earlier audio.
+        {
+            let mut samples = buffer.lock();
+            let captured = std::mem::replace(&mut *samples, suspended.samples);
+            samples.extend(captured);
+        }
+
+        let device_name = suspended.device_name.clone();
+        self.active = Some(ActiveRecording {
+            stream,
+            buffer,
+            sample_rate: suspended.sample_rate,
+            channels: suspended.channels,
+            started_at: suspended.started_at,
+            device_name: suspended.device_name,
+        });
+        Ok(device_name)
+    }
+
+    fn notify_device_event(&self, event: DeviceEvent) {
+        self.device_subscribers
+            .lock()
+            .retain(|subscriber| subscriber.send(event.clone()).is_ok());
+    }
+}
+
+/// Room for about a second of audio, so capture doesn't reallocate right away.
+fn buffer_capacity(sample_rate: u32, channels: u16) -> usize {
+    (sample_rate as usize * channels as usize).max(48_000)
+}
+
+fn find_input_device(name: &str) -> Option<cpal::Device> {
+    cpal::default_host()
+        .input_devices()
+        .ok()?
+        .find(|device| device.name().map(|n| n == name).unwrap_or(false))
+}
+
+fn open_input_stream(
+    device: &cpal::Device,
+    buffer: Arc<Mutex<Vec<i16>>>,
+    amplitude: Arc<AmplitudeTap>,
+) -> Result<(Stream, u32, u16)> {
+    let config = device
+        .default_input_config()
+        .context("No supported input configuration found")?;
+    let format = config.sample_format();
+    let stream_config: cpal::StreamConfig = config.clone().into();
+    let sample_rate = stream_config.sample_rate.0;
+    let channels = stream_config.channels;
+
+    let err_fn = |err| {
+        error!("Microphone stream error: {err}");
+    };
+
+    let stream = match format {
+        SampleFormat::F32 => device.build_input_stream(
+            &stream_config,
+            move |data: &[f32], _| {
+                push_f32_samples(data, &buffer);
+                publish_amplitude(&amplitude, calculate_rms(data));
+            },
+            err_fn,
+            None,
+        )?,
+        SampleFormat::I16 => device.build_input_stream(
+            &stream_config,
+            move |data: &[i16], _| {
+                push_i16_samples(data, &buffer);
+                let rms = rms_of(data.iter().map(|&s| s as f32 / i16::MAX as f32));
+                publish_amplitude(&amplitude, rms);
+            },
+            err_fn,
+            None,
+        )?,
+        SampleFormat::U16 => device.build_input_stream(
+            &stream_config,
+            move |data: &[u16], _| {
+                push_u16_samples(data, &buffer);
+                let rms = rms_of(
+                    data.iter()
+                        .map(|&s| (s as i32 - i16::MAX as i32) as f32 / i16::MAX as f32),
+                );
+                publish_amplitude(&amplitude, rms);
+            },
+            err_fn,
+            None,
+        )?,
+        _ => return Err(anyhow!("Unsupported sample format")),
+    };
+
+    Ok((stream, sample_rate, channels))
 }
 
 /// Configuration for recording validation
@@ -234,6 +683,8 @@ pub struct ValidationConfig {
     pub min_rms_energy: f32,
     /// Minimum percentage of frames that must contain speech (default: 5%)
     pub min_speech_percentage: f32,
+    /// Maximum spectral flatness before the audio counts as noise (default: 0.85)
+    pub max_spectral_flatness: f32,
 }
 
 impl Default for ValidationConfig {
@@ -242,19 +693,26 @@ impl Default for ValidationConfig {
             min_duration_ms: 300,
             min_rms_energy: 0.0003,
             min_speech_percentage: 5.0,
+            max_spectral_flatness: 0.85,
         }
     }
 }
 
-/// Validates if a recording contains meaningful audio worth transcribing.
-/// Returns Ok(()) if valid, or Err with the rejection reason.
-pub fn validate_recording(recording: &CompletedRecording) -> Result<(), RecordingRejectionReason> {
-    validate_recording_with_config(recording, &ValidationConfig::default())
+/// A recording's samples scaled to -1.0..=1.0, the form every analysis pass works on.
+pub fn normalized_samples(samples: &[i16]) -> Vec<f32> {
+    samples
+        .iter()
+        .map(|sample| *sample as f32 / i16::MAX as f32)
+        .collect()
 }
 
-/// Validates a recording with custom configuration.
+/// Validates if a recording contains meaningful audio worth transcribing.
+/// `samples_f32` is the recording's [`normalized_samples`], so callers that analyze it
+/// further only convert it once.
+/// Returns Ok(()) if valid, or Err with the rejection reason.
 pub fn validate_recording_with_config(
     recording: &CompletedRecording,
+    samples_f32: &[f32],
     config: &ValidationConfig,
 ) -> Result<(), RecordingRejectionReason> {
     // Check 1: Empty buffer
@@ -271,15 +729,8 @@ pub fn validate_recording_with_config(
         });
     }
 
-    // Convert to f32 for analysis
-    let samples_f32: Vec<f32> = recording
-        .samples
-        .iter()
-        .map(|s| *s as f32 / i16::MAX as f32)
-        .collect();
-
     // Check 3: RMS energy level (catches silence/very quiet recordings)
-    let rms = calculate_rms(&samples_f32);
+    let rms = calculate_rms(samples_f32);
     if rms < config.min_rms_energy {
         return Err(RecordingRejectionReason::TooQuiet {
             rms,
@@ -288,21 +739,131 @@ pub fn validate_recording_with_config(
     }
 
     // Check 4: Voice Activity Detection - ensure at least some speech is present
-    let speech_percentage = calculate_speech_percentage(&samples_f32, recording.sample_rate);
+    let speech_percentage = calculate_speech_percentage(samples_f32, recording.sample_rate);
     if speech_percentage < config.min_speech_percentage {
         return Err(RecordingRejectionReason::NoSpeechDetected);
     }
 
+    // Check 5: Spectral flatness - VAD can mistake steady noise for speech
+    let spectral_flatness = calculate_spectral_flatness(samples_f32);
+    if spectral_flatness > config.max_spectral_flatness {
+        return Err(RecordingRejectionReason::BackgroundNoise { spectral_flatness });
+    }
+
     Ok(())
 }
 
 /// Calculate Root Mean Square energy of audio samples
 fn calculate_rms(samples: &[f32]) -> f32 {
-    if samples.is_empty() {
+    rms_of(samples.iter().copied())
+}
+
+/// RMS of samples converted on the fly, so the input callbacks never allocate.
+fn rms_of(samples: impl ExactSizeIterator<Item = f32>) -> f32 {
+    let len = samples.len();
+    if len == 0 {
+        return 0.0;
+    }
+    let sum_squares: f32 = samples.map(|s| s * s).sum();
+    (sum_squares / len as f32).sqrt()
+}
+
+/// Level reported for digital silence, which has no finite dB value.
+pub const SILENCE_DB: f32 = -100.0;
+
+/// RMS level of audio samples in dBFS, no lower than [`SILENCE_DB`].
+pub fn calculate_rms_db(samples: &[f32]) -> f32 {
+    let rms = calculate_rms(samples);
+    if rms > 0.0 {
+        (20.0 * rms.log10()).max(SILENCE_DB)
+    } else {
+        SILENCE_DB
+    }
+}
+
+/// Samples per spectrum frame; a power of two for the FFT.
+const FLATNESS_FRAME_LEN: usize = 512;
+/// Frames averaged into the spectrum, spread evenly over the recording.
+const FLATNESS_MAX_FRAMES: usize = 64;
+
+/// Calculate spectral flatness (Wiener entropy) of the average power spectrum: its
+/// geometric mean over its arithmetic mean. Near 1 for broadband noise, near 0 for
+/// tonal sound such as voiced speech.
+fn calculate_spectral_flatness(samples: &[f32]) -> f32 {
+    let frame_count = samples.len() / FLATNESS_FRAME_LEN;
+    if frame_count == 0 {
         return 0.0;
     }
-    let sum_squares: f32 = samples.iter().map(|s| s * s).sum();
-    (sum_squares / samples.len() as f32).sqrt()
+
+    let window: Vec<f32> = (0..FLATNESS_FRAME_LEN)
+        .map(|i| 0.5 - 0.5 * (2.0 * PI * i as f32 / FLATNESS_FRAME_LEN as f32).cos())
+        .collect();
+    let bins = FLATNESS_FRAME_LEN / 2;
+    let mut power = vec![0.0f32; bins];
+    let mut re = vec![0.0f32; FLATNESS_FRAME_LEN];
+    let mut im = vec![0.0f32; FLATNESS_FRAME_LEN];
+
+    let step = frame_count.div_ceil(FLATNESS_MAX_FRAMES);
+    for frame in samples.chunks_exact(FLATNESS_FRAME_LEN).step_by(step) {
+        for ((re, im), (sample, weight)) in re
+            .iter_mut()
+            .zip(im.iter_mut())
+            .zip(frame.iter().zip(&window))
+        {
+            *re = sample * weight;
+            *im = 0.0;
+        }
+        fft(&mut re, &mut im);
+        // Bin 0 is the DC offset, not sound
+        for (bin, total) in power.iter_mut().enumerate().skip(1) {
+            *total += re[bin] * re[bin] + im[bin] * im[bin];
+        }
+    }
+
+    let power = &power[1..];
+    let arithmetic_mean = power.iter().sum::<f32>() / power.len() as f32;
+    if arithmetic_mean <= 0.0 {
+        return 0.0;
+    }
+    let log_mean = power.iter().map(|p| (p + 1e-12).ln()).sum::<f32>() / power.len() as f32;
+    (log_mean.exp() / arithmetic_mean).min(1.0)
+}
+
+/// In-place iterative radix-2 FFT. Both slices must have the same power-of-two length.
+pub(crate) fn fft(re: &mut [f32], im: &mut [f32]) {
+    let n = re.len();
+
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= n {
+        let angle = -2.0 * PI / len as f32;
+        for start in (0..n).step_by(len) {
+            for k in 0..len / 2 {
+                let (sin, cos) = (angle * k as f32).sin_cos();
+                let (a, b) = (start + k, start + k + len / 2);
+                let tr = re[b] * cos - im[b] * sin;
+                let ti = re[b] * sin + im[b] * cos;
+                re[b] = re[a] - tr;
+                im[b] = im[a] - ti;
+                re[a] += tr;
+                im[a] += ti;
+            }
+        }
+        len <<= 1;
+    }
 }
 
 /// Calculate percentage of frames containing speech using VAD
@@ -366,6 +927,7 @@ fn calculate_speech_percentage(samples: &[f32], sample_rate: u32) -> f32 {
 pub fn persist_recording(
     base_dir: PathBuf,
     recording: CompletedRecording,
+    bitrate_kbps: u32,
 ) -> Result<RecordingSaved> {
     if recording.samples.is_empty() {
         return Err(anyhow!("Recording buffer is empty"));
@@ -383,6 +945,7 @@ pub fn persist_recording(
         &recording.samples,
         recording.sample_rate,
         recording.channels,
+        bitrate_kbps,
     )?;
     fs::write(&file_path, mp3_bytes)
         .with_context(|| format!("Failed to write recording file at {}", file_path.display()))?;
@@ -394,7 +957,23 @@ pub fn persist_recording(
     })
 }
 
-fn encode_to_mp3(samples: &[i16], sample_rate: u32, channels: u16) -> Result<Vec<u8>> {
+fn lame_bitrate(kbps: u32) -> Result<Bitrate> {
+    match kbps {
+        32 => Ok(Bitrate::Kbps32),
+        48 => Ok(Bitrate::Kbps48),
+        64 => Ok(Bitrate::Kbps64),
+        96 => Ok(Bitrate::Kbps96),
+        128 => Ok(Bitrate::Kbps128),
+        _ => Err(anyhow!("Unsupported bitrate: {kbps} kbps")),
+    }
+}
+
+fn encode_to_mp3(
+    samples: &[i16],
+    sample_rate: u32,
+    channels: u16,
+    bitrate_kbps: u32,
+) -> Result<Vec<u8>> {
     // Minimum samples needed for MP3 encoding (at least one frame worth)
     // MP3 frames are typically 1152 samples for MPEG-1
     const MIN_SAMPLES: usize = 1152;
@@ -417,7 +996,7 @@ fn encode_to_mp3(samples: &[i16], sample_rate: u32, channels: u16) -> Result<Vec
         .set_num_channels(constrained_channels as u8)
         .map_err(|err| anyhow!("Invalid channel count: {err}"))?;
     builder
-        .set_brate(Bitrate::Kbps128)
+        .set_brate(lame_bitrate(bitrate_kbps)?)
         .map_err(|err| anyhow!("Failed to set bitrate: {err}"))?;
     builder
         .set_quality(Quality::VeryNice)
@@ -455,6 +1034,23 @@ fn encode_to_mp3(samples: &[i16], sample_rate: u32, channels: u16) -> Result<Vec
     Ok(output)
 }
 
+/// Write mono 16-bit PCM samples to a WAV file.
+pub fn write_wav(path: &Path, samples: &[i16], sample_rate: u32) -> Result<()> {
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut writer = hound::WavWriter::create(path, spec)
+        .with_context(|| format!("Failed to create WAV file at {}", path.display()))?;
+    for &sample in samples {
+        writer.write_sample(sample)?;
+    }
+    writer.finalize().context("Failed to finalize WAV file")?;
+    Ok(())
+}
+
 fn samples_to_mono_f32(samples: &[i16], channels: usize) -> Vec<f32> {
     if samples.is_empty() {
         return Vec::new();
@@ -482,9 +1078,9 @@ fn samples_to_mono_f32(samples: &[i16], channels: usize) -> Vec<f32> {
     mono
 }
 
-fn apply_filters(samples: &mut [f32], sample_rate: u32) {
-    apply_high_pass(samples, sample_rate, 120.0);
-    apply_low_pass(samples, sample_rate, 8_000.0);
+fn apply_filters(samples: &mut [f32], sample_rate: u32, profile: LanguageFilterProfile) {
+    apply_high_pass(samples, sample_rate, profile.high_pass_hz);
+    apply_low_pass(samples, sample_rate, profile.low_pass_hz);
 }
 
 fn apply_high_pass(samples: &mut [f32], sample_rate: u32, cutoff: f32) {
@@ -569,10 +1165,24 @@ fn apply_frame_normalization(samples: &mut [f32], sample_rate: u32) {
     }
 }
 
-fn trim_silence(samples: &[f32], sample_rate: u32) -> Vec<f32> {
+/// `(hang_ms, min_gap_ms, pre_roll_frames)` for a trim mode: how long to keep going after
+/// speech stops, the longest pause restored in full, and frames kept ahead of speech.
+fn trim_params(mode: TrimMode) -> Option<(f32, f32, usize)> {
+    match mode {
+        TrimMode::Conservative => Some((500.0, 900.0, 6)),
+        TrimMode::Balanced => Some((350.0, 600.0, 4)),
+        TrimMode::Aggressive => Some((200.0, 300.0, 2)),
+        TrimMode::Disabled => None,
+    }
+}
+
+fn trim_silence(samples: &[f32], sample_rate: u32, mode: TrimMode) -> Vec<f32> {
     if samples.is_empty() {
         return Vec::new();
     }
+    let Some(params) = trim_params(mode) else {
+        return samples.to_vec();
+    };
 
     let vad_rate = match sample_rate {
         8000 | 16000 | 32000 | 48000 => sample_rate,
@@ -618,10 +1228,56 @@ fn trim_silence(samples: &[f32], sample_rate: u32) -> Vec<f32> {
         return samples.to_vec();
     }
 
-    let hang_duration_ms = 350f32;
-    let hang_frames = ((hang_duration_ms / frame_ms as f32).ceil()) as usize;
-    let pre_roll = 4usize;
-    let min_gap_ms = 600f32;
+    let keep_mask = speech_keep_mask(&speech_frames, frame_ms, params);
+
+    let samples_per_vad_sample = sample_rate as f32 / vad_rate as f32;
+    let mut intervals: Vec<(usize, usize)> = Vec::new();
+    let mut current: Option<(usize, usize)> = None;
+    for (idx, keep) in keep_mask.iter().enumerate() {
+        let start = ((idx * frame_len) as f32 * samples_per_vad_sample) as usize;
+        let end = (((idx + 1) * frame_len) as f32 * samples_per_vad_sample).ceil() as usize;
+        if *keep {
+            if let Some(interval) = current.as_mut() {
+                interval.1 = end;
+            } else {
+                current = Some((start, end));
+            }
+        } else if let Some(interval) = current.take() {
+            intervals.push(interval);
+        }
+    }
+    if let Some(interval) = current.take() {
+        intervals.push(interval);
+    }
+
+    if intervals.is_empty() {
+        return samples.to_vec();
+    }
+
+    let mut output = Vec::new();
+    for (start, end) in intervals {
+        let clamped_start = start.min(samples.len());
+        let clamped_end = end.min(samples.len());
+        if clamped_start < clamped_end {
+            output.extend_from_slice(&samples[clamped_start..clamped_end]);
+        }
+    }
+
+    if output.is_empty() {
+        samples.to_vec()
+    } else {
+        output
+    }
+}
+
+/// Which VAD frames to keep: speech, `hang_ms` after it, pauses up to `min_gap_ms`, and
+/// `pre_roll` frames ahead of anything kept.
+fn speech_keep_mask(
+    speech_frames: &[bool],
+    frame_ms: usize,
+    (hang_ms, min_gap_ms, pre_roll): (f32, f32, usize),
+) -> Vec<bool> {
+    let hang_frames = ((hang_ms / frame_ms as f32).ceil()) as usize;
     let min_gap_frames = ((min_gap_ms / frame_ms as f32).ceil()) as usize;
     let mut keep_mask = vec![false; speech_frames.len()];
     let mut hang = 0usize;
@@ -666,44 +1322,7 @@ fn trim_silence(samples: &[f32], sample_rate: u32) -> Vec<f32> {
         }
     }
 
-    let samples_per_vad_sample = sample_rate as f32 / vad_rate as f32;
-    let mut intervals: Vec<(usize, usize)> = Vec::new();
-    let mut current: Option<(usize, usize)> = None;
-    for (idx, keep) in keep_mask.iter().enumerate() {
-        let start = ((idx * frame_len) as f32 * samples_per_vad_sample) as usize;
-        let end = (((idx + 1) * frame_len) as f32 * samples_per_vad_sample).ceil() as usize;
-        if *keep {
-            if let Some(interval) = current.as_mut() {
-                interval.1 = end;
-            } else {
-                current = Some((start, end));
-            }
-        } else if let Some(interval) = current.take() {
-            intervals.push(interval);
-        }
-    }
-    if let Some(interval) = current.take() {
-        intervals.push(interval);
-    }
-
-    if intervals.is_empty() {
-        return samples.to_vec();
-    }
-
-    let mut output = Vec::new();
-    for (start, end) in intervals {
-        let clamped_start = start.min(samples.len());
-        let clamped_end = end.min(samples.len());
-        if clamped_start < clamped_end {
-            output.extend_from_slice(&samples[clamped_start..clamped_end]);
-        }
-    }
-
-    if output.is_empty() {
-        samples.to_vec()
-    } else {
-        output
-    }
+    keep_mask
 }
 
 fn resample_linear(input: &[f32], in_rate: u32, out_rate: u32) -> Vec<f32> {
@@ -732,6 +1351,15 @@ fn resample_linear(input: &[f32], in_rate: u32, out_rate: u32) -> Vec<f32> {
     output
 }
 
+fn publish_amplitude(amplitude: &AmplitudeTap, rms: f32) {
+    amplitude.latest_rms.store(rms.to_bits(), Ordering::Relaxed);
+    let mut subscribers = amplitude.subscribers.lock();
+    if subscribers.is_empty() {
+        return;
+    }
+    subscribers.retain(|tx| !matches!(tx.try_send(rms), Err(TrySendError::Disconnected(_))));
+}
+
 fn push_f32_samples(data: &[f32], buffer: &Arc<Mutex<Vec<i16>>>) {
     let mut writer = buffer.lock();
     for &sample in data {
@@ -770,3 +1398,198 @@ fn downmix_to_mono(samples: &[i16], channels: usize) -> Vec<i16> {
     }
     mono
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_wav_roundtrip() {
+        let sample_rate = 16_000;
+        let samples: Vec<i16> = (0..sample_rate)
+            .map(|i| ((i as f32 * 440.0 * 2.0 * PI / sample_rate as f32).sin() * 8000.0) as i16)
+            .collect();
+        let path = std::env::temp_dir().join(format!("glimpse-wav-{}.wav", std::process::id()));
+
+        write_wav(&path, &samples, sample_rate).unwrap();
+
+        let mut reader = hound::WavReader::open(&path).unwrap();
+        assert_eq!(reader.spec().sample_rate, sample_rate);
+        assert_eq!(reader.spec().channels, 1);
+        let decoded: Vec<i16> = reader.samples::<i16>().map(|s| s.unwrap()).collect();
+        assert_eq!(decoded.len(), samples.len());
+        assert_eq!(decoded, samples);
+
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_lower_bitrate_encodes_smaller() {
+        let sample_rate = 48_000;
+        let samples: Vec<i16> = (0..sample_rate * 2)
+            .map(|i| ((i as f32 * 220.0 * 2.0 * PI / sample_rate as f32).sin() * 8000.0) as i16)
+            .collect();
+
+        let low = encode_to_mp3(&samples, sample_rate, 1, 32).unwrap();
+        let high = encode_to_mp3(&samples, sample_rate, 1, 128).unwrap();
+        assert!(low.len() < high.len());
+        assert!(encode_to_mp3(&samples, sample_rate, 1, 100).is_err());
+    }
+
+    fn white_noise(len: usize) -> Vec<f32> {
+        // Deterministic LCG so the test doesn't depend on a random seed
+        let mut state = 0x2545_f491_u32;
+        (0..len)
+            .map(|_| {
+                state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                (state >> 8) as f32 / (1 << 24) as f32 * 2.0 - 1.0
+            })
+            .collect()
+    }
+
+    fn tone(len: usize, sample_rate: u32) -> Vec<f32> {
+        (0..len)
+            .map(|i| (i as f32 * 440.0 * 2.0 * PI / sample_rate as f32).sin() * 0.5)
+            .collect()
+    }
+
+    fn recording_of(samples: &[f32], sample_rate: u32) -> CompletedRecording {
+        let ended_at = Local::now();
+        let duration_ms = samples.len() as i64 * 1000 / i64::from(sample_rate);
+        CompletedRecording {
+            samples: samples
+                .iter()
+                .map(|s| (s * i16::MAX as f32) as i16)
+                .collect(),
+            sample_rate,
+            channels: 1,
+            started_at: ended_at - chrono::Duration::milliseconds(duration_ms),
+            ended_at,
+        }
+    }
+
+    #[test]
+    fn test_calculate_rms_db() {
+        assert!(calculate_rms_db(&[1.0, -1.0, 1.0, -1.0]).abs() < 1e-4);
+        assert!((calculate_rms_db(&[0.1, -0.1]) + 20.0).abs() < 1e-4);
+        assert_eq!(calculate_rms_db(&[0.0; 64]), SILENCE_DB);
+        assert_eq!(calculate_rms_db(&[]), SILENCE_DB);
+    }
+
+    #[test]
+    fn test_spectral_flatness() {
+        let sample_rate = 16_000;
+        let noise = calculate_spectral_flatness(&white_noise(sample_rate as usize));
+        let tone = calculate_spectral_flatness(&tone(sample_rate as usize, sample_rate));
+        assert!(noise > 0.9, "white noise flatness {noise}");
+        assert!(tone < 0.1, "tone flatness {tone}");
+
+        assert_eq!(calculate_spectral_flatness(&[]), 0.0);
+        assert_eq!(calculate_spectral_flatness(&[0.0; 2048]), 0.0);
+    }
+
+    #[test]
+    fn test_background_noise_is_rejected() {
+        let sample_rate = 16_000;
+        // Leave speech detection out so only the flatness check decides
+        let config = ValidationConfig {
+            min_speech_percentage: 0.0,
+            ..ValidationConfig::default()
+        };
+
+        let noise = recording_of(&white_noise(sample_rate as usize), sample_rate);
+        assert!(matches!(
+            validate_recording_with_config(&noise, &normalized_samples(&noise.samples), &config),
+            Err(RecordingRejectionReason::BackgroundNoise { spectral_flatness })
+                if spectral_flatness > config.max_spectral_flatness
+        ));
+
+        let tone = recording_of(&tone(sample_rate as usize, sample_rate), sample_rate);
+        assert!(
+            validate_recording_with_config(&tone, &normalized_samples(&tone.samples), &config)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_filter_profile_for_language() {
+        assert_eq!(filter_profile_for_language(None), DEFAULT_FILTER_PROFILE);
+        for unknown in ["en", "xx", "auto"] {
+            assert_eq!(
+                filter_profile_for_language(Some(unknown)),
+                DEFAULT_FILTER_PROFILE
+            );
+        }
+
+        let mandarin = filter_profile_for_language(Some("zh"));
+        assert_eq!(mandarin.high_pass_hz, 80.0);
+        assert_eq!(filter_profile_for_language(Some("zh-CN")), mandarin);
+
+        let arabic = filter_profile_for_language(Some("AR"));
+        assert_eq!(arabic.low_pass_hz, 10_000.0);
+
+        let spanish = filter_profile_for_language(Some("es_MX"));
+        assert_eq!(spanish, profile(120.0, 9_000.0));
+    }
+
+    fn kept_frames(mode: TrimMode) -> usize {
+        let mut speech_frames = vec![false; 100];
+        speech_frames[10..15].fill(true);
+        speech_frames[55..60].fill(true);
+        let params = trim_params(mode).unwrap();
+        speech_keep_mask(&speech_frames, 30, params)
+            .iter()
+            .filter(|keep| **keep)
+            .count()
+    }
+
+    #[test]
+    fn test_trim_mode_frame_retention() {
+        assert_eq!(kept_frames(TrimMode::Conservative), 100);
+        assert_eq!(kept_frames(TrimMode::Balanced), 48);
+        assert_eq!(kept_frames(TrimMode::Aggressive), 36);
+    }
+
+    #[test]
+    fn test_trim_disabled_keeps_samples() {
+        let samples: Vec<f32> = (0..16_000).map(|i| (i % 7) as f32 * 0.01).collect();
+        assert_eq!(trim_silence(&samples, 16_000, TrimMode::Disabled), samples);
+    }
+
+    #[test]
+    fn test_drain_hands_over_audio_and_keeps_the_session() {
+        let session_start = Local::now() - chrono::Duration::seconds(10);
+        let chunk_start = Local::now() - chrono::Duration::seconds(1);
+        let mut core = RecorderCore {
+            active: None,
+            suspended: Some(SuspendedRecording {
+                samples: recording_of(&tone(16_000, 16_000), 16_000).samples,
+                sample_rate: 16_000,
+                channels: 1,
+                started_at: chunk_start,
+                device_name: "Mic".to_string(),
+                lost_at: Instant::now(),
+                gave_up: false,
+            }),
+            reconnect_grace: None,
+            amplitude: Arc::new(AmplitudeTap::default()),
+            device_subscribers: Arc::new(Mutex::new(Vec::new())),
+            language_hint: Arc::new(Mutex::new(None)),
+            trim_mode: Arc::new(Mutex::new(TrimMode::Disabled)),
+            recording_started_at: Arc::new(Mutex::new(Some(session_start))),
+        };
+
+        let chunk = core.drain().unwrap().unwrap();
+        assert_eq!(chunk.samples.len(), 16_000);
+        assert_eq!(chunk.started_at, chunk_start);
+
+        // The next chunk starts where this one ended, within the same session
+        let suspended = core.suspended.as_ref().unwrap();
+        assert!(suspended.samples.is_empty());
+        assert!(suspended.started_at >= chunk.started_at);
+        assert_eq!(*core.recording_started_at.lock(), Some(session_start));
+
+        assert!(core.stop().unwrap().unwrap().samples.is_empty());
+        assert!(core.drain().unwrap().is_none());
+    }
+}
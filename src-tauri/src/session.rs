@@ -0,0 +1,113 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use chrono::{DateTime, Local};
+use serde::Serialize;
+
+/// Oldest entries are dropped once the clipboard history holds this many.
+const MAX_CLIPBOARD_HISTORY: usize = 100;
+
+/// Running totals for the recordings made during a single app run.
+#[derive(Debug, Default)]
+pub struct RecordingSession {
+    pub recordings_count: u32,
+    pub total_audio_seconds: f32,
+    pub total_words: u32,
+    pub latency_samples: Vec<Duration>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionStats {
+    pub recordings_count: u32,
+    pub total_audio_seconds: f32,
+    pub total_words: u32,
+    pub average_latency_ms: Option<u64>,
+}
+
+impl RecordingSession {
+    pub fn record(&mut self, audio_seconds: f32, words: u32, latency: Duration) {
+        self.recordings_count += 1;
+        self.total_audio_seconds += audio_seconds;
+        self.total_words += words;
+        self.latency_samples.push(latency);
+    }
+
+    pub fn average_latency(&self) -> Option<Duration> {
+        if self.latency_samples.is_empty() {
+            return None;
+        }
+        let total: Duration = self.latency_samples.iter().sum();
+        Some(total / self.latency_samples.len() as u32)
+    }
+
+    pub fn snapshot(&self) -> SessionStats {
+        SessionStats {
+            recordings_count: self.recordings_count,
+            total_audio_seconds: self.total_audio_seconds,
+            total_words: self.total_words,
+            average_latency_ms: self
+                .average_latency()
+                .map(|latency| latency.as_millis() as u64),
+        }
+    }
+}
+
+/// A transcription Glimpse pasted during this app run.
+#[derive(Debug, Clone, Serialize)]
+pub struct ClipboardEntry {
+    pub text: String,
+    pub pasted_at: DateTime<Local>,
+    pub transcription_id: Option<String>,
+}
+
+/// What Glimpse pasted this session, newest last. Kept in memory only, and never includes
+/// anything the user copied themselves.
+#[derive(Debug, Default)]
+pub struct ClipboardHistory {
+    entries: VecDeque<ClipboardEntry>,
+}
+
+impl ClipboardHistory {
+    pub fn push(&mut self, entry: ClipboardEntry) {
+        if self.entries.len() == MAX_CLIPBOARD_HISTORY {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+    }
+
+    pub fn entries(&self) -> Vec<ClipboardEntry> {
+        self.entries.iter().cloned().collect()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clipboard_history_drops_oldest_past_cap() {
+        let mut history = ClipboardHistory::default();
+        for index in 0..MAX_CLIPBOARD_HISTORY + 5 {
+            history.push(ClipboardEntry {
+                text: format!("entry {index}"),
+                pasted_at: Local::now(),
+                transcription_id: None,
+            });
+        }
+
+        let entries = history.entries();
+        assert_eq!(entries.len(), MAX_CLIPBOARD_HISTORY);
+        assert_eq!(entries[0].text, "entry 5");
+        assert_eq!(
+            entries.last().unwrap().text,
+            format!("entry {}", MAX_CLIPBOARD_HISTORY + 4)
+        );
+
+        history.clear();
+        assert!(history.entries().is_empty());
+    }
+}
@@ -1,12 +1,21 @@
 use std::{fs, path::PathBuf};
 
-use anyhow::{Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
 use parking_lot::Mutex;
 use rusqlite::{params, Connection, OptionalExtension};
 use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, Manager};
 
+use tracing::{error, info, warn};
+
+use crate::keyboard::KeyboardLayout;
+use crate::recorder::ValidationConfig;
+use crate::shortcuts_display::ShortcutDisplayFormat;
+
 const SETTINGS_DB_FILE_NAME: &str = "settings.db";
+/// Magic header identifying an exported settings backup.
+const SETTINGS_EXPORT_MAGIC: &str = "glimpse-settings-v1";
 const KEY_ONBOARDING_COMPLETED: &str = "onboarding_completed";
 const KEY_SMART_SHORTCUT: &str = "smart_shortcut";
 const KEY_SMART_ENABLED: &str = "smart_enabled";
@@ -14,6 +23,8 @@ const KEY_HOLD_SHORTCUT: &str = "hold_shortcut";
 const KEY_HOLD_ENABLED: &str = "hold_enabled";
 const KEY_TOGGLE_SHORTCUT: &str = "toggle_shortcut";
 const KEY_TOGGLE_ENABLED: &str = "toggle_enabled";
+const KEY_COPY_LATEST_SHORTCUT: &str = "copy_latest_shortcut";
+const KEY_COPY_LATEST_ENABLED: &str = "copy_latest_enabled";
 const KEY_TRANSCRIPTION_MODE: &str = "transcription_mode";
 const KEY_LOCAL_MODEL: &str = "local_model";
 const KEY_MICROPHONE_DEVICE: &str = "microphone_device";
@@ -26,11 +37,74 @@ const KEY_LLM_MODEL: &str = "llm_model";
 const KEY_USER_CONTEXT: &str = "user_context";
 const KEY_DICTIONARY: &str = "dictionary";
 const KEY_REPLACEMENTS: &str = "replacements";
+const KEY_REPLACEMENT_PROFILES: &str = "replacement_profiles";
+const KEY_ACTIVE_REPLACEMENT_PROFILE_ID: &str = "active_replacement_profile_id";
+const KEY_FOCUS_MODE: &str = "focus_mode";
+const KEY_SHORTCUT_DISPLAY_FORMAT: &str = "shortcut_display_format";
+const KEY_LLM_CLEANUP_MIN_WORDS: &str = "llm_cleanup_min_words";
+const KEY_WEBHOOK_URL: &str = "webhook_url";
+const KEY_WEBHOOK_SECRET: &str = "webhook_secret";
+const KEY_WEBHOOK_ON_EVERY_TRANSCRIPTION: &str = "webhook_on_every_transcription";
+const KEY_VALIDATION_MIN_DURATION_MS: &str = "validation_min_duration_ms";
+const KEY_VALIDATION_MIN_RMS_ENERGY: &str = "validation_min_rms_energy";
+const KEY_VALIDATION_MIN_SPEECH_PERCENTAGE: &str = "validation_min_speech_percentage";
+const KEY_SPECTRAL_FLATNESS_THRESHOLD: &str = "spectral_flatness_threshold";
+const KEY_AUTO_STOP_SILENCE_SECONDS: &str = "auto_stop_silence_seconds";
+const KEY_AUTO_STOP_SILENCE_RMS: &str = "auto_stop_silence_rms";
+const KEY_HYBRID_LOCAL_TIMEOUT_SECONDS: &str = "hybrid_local_timeout_seconds";
+const KEY_RECONNECT_GRACE_SECONDS: &str = "reconnect_grace_seconds";
+const KEY_PASTE_MODE: &str = "paste_mode";
+const KEY_AUDIO_BITRATE_KBPS: &str = "audio_bitrate_kbps";
+const KEY_MIN_WORD_CONFIDENCE: &str = "min_word_confidence";
+const KEY_SHORTCUT_COOLDOWN_MS: &str = "shortcut_cooldown_ms";
+const KEY_MODEL_IDLE_TIMEOUT_SECONDS: &str = "model_idle_timeout_seconds";
+const KEY_BACKUP_PATH: &str = "backup_path";
+const KEY_AUTO_BACKUP_ENABLED: &str = "auto_backup_enabled";
+const KEY_SMART_TAP_THRESHOLD_MS: &str = "smart_tap_threshold_ms";
+const KEY_MIN_RECORDING_DURATION_MS: &str = "min_recording_duration_ms";
+const KEY_DAILY_WORD_GOAL: &str = "daily_word_goal";
+const KEY_LOCAL_TRANSCRIPTION_TIMEOUT_SECONDS: &str = "local_transcription_timeout_seconds";
+const KEY_SPOTLIGHT_INDEXING_ENABLED: &str = "spotlight_indexing_enabled";
+const KEY_TRIM_MODE: &str = "trim_mode";
+const KEY_DOUBLE_TAP_WINDOW_MS: &str = "double_tap_window_ms";
+const KEY_DOUBLE_TAP_ACTION: &str = "double_tap_action";
+const KEY_LLM_SYSTEM_PROMPT_OVERRIDE: &str = "llm_system_prompt_override";
+const KEY_SMART_PUNCTUATION_ENABLED: &str = "smart_punctuation_enabled";
+const KEY_TRAY_ICON_MODE: &str = "tray_icon_mode";
+const KEY_CLIPBOARD_HISTORY_ENABLED: &str = "clipboard_history_enabled";
+const KEY_REPLACE_CONSECUTIVE_SPACES: &str = "replace_consecutive_spaces";
+const KEY_STRIP_TRAILING_PERIOD: &str = "strip_trailing_period";
+const KEY_LLM_STREAMING_ENABLED: &str = "llm_streaming_enabled";
+const KEY_OVERLAY_OPACITY: &str = "overlay_opacity";
+const KEY_OVERLAY_CORNER_RADIUS: &str = "overlay_corner_radius";
+const KEY_AUTO_PARAGRAPH_ENABLED: &str = "auto_paragraph_enabled";
+const KEY_SENTENCES_PER_PARAGRAPH: &str = "sentences_per_paragraph";
+const KEY_ANALYTICS_ENABLED: &str = "analytics_enabled";
+const KEY_KEYBOARD_LAYOUT: &str = "keyboard_layout";
+const KEY_WAL_AUTO_CHECKPOINT_ENABLED: &str = "wal_auto_checkpoint_enabled";
+const KEY_CUSTOM_API_HEADERS: &str = "custom_api_headers";
+const KEY_OVERLAY_POSITION: &str = "overlay_position";
+const KEY_CONTINUOUS_SHORTCUT: &str = "continuous_shortcut";
+const KEY_CONTINUOUS_ENABLED: &str = "continuous_enabled";
+const KEY_CHUNK_DURATION_SECONDS: &str = "chunk_duration_seconds";
+const KEY_DUPLICATE_DETECTION_ENABLED: &str = "duplicate_detection_enabled";
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Replacement {
     pub from: String,
     pub to: String,
+    /// Higher priorities are applied first.
+    #[serde(default)]
+    pub priority: i32,
+}
+
+/// A named set of replacements, e.g. for coding vs. email vocabulary.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ReplacementProfile {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub replacements: Vec<Replacement>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -51,13 +125,17 @@ pub struct UserSettings {
     pub toggle_shortcut: String,
     #[serde(default)]
     pub toggle_enabled: bool,
+    #[serde(default)]
+    pub copy_latest_shortcut: String,
+    #[serde(default)]
+    pub copy_latest_enabled: bool,
     #[serde(default = "default_transcription_mode")]
     pub transcription_mode: TranscriptionMode,
     #[serde(default = "default_local_model")]
     pub local_model: String,
     pub microphone_device: Option<String>,
     #[serde(default = "default_language")]
-    pub language: String,
+    pub language: LanguageSetting,
     #[serde(default)]
     pub llm_cleanup_enabled: bool,
     #[serde(default = "default_llm_provider")]
@@ -70,10 +148,159 @@ pub struct UserSettings {
     pub llm_model: String,
     #[serde(default)]
     pub user_context: String,
+    /// Replaces the built-in cleanup prompt when set.
+    #[serde(default)]
+    pub llm_system_prompt_override: Option<String>,
     #[serde(default)]
     pub dictionary: Vec<String>,
+    /// Replacements of the default profile, used when no other profile is active.
     #[serde(default)]
     pub replacements: Vec<Replacement>,
+    #[serde(default)]
+    pub replacement_profiles: Vec<ReplacementProfile>,
+    #[serde(default)]
+    pub active_replacement_profile_id: Option<String>,
+    #[serde(default)]
+    pub focus_mode: bool,
+    #[serde(default)]
+    pub shortcut_display_format: ShortcutDisplayFormat,
+    #[serde(default = "default_llm_cleanup_min_words")]
+    pub llm_cleanup_min_words: u32,
+    #[serde(default)]
+    pub webhook_url: Option<String>,
+    /// Shared secret webhook payloads are signed with, if set. Stored encrypted.
+    #[serde(default)]
+    pub webhook_secret: Option<String>,
+    #[serde(default)]
+    pub webhook_on_every_transcription: bool,
+    #[serde(default = "default_validation_min_duration_ms")]
+    pub validation_min_duration_ms: i64,
+    #[serde(default = "default_validation_min_rms_energy")]
+    pub validation_min_rms_energy: f32,
+    #[serde(default = "default_validation_min_speech_percentage")]
+    pub validation_min_speech_percentage: f32,
+    /// Recordings whose spectrum is flatter than this are rejected as background noise.
+    #[serde(default = "default_spectral_flatness_threshold")]
+    pub spectral_flatness_threshold: f32,
+    /// Seconds of continuous silence before a toggle recording stops itself; `None` disables it.
+    #[serde(default = "default_auto_stop_silence_seconds")]
+    pub auto_stop_silence_seconds: Option<u32>,
+    /// Buffer RMS below which the input counts as silence for auto-stop.
+    #[serde(default = "default_auto_stop_silence_rms")]
+    pub auto_stop_silence_rms: f32,
+    /// How long hybrid mode waits on the local model before falling back to cloud.
+    #[serde(default = "default_hybrid_local_timeout_seconds")]
+    pub hybrid_local_timeout_seconds: u32,
+    /// Seconds to wait for a disconnected microphone before giving up; `None` never waits.
+    #[serde(default = "default_reconnect_grace_seconds")]
+    pub reconnect_grace_seconds: Option<u32>,
+    #[serde(default)]
+    pub paste_mode: PasteMode,
+    /// MP3 bitrate for saved recordings; see `recorder::SUPPORTED_BITRATES_KBPS`.
+    #[serde(default = "default_audio_bitrate_kbps")]
+    pub audio_bitrate_kbps: u32,
+    /// Set on load when the stored API key can't be decrypted on this machine, e.g. after the
+    /// hardware identifier changed. The stored key is kept until a new one is entered.
+    #[serde(default, skip_deserializing)]
+    pub llm_api_key_needs_reentry: bool,
+    /// Cloud words scored below this (0–1) are dropped from transcripts; 0 disables it.
+    #[serde(default)]
+    pub min_word_confidence: f32,
+    /// Shortcut presses within this many milliseconds of a recording start are ignored,
+    /// to absorb key bounce from macro pads; 0 disables it.
+    #[serde(default)]
+    pub shortcut_cooldown_ms: u64,
+    /// Seconds a local model may sit unused before it is unloaded; 0 keeps it loaded.
+    #[serde(default = "default_model_idle_timeout_seconds")]
+    pub model_idle_timeout_seconds: u32,
+    /// Folder database backups are written to, outside the app data directory.
+    #[serde(default)]
+    pub backup_path: Option<String>,
+    /// Back up to `backup_path` in the background every few transcriptions.
+    #[serde(default)]
+    pub auto_backup_enabled: bool,
+    /// Smart shortcut presses released sooner than this are taps that start toggle mode;
+    /// longer ones are holds.
+    #[serde(default = "default_smart_tap_threshold_ms")]
+    pub smart_tap_threshold_ms: u64,
+    /// Recordings shorter than this are discarded without transcribing.
+    #[serde(default = "default_min_recording_duration_ms")]
+    pub min_recording_duration_ms: u64,
+    /// Words to dictate each day; reaching it is celebrated once per day.
+    #[serde(default)]
+    pub daily_word_goal: Option<u32>,
+    /// Wall time a local model gets per transcription before it's treated as stalled.
+    #[serde(default = "default_local_transcription_timeout_seconds")]
+    pub local_transcription_timeout_seconds: u32,
+    /// Attach transcripts to their recordings so Spotlight can search them (macOS only).
+    #[serde(default = "default_spotlight_indexing_enabled")]
+    pub spotlight_indexing_enabled: bool,
+    /// How much silence is cut from recordings before they're transcribed.
+    #[serde(default)]
+    pub trim_mode: TrimMode,
+    /// Two smart shortcut presses this close together run `double_tap_action` instead of
+    /// recording; 0 disables it.
+    #[serde(default)]
+    pub double_tap_window_ms: u64,
+    #[serde(default)]
+    pub double_tap_action: DoubleTapAction,
+    /// Add sentence capitals and final periods to local transcripts from engines that don't
+    /// punctuate (everything but Whisper) when LLM cleanup didn't run.
+    #[serde(default)]
+    pub smart_punctuation_enabled: bool,
+    #[serde(default)]
+    pub tray_icon_mode: TrayIconMode,
+    /// Keep what Glimpse pasted this session so it can be looked up again. Never saved to disk.
+    #[serde(default)]
+    pub clipboard_history_enabled: bool,
+    /// Collapse runs of whitespace in the final transcript to single spaces.
+    #[serde(default = "default_replace_consecutive_spaces")]
+    pub replace_consecutive_spaces: bool,
+    /// Drop the final period, for dictating into search bars and the like.
+    #[serde(default)]
+    pub strip_trailing_period: bool,
+    /// Ask OpenAI-compatible providers to stream the cleanup reply so it can be shown as it
+    /// arrives. Defaults to on for OpenAI only, whose endpoint is known to support it.
+    #[serde(default)]
+    pub llm_streaming_enabled: bool,
+    /// Opacity of the recording pill, from 0.3 to 1.
+    #[serde(default = "default_overlay_opacity")]
+    pub overlay_opacity: f32,
+    /// Corner radius of the frosted backdrop behind the pill on macOS; 0 for no backdrop.
+    #[serde(default)]
+    pub overlay_corner_radius: f32,
+    /// Break long transcripts into paragraphs of `sentences_per_paragraph` sentences.
+    #[serde(default)]
+    pub auto_paragraph_enabled: bool,
+    #[serde(default = "default_sentences_per_paragraph")]
+    pub sentences_per_paragraph: u32,
+    /// Send anonymous usage events to Aptabase.
+    #[serde(default = "default_analytics_enabled")]
+    pub analytics_enabled: bool,
+    /// Layout used to label shortcut keys; shortcuts themselves are stored by QWERTY position.
+    #[serde(default)]
+    pub keyboard_layout: KeyboardLayout,
+    /// Checkpoint the history database's write-ahead log whenever it grows past 10 MB.
+    #[serde(default = "default_wal_auto_checkpoint_enabled")]
+    pub wal_auto_checkpoint_enabled: bool,
+    /// Extra `(name, value)` headers sent with every transcription API request.
+    #[serde(default)]
+    pub custom_api_headers: Vec<(String, String)>,
+    /// Where the pill appears on the screen under the cursor.
+    #[serde(default)]
+    pub overlay_position: OverlayPosition,
+    /// Starts and stops a continuous recording, which is saved and transcribed in chunks.
+    #[serde(default)]
+    pub continuous_shortcut: String,
+    #[serde(default)]
+    pub continuous_enabled: bool,
+    /// Length of each chunk of a continuous recording.
+    #[serde(default = "default_chunk_duration_seconds")]
+    pub chunk_duration_seconds: u32,
+    /// Hold back a recording that sounds like one made moments earlier until the user
+    /// chooses to transcribe it anyway.
+    #[serde(default = "default_true")]
+    pub duplicate_detection_enabled: bool,
 }
 
 fn default_smart_shortcut() -> String {
@@ -102,6 +329,8 @@ impl Default for UserSettings {
             hold_enabled: false,
             toggle_shortcut: default_toggle_shortcut(),
             toggle_enabled: false,
+            copy_latest_shortcut: String::new(),
+            copy_latest_enabled: false,
             transcription_mode: default_transcription_mode(),
             local_model: default_local_model(),
             microphone_device: None,
@@ -112,17 +341,127 @@ impl Default for UserSettings {
             llm_api_key: String::new(),
             llm_model: String::new(),
             user_context: String::new(),
+            llm_system_prompt_override: None,
             dictionary: Vec::new(),
             replacements: Vec::new(),
+            replacement_profiles: Vec::new(),
+            active_replacement_profile_id: None,
+            focus_mode: false,
+            shortcut_display_format: ShortcutDisplayFormat::default(),
+            llm_cleanup_min_words: default_llm_cleanup_min_words(),
+            webhook_url: None,
+            webhook_secret: None,
+            webhook_on_every_transcription: false,
+            validation_min_duration_ms: default_validation_min_duration_ms(),
+            validation_min_rms_energy: default_validation_min_rms_energy(),
+            validation_min_speech_percentage: default_validation_min_speech_percentage(),
+            spectral_flatness_threshold: default_spectral_flatness_threshold(),
+            auto_stop_silence_seconds: default_auto_stop_silence_seconds(),
+            auto_stop_silence_rms: default_auto_stop_silence_rms(),
+            hybrid_local_timeout_seconds: default_hybrid_local_timeout_seconds(),
+            reconnect_grace_seconds: default_reconnect_grace_seconds(),
+            paste_mode: PasteMode::default(),
+            audio_bitrate_kbps: default_audio_bitrate_kbps(),
+            llm_api_key_needs_reentry: false,
+            min_word_confidence: 0.0,
+            shortcut_cooldown_ms: 0,
+            model_idle_timeout_seconds: default_model_idle_timeout_seconds(),
+            backup_path: None,
+            auto_backup_enabled: false,
+            smart_tap_threshold_ms: default_smart_tap_threshold_ms(),
+            min_recording_duration_ms: default_min_recording_duration_ms(),
+            daily_word_goal: None,
+            local_transcription_timeout_seconds: default_local_transcription_timeout_seconds(),
+            spotlight_indexing_enabled: default_spotlight_indexing_enabled(),
+            trim_mode: TrimMode::default(),
+            double_tap_window_ms: 0,
+            double_tap_action: DoubleTapAction::default(),
+            smart_punctuation_enabled: false,
+            tray_icon_mode: TrayIconMode::default(),
+            clipboard_history_enabled: false,
+            replace_consecutive_spaces: default_replace_consecutive_spaces(),
+            strip_trailing_period: false,
+            llm_streaming_enabled: default_llm_streaming_enabled(&default_llm_provider()),
+            overlay_opacity: default_overlay_opacity(),
+            overlay_corner_radius: 0.0,
+            auto_paragraph_enabled: false,
+            sentences_per_paragraph: default_sentences_per_paragraph(),
+            analytics_enabled: default_analytics_enabled(),
+            keyboard_layout: KeyboardLayout::default(),
+            wal_auto_checkpoint_enabled: default_wal_auto_checkpoint_enabled(),
+            custom_api_headers: Vec::new(),
+            overlay_position: OverlayPosition::default(),
+            continuous_shortcut: String::new(),
+            continuous_enabled: false,
+            chunk_duration_seconds: default_chunk_duration_seconds(),
+            duplicate_detection_enabled: default_true(),
         }
     }
 }
 
+impl UserSettings {
+    /// Replacements of the active profile, or the default set if none is active.
+    pub fn active_replacements(&self) -> &[Replacement] {
+        self.active_replacement_profile_id
+            .as_deref()
+            .and_then(|id| {
+                self.replacement_profiles
+                    .iter()
+                    .find(|profile| profile.id == id)
+            })
+            .map(|profile| profile.replacements.as_slice())
+            .unwrap_or(&self.replacements)
+    }
+}
+
+/// Settings backup file. `data` holds the password-encrypted JSON of `UserSettings`,
+/// with the LLM API key in plaintext inside the encrypted payload.
+#[derive(Serialize, Deserialize)]
+struct SettingsExport {
+    magic: String,
+    data: String,
+}
+
+/// Serialize `settings` into a backup file encrypted with `password`.
+pub fn export_encrypted(settings: &UserSettings, password: &str) -> Result<String> {
+    let json = serde_json::to_vec(settings).context("Failed to serialize settings")?;
+    let encrypted = crate::crypto::encrypt_with_password(&json, password)
+        .map_err(|e| anyhow!("Failed to encrypt settings: {}", e))?;
+
+    serde_json::to_string_pretty(&SettingsExport {
+        magic: SETTINGS_EXPORT_MAGIC.to_string(),
+        data: BASE64.encode(encrypted),
+    })
+    .context("Failed to serialize settings export")
+}
+
+/// Decrypt a backup written by `export_encrypted`.
+pub fn import_encrypted(contents: &str, password: &str) -> Result<UserSettings> {
+    let export: SettingsExport =
+        serde_json::from_str(contents).context("Not a Glimpse settings file")?;
+    if export.magic != SETTINGS_EXPORT_MAGIC {
+        bail!("Unsupported settings file version: {}", export.magic);
+    }
+
+    let encrypted = BASE64
+        .decode(export.data.trim())
+        .context("Settings file is corrupted")?;
+    let json =
+        crate::crypto::decrypt_with_password(&encrypted, password).map_err(|e| anyhow!(e))?;
+    let json = String::from_utf8(json).context("Settings file is corrupted")?;
+
+    serde_json::from_str::<UserSettings>(&json).context("Settings file contains invalid settings")
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum TranscriptionMode {
     Cloud,
+    /// Stored as `"whisper"` by versions that only had the one local engine.
+    #[serde(alias = "whisper")]
     Local,
+    /// Try the local model first and fall back to cloud if it fails or times out.
+    Hybrid,
 }
 
 impl Default for TranscriptionMode {
@@ -135,6 +474,114 @@ fn default_transcription_mode() -> TranscriptionMode {
     TranscriptionMode::Cloud
 }
 
+/// How auto-paste inserts a transcription into the focused app.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PasteMode {
+    /// Put the text on the clipboard, press Cmd+V, then restore the previous clipboard.
+    #[default]
+    Clipboard,
+    /// Type the text as key events, leaving the clipboard alone.
+    InputSimulation,
+}
+
+/// Spoken language of recordings. Stored and sent to the frontend as the language code,
+/// or `"auto"` to let the speech model detect it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(from = "String", into = "String")]
+pub enum LanguageSetting {
+    Auto,
+    Fixed(String),
+}
+
+impl LanguageSetting {
+    /// The language to transcribe in, or `None` to detect it.
+    pub fn code(&self) -> Option<&str> {
+        match self {
+            Self::Auto => None,
+            Self::Fixed(code) => Some(code.as_str()).filter(|code| !code.is_empty()),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Auto => "auto",
+            Self::Fixed(code) => code,
+        }
+    }
+}
+
+impl From<String> for LanguageSetting {
+    fn from(value: String) -> Self {
+        let value = value.trim();
+        if value.eq_ignore_ascii_case("auto") {
+            Self::Auto
+        } else {
+            Self::Fixed(value.to_string())
+        }
+    }
+}
+
+impl From<LanguageSetting> for String {
+    fn from(setting: LanguageSetting) -> Self {
+        setting.as_str().to_string()
+    }
+}
+
+/// How aggressively silence is trimmed from a finished recording.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum TrimMode {
+    /// Keep longer pauses, for dictation with natural breaks.
+    Conservative,
+    #[default]
+    Balanced,
+    /// Cut most pauses, for meetings and long recordings.
+    Aggressive,
+    Disabled,
+}
+
+/// What a double tap of the smart shortcut does.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DoubleTapAction {
+    #[default]
+    OpenSettings,
+    /// Copy the latest successful transcription to the clipboard.
+    CopyLast,
+    Disabled,
+}
+
+/// Whether the tray icon changes with the recording state.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum TrayIconMode {
+    /// Always the plain icon, for anyone who finds the changing one distracting.
+    Static,
+    #[default]
+    Dynamic,
+}
+
+/// Where the recording pill is placed on the screen under the cursor.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "snake_case", tag = "mode")]
+pub enum OverlayPosition {
+    /// Centered near the bottom of the screen.
+    #[default]
+    Auto,
+    TopCenter,
+    BottomCenter,
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+    /// Top-left corner of the pill at fractions (0–1) of the screen's width and height.
+    Custom {
+        x_percent: f32,
+        y_percent: f32,
+    },
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum LlmProvider {
@@ -163,12 +610,100 @@ fn default_llm_provider() -> LlmProvider {
     LlmProvider::None
 }
 
+pub fn default_llm_streaming_enabled(provider: &LlmProvider) -> bool {
+    matches!(provider, LlmProvider::OpenAI)
+}
+
 pub fn default_local_model() -> String {
     "parakeet_tdt_int8".to_string()
 }
 
-fn default_language() -> String {
-    "en".to_string()
+fn default_llm_cleanup_min_words() -> u32 {
+    5
+}
+
+fn default_validation_min_duration_ms() -> i64 {
+    ValidationConfig::default().min_duration_ms
+}
+
+fn default_validation_min_rms_energy() -> f32 {
+    ValidationConfig::default().min_rms_energy
+}
+
+fn default_validation_min_speech_percentage() -> f32 {
+    ValidationConfig::default().min_speech_percentage
+}
+
+fn default_spectral_flatness_threshold() -> f32 {
+    ValidationConfig::default().max_spectral_flatness
+}
+
+fn default_auto_stop_silence_seconds() -> Option<u32> {
+    Some(30)
+}
+
+fn default_auto_stop_silence_rms() -> f32 {
+    0.01
+}
+
+fn default_hybrid_local_timeout_seconds() -> u32 {
+    30
+}
+
+fn default_model_idle_timeout_seconds() -> u32 {
+    crate::local_transcription::DEFAULT_IDLE_TIMEOUT_SECONDS
+}
+
+fn default_smart_tap_threshold_ms() -> u64 {
+    200
+}
+
+fn default_min_recording_duration_ms() -> u64 {
+    300
+}
+
+fn default_local_transcription_timeout_seconds() -> u32 {
+    120
+}
+
+fn default_spotlight_indexing_enabled() -> bool {
+    cfg!(target_os = "macos")
+}
+
+fn default_audio_bitrate_kbps() -> u32 {
+    64
+}
+
+fn default_reconnect_grace_seconds() -> Option<u32> {
+    Some(5)
+}
+
+fn default_replace_consecutive_spaces() -> bool {
+    true
+}
+
+fn default_overlay_opacity() -> f32 {
+    1.0
+}
+
+fn default_sentences_per_paragraph() -> u32 {
+    4
+}
+
+fn default_analytics_enabled() -> bool {
+    true
+}
+
+fn default_wal_auto_checkpoint_enabled() -> bool {
+    true
+}
+
+fn default_chunk_duration_seconds() -> u32 {
+    300
+}
+
+fn default_language() -> LanguageSetting {
+    LanguageSetting::Fixed("en".to_string())
 }
 
 pub struct SettingsStore {
@@ -177,7 +712,11 @@ pub struct SettingsStore {
 
 impl SettingsStore {
     pub fn new(app: &AppHandle) -> Result<Self> {
-        let path = db_path(app)?;
+        Self::open(db_path(app)?)
+    }
+
+    /// Open the settings database at `path`, creating it if needed.
+    pub fn open(path: PathBuf) -> Result<Self> {
         if let Some(parent) = path.parent() {
             fs::create_dir_all(parent)
                 .with_context(|| format!("Failed to create settings dir {}", parent.display()))?;
@@ -202,6 +741,7 @@ impl SettingsStore {
             [],
         )
         .context("Failed to create settings table")?;
+        migrate_transcription_mode(&conn)?;
         Ok(())
     }
 
@@ -226,6 +766,13 @@ impl SettingsStore {
             self.read_value(&conn, KEY_TOGGLE_SHORTCUT, settings.toggle_shortcut.clone())?;
         settings.toggle_enabled =
             self.read_value(&conn, KEY_TOGGLE_ENABLED, settings.toggle_enabled)?;
+        settings.copy_latest_shortcut = self.read_value(
+            &conn,
+            KEY_COPY_LATEST_SHORTCUT,
+            settings.copy_latest_shortcut.clone(),
+        )?;
+        settings.copy_latest_enabled =
+            self.read_value(&conn, KEY_COPY_LATEST_ENABLED, settings.copy_latest_enabled)?;
         settings.transcription_mode = self.read_value(
             &conn,
             KEY_TRANSCRIPTION_MODE,
@@ -254,13 +801,22 @@ impl SettingsStore {
                     Err(e) => {
                         if !crate::crypto::looks_encrypted(&encrypted_key) {
                             settings.llm_api_key = encrypted_key;
+                        } else if let Some(decrypted) = decrypt_with_legacy_uuid(&encrypted_key) {
+                            // Re-encrypted with the current identifier on the next save
+                            info!(
+                                "Recovered API key encrypted with a previous hardware identifier"
+                            );
+                            settings.llm_api_key = decrypted;
                         } else {
-                            eprintln!("Error: Failed to decrypt API key: {}. Key will need to be re-entered.", e);
+                            error!(
+                                "Failed to decrypt API key: {e}. Key will need to be re-entered."
+                            );
+                            settings.llm_api_key_needs_reentry = true;
                         }
                     }
                 }
             } else {
-                eprintln!("Warning: Could not get hardware UUID, API key won't be encrypted");
+                warn!("Could not get hardware UUID, API key won't be encrypted");
                 settings.llm_api_key = encrypted_key;
             }
         }
@@ -272,6 +828,223 @@ impl SettingsStore {
             self.read_value(&conn, KEY_DICTIONARY, settings.dictionary.clone())?;
         settings.replacements =
             self.read_value(&conn, KEY_REPLACEMENTS, settings.replacements.clone())?;
+        settings.replacement_profiles = self.read_value(
+            &conn,
+            KEY_REPLACEMENT_PROFILES,
+            settings.replacement_profiles.clone(),
+        )?;
+        settings.active_replacement_profile_id = self.read_value(
+            &conn,
+            KEY_ACTIVE_REPLACEMENT_PROFILE_ID,
+            settings.active_replacement_profile_id.clone(),
+        )?;
+        settings.focus_mode = self.read_value(&conn, KEY_FOCUS_MODE, settings.focus_mode)?;
+        settings.shortcut_display_format = self.read_value(
+            &conn,
+            KEY_SHORTCUT_DISPLAY_FORMAT,
+            settings.shortcut_display_format,
+        )?;
+        settings.llm_cleanup_min_words = self.read_value(
+            &conn,
+            KEY_LLM_CLEANUP_MIN_WORDS,
+            settings.llm_cleanup_min_words,
+        )?;
+        settings.webhook_url =
+            self.read_value(&conn, KEY_WEBHOOK_URL, settings.webhook_url.clone())?;
+        let stored_secret: Option<String> = self.read_value(&conn, KEY_WEBHOOK_SECRET, None)?;
+        settings.webhook_secret = stored_secret.and_then(|stored| {
+            let secret = decrypt_secret(&stored);
+            if secret.is_none() {
+                error!("Failed to decrypt webhook secret. It will need to be re-entered.");
+            }
+            secret
+        });
+        settings.webhook_on_every_transcription = self.read_value(
+            &conn,
+            KEY_WEBHOOK_ON_EVERY_TRANSCRIPTION,
+            settings.webhook_on_every_transcription,
+        )?;
+        settings.validation_min_duration_ms = self.read_value(
+            &conn,
+            KEY_VALIDATION_MIN_DURATION_MS,
+            settings.validation_min_duration_ms,
+        )?;
+        settings.validation_min_rms_energy = self.read_value(
+            &conn,
+            KEY_VALIDATION_MIN_RMS_ENERGY,
+            settings.validation_min_rms_energy,
+        )?;
+        settings.validation_min_speech_percentage = self.read_value(
+            &conn,
+            KEY_VALIDATION_MIN_SPEECH_PERCENTAGE,
+            settings.validation_min_speech_percentage,
+        )?;
+        settings.spectral_flatness_threshold = self.read_value(
+            &conn,
+            KEY_SPECTRAL_FLATNESS_THRESHOLD,
+            settings.spectral_flatness_threshold,
+        )?;
+        settings.auto_stop_silence_seconds = self.read_value(
+            &conn,
+            KEY_AUTO_STOP_SILENCE_SECONDS,
+            settings.auto_stop_silence_seconds,
+        )?;
+        settings.auto_stop_silence_rms = self.read_value(
+            &conn,
+            KEY_AUTO_STOP_SILENCE_RMS,
+            settings.auto_stop_silence_rms,
+        )?;
+        settings.hybrid_local_timeout_seconds = self.read_value(
+            &conn,
+            KEY_HYBRID_LOCAL_TIMEOUT_SECONDS,
+            settings.hybrid_local_timeout_seconds,
+        )?;
+        settings.reconnect_grace_seconds = self.read_value(
+            &conn,
+            KEY_RECONNECT_GRACE_SECONDS,
+            settings.reconnect_grace_seconds,
+        )?;
+        settings.paste_mode =
+            self.read_value(&conn, KEY_PASTE_MODE, settings.paste_mode.clone())?;
+        settings.audio_bitrate_kbps =
+            self.read_value(&conn, KEY_AUDIO_BITRATE_KBPS, settings.audio_bitrate_kbps)?;
+        settings.min_word_confidence =
+            self.read_value(&conn, KEY_MIN_WORD_CONFIDENCE, settings.min_word_confidence)?;
+        settings.shortcut_cooldown_ms = self.read_value(
+            &conn,
+            KEY_SHORTCUT_COOLDOWN_MS,
+            settings.shortcut_cooldown_ms,
+        )?;
+        settings.model_idle_timeout_seconds = self.read_value(
+            &conn,
+            KEY_MODEL_IDLE_TIMEOUT_SECONDS,
+            settings.model_idle_timeout_seconds,
+        )?;
+        settings.backup_path =
+            self.read_value(&conn, KEY_BACKUP_PATH, settings.backup_path.clone())?;
+        settings.auto_backup_enabled =
+            self.read_value(&conn, KEY_AUTO_BACKUP_ENABLED, settings.auto_backup_enabled)?;
+        settings.smart_tap_threshold_ms = self.read_value(
+            &conn,
+            KEY_SMART_TAP_THRESHOLD_MS,
+            settings.smart_tap_threshold_ms,
+        )?;
+        settings.min_recording_duration_ms = self.read_value(
+            &conn,
+            KEY_MIN_RECORDING_DURATION_MS,
+            settings.min_recording_duration_ms,
+        )?;
+        settings.daily_word_goal =
+            self.read_value(&conn, KEY_DAILY_WORD_GOAL, settings.daily_word_goal)?;
+        settings.local_transcription_timeout_seconds = self.read_value(
+            &conn,
+            KEY_LOCAL_TRANSCRIPTION_TIMEOUT_SECONDS,
+            settings.local_transcription_timeout_seconds,
+        )?;
+        settings.spotlight_indexing_enabled = self.read_value(
+            &conn,
+            KEY_SPOTLIGHT_INDEXING_ENABLED,
+            settings.spotlight_indexing_enabled,
+        )?;
+        settings.trim_mode = self.read_value(&conn, KEY_TRIM_MODE, settings.trim_mode)?;
+        settings.double_tap_window_ms = self.read_value(
+            &conn,
+            KEY_DOUBLE_TAP_WINDOW_MS,
+            settings.double_tap_window_ms,
+        )?;
+        settings.double_tap_action =
+            self.read_value(&conn, KEY_DOUBLE_TAP_ACTION, settings.double_tap_action)?;
+        settings.llm_system_prompt_override = self.read_value(
+            &conn,
+            KEY_LLM_SYSTEM_PROMPT_OVERRIDE,
+            settings.llm_system_prompt_override.clone(),
+        )?;
+        settings.smart_punctuation_enabled = self.read_value(
+            &conn,
+            KEY_SMART_PUNCTUATION_ENABLED,
+            settings.smart_punctuation_enabled,
+        )?;
+        settings.tray_icon_mode =
+            self.read_value(&conn, KEY_TRAY_ICON_MODE, settings.tray_icon_mode)?;
+        settings.clipboard_history_enabled = self.read_value(
+            &conn,
+            KEY_CLIPBOARD_HISTORY_ENABLED,
+            settings.clipboard_history_enabled,
+        )?;
+        settings.replace_consecutive_spaces = self.read_value(
+            &conn,
+            KEY_REPLACE_CONSECUTIVE_SPACES,
+            settings.replace_consecutive_spaces,
+        )?;
+        settings.strip_trailing_period = self.read_value(
+            &conn,
+            KEY_STRIP_TRAILING_PERIOD,
+            settings.strip_trailing_period,
+        )?;
+        settings.llm_streaming_enabled = self.read_value(
+            &conn,
+            KEY_LLM_STREAMING_ENABLED,
+            default_llm_streaming_enabled(&settings.llm_provider),
+        )?;
+        settings.overlay_opacity =
+            self.read_value(&conn, KEY_OVERLAY_OPACITY, settings.overlay_opacity)?;
+        settings.overlay_corner_radius = self.read_value(
+            &conn,
+            KEY_OVERLAY_CORNER_RADIUS,
+            settings.overlay_corner_radius,
+        )?;
+        settings.auto_paragraph_enabled = self.read_value(
+            &conn,
+            KEY_AUTO_PARAGRAPH_ENABLED,
+            settings.auto_paragraph_enabled,
+        )?;
+        settings.sentences_per_paragraph = self.read_value(
+            &conn,
+            KEY_SENTENCES_PER_PARAGRAPH,
+            settings.sentences_per_paragraph,
+        )?;
+        settings.analytics_enabled =
+            self.read_value(&conn, KEY_ANALYTICS_ENABLED, settings.analytics_enabled)?;
+        settings.keyboard_layout =
+            self.read_value(&conn, KEY_KEYBOARD_LAYOUT, settings.keyboard_layout)?;
+        settings.wal_auto_checkpoint_enabled = self.read_value(
+            &conn,
+            KEY_WAL_AUTO_CHECKPOINT_ENABLED,
+            settings.wal_auto_checkpoint_enabled,
+        )?;
+        let stored_headers: Vec<(String, String)> =
+            self.read_value(&conn, KEY_CUSTOM_API_HEADERS, Vec::new())?;
+        settings.custom_api_headers = stored_headers
+            .into_iter()
+            .filter_map(|(name, value)| match decrypt_secret(&value) {
+                Some(value) => Some((name, value)),
+                None => {
+                    error!(
+                        "Failed to decrypt custom header {name}. It will need to be re-entered."
+                    );
+                    None
+                }
+            })
+            .collect();
+        settings.overlay_position =
+            self.read_value(&conn, KEY_OVERLAY_POSITION, settings.overlay_position)?;
+        settings.continuous_shortcut = self.read_value(
+            &conn,
+            KEY_CONTINUOUS_SHORTCUT,
+            settings.continuous_shortcut.clone(),
+        )?;
+        settings.continuous_enabled =
+            self.read_value(&conn, KEY_CONTINUOUS_ENABLED, settings.continuous_enabled)?;
+        settings.chunk_duration_seconds = self.read_value(
+            &conn,
+            KEY_CHUNK_DURATION_SECONDS,
+            settings.chunk_duration_seconds,
+        )?;
+        settings.duplicate_detection_enabled = self.read_value(
+            &conn,
+            KEY_DUPLICATE_DETECTION_ENABLED,
+            settings.duplicate_detection_enabled,
+        )?;
 
         Ok(settings)
     }
@@ -290,6 +1063,16 @@ impl SettingsStore {
         self.write_value(&conn, KEY_HOLD_ENABLED, &settings.hold_enabled)?;
         self.write_value(&conn, KEY_TOGGLE_SHORTCUT, &settings.toggle_shortcut)?;
         self.write_value(&conn, KEY_TOGGLE_ENABLED, &settings.toggle_enabled)?;
+        self.write_value(
+            &conn,
+            KEY_COPY_LATEST_SHORTCUT,
+            &settings.copy_latest_shortcut,
+        )?;
+        self.write_value(
+            &conn,
+            KEY_COPY_LATEST_ENABLED,
+            &settings.copy_latest_enabled,
+        )?;
         self.write_value(&conn, KEY_TRANSCRIPTION_MODE, &settings.transcription_mode)?;
         self.write_value(&conn, KEY_LOCAL_MODEL, &settings.local_model)?;
         self.write_value(&conn, KEY_MICROPHONE_DEVICE, &settings.microphone_device)?;
@@ -302,24 +1085,239 @@ impl SettingsStore {
         self.write_value(&conn, KEY_LLM_PROVIDER, &settings.llm_provider)?;
         self.write_value(&conn, KEY_LLM_ENDPOINT, &settings.llm_endpoint)?;
 
-        let stored_key = if settings.llm_api_key.is_empty() {
-            String::new()
-        } else if let Some(hardware_uuid) = crate::crypto::get_hardware_uuid() {
-            crate::crypto::encrypt(&settings.llm_api_key, &hardware_uuid)
-                .map_err(|e| anyhow::anyhow!("Failed to encrypt API key: {}", e))?
-        } else {
-            eprintln!("Warning: Could not get hardware UUID, storing API key unencrypted");
-            settings.llm_api_key.clone()
-        };
-        self.write_value(&conn, KEY_LLM_API_KEY, &stored_key)?;
+        // Keep an undecryptable key around until the user replaces it
+        if !(settings.llm_api_key.is_empty() && settings.llm_api_key_needs_reentry) {
+            let stored_key = if settings.llm_api_key.is_empty() {
+                String::new()
+            } else if let Some(hardware_uuid) = crate::crypto::get_hardware_uuid() {
+                crate::crypto::encrypt(&settings.llm_api_key, &hardware_uuid)
+                    .map_err(|e| anyhow::anyhow!("Failed to encrypt API key: {}", e))?
+            } else {
+                warn!("Could not get hardware UUID, storing API key unencrypted");
+                settings.llm_api_key.clone()
+            };
+            self.write_value(&conn, KEY_LLM_API_KEY, &stored_key)?;
+        }
 
         self.write_value(&conn, KEY_LLM_MODEL, &settings.llm_model)?;
         self.write_value(&conn, KEY_USER_CONTEXT, &settings.user_context)?;
         self.write_value(&conn, KEY_DICTIONARY, &settings.dictionary)?;
         self.write_value(&conn, KEY_REPLACEMENTS, &settings.replacements)?;
+        self.write_value(
+            &conn,
+            KEY_REPLACEMENT_PROFILES,
+            &settings.replacement_profiles,
+        )?;
+        self.write_value(
+            &conn,
+            KEY_ACTIVE_REPLACEMENT_PROFILE_ID,
+            &settings.active_replacement_profile_id,
+        )?;
+        self.write_value(&conn, KEY_FOCUS_MODE, &settings.focus_mode)?;
+        self.write_value(
+            &conn,
+            KEY_SHORTCUT_DISPLAY_FORMAT,
+            &settings.shortcut_display_format,
+        )?;
+        self.write_value(
+            &conn,
+            KEY_LLM_CLEANUP_MIN_WORDS,
+            &settings.llm_cleanup_min_words,
+        )?;
+        self.write_value(&conn, KEY_WEBHOOK_URL, &settings.webhook_url)?;
+        let stored_secret = settings
+            .webhook_secret
+            .as_deref()
+            .map(|secret| encrypt_secret(secret, "webhook secret"))
+            .transpose()?;
+        self.write_value(&conn, KEY_WEBHOOK_SECRET, &stored_secret)?;
+        self.write_value(
+            &conn,
+            KEY_WEBHOOK_ON_EVERY_TRANSCRIPTION,
+            &settings.webhook_on_every_transcription,
+        )?;
+        self.write_value(
+            &conn,
+            KEY_VALIDATION_MIN_DURATION_MS,
+            &settings.validation_min_duration_ms,
+        )?;
+        self.write_value(
+            &conn,
+            KEY_VALIDATION_MIN_RMS_ENERGY,
+            &settings.validation_min_rms_energy,
+        )?;
+        self.write_value(
+            &conn,
+            KEY_VALIDATION_MIN_SPEECH_PERCENTAGE,
+            &settings.validation_min_speech_percentage,
+        )?;
+        self.write_value(
+            &conn,
+            KEY_SPECTRAL_FLATNESS_THRESHOLD,
+            &settings.spectral_flatness_threshold,
+        )?;
+        self.write_value(
+            &conn,
+            KEY_AUTO_STOP_SILENCE_SECONDS,
+            &settings.auto_stop_silence_seconds,
+        )?;
+        self.write_value(
+            &conn,
+            KEY_AUTO_STOP_SILENCE_RMS,
+            &settings.auto_stop_silence_rms,
+        )?;
+        self.write_value(
+            &conn,
+            KEY_HYBRID_LOCAL_TIMEOUT_SECONDS,
+            &settings.hybrid_local_timeout_seconds,
+        )?;
+        self.write_value(
+            &conn,
+            KEY_RECONNECT_GRACE_SECONDS,
+            &settings.reconnect_grace_seconds,
+        )?;
+        self.write_value(&conn, KEY_PASTE_MODE, &settings.paste_mode)?;
+        self.write_value(&conn, KEY_AUDIO_BITRATE_KBPS, &settings.audio_bitrate_kbps)?;
+        self.write_value(
+            &conn,
+            KEY_MIN_WORD_CONFIDENCE,
+            &settings.min_word_confidence,
+        )?;
+        self.write_value(
+            &conn,
+            KEY_SHORTCUT_COOLDOWN_MS,
+            &settings.shortcut_cooldown_ms,
+        )?;
+        self.write_value(
+            &conn,
+            KEY_MODEL_IDLE_TIMEOUT_SECONDS,
+            &settings.model_idle_timeout_seconds,
+        )?;
+        self.write_value(&conn, KEY_BACKUP_PATH, &settings.backup_path)?;
+        self.write_value(
+            &conn,
+            KEY_AUTO_BACKUP_ENABLED,
+            &settings.auto_backup_enabled,
+        )?;
+        self.write_value(
+            &conn,
+            KEY_SMART_TAP_THRESHOLD_MS,
+            &settings.smart_tap_threshold_ms,
+        )?;
+        self.write_value(
+            &conn,
+            KEY_MIN_RECORDING_DURATION_MS,
+            &settings.min_recording_duration_ms,
+        )?;
+        self.write_value(&conn, KEY_DAILY_WORD_GOAL, &settings.daily_word_goal)?;
+        self.write_value(
+            &conn,
+            KEY_LOCAL_TRANSCRIPTION_TIMEOUT_SECONDS,
+            &settings.local_transcription_timeout_seconds,
+        )?;
+        self.write_value(
+            &conn,
+            KEY_SPOTLIGHT_INDEXING_ENABLED,
+            &settings.spotlight_indexing_enabled,
+        )?;
+        self.write_value(&conn, KEY_TRIM_MODE, &settings.trim_mode)?;
+        self.write_value(
+            &conn,
+            KEY_DOUBLE_TAP_WINDOW_MS,
+            &settings.double_tap_window_ms,
+        )?;
+        self.write_value(&conn, KEY_DOUBLE_TAP_ACTION, &settings.double_tap_action)?;
+        self.write_value(
+            &conn,
+            KEY_LLM_SYSTEM_PROMPT_OVERRIDE,
+            &settings.llm_system_prompt_override,
+        )?;
+        self.write_value(
+            &conn,
+            KEY_SMART_PUNCTUATION_ENABLED,
+            &settings.smart_punctuation_enabled,
+        )?;
+        self.write_value(&conn, KEY_TRAY_ICON_MODE, &settings.tray_icon_mode)?;
+        self.write_value(
+            &conn,
+            KEY_CLIPBOARD_HISTORY_ENABLED,
+            &settings.clipboard_history_enabled,
+        )?;
+        self.write_value(
+            &conn,
+            KEY_REPLACE_CONSECUTIVE_SPACES,
+            &settings.replace_consecutive_spaces,
+        )?;
+        self.write_value(
+            &conn,
+            KEY_STRIP_TRAILING_PERIOD,
+            &settings.strip_trailing_period,
+        )?;
+        self.write_value(
+            &conn,
+            KEY_LLM_STREAMING_ENABLED,
+            &settings.llm_streaming_enabled,
+        )?;
+        self.write_value(&conn, KEY_OVERLAY_OPACITY, &settings.overlay_opacity)?;
+        self.write_value(
+            &conn,
+            KEY_OVERLAY_CORNER_RADIUS,
+            &settings.overlay_corner_radius,
+        )?;
+        self.write_value(
+            &conn,
+            KEY_AUTO_PARAGRAPH_ENABLED,
+            &settings.auto_paragraph_enabled,
+        )?;
+        self.write_value(
+            &conn,
+            KEY_SENTENCES_PER_PARAGRAPH,
+            &settings.sentences_per_paragraph,
+        )?;
+        self.write_value(&conn, KEY_ANALYTICS_ENABLED, &settings.analytics_enabled)?;
+        self.write_value(&conn, KEY_KEYBOARD_LAYOUT, &settings.keyboard_layout)?;
+        self.write_value(
+            &conn,
+            KEY_WAL_AUTO_CHECKPOINT_ENABLED,
+            &settings.wal_auto_checkpoint_enabled,
+        )?;
+        let stored_headers = settings
+            .custom_api_headers
+            .iter()
+            .map(|(name, value)| Ok((name.clone(), encrypt_secret(value, "custom headers")?)))
+            .collect::<Result<Vec<_>>>()?;
+        self.write_value(&conn, KEY_CUSTOM_API_HEADERS, &stored_headers)?;
+        self.write_value(&conn, KEY_OVERLAY_POSITION, &settings.overlay_position)?;
+        self.write_value(
+            &conn,
+            KEY_CONTINUOUS_SHORTCUT,
+            &settings.continuous_shortcut,
+        )?;
+        self.write_value(&conn, KEY_CONTINUOUS_ENABLED, &settings.continuous_enabled)?;
+        self.write_value(
+            &conn,
+            KEY_CHUNK_DURATION_SECONDS,
+            &settings.chunk_duration_seconds,
+        )?;
+        self.write_value(
+            &conn,
+            KEY_DUPLICATE_DETECTION_ENABLED,
+            &settings.duplicate_detection_enabled,
+        )?;
         Ok(())
     }
 
+    /// Version of a one-off maintenance task last run against this install (0 if never).
+    pub fn maintenance_version(&self, task: &str) -> Result<u32> {
+        let conn = self.conn.lock();
+        self.read_value(&conn, &format!("maintenance_{task}"), 0)
+    }
+
+    pub fn set_maintenance_version(&self, task: &str, version: u32) -> Result<()> {
+        let conn = self.conn.lock();
+        self.write_value(&conn, &format!("maintenance_{task}"), &version)
+    }
+
     fn read_value<T>(&self, conn: &Connection, key: &str, default: T) -> Result<T>
     where
         T: for<'de> Deserialize<'de>,
@@ -355,13 +1353,221 @@ impl SettingsStore {
     }
 }
 
+/// Rewrite the `"whisper"` transcription mode of early versions to `"local"`.
+fn migrate_transcription_mode(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "UPDATE settings SET value = '\"local\"' WHERE key = ?1 AND value = '\"whisper\"'",
+        params![KEY_TRANSCRIPTION_MODE],
+    )
+    .context("Failed to migrate legacy transcription mode")?;
+    Ok(())
+}
+
 fn db_path(app: &AppHandle) -> Result<PathBuf> {
     let resolver = app.path();
-    let mut dir = resolver
+    let dir = resolver
         .app_config_dir()
         .or_else(|_| resolver.app_data_dir())
         .context("Unable to resolve config directory")?;
-    dir.push("Glimpse");
-    dir.push(SETTINGS_DB_FILE_NAME);
-    Ok(dir)
+    Ok(db_path_in(dir))
+}
+
+/// Where the settings database lives inside the app config directory.
+pub fn db_path_in(mut config_dir: PathBuf) -> PathBuf {
+    config_dir.push("Glimpse");
+    config_dir.push(SETTINGS_DB_FILE_NAME);
+    config_dir
+}
+
+/// Custom header values and the webhook secret are credentials, so they are stored
+/// encrypted like the LLM API key. `what` names the value in messages.
+fn encrypt_secret(value: &str, what: &str) -> Result<String> {
+    match crate::crypto::get_hardware_uuid() {
+        Some(hardware_uuid) => crate::crypto::encrypt(value, &hardware_uuid)
+            .map_err(|e| anyhow::anyhow!("Failed to encrypt {}: {}", what, e)),
+        None => {
+            warn!("Could not get hardware UUID, storing {what} unencrypted");
+            Ok(value.to_string())
+        }
+    }
+}
+
+/// Values saved before they were encrypted come back as they are; `None` means the
+/// value is encrypted but no known hardware identifier opens it.
+fn decrypt_secret(stored: &str) -> Option<String> {
+    let decrypted = crate::crypto::get_hardware_uuid()
+        .and_then(|hardware_uuid| crate::crypto::decrypt(stored, &hardware_uuid).ok())
+        .or_else(|| decrypt_with_legacy_uuid(stored));
+    match decrypted {
+        Some(value) => Some(value),
+        None if !crate::crypto::looks_encrypted(stored) => Some(stored.to_string()),
+        None => None,
+    }
+}
+
+fn decrypt_with_legacy_uuid(encrypted: &str) -> Option<String> {
+    crate::crypto::legacy_hardware_uuids()
+        .iter()
+        .find_map(|uuid| crate::crypto::decrypt(encrypted, uuid).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_language_setting_round_trips_as_string() {
+        let auto: LanguageSetting = serde_json::from_str("\"Auto\"").unwrap();
+        assert_eq!(auto, LanguageSetting::Auto);
+        assert_eq!(auto.code(), None);
+        assert_eq!(serde_json::to_string(&auto).unwrap(), "\"auto\"");
+
+        let fixed: LanguageSetting = serde_json::from_str("\"de\"").unwrap();
+        assert_eq!(fixed.code(), Some("de"));
+        assert_eq!(serde_json::to_string(&fixed).unwrap(), "\"de\"");
+        assert_eq!(LanguageSetting::from(String::new()).code(), None);
+    }
+
+    #[test]
+    fn test_missing_fields_deserialize_to_defaults() {
+        let parsed: UserSettings = serde_json::from_str("{}").unwrap();
+        assert_eq!(
+            serde_json::to_value(&parsed).unwrap(),
+            serde_json::to_value(UserSettings::default()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_auto_stop_defaults_to_thirty_seconds() {
+        let settings = UserSettings::default();
+        assert_eq!(settings.auto_stop_silence_seconds, Some(30));
+        assert_eq!(settings.auto_stop_silence_rms, 0.01);
+    }
+
+    #[test]
+    fn test_custom_headers_round_trip_through_storage() {
+        let store = SettingsStore {
+            conn: Mutex::new(Connection::open_in_memory().unwrap()),
+        };
+        store.init_schema().unwrap();
+        let headers = vec![("X-Tenant-Token".to_string(), "tenant-secret".to_string())];
+        let settings = UserSettings {
+            custom_api_headers: headers.clone(),
+            ..UserSettings::default()
+        };
+        store.save(&settings).unwrap();
+
+        let raw: String = store
+            .conn
+            .lock()
+            .query_row(
+                "SELECT value FROM settings WHERE key = ?1",
+                params![KEY_CUSTOM_API_HEADERS],
+                |row| row.get(0),
+            )
+            .unwrap();
+        if crate::crypto::get_hardware_uuid().is_some() {
+            assert!(!raw.contains("tenant-secret"));
+        }
+        assert_eq!(store.load().unwrap().custom_api_headers, headers);
+
+        // Headers saved before encryption still load
+        store
+            .conn
+            .lock()
+            .execute(
+                "UPDATE settings SET value = ?1 WHERE key = ?2",
+                params![
+                    r#"[["X-Tenant-Token","tenant-secret"]]"#,
+                    KEY_CUSTOM_API_HEADERS
+                ],
+            )
+            .unwrap();
+        assert_eq!(store.load().unwrap().custom_api_headers, headers);
+    }
+
+    #[test]
+    fn test_webhook_secret_round_trips_through_storage() {
+        let store = SettingsStore {
+            conn: Mutex::new(Connection::open_in_memory().unwrap()),
+        };
+        store.init_schema().unwrap();
+        let settings = UserSettings {
+            webhook_secret: Some("signing-secret".to_string()),
+            ..UserSettings::default()
+        };
+        store.save(&settings).unwrap();
+
+        let raw: String = store
+            .conn
+            .lock()
+            .query_row(
+                "SELECT value FROM settings WHERE key = ?1",
+                params![KEY_WEBHOOK_SECRET],
+                |row| row.get(0),
+            )
+            .unwrap();
+        if crate::crypto::get_hardware_uuid().is_some() {
+            assert!(!raw.contains("signing-secret"));
+        }
+        assert_eq!(
+            store.load().unwrap().webhook_secret,
+            settings.webhook_secret
+        );
+
+        // A secret saved before encryption still loads
+        store
+            .conn
+            .lock()
+            .execute(
+                "UPDATE settings SET value = ?1 WHERE key = ?2",
+                params![r#""signing-secret""#, KEY_WEBHOOK_SECRET],
+            )
+            .unwrap();
+        assert_eq!(
+            store.load().unwrap().webhook_secret,
+            settings.webhook_secret
+        );
+
+        store.save(&UserSettings::default()).unwrap();
+        assert_eq!(store.load().unwrap().webhook_secret, None);
+    }
+
+    #[test]
+    fn test_legacy_whisper_mode_migrates_to_local() {
+        let store = SettingsStore {
+            conn: Mutex::new(Connection::open_in_memory().unwrap()),
+        };
+        store.init_schema().unwrap();
+        store
+            .conn
+            .lock()
+            .execute(
+                "INSERT INTO settings (key, value) VALUES (?1, '\"whisper\"')",
+                params![KEY_TRANSCRIPTION_MODE],
+            )
+            .unwrap();
+
+        // Older rows still parse before the migration has run
+        assert_eq!(
+            serde_json::from_str::<TranscriptionMode>("\"whisper\"").unwrap(),
+            TranscriptionMode::Local
+        );
+
+        store.init_schema().unwrap();
+        let raw: String = store
+            .conn
+            .lock()
+            .query_row(
+                "SELECT value FROM settings WHERE key = ?1",
+                params![KEY_TRANSCRIPTION_MODE],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(raw, "\"local\"");
+        assert_eq!(
+            store.load().unwrap().transcription_mode,
+            TranscriptionMode::Local
+        );
+    }
 }
@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+
+/// How shortcut strings such as `"Control+Space"` are rendered for the user.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ShortcutDisplayFormat {
+    #[default]
+    Raw,
+    Pretty,
+    Symbol,
+}
+
+pub fn format_shortcut(raw: &str, format: ShortcutDisplayFormat) -> String {
+    if matches!(format, ShortcutDisplayFormat::Raw) {
+        return raw.to_string();
+    }
+
+    let keys: Vec<&str> = raw
+        .split('+')
+        .map(|key| key.trim())
+        .filter(|key| !key.is_empty())
+        .collect();
+
+    match format {
+        ShortcutDisplayFormat::Raw => raw.to_string(),
+        ShortcutDisplayFormat::Pretty => keys
+            .iter()
+            .map(|key| pretty_key(key))
+            .collect::<Vec<_>>()
+            .join("+"),
+        ShortcutDisplayFormat::Symbol => keys.iter().map(|key| symbol_key(key)).collect(),
+    }
+}
+
+fn pretty_key(key: &str) -> String {
+    match key.to_ascii_lowercase().as_str() {
+        "control" | "ctrl" => "Ctrl".to_string(),
+        "alt" | "option" => {
+            if cfg!(target_os = "macos") {
+                "Option".to_string()
+            } else {
+                "Alt".to_string()
+            }
+        }
+        "command" | "cmd" | "super" | "meta" => {
+            if cfg!(target_os = "macos") {
+                "Cmd".to_string()
+            } else {
+                "Win".to_string()
+            }
+        }
+        "shift" => "Shift".to_string(),
+        _ => key.to_string(),
+    }
+}
+
+fn symbol_key(key: &str) -> String {
+    match key.to_ascii_lowercase().as_str() {
+        "control" | "ctrl" => "⌃".to_string(),
+        "shift" => "⇧".to_string(),
+        "alt" | "option" => "⌥".to_string(),
+        "command" | "cmd" | "super" | "meta" => "⌘".to_string(),
+        _ => key.to_string(),
+    }
+}
@@ -1,15 +1,33 @@
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::io;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
-use anyhow::{Context, Result};
-use chrono::{DateTime, Local, TimeZone};
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Days, Local, NaiveDate, TimeZone};
 use parking_lot::Mutex;
 use rusqlite::{params, types::Type, Connection, OptionalExtension, Row, ToSql};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::audio;
+
+const MAX_TAGS: usize = 20;
+const MAX_TAG_LENGTH: usize = 32;
+/// Only transcriptions made this close together are compared when looking for duplicates.
+const DUPLICATE_WINDOW_SECONDS: i64 = 60;
+/// Stands in for the text and audio path of a redacted transcription.
+const REDACTED_PLACEHOLDER: &str = "[redacted]";
+/// Deleted transcriptions can be restored for this long before they are purged.
+const DELETED_RETENTION_DAYS: i64 = 30;
+/// Longest span the report queries cover, to bound query cost. Longer requests are
+/// clamped to it.
+pub const MAX_REPORT_DAYS: u32 = 365;
+/// Size of the write-ahead log past which writes checkpoint it first.
+const WAL_CHECKPOINT_THRESHOLD_BYTES: u64 = 10 * 1024 * 1024;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TranscriptionRecord {
     pub id: String,
@@ -35,6 +53,30 @@ pub struct TranscriptionRecord {
     pub audio_duration_seconds: f32,
     #[serde(default)]
     pub synced: bool,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// User rating of the transcription: `Some(true)` good, `Some(false)` bad, `None` unrated
+    #[serde(default)]
+    pub feedback: Option<bool>,
+    /// The text before replacements were applied (after LLM cleanup, if any).
+    /// `None` when no replacement changed the transcript.
+    #[serde(default)]
+    pub replacement_applied_text: Option<String>,
+    /// Speech model confidence in the transcript, 0–1, if the engine reported one
+    #[serde(default)]
+    pub confidence: Option<f32>,
+    /// Language the speech model detected, when it was set to detect it
+    #[serde(default)]
+    pub detected_language: Option<String>,
+    /// Text and audio were wiped by the user; the row is kept so sync can see the redaction
+    #[serde(default)]
+    pub is_redacted: bool,
+    /// Marked by the user for review; LLM cleanup through a retry clears it
+    #[serde(default)]
+    pub flagged: bool,
+    /// When the user deleted the transcription; it can be restored until it is purged
+    #[serde(default)]
+    pub deleted_at: Option<DateTime<Local>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
@@ -44,6 +86,19 @@ pub enum TranscriptionStatus {
     Error,
 }
 
+/// Which live transcriptions `get_paginated` and `get_count` return. The default matches
+/// all of them except redacted ones.
+#[derive(Debug, Clone, Default)]
+pub struct TranscriptionFilter<'a> {
+    /// Substring of the text or raw text.
+    pub search_query: Option<&'a str>,
+    pub since: Option<DateTime<Local>>,
+    pub until: Option<DateTime<Local>>,
+    pub status: Option<TranscriptionStatus>,
+    pub include_redacted: bool,
+    pub flagged_only: bool,
+}
+
 impl TranscriptionStatus {
     fn as_str(&self) -> &'static str {
         match self {
@@ -61,8 +116,86 @@ impl TranscriptionStatus {
     }
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelUsageStats {
+    pub speech_model: String,
+    pub transcription_count: u32,
+    pub total_words: u32,
+    pub total_audio_seconds: f32,
+    pub avg_words_per_minute: f32,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct DailyStats {
+    /// Local calendar day as `YYYY-MM-DD`.
+    pub date: String,
+    pub count: u32,
+    pub words: u32,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct DailyWordCount {
+    /// Local calendar day as `YYYY-MM-DD`.
+    pub date: String,
+    pub words: u32,
+    pub recordings: u32,
+    pub total_seconds: f32,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct HeatmapCell {
+    /// Local day of the week, 0 being Sunday.
+    pub day_of_week: u8,
+    /// Local hour, 0–23.
+    pub hour_of_day: u8,
+    pub count: u32,
+    pub total_words: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchResult {
+    pub id: String,
+    pub timestamp: DateTime<Local>,
+    /// Text around the first match, with `…` where it was cut.
+    pub snippet: String,
+    pub match_count: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelAccuracy {
+    pub speech_model: String,
+    pub good: u32,
+    pub bad: u32,
+}
+
+impl ModelAccuracy {
+    pub fn total(&self) -> u32 {
+        self.good + self.bad
+    }
+
+    pub fn positive_percentage(&self) -> u32 {
+        if self.total() == 0 {
+            return 0;
+        }
+        ((self.good as f32 / self.total() as f32) * 100.0).round() as u32
+    }
+}
+
 pub struct StorageManager {
     connection: Arc<Mutex<Connection>>,
+    db_path: PathBuf,
+    /// Whether the `transcriptions_fts` index is available for text search.
+    fts_enabled: bool,
+    /// Whether inserts, updates and deletes checkpoint the write-ahead log once it passes
+    /// `WAL_CHECKPOINT_THRESHOLD_BYTES`.
+    auto_checkpoint: AtomicBool,
+}
+
+/// Frame counts reported by `PRAGMA wal_checkpoint`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct CheckpointStats {
+    pub log_frames: u32,
+    pub checkpointed_frames: u32,
 }
 
 #[derive(Debug, Clone)]
@@ -71,6 +204,8 @@ pub struct TranscriptionMetadata {
     pub llm_model: Option<String>,
     pub word_count: u32,
     pub audio_duration_seconds: f32,
+    pub confidence: Option<f32>,
+    pub detected_language: Option<String>,
 }
 
 impl Default for TranscriptionMetadata {
@@ -80,6 +215,8 @@ impl Default for TranscriptionMetadata {
             llm_model: None,
             word_count: 0,
             audio_duration_seconds: 0.0,
+            confidence: None,
+            detected_language: None,
         }
     }
 }
@@ -101,18 +238,33 @@ impl StorageManager {
 
         Self::configure_connection(&connection)?;
         Self::apply_migrations(&connection)?;
+        let fts_enabled = match Self::ensure_fts_index(&connection) {
+            Ok(()) => true,
+            Err(err) => {
+                eprintln!("Full-text search unavailable, falling back to LIKE: {err}");
+                false
+            }
+        };
 
         Ok(Self {
             connection: Arc::new(Mutex::new(connection)),
+            db_path,
+            fts_enabled,
+            auto_checkpoint: AtomicBool::new(true),
         })
     }
 
+    pub fn set_auto_checkpoint(&self, enabled: bool) {
+        self.auto_checkpoint.store(enabled, Ordering::Relaxed);
+    }
+
     pub fn save_transcription(
         &self,
         text: String,
         audio_path: String,
         status: TranscriptionStatus,
         error_message: Option<String>,
+        pre_replacement_text: Option<String>,
         metadata: TranscriptionMetadata,
     ) -> Result<TranscriptionRecord> {
         let record = TranscriptionRecord {
@@ -129,9 +281,18 @@ impl StorageManager {
             word_count: metadata.word_count,
             audio_duration_seconds: metadata.audio_duration_seconds,
             synced: false,
+            tags: Vec::new(),
+            feedback: None,
+            replacement_applied_text: pre_replacement_text,
+            confidence: metadata.confidence,
+            detected_language: metadata.detected_language,
+            is_redacted: false,
+            flagged: false,
+            deleted_at: None,
         };
 
         let conn = self.connection.lock();
+        self.checkpoint_if_needed(&conn);
         Self::insert_record(&conn, &record)?;
         Ok(record)
     }
@@ -143,6 +304,7 @@ impl StorageManager {
             return Ok(false);
         }
 
+        self.checkpoint_if_needed(&conn);
         Self::insert_record(&conn, &record)?;
         Ok(true)
     }
@@ -152,6 +314,7 @@ impl StorageManager {
         raw_text: String,
         cleaned_text: String,
         audio_path: String,
+        pre_replacement_text: Option<String>,
         metadata: TranscriptionMetadata,
     ) -> Result<TranscriptionRecord> {
         let record = TranscriptionRecord {
@@ -168,9 +331,18 @@ impl StorageManager {
             word_count: metadata.word_count,
             audio_duration_seconds: metadata.audio_duration_seconds,
             synced: false,
+            tags: Vec::new(),
+            feedback: None,
+            replacement_applied_text: pre_replacement_text,
+            confidence: metadata.confidence,
+            detected_language: metadata.detected_language,
+            is_redacted: false,
+            flagged: false,
+            deleted_at: None,
         };
 
         let conn = self.connection.lock();
+        self.checkpoint_if_needed(&conn);
         Self::insert_record(&conn, &record)?;
         Ok(record)
     }
@@ -182,14 +354,22 @@ impl StorageManager {
         llm_model: Option<String>,
     ) -> Result<Option<TranscriptionRecord>> {
         let conn = self.connection.lock();
+        self.checkpoint_if_needed(&conn);
         Self::apply_llm_cleanup(&conn, id, &cleaned_text, llm_model.as_deref())
     }
 
     pub fn revert_to_raw(&self, id: &str) -> Result<Option<TranscriptionRecord>> {
         let conn = self.connection.lock();
+        self.checkpoint_if_needed(&conn);
         Self::revert_to_raw_internal(&conn, id)
     }
 
+    pub fn revert_to_pre_replacement(&self, id: &str) -> Result<Option<TranscriptionRecord>> {
+        let conn = self.connection.lock();
+        self.checkpoint_if_needed(&conn);
+        Self::revert_to_pre_replacement_internal(&conn, id)
+    }
+
     pub fn mark_as_synced(&self, id: &str) -> Result<()> {
         let conn = self.connection.lock();
         conn.execute(
@@ -199,241 +379,1258 @@ impl StorageManager {
         Ok(())
     }
 
-    pub fn get_all(&self) -> Vec<TranscriptionRecord> {
-        match self.load_all_from_db() {
-            Ok(records) => records,
-            Err(err) => {
-                eprintln!("Failed to load transcriptions: {err}");
-                Vec::new()
-            }
+    pub fn set_tags(&self, id: &str, tags: Vec<String>) -> Result<Option<TranscriptionRecord>> {
+        if tags.len() > MAX_TAGS {
+            return Err(anyhow!("A transcription can have at most {MAX_TAGS} tags"));
+        }
+        if let Some(tag) = tags.iter().find(|tag| tag.chars().count() > MAX_TAG_LENGTH) {
+            return Err(anyhow!(
+                "Tag \"{tag}\" is longer than {MAX_TAG_LENGTH} characters"
+            ));
+        }
+        if tags.iter().any(|tag| tag.is_empty() || tag.contains(',')) {
+            return Err(anyhow!("Tags cannot be empty or contain commas"));
         }
-    }
 
-    pub fn delete(&self, id: &str) -> Result<Option<String>> {
         let conn = self.connection.lock();
-        let record = Self::get_record(&conn, id)?;
-        if record.is_some() {
-            conn.execute("DELETE FROM transcriptions WHERE id = ?1", params![id])?;
+        self.checkpoint_if_needed(&conn);
+        let updated = conn.execute(
+            "UPDATE transcriptions SET tags = ?1 WHERE id = ?2 AND deleted_at IS NULL",
+            params![tags.join(","), id],
+        )?;
+        if updated == 0 {
+            return Ok(None);
         }
-        Ok(record.map(|r| r.audio_path))
+        Self::get_record(&conn, id)
+    }
+
+    pub fn get_by_tag(&self, tag: &str) -> Result<Vec<TranscriptionRecord>> {
+        let conn = self.connection.lock();
+        let escaped = tag
+            .trim()
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_");
+        let mut stmt = conn.prepare(
+            "SELECT id, timestamp, text, raw_text, audio_path, status, error_message, llm_cleaned,
+                    speech_model, llm_model, word_count, audio_duration_seconds, synced, tags,
+                    feedback, pre_replacement_text, confidence, detected_language, is_redacted,
+                    flagged, deleted_at
+             FROM transcriptions
+             WHERE deleted_at IS NULL AND ',' || tags || ',' LIKE ?1 ESCAPE '\\'
+             ORDER BY timestamp DESC",
+        )?;
+
+        let records = stmt
+            .query_map(params![format!("%,{escaped},%")], |row| {
+                Self::record_from_row(row)
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(records)
     }
 
-    /// Delete all transcription records and return their audio paths
-    pub fn delete_all(&self) -> Result<Vec<String>> {
+    /// Failed transcriptions whose recording is still on disk, oldest first.
+    pub fn get_all_failed(&self) -> Result<Vec<TranscriptionRecord>> {
         let conn = self.connection.lock();
-        let mut stmt = conn.prepare("SELECT audio_path FROM transcriptions")?;
-        let paths = stmt
-            .query_map([], |row| row.get(0))?
-            .collect::<rusqlite::Result<Vec<String>>>()?;
-        conn.execute("DELETE FROM transcriptions", [])?;
-        Ok(paths)
+        let mut stmt = conn.prepare(
+            "SELECT id, timestamp, text, raw_text, audio_path, status, error_message, llm_cleaned,
+                    speech_model, llm_model, word_count, audio_duration_seconds, synced, tags,
+                    feedback, pre_replacement_text, confidence, detected_language, is_redacted,
+                    flagged, deleted_at
+             FROM transcriptions
+             WHERE status = 'error' AND deleted_at IS NULL
+             ORDER BY timestamp ASC",
+        )?;
+
+        let records = stmt
+            .query_map([], Self::record_from_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(records
+            .into_iter()
+            .filter(|record| Path::new(&record.audio_path).is_file())
+            .collect())
     }
 
-    pub fn get_by_id(&self, id: &str) -> Option<TranscriptionRecord> {
+    pub fn set_feedback(&self, id: &str, positive: bool) -> Result<Option<TranscriptionRecord>> {
         let conn = self.connection.lock();
-        match Self::get_record(&conn, id) {
-            Ok(record) => record,
-            Err(err) => {
-                eprintln!("Failed to read transcription {id}: {err}");
-                None
-            }
+        self.checkpoint_if_needed(&conn);
+        let updated = conn.execute(
+            "UPDATE transcriptions SET feedback = ?1 WHERE id = ?2 AND deleted_at IS NULL",
+            params![if positive { 1 } else { 0 }, id],
+        )?;
+        if updated == 0 {
+            return Ok(None);
         }
+        Self::get_record(&conn, id)
     }
 
-    pub fn get_paginated(
-        &self,
-        limit: u32,
-        offset: u32,
-        search_query: Option<&str>,
-    ) -> Result<Vec<TranscriptionRecord>> {
+    /// Flag or unflag a transcription for review. Returns `false` if no live transcription
+    /// has that id.
+    pub fn set_flagged(&self, id: &str, flagged: bool) -> Result<bool> {
         let conn = self.connection.lock();
-        let (where_clause, params) = Self::build_search_query(search_query);
+        self.checkpoint_if_needed(&conn);
+        let updated = conn.execute(
+            "UPDATE transcriptions SET flagged = ?1 WHERE id = ?2 AND deleted_at IS NULL",
+            params![if flagged { 1 } else { 0 }, id],
+        )?;
+        Ok(updated > 0)
+    }
 
-        let sql = format!(
+    /// Transcriptions flagged for review, newest first.
+    pub fn get_flagged(&self) -> Result<Vec<TranscriptionRecord>> {
+        let conn = self.connection.lock();
+        let mut stmt = conn.prepare(
             "SELECT id, timestamp, text, raw_text, audio_path, status, error_message, llm_cleaned,
-                    speech_model, llm_model, word_count, audio_duration_seconds, synced
+                    speech_model, llm_model, word_count, audio_duration_seconds, synced, tags,
+                    feedback, pre_replacement_text, confidence, detected_language, is_redacted,
+                    flagged, deleted_at
              FROM transcriptions
-             {}
-             ORDER BY timestamp DESC
-             LIMIT ?{} OFFSET ?{}",
-            where_clause,
-            params.len() + 1,
-            params.len() + 2
-        );
-
-        let mut stmt = conn.prepare(&sql)?;
-        let mut query_params = params;
-        query_params.push(Box::new(limit));
-        query_params.push(Box::new(offset));
+             WHERE flagged = 1 AND deleted_at IS NULL
+             ORDER BY timestamp DESC",
+        )?;
 
         let records = stmt
-            .query_map(rusqlite::params_from_iter(query_params.iter()), |row| {
-                Self::record_from_row(row)
-            })?
+            .query_map([], Self::record_from_row)?
             .collect::<rusqlite::Result<Vec<_>>>()?;
-
         Ok(records)
     }
 
-    pub fn get_count(&self, search_query: Option<&str>) -> Result<usize> {
+    /// Count positive and negative ratings per speech model.
+    pub fn get_model_accuracy_report(&self) -> Result<Vec<ModelAccuracy>> {
         let conn = self.connection.lock();
-        let (where_clause, params) = Self::build_search_query(search_query);
+        let mut stmt = conn.prepare(
+            "SELECT speech_model,
+                    SUM(CASE WHEN feedback = 1 THEN 1 ELSE 0 END) AS good,
+                    SUM(CASE WHEN feedback = 0 THEN 1 ELSE 0 END) AS bad
+             FROM transcriptions
+             WHERE feedback IS NOT NULL AND deleted_at IS NULL
+             GROUP BY speech_model
+             ORDER BY speech_model",
+        )?;
 
-        let sql = format!("SELECT COUNT(*) FROM transcriptions {}", where_clause);
+        let report = stmt
+            .query_map([], |row| {
+                Ok(ModelAccuracy {
+                    speech_model: row.get("speech_model")?,
+                    good: row.get::<_, i64>("good")? as u32,
+                    bad: row.get::<_, i64>("bad")? as u32,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(report)
+    }
 
-        let mut stmt = conn.prepare(&sql)?;
-        let count: usize =
-            stmt.query_row(rusqlite::params_from_iter(params.iter()), |row| row.get(0))?;
+    /// Totals for successful transcriptions, grouped by speech model.
+    pub fn get_usage_stats_by_model(&self) -> Result<Vec<ModelUsageStats>> {
+        let conn = self.connection.lock();
+        let mut stmt = conn.prepare(
+            "SELECT speech_model,
+                    COUNT(*) AS transcription_count,
+                    COALESCE(SUM(word_count), 0) AS total_words,
+                    COALESCE(SUM(audio_duration_seconds), 0) AS total_audio_seconds
+             FROM transcriptions
+             WHERE status = 'success' AND deleted_at IS NULL
+             GROUP BY speech_model
+             ORDER BY transcription_count DESC, speech_model",
+        )?;
 
-        Ok(count)
+        let stats = stmt
+            .query_map([], |row| {
+                let total_words = row.get::<_, i64>("total_words")? as u32;
+                let total_audio_seconds = row.get::<_, f64>("total_audio_seconds")? as f32;
+                let avg_words_per_minute = if total_audio_seconds > 0.0 {
+                    total_words as f32 / (total_audio_seconds / 60.0)
+                } else {
+                    0.0
+                };
+                Ok(ModelUsageStats {
+                    speech_model: row.get("speech_model")?,
+                    transcription_count: row.get::<_, i64>("transcription_count")? as u32,
+                    total_words,
+                    total_audio_seconds,
+                    avg_words_per_minute,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(stats)
     }
 
-    fn build_search_query(search_query: Option<&str>) -> (String, Vec<Box<dyn ToSql>>) {
-        if let Some(query) = search_query {
-            if !query.trim().is_empty() {
-                let like_query = format!("%{}%", query.trim());
-                return (
-                    "WHERE text LIKE ?1 OR raw_text LIKE ?1".to_string(),
-                    vec![Box::new(like_query)],
-                );
-            }
-        }
-        ("".to_string(), Vec::new())
+    /// Successful transcriptions per local day over the last `days` days (at most
+    /// [`MAX_REPORT_DAYS`]), oldest first. Days without transcriptions are included
+    /// with zero counts.
+    pub fn get_usage_stats_by_day(&self, days: u32) -> Result<Vec<DailyStats>> {
+        Ok(self
+            .get_daily_word_counts(days)?
+            .into_iter()
+            .map(|day| DailyStats {
+                date: day.date,
+                count: day.recordings,
+                words: day.words,
+            })
+            .collect())
     }
 
-    fn insert_record(conn: &Connection, record: &TranscriptionRecord) -> Result<()> {
-        let timestamp = record.timestamp.timestamp_millis();
-        conn.execute(
-            "INSERT INTO transcriptions (
-                id,
-                timestamp,
-                text,
-                raw_text,
-                audio_path,
-                status,
-                error_message,
-                llm_cleaned,
-                speech_model,
-                llm_model,
-                word_count,
-                audio_duration_seconds,
-                synced
-             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
-            params![
-                record.id,
-                timestamp,
-                record.text,
-                record.raw_text,
-                record.audio_path,
-                record.status.as_str(),
-                record.error_message,
-                if record.llm_cleaned { 1 } else { 0 },
-                record.speech_model,
-                record.llm_model,
-                record.word_count as i64,
-                record.audio_duration_seconds as f64,
-                if record.synced { 1 } else { 0 },
-            ],
+    /// Successful transcriptions grouped by local day of week and hour: all 7×24 cells,
+    /// Sunday midnight first, with empty ones as zeros.
+    pub fn get_heatmap_data(&self) -> Result<Vec<HeatmapCell>> {
+        let conn = self.connection.lock();
+        let mut stmt = conn.prepare(
+            "SELECT CAST(strftime('%w', timestamp / 1000, 'unixepoch', 'localtime') AS INTEGER) AS dow,
+                    CAST(strftime('%H', timestamp / 1000, 'unixepoch', 'localtime') AS INTEGER) AS hour,
+                    COUNT(*) AS count,
+                    COALESCE(SUM(word_count), 0) AS words
+             FROM transcriptions
+             WHERE status = 'success' AND deleted_at IS NULL
+             GROUP BY dow, hour",
         )?;
-        Ok(())
-    }
-
-    fn apply_llm_cleanup(
-        conn: &Connection,
-        id: &str,
-        cleaned_text: &str,
-        llm_model: Option<&str>,
-    ) -> Result<Option<TranscriptionRecord>> {
-        if let Some(mut record) = Self::get_record(conn, id)? {
-            if record.raw_text.is_none() {
-                record.raw_text = Some(record.text.clone());
-            }
-            record.text = cleaned_text.to_string();
-            record.llm_cleaned = true;
-            record.llm_model = llm_model.map(|value| value.to_string());
-            record.word_count = count_words(&record.text);
-            record.synced = false;
+        let totals = stmt
+            .query_map([], |row| {
+                Ok((
+                    (row.get::<_, u8>("dow")?, row.get::<_, u8>("hour")?),
+                    (
+                        row.get::<_, i64>("count")? as u32,
+                        row.get::<_, i64>("words")? as u32,
+                    ),
+                ))
+            })?
+            .collect::<rusqlite::Result<HashMap<_, _>>>()?;
 
-            conn.execute(
-                "UPDATE transcriptions
-                 SET text = ?1, raw_text = ?2, llm_cleaned = 1, llm_model = ?3, word_count = ?4, synced = 0
-                 WHERE id = ?5",
-                params![
-                    record.text,
-                    record.raw_text,
-                    record.llm_model,
-                    record.word_count as i64,
-                    id
-                ],
-            )?;
+        Ok(fill_heatmap(&totals))
+    }
 
-            Ok(Some(record))
-        } else {
-            Ok(None)
-        }
+    /// Local hour with the most successful transcriptions across all days, earliest on a
+    /// tie, or `None` before the first one.
+    pub fn get_peak_recording_hour(&self) -> Result<Option<u8>> {
+        Ok(peak_hour(&self.get_heatmap_data()?))
     }
 
-    fn revert_to_raw_internal(conn: &Connection, id: &str) -> Result<Option<TranscriptionRecord>> {
-        if let Some(mut record) = Self::get_record(conn, id)? {
-            if let Some(raw) = record.raw_text.take() {
-                record.text = raw;
-                record.llm_cleaned = false;
-                record.word_count = count_words(&record.text);
-                record.llm_model = None;
-                record.synced = false;
-                conn.execute(
-                    "UPDATE transcriptions
-                     SET text = ?1, raw_text = NULL, llm_cleaned = 0, llm_model = NULL, word_count = ?2, synced = 0
-                     WHERE id = ?3",
-                    params![record.text, record.word_count as i64, id],
-                )?;
-                return Ok(Some(record));
-            }
+    /// Words, recordings and audio seconds of successful transcriptions for each of the
+    /// last `days` local days (at most [`MAX_REPORT_DAYS`]), oldest first. Days without
+    /// recordings are included as zeros.
+    pub fn get_daily_word_counts(&self, days: u32) -> Result<Vec<DailyWordCount>> {
+        let days = days.min(MAX_REPORT_DAYS);
+        if days == 0 {
+            return Ok(Vec::new());
         }
-        Ok(None)
+
+        let today = Local::now().date_naive();
+        let first_day = today
+            .checked_sub_days(Days::new(u64::from(days - 1)))
+            .unwrap_or(NaiveDate::MIN);
+
+        let conn = self.connection.lock();
+        let mut stmt = conn.prepare(
+            "SELECT strftime('%Y-%m-%d', timestamp / 1000, 'unixepoch', 'localtime') AS day,
+                    COALESCE(SUM(word_count), 0) AS words,
+                    COUNT(*) AS recordings,
+                    COALESCE(SUM(audio_duration_seconds), 0) AS total_seconds
+             FROM transcriptions
+             WHERE status = 'success' AND deleted_at IS NULL
+               AND strftime('%Y-%m-%d', timestamp / 1000, 'unixepoch', 'localtime') >= ?1
+             GROUP BY day",
+        )?;
+        let totals = stmt
+            .query_map(params![first_day.format("%Y-%m-%d").to_string()], |row| {
+                Ok((
+                    row.get::<_, String>("day")?,
+                    (
+                        row.get::<_, i64>("words")? as u32,
+                        row.get::<_, i64>("recordings")? as u32,
+                        row.get::<_, f64>("total_seconds")? as f32,
+                    ),
+                ))
+            })?
+            .collect::<rusqlite::Result<HashMap<_, _>>>()?;
+
+        Ok(first_day
+            .iter_days()
+            .take_while(|day| *day <= today)
+            .map(|day| {
+                let date = day.format("%Y-%m-%d").to_string();
+                let (words, recordings, total_seconds) =
+                    totals.get(&date).copied().unwrap_or((0, 0, 0.0));
+                DailyWordCount {
+                    date,
+                    words,
+                    recordings,
+                    total_seconds,
+                }
+            })
+            .collect())
     }
 
-    fn get_record(conn: &Connection, id: &str) -> Result<Option<TranscriptionRecord>> {
-        conn.query_row(
-            "SELECT id, timestamp, text, raw_text, audio_path, status, error_message, llm_cleaned,
-                    speech_model, llm_model, word_count, audio_duration_seconds, synced
-             FROM transcriptions WHERE id = ?1",
-            params![id],
-            |row| Self::record_from_row(row),
-        )
-        .optional()
-        .map_err(Into::into)
+    pub fn mark_webhook_pending(&self, id: &str, pending: bool) -> Result<()> {
+        let conn = self.connection.lock();
+        conn.execute(
+            "UPDATE transcriptions SET webhook_pending = ?1 WHERE id = ?2",
+            params![if pending { 1 } else { 0 }, id],
+        )?;
+        Ok(())
     }
 
-    fn load_all_from_db(&self) -> Result<Vec<TranscriptionRecord>> {
+    /// Transcriptions whose webhook send failed with a retriable error, oldest first.
+    pub fn get_pending_webhooks(&self) -> Result<Vec<TranscriptionRecord>> {
         let conn = self.connection.lock();
         let mut stmt = conn.prepare(
             "SELECT id, timestamp, text, raw_text, audio_path, status, error_message, llm_cleaned,
-                    speech_model, llm_model, word_count, audio_duration_seconds, synced
-             FROM transcriptions ORDER BY timestamp DESC",
+                    speech_model, llm_model, word_count, audio_duration_seconds, synced, tags,
+                    feedback, pre_replacement_text, confidence, detected_language, is_redacted,
+                    flagged, deleted_at
+             FROM transcriptions
+             WHERE webhook_pending = 1 AND deleted_at IS NULL
+             ORDER BY timestamp ASC",
         )?;
 
         let records = stmt
-            .query_map([], |row| Self::record_from_row(row))?
+            .query_map([], Self::record_from_row)?
             .collect::<rusqlite::Result<Vec<_>>>()?;
         Ok(records)
     }
 
-    fn record_from_row(row: &Row<'_>) -> rusqlite::Result<TranscriptionRecord> {
-        let timestamp_ms: i64 = row.get("timestamp")?;
-        let timestamp = Local
-            .timestamp_millis_opt(timestamp_ms)
-            .single()
-            .ok_or_else(|| {
-                rusqlite::Error::FromSqlConversionFailure(
-                    0,
-                    Type::Integer,
-                    Box::new(io::Error::new(
-                        io::ErrorKind::InvalidData,
-                        format!("Invalid timestamp stored in database: {timestamp_ms}"),
-                    )) as Box<dyn std::error::Error + Send + Sync + 'static>,
-                )
-            })?;
+    pub fn get_all(&self) -> Vec<TranscriptionRecord> {
+        match self.load_all_from_db() {
+            Ok(records) => records,
+            Err(err) => {
+                eprintln!("Failed to load transcriptions: {err}");
+                Vec::new()
+            }
+        }
+    }
+
+    /// Move a transcription to the recently deleted list, where it stays restorable for
+    /// [`DELETED_RETENTION_DAYS`]. Returns `false` when there is no such live transcription.
+    pub fn delete(&self, id: &str) -> Result<bool> {
+        let conn = self.connection.lock();
+        self.checkpoint_if_needed(&conn);
+        let updated = conn.execute(
+            "UPDATE transcriptions SET deleted_at = ?1 WHERE id = ?2 AND deleted_at IS NULL",
+            params![Local::now().timestamp_millis(), id],
+        )?;
+        Ok(updated > 0)
+    }
+
+    /// Remove a transcription's row outright, skipping the recovery window. Returns its audio
+    /// path, or `None` when there is no such transcription.
+    pub fn delete_permanently(&self, id: &str) -> Result<Option<String>> {
+        let conn = self.connection.lock();
+        self.checkpoint_if_needed(&conn);
+        let record = Self::get_record(&conn, id)?;
+        if record.is_some() {
+            conn.execute("DELETE FROM transcriptions WHERE id = ?1", params![id])?;
+        }
+        Ok(record.map(|r| r.audio_path))
+    }
+
+    /// Bring back a deleted transcription. Returns `false` when it isn't in the recently
+    /// deleted list.
+    pub fn restore(&self, id: &str) -> Result<bool> {
+        let conn = self.connection.lock();
+        let updated = conn.execute(
+            "UPDATE transcriptions SET deleted_at = NULL WHERE id = ?1 AND deleted_at IS NOT NULL",
+            params![id],
+        )?;
+        Ok(updated > 0)
+    }
+
+    /// Deleted transcriptions that can still be restored, most recently deleted first.
+    pub fn get_deleted(&self) -> Result<Vec<TranscriptionRecord>> {
+        let conn = self.connection.lock();
+        let mut stmt = conn.prepare(
+            "SELECT id, timestamp, text, raw_text, audio_path, status, error_message, llm_cleaned,
+                    speech_model, llm_model, word_count, audio_duration_seconds, synced, tags,
+                    feedback, pre_replacement_text, confidence, detected_language, is_redacted,
+                    flagged, deleted_at
+             FROM transcriptions
+             WHERE deleted_at IS NOT NULL
+             ORDER BY deleted_at DESC",
+        )?;
+
+        let records = stmt
+            .query_map([], Self::record_from_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(records)
+    }
+
+    /// Hard-delete transcriptions deleted more than [`DELETED_RETENTION_DAYS`] ago, along
+    /// with recordings no remaining transcription shares. Returns the number purged.
+    pub fn purge_old_deleted(&self) -> Result<u32> {
+        let cutoff = Local::now() - chrono::Duration::days(DELETED_RETENTION_DAYS);
+        let mut conn = self.connection.lock();
+        let tx = conn.transaction()?;
+        let audio_paths = {
+            let mut stmt = tx.prepare(
+                "SELECT audio_path FROM transcriptions
+                 WHERE deleted_at IS NOT NULL AND deleted_at < ?1",
+            )?;
+            let paths = stmt
+                .query_map(params![cutoff.timestamp_millis()], |row| row.get(0))?
+                .collect::<rusqlite::Result<Vec<String>>>()?;
+            paths
+        };
+        let purged = tx.execute(
+            "DELETE FROM transcriptions WHERE deleted_at IS NOT NULL AND deleted_at < ?1",
+            params![cutoff.timestamp_millis()],
+        )?;
+        // A merged record keeps its first part's audio, and a retry shares the original's
+        let mut orphaned = Vec::new();
+        for audio_path in audio_paths {
+            let in_use: bool = tx.query_row(
+                "SELECT EXISTS(SELECT 1 FROM transcriptions WHERE audio_path = ?1)",
+                params![audio_path],
+                |row| row.get(0),
+            )?;
+            if !in_use && !orphaned.contains(&audio_path) {
+                orphaned.push(audio_path);
+            }
+        }
+        tx.commit()?;
+
+        for audio_path in &orphaned {
+            if !audio_path.is_empty() {
+                let _ = fs::remove_file(audio_path);
+            }
+        }
+        Ok(purged as u32)
+    }
+
+    /// Wipe the text and audio path of a transcription but keep its row, so cloud sync
+    /// picks up the redaction instead of a deletion. Returns the audio path it had, for the
+    /// caller to remove, or `None` when there is no such transcription.
+    pub fn anonymize_transcription(&self, id: &str) -> Result<Option<String>> {
+        let mut conn = self.connection.lock();
+        self.checkpoint_if_needed(&conn);
+        let tx = conn.transaction()?;
+        let Some(record) = Self::get_live_record(&tx, id)? else {
+            return Ok(None);
+        };
+        tx.execute(
+            "UPDATE transcriptions
+             SET text = ?1, raw_text = ?1, audio_path = ?1, pre_replacement_text = NULL,
+                 word_count = 0, audio_duration_seconds = 0, is_redacted = 1, synced = 0
+             WHERE id = ?2 AND deleted_at IS NULL",
+            params![REDACTED_PLACEHOLDER, id],
+        )?;
+        tx.commit()?;
+        Ok(Some(record.audio_path))
+    }
+
+    /// Replace several successful transcriptions, such as a long recording made in chunks,
+    /// with one record. Texts are joined oldest first and the first record's audio is kept.
+    /// The originals move to the recently deleted list.
+    pub fn merge_transcriptions(&self, ids: Vec<String>) -> Result<TranscriptionRecord> {
+        let mut ids = ids;
+        ids.sort();
+        ids.dedup();
+        if ids.len() < 2 {
+            return Err(anyhow!("At least two transcriptions are needed to merge"));
+        }
+
+        let mut conn = self.connection.lock();
+        self.checkpoint_if_needed(&conn);
+        let tx = conn.transaction()?;
+
+        let mut records = Vec::with_capacity(ids.len());
+        for id in &ids {
+            let record = Self::get_live_record(&tx, id)?
+                .ok_or_else(|| anyhow!("Transcription not found: {id}"))?;
+            if record.status != TranscriptionStatus::Success {
+                return Err(anyhow!("Only successful transcriptions can be merged"));
+            }
+            records.push(record);
+        }
+        records.sort_by_key(|record| record.timestamp);
+
+        let mut tags: Vec<String> = Vec::new();
+        for tag in records.iter().flat_map(|record| &record.tags) {
+            if !tags.contains(tag) {
+                tags.push(tag.clone());
+            }
+        }
+        tags.truncate(MAX_TAGS);
+
+        let first = &records[0];
+        let merged = TranscriptionRecord {
+            id: Uuid::new_v4().to_string(),
+            timestamp: first.timestamp,
+            text: records
+                .iter()
+                .map(|record| record.text.trim())
+                .filter(|text| !text.is_empty())
+                .collect::<Vec<_>>()
+                .join(" "),
+            raw_text: None,
+            audio_path: first.audio_path.clone(),
+            status: TranscriptionStatus::Success,
+            error_message: None,
+            llm_cleaned: false,
+            speech_model: first.speech_model.clone(),
+            llm_model: None,
+            word_count: records.iter().map(|record| record.word_count).sum(),
+            audio_duration_seconds: records
+                .iter()
+                .map(|record| record.audio_duration_seconds)
+                .sum(),
+            synced: false,
+            tags,
+            feedback: None,
+            replacement_applied_text: None,
+            confidence: None,
+            detected_language: first.detected_language.clone(),
+            is_redacted: false,
+            flagged: records.iter().any(|record| record.flagged),
+            deleted_at: None,
+        };
+
+        let deleted_at = Local::now().timestamp_millis();
+        for record in &records {
+            tx.execute(
+                "UPDATE transcriptions SET deleted_at = ?1 WHERE id = ?2",
+                params![deleted_at, record.id],
+            )?;
+        }
+        Self::insert_record(&tx, &merged)?;
+        tx.commit()?;
+
+        Ok(merged)
+    }
+
+    /// Pairs of successful transcriptions made within a minute of each other whose word
+    /// sets have a Jaccard similarity of at least `similarity_threshold`, as
+    /// `(older_id, newer_id, similarity)`.
+    pub fn find_near_duplicates(
+        &self,
+        similarity_threshold: f32,
+    ) -> Result<Vec<(String, String, f32)>> {
+        let mut records = self.load_all_from_db()?;
+        records.retain(|record| {
+            record.status == TranscriptionStatus::Success && !record.text.trim().is_empty()
+        });
+        records.sort_by_key(|record| record.timestamp);
+        let word_sets: Vec<HashSet<String>> = records
+            .iter()
+            .map(|record| word_set(&record.text))
+            .collect();
+
+        let window = chrono::Duration::seconds(DUPLICATE_WINDOW_SECONDS);
+        let mut pairs = Vec::new();
+        for (i, record) in records.iter().enumerate() {
+            for (j, other) in records.iter().enumerate().skip(i + 1) {
+                // Sorted by time, so everything after this is further apart too
+                if other.timestamp - record.timestamp > window {
+                    break;
+                }
+                let similarity = jaccard_similarity(&word_sets[i], &word_sets[j]);
+                if similarity >= similarity_threshold {
+                    pairs.push((record.id.clone(), other.id.clone(), similarity));
+                }
+            }
+        }
+        Ok(pairs)
+    }
+
+    /// Move whichever of two transcriptions has fewer words to the recently deleted list,
+    /// keeping the newer one on a tie. Returns the survivor and the deleted record.
+    pub fn delete_duplicate_keeping_longer(
+        &self,
+        id1: &str,
+        id2: &str,
+    ) -> Result<(TranscriptionRecord, TranscriptionRecord)> {
+        if id1 == id2 {
+            return Err(anyhow!("A transcription can't be a duplicate of itself"));
+        }
+        let conn = self.connection.lock();
+        self.checkpoint_if_needed(&conn);
+        let first = Self::get_live_record(&conn, id1)?
+            .ok_or_else(|| anyhow!("Transcription {id1} not found"))?;
+        let second = Self::get_live_record(&conn, id2)?
+            .ok_or_else(|| anyhow!("Transcription {id2} not found"))?;
+
+        let first_survives =
+            (first.word_count, first.timestamp) > (second.word_count, second.timestamp);
+        let (survivor, removed) = if first_survives {
+            (first, second)
+        } else {
+            (second, first)
+        };
+        conn.execute(
+            "UPDATE transcriptions SET deleted_at = ?1 WHERE id = ?2",
+            params![Local::now().timestamp_millis(), removed.id],
+        )?;
+        Ok((survivor, removed))
+    }
+
+    /// Move every live transcription to the recently deleted list. Returns how many were
+    /// deleted.
+    pub fn delete_all(&self) -> Result<u32> {
+        let conn = self.connection.lock();
+        self.checkpoint_if_needed(&conn);
+        let updated = conn.execute(
+            "UPDATE transcriptions SET deleted_at = ?1 WHERE deleted_at IS NULL",
+            params![Local::now().timestamp_millis()],
+        )?;
+        Ok(updated as u32)
+    }
+
+    /// Rebuild the database file to hand back space left behind by deletions.
+    /// Returns the number of bytes freed.
+    pub fn vacuum(&self) -> Result<u64> {
+        // VACUUM needs exclusive access, so hold the connection for the whole operation
+        let conn = self.connection.lock();
+        conn.execute_batch("PRAGMA wal_checkpoint(TRUNCATE);")?;
+        let before = file_size(&self.db_path);
+
+        conn.execute_batch("PRAGMA optimize;\nVACUUM;")?;
+        if self.fts_enabled {
+            // VACUUM may renumber the rowids the index is keyed on
+            Self::rebuild_fts_index(&conn)?;
+        }
+        // In WAL mode the rebuilt pages land in the log until checkpointed
+        conn.execute_batch("PRAGMA wal_checkpoint(TRUNCATE);")?;
+        let after = file_size(&self.db_path);
+
+        Ok(before.saturating_sub(after))
+    }
+
+    /// Size of the database on disk, including its write-ahead log.
+    pub fn db_size_bytes(&self) -> u64 {
+        file_size(&self.db_path) + file_size(&self.wal_path())
+    }
+
+    fn wal_path(&self) -> PathBuf {
+        let mut wal_path = self.db_path.clone().into_os_string();
+        wal_path.push("-wal");
+        PathBuf::from(wal_path)
+    }
+
+    /// Copy every frame in the write-ahead log back into the database file.
+    pub fn checkpoint(&self) -> Result<CheckpointStats> {
+        let conn = self.connection.lock();
+        Self::run_checkpoint(&conn)
+    }
+
+    fn run_checkpoint(conn: &Connection) -> Result<CheckpointStats> {
+        // The row is (busy, log frames, checkpointed frames). A busy checkpoint still reports
+        // how far it got, and both counts are -1 when the database isn't in WAL mode.
+        let (log_frames, checkpointed_frames) =
+            conn.query_row("PRAGMA wal_checkpoint(FULL)", [], |row| {
+                Ok((row.get::<_, i64>(1)?, row.get::<_, i64>(2)?))
+            })?;
+        Ok(CheckpointStats {
+            log_frames: log_frames.max(0) as u32,
+            checkpointed_frames: checkpointed_frames.max(0) as u32,
+        })
+    }
+
+    /// Checkpoint before a write when auto-checkpointing is on and the log has grown too large.
+    fn checkpoint_if_needed(&self, conn: &Connection) {
+        if self.auto_checkpoint.load(Ordering::Relaxed) {
+            self.truncate_wal_past(conn, WAL_CHECKPOINT_THRESHOLD_BYTES);
+        }
+    }
+
+    /// Checkpoint and truncate the write-ahead log once it is at least `threshold` bytes,
+    /// returning whether it did. A FULL checkpoint leaves the file at its old size, so every
+    /// later write would find it over the threshold again.
+    fn truncate_wal_past(&self, conn: &Connection, threshold: u64) -> bool {
+        if file_size(&self.wal_path()) < threshold {
+            return false;
+        }
+        if let Err(err) = conn.execute_batch("PRAGMA wal_checkpoint(TRUNCATE);") {
+            eprintln!("Failed to checkpoint transcription database: {err}");
+        }
+        true
+    }
+
+    /// Write a consistent snapshot of the database to `dest`, which must not exist yet.
+    /// Unlike copying the file, this includes anything still sitting in the write-ahead log.
+    pub fn backup_to(&self, dest: &Path) -> Result<()> {
+        let conn = self.connection.lock();
+        conn.execute("VACUUM INTO ?1", params![dest.to_string_lossy()])
+            .with_context(|| format!("Failed to write database backup to {}", dest.display()))?;
+        Ok(())
+    }
+
+    pub fn get_by_id(&self, id: &str) -> Option<TranscriptionRecord> {
+        let conn = self.connection.lock();
+        match Self::get_live_record(&conn, id) {
+            Ok(record) => record,
+            Err(err) => {
+                eprintln!("Failed to read transcription {id}: {err}");
+                None
+            }
+        }
+    }
+
+    pub fn get_paginated(
+        &self,
+        limit: u32,
+        offset: u32,
+        filter: &TranscriptionFilter,
+    ) -> Result<Vec<TranscriptionRecord>> {
+        let conn = self.connection.lock();
+        let (where_clause, params) = Self::build_filter_query(filter, self.fts_enabled);
+
+        let sql = format!(
+            "SELECT id, timestamp, text, raw_text, audio_path, status, error_message, llm_cleaned,
+                    speech_model, llm_model, word_count, audio_duration_seconds, synced, tags,
+                    feedback, pre_replacement_text, confidence, detected_language, is_redacted,
+                    flagged, deleted_at
+             FROM transcriptions
+             {}
+             ORDER BY timestamp DESC
+             LIMIT ?{} OFFSET ?{}",
+            where_clause,
+            params.len() + 1,
+            params.len() + 2
+        );
+
+        let mut stmt = conn.prepare(&sql)?;
+        let mut query_params = params;
+        query_params.push(Box::new(limit));
+        query_params.push(Box::new(offset));
+
+        let records = stmt
+            .query_map(rusqlite::params_from_iter(query_params.iter()), |row| {
+                Self::record_from_row(row)
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(records)
+    }
+
+    /// Most recent transcription that completed successfully.
+    pub fn get_latest_successful(&self) -> Result<Option<TranscriptionRecord>> {
+        let records = self.get_paginated(
+            1,
+            0,
+            &TranscriptionFilter {
+                status: Some(TranscriptionStatus::Success),
+                ..TranscriptionFilter::default()
+            },
+        )?;
+        Ok(records.into_iter().next())
+    }
+
+    pub fn get_count(&self, filter: &TranscriptionFilter) -> Result<usize> {
+        let conn = self.connection.lock();
+        let (where_clause, params) = Self::build_filter_query(filter, self.fts_enabled);
+
+        let sql = format!("SELECT COUNT(*) FROM transcriptions {}", where_clause);
+
+        let mut stmt = conn.prepare(&sql)?;
+        let count: usize =
+            stmt.query_row(rusqlite::params_from_iter(params.iter()), |row| row.get(0))?;
+
+        Ok(count)
+    }
+
+    /// Transcriptions containing any word of `query`, newest first, each with a snippet of at
+    /// most `max_snippet_chars` characters around the first match.
+    pub fn full_text_search_with_highlight(
+        &self,
+        query: &str,
+        max_snippet_chars: usize,
+    ) -> Result<Vec<SearchResult>> {
+        let mut words: Vec<String> = query
+            .split_whitespace()
+            .map(|word| word.chars().map(lowercase_char).collect())
+            .collect();
+        words.sort();
+        words.dedup();
+        if words.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // SQLite's LIKE only folds ASCII case, so narrowing happens in the trigram index (which
+        // folds Unicode case) when it can match every word; otherwise every live row is scanned
+        // and matched below.
+        let mut params: Vec<String> = Vec::new();
+        let text_filter = if self.fts_enabled && words.iter().all(|word| fts_can_match(word)) {
+            params.push(
+                words
+                    .iter()
+                    .map(|word| format!("text : \"{}\"", word.replace('"', "\"\"")))
+                    .collect::<Vec<_>>()
+                    .join(" OR "),
+            );
+            "AND rowid IN (SELECT rowid FROM transcriptions_fts WHERE transcriptions_fts MATCH ?1)"
+        } else {
+            ""
+        };
+        let sql = format!(
+            "SELECT id, timestamp, text, raw_text, audio_path, status, error_message, llm_cleaned,
+                    speech_model, llm_model, word_count, audio_duration_seconds, synced, tags,
+                    feedback, pre_replacement_text, confidence, detected_language, is_redacted,
+                    flagged, deleted_at
+             FROM transcriptions
+             WHERE deleted_at IS NULL {text_filter}
+             ORDER BY timestamp DESC"
+        );
+
+        let conn = self.connection.lock();
+        let mut stmt = conn.prepare(&sql)?;
+        let records = stmt
+            .query_map(rusqlite::params_from_iter(params.iter()), |row| {
+                Self::record_from_row(row)
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let words: Vec<Vec<char>> = words.iter().map(|word| word.chars().collect()).collect();
+        Ok(records
+            .into_iter()
+            .filter_map(|record| {
+                let text: Vec<char> = record.text.chars().map(lowercase_char).collect();
+                let match_count: usize = words
+                    .iter()
+                    .map(|word| find_char_matches(&text, word).len())
+                    .sum();
+                if match_count == 0 {
+                    return None;
+                }
+                let first_match = words
+                    .iter()
+                    .filter_map(|word| {
+                        find_char_matches(&text, word)
+                            .first()
+                            .map(|start| (*start, word.len()))
+                    })
+                    .min();
+                Some(SearchResult {
+                    snippet: snippet_around(&record.text, first_match, max_snippet_chars),
+                    id: record.id,
+                    timestamp: record.timestamp,
+                    match_count: match_count as u32,
+                })
+            })
+            .collect())
+    }
+
+    /// Every record with a timestamp in `[since, until]`, oldest first.
+    pub fn get_range(
+        &self,
+        since: DateTime<Local>,
+        until: DateTime<Local>,
+    ) -> Result<Vec<TranscriptionRecord>> {
+        self.export_range(Some(since), Some(until))
+    }
+
+    /// Load every record in the optional `[since, until]` range, oldest first.
+    pub fn export_range(
+        &self,
+        since: Option<DateTime<Local>>,
+        until: Option<DateTime<Local>>,
+    ) -> Result<Vec<TranscriptionRecord>> {
+        let conn = self.connection.lock();
+        let filter = TranscriptionFilter {
+            since,
+            until,
+            include_redacted: true,
+            ..TranscriptionFilter::default()
+        };
+        let (where_clause, params) = Self::build_filter_query(&filter, false);
+
+        let sql = format!(
+            "SELECT id, timestamp, text, raw_text, audio_path, status, error_message, llm_cleaned,
+                    speech_model, llm_model, word_count, audio_duration_seconds, synced, tags,
+                    feedback, pre_replacement_text, confidence, detected_language, is_redacted,
+                    flagged, deleted_at
+             FROM transcriptions
+             {}
+             ORDER BY timestamp ASC",
+            where_clause
+        );
+
+        let mut stmt = conn.prepare(&sql)?;
+        let records = stmt
+            .query_map(rusqlite::params_from_iter(params.iter()), |row| {
+                Self::record_from_row(row)
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(records)
+    }
+
+    /// Fill in `word_count` for records saved before it was tracked. Returns the rows updated.
+    pub fn backfill_word_counts(&self) -> Result<u32> {
+        let mut conn = self.connection.lock();
+        let tx = conn.transaction()?;
+
+        let rows = {
+            let mut stmt = tx.prepare(
+                "SELECT id, text FROM transcriptions WHERE word_count = 0 AND text != ''",
+            )?;
+            let rows = stmt
+                .query_map([], |row| {
+                    Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+                })?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            rows
+        };
+
+        let mut updated = 0;
+        {
+            let mut stmt = tx.prepare("UPDATE transcriptions SET word_count = ?1 WHERE id = ?2")?;
+            for (id, text) in rows {
+                let word_count = text.split_whitespace().count() as u32;
+                if word_count > 0 {
+                    updated += stmt.execute(params![word_count, id])? as u32;
+                }
+            }
+        }
+
+        tx.commit()?;
+        Ok(updated)
+    }
+
+    /// Measure successful recordings saved without a usable duration from their audio files.
+    /// Relative audio paths resolve against `base_dir`; unreadable files are left untouched.
+    /// Returns the number of records fixed.
+    pub fn backfill_audio_durations(&self, base_dir: &Path) -> Result<u32> {
+        let rows = {
+            let conn = self.connection.lock();
+            let mut stmt = conn.prepare(
+                "SELECT id, audio_path FROM transcriptions
+                 WHERE audio_duration_seconds < 0.1 AND status = 'success'",
+            )?;
+            let rows = stmt
+                .query_map([], |row| {
+                    Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+                })?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            rows
+        };
+
+        // Read the files outside the lock so a large backlog doesn't stall other queries.
+        let durations: Vec<(String, f32)> = rows
+            .into_iter()
+            .filter_map(|(id, audio_path)| {
+                let path = base_dir.join(audio_path);
+                match audio::get_audio_duration_from_mp3(&path) {
+                    Ok(seconds) if seconds >= 0.1 => Some((id, seconds)),
+                    _ => None,
+                }
+            })
+            .collect();
+        if durations.is_empty() {
+            return Ok(0);
+        }
+
+        let mut conn = self.connection.lock();
+        let tx = conn.transaction()?;
+        let mut updated = 0;
+        {
+            let mut stmt =
+                tx.prepare("UPDATE transcriptions SET audio_duration_seconds = ?1 WHERE id = ?2")?;
+            for (id, seconds) in durations {
+                updated += stmt.execute(params![seconds as f64, id])? as u32;
+            }
+        }
+
+        tx.commit()?;
+        Ok(updated)
+    }
+
+    /// Number of consecutive days, ending today, with at least one transcription.
+    pub fn get_transcription_streak(&self) -> Result<u32> {
+        let conn = self.connection.lock();
+        let days = Self::transcription_days(&conn)?;
+
+        let mut expected = Local::now().date_naive();
+        let mut streak = 0;
+        for day in days {
+            if day != expected {
+                break;
+            }
+            streak += 1;
+            match expected.pred_opt() {
+                Some(previous) => expected = previous,
+                None => break,
+            }
+        }
+        Ok(streak)
+    }
+
+    /// Words across today's successful transcriptions, in local time.
+    pub fn get_words_today(&self) -> Result<u32> {
+        let conn = self.connection.lock();
+        let words: i64 = conn.query_row(
+            "SELECT COALESCE(SUM(word_count), 0)
+             FROM transcriptions
+             WHERE status = 'success' AND deleted_at IS NULL
+               AND date(timestamp / 1000, 'unixepoch', 'localtime') = date('now', 'localtime')",
+            [],
+            |row| row.get(0),
+        )?;
+        Ok(words as u32)
+    }
+
+    /// Longest run of consecutive days with at least one transcription.
+    pub fn get_longest_streak(&self) -> Result<u32> {
+        let conn = self.connection.lock();
+        let days = Self::transcription_days(&conn)?;
+
+        let mut longest = 0;
+        let mut current = 0;
+        let mut previous: Option<NaiveDate> = None;
+        for day in days {
+            current = match previous {
+                Some(prev) if prev.pred_opt() == Some(day) => current + 1,
+                _ => 1,
+            };
+            longest = longest.max(current);
+            previous = Some(day);
+        }
+        Ok(longest)
+    }
+
+    /// Distinct local calendar days that have transcriptions, most recent first.
+    fn transcription_days(conn: &Connection) -> Result<Vec<NaiveDate>> {
+        let mut stmt = conn.prepare(
+            "SELECT DISTINCT date(timestamp / 1000, 'unixepoch', 'localtime') AS day
+             FROM transcriptions
+             WHERE deleted_at IS NULL
+             ORDER BY day DESC",
+        )?;
+        let days = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(days
+            .iter()
+            .filter_map(|day| NaiveDate::parse_from_str(day, "%Y-%m-%d").ok())
+            .collect())
+    }
+
+    fn build_filter_query(
+        filter: &TranscriptionFilter,
+        use_fts: bool,
+    ) -> (String, Vec<Box<dyn ToSql>>) {
+        let mut clauses = vec!["deleted_at IS NULL".to_string()];
+        let mut params: Vec<Box<dyn ToSql>> = Vec::new();
+
+        if let Some(query) = filter
+            .search_query
+            .map(str::trim)
+            .filter(|query| !query.is_empty())
+        {
+            if use_fts && fts_can_match(query) {
+                // A quoted trigram phrase matches the query as a substring, like LIKE does
+                params.push(Box::new(format!("\"{}\"", query.replace('"', "\"\""))));
+                clauses.push(format!(
+                    "rowid IN (SELECT rowid FROM transcriptions_fts WHERE transcriptions_fts MATCH ?{})",
+                    params.len()
+                ));
+            } else {
+                params.push(Box::new(format!("%{query}%")));
+                let index = params.len();
+                clauses.push(format!("(text LIKE ?{index} OR raw_text LIKE ?{index})"));
+            }
+        }
+        if let Some(since) = filter.since {
+            params.push(Box::new(since.timestamp_millis()));
+            clauses.push(format!("timestamp >= ?{}", params.len()));
+        }
+        if let Some(until) = filter.until {
+            params.push(Box::new(until.timestamp_millis()));
+            clauses.push(format!("timestamp <= ?{}", params.len()));
+        }
+        if let Some(status) = &filter.status {
+            params.push(Box::new(status.as_str()));
+            clauses.push(format!("status = ?{}", params.len()));
+        }
+        if !filter.include_redacted {
+            clauses.push("is_redacted = 0".to_string());
+        }
+        if filter.flagged_only {
+            clauses.push("flagged = 1".to_string());
+        }
+
+        (format!("WHERE {}", clauses.join(" AND ")), params)
+    }
+
+    fn insert_record(conn: &Connection, record: &TranscriptionRecord) -> Result<()> {
+        let timestamp = record.timestamp.timestamp_millis();
+        conn.execute(
+            "INSERT INTO transcriptions (
+                id,
+                timestamp,
+                text,
+                raw_text,
+                audio_path,
+                status,
+                error_message,
+                llm_cleaned,
+                speech_model,
+                llm_model,
+                word_count,
+                audio_duration_seconds,
+                synced,
+                tags,
+                feedback,
+                pre_replacement_text,
+                confidence,
+                detected_language,
+                is_redacted,
+                flagged,
+                deleted_at
+             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21)",
+            params![
+                record.id,
+                timestamp,
+                record.text,
+                record.raw_text,
+                record.audio_path,
+                record.status.as_str(),
+                record.error_message,
+                if record.llm_cleaned { 1 } else { 0 },
+                record.speech_model,
+                record.llm_model,
+                record.word_count as i64,
+                record.audio_duration_seconds as f64,
+                if record.synced { 1 } else { 0 },
+                record.tags.join(","),
+                record.feedback.map(|positive| if positive { 1 } else { 0 }),
+                record.replacement_applied_text,
+                record.confidence.map(f64::from),
+                record.detected_language,
+                if record.is_redacted { 1 } else { 0 },
+                if record.flagged { 1 } else { 0 },
+                record.deleted_at.map(|deleted_at| deleted_at.timestamp_millis()),
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn apply_llm_cleanup(
+        conn: &Connection,
+        id: &str,
+        cleaned_text: &str,
+        llm_model: Option<&str>,
+    ) -> Result<Option<TranscriptionRecord>> {
+        if let Some(mut record) = Self::get_live_record(conn, id)? {
+            if record.raw_text.is_none() {
+                record.raw_text = Some(record.text.clone());
+            }
+            record.text = cleaned_text.to_string();
+            record.llm_cleaned = true;
+            record.llm_model = llm_model.map(|value| value.to_string());
+            record.word_count = count_words(&record.text);
+            record.synced = false;
+            record.replacement_applied_text = None;
+
+            conn.execute(
+                "UPDATE transcriptions
+                 SET text = ?1, raw_text = ?2, llm_cleaned = 1, llm_model = ?3, word_count = ?4, synced = 0,
+                     pre_replacement_text = NULL
+                 WHERE id = ?5 AND deleted_at IS NULL",
+                params![
+                    record.text,
+                    record.raw_text,
+                    record.llm_model,
+                    record.word_count as i64,
+                    id
+                ],
+            )?;
+
+            Ok(Some(record))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn revert_to_raw_internal(conn: &Connection, id: &str) -> Result<Option<TranscriptionRecord>> {
+        if let Some(mut record) = Self::get_record(conn, id)? {
+            if let Some(raw) = record.raw_text.take() {
+                record.text = raw;
+                record.llm_cleaned = false;
+                record.word_count = count_words(&record.text);
+                record.llm_model = None;
+                record.synced = false;
+                record.replacement_applied_text = None;
+                conn.execute(
+                    "UPDATE transcriptions
+                     SET text = ?1, raw_text = NULL, llm_cleaned = 0, llm_model = NULL, word_count = ?2, synced = 0,
+                         pre_replacement_text = NULL
+                     WHERE id = ?3",
+                    params![record.text, record.word_count as i64, id],
+                )?;
+                return Ok(Some(record));
+            }
+        }
+        Ok(None)
+    }
+
+    fn revert_to_pre_replacement_internal(
+        conn: &Connection,
+        id: &str,
+    ) -> Result<Option<TranscriptionRecord>> {
+        if let Some(mut record) = Self::get_record(conn, id)? {
+            if let Some(original) = record.replacement_applied_text.take() {
+                record.text = original;
+                record.word_count = count_words(&record.text);
+                record.synced = false;
+                conn.execute(
+                    "UPDATE transcriptions
+                     SET text = ?1, pre_replacement_text = NULL, word_count = ?2, synced = 0
+                     WHERE id = ?3",
+                    params![record.text, record.word_count as i64, id],
+                )?;
+                return Ok(Some(record));
+            }
+        }
+        Ok(None)
+    }
+
+    fn get_record(conn: &Connection, id: &str) -> Result<Option<TranscriptionRecord>> {
+        conn.query_row(
+            "SELECT id, timestamp, text, raw_text, audio_path, status, error_message, llm_cleaned,
+                    speech_model, llm_model, word_count, audio_duration_seconds, synced, tags,
+                    feedback, pre_replacement_text, confidence, detected_language, is_redacted,
+                    flagged, deleted_at
+             FROM transcriptions WHERE id = ?1",
+            params![id],
+            |row| Self::record_from_row(row),
+        )
+        .optional()
+        .map_err(Into::into)
+    }
+
+    /// Like [`Self::get_record`], but treats deleted transcriptions as missing.
+    fn get_live_record(conn: &Connection, id: &str) -> Result<Option<TranscriptionRecord>> {
+        Ok(Self::get_record(conn, id)?.filter(|record| record.deleted_at.is_none()))
+    }
+
+    fn load_all_from_db(&self) -> Result<Vec<TranscriptionRecord>> {
+        let conn = self.connection.lock();
+        let mut stmt = conn.prepare(
+            "SELECT id, timestamp, text, raw_text, audio_path, status, error_message, llm_cleaned,
+                    speech_model, llm_model, word_count, audio_duration_seconds, synced, tags,
+                    feedback, pre_replacement_text, confidence, detected_language, is_redacted,
+                    flagged, deleted_at
+             FROM transcriptions WHERE deleted_at IS NULL ORDER BY timestamp DESC",
+        )?;
+
+        let records = stmt
+            .query_map([], |row| Self::record_from_row(row))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(records)
+    }
+
+    fn record_from_row(row: &Row<'_>) -> rusqlite::Result<TranscriptionRecord> {
+        let timestamp_ms: i64 = row.get("timestamp")?;
+        let timestamp = Local
+            .timestamp_millis_opt(timestamp_ms)
+            .single()
+            .ok_or_else(|| {
+                rusqlite::Error::FromSqlConversionFailure(
+                    0,
+                    Type::Integer,
+                    Box::new(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("Invalid timestamp stored in database: {timestamp_ms}"),
+                    )) as Box<dyn std::error::Error + Send + Sync + 'static>,
+                )
+            })?;
 
         let status_value: String = row.get("status")?;
         let status = TranscriptionStatus::from_str(&status_value).map_err(|err| {
@@ -459,6 +1656,33 @@ impl StorageManager {
             word_count: row.get::<_, i64>("word_count")? as u32,
             audio_duration_seconds: row.get::<_, f64>("audio_duration_seconds")? as f32,
             synced: row.get::<_, i64>("synced").unwrap_or(0) == 1,
+            tags: row
+                .get::<_, String>("tags")
+                .unwrap_or_default()
+                .split(',')
+                .filter(|tag| !tag.is_empty())
+                .map(String::from)
+                .collect(),
+            feedback: row
+                .get::<_, Option<i64>>("feedback")
+                .unwrap_or(None)
+                .map(|value| value == 1),
+            replacement_applied_text: row
+                .get::<_, Option<String>>("pre_replacement_text")
+                .unwrap_or(None),
+            confidence: row
+                .get::<_, Option<f64>>("confidence")
+                .unwrap_or(None)
+                .map(|value| value as f32),
+            detected_language: row
+                .get::<_, Option<String>>("detected_language")
+                .unwrap_or(None),
+            is_redacted: row.get::<_, i64>("is_redacted").unwrap_or(0) == 1,
+            flagged: row.get::<_, i64>("flagged").unwrap_or(0) == 1,
+            deleted_at: row
+                .get::<_, Option<i64>>("deleted_at")
+                .unwrap_or(None)
+                .and_then(|millis| Local.timestamp_millis_opt(millis).single()),
         })
     }
 
@@ -484,7 +1708,15 @@ impl StorageManager {
                 llm_model TEXT NULL,
                 word_count INTEGER NOT NULL DEFAULT 0,
                 audio_duration_seconds REAL NOT NULL DEFAULT 0,
-                synced INTEGER NOT NULL DEFAULT 0
+                synced INTEGER NOT NULL DEFAULT 0,
+                tags TEXT NOT NULL DEFAULT '',
+                feedback INTEGER NULL,
+                pre_replacement_text TEXT NULL,
+                confidence REAL NULL,
+                detected_language TEXT NULL,
+                is_redacted INTEGER NOT NULL DEFAULT 0,
+                deleted_at INTEGER NULL,
+                flagged INTEGER NOT NULL DEFAULT 0
             );
             CREATE INDEX IF NOT EXISTS idx_transcriptions_timestamp ON transcriptions(timestamp);
             CREATE INDEX IF NOT EXISTS idx_transcriptions_status ON transcriptions(status);",
@@ -520,6 +1752,112 @@ impl StorageManager {
             "synced",
             "ALTER TABLE transcriptions ADD COLUMN synced INTEGER NOT NULL DEFAULT 0",
         )?;
+        Self::ensure_column(
+            conn,
+            "transcriptions",
+            "webhook_pending",
+            "ALTER TABLE transcriptions ADD COLUMN webhook_pending INTEGER NOT NULL DEFAULT 0",
+        )?;
+        Self::ensure_column(
+            conn,
+            "transcriptions",
+            "tags",
+            "ALTER TABLE transcriptions ADD COLUMN tags TEXT NOT NULL DEFAULT ''",
+        )?;
+        Self::ensure_column(
+            conn,
+            "transcriptions",
+            "feedback",
+            "ALTER TABLE transcriptions ADD COLUMN feedback INTEGER NULL",
+        )?;
+        Self::ensure_column(
+            conn,
+            "transcriptions",
+            "pre_replacement_text",
+            "ALTER TABLE transcriptions ADD COLUMN pre_replacement_text TEXT NULL",
+        )?;
+        Self::ensure_column(
+            conn,
+            "transcriptions",
+            "confidence",
+            "ALTER TABLE transcriptions ADD COLUMN confidence REAL NULL",
+        )?;
+        Self::ensure_column(
+            conn,
+            "transcriptions",
+            "detected_language",
+            "ALTER TABLE transcriptions ADD COLUMN detected_language TEXT NULL",
+        )?;
+        Self::ensure_column(
+            conn,
+            "transcriptions",
+            "is_redacted",
+            "ALTER TABLE transcriptions ADD COLUMN is_redacted INTEGER NOT NULL DEFAULT 0",
+        )?;
+        Self::ensure_column(
+            conn,
+            "transcriptions",
+            "deleted_at",
+            "ALTER TABLE transcriptions ADD COLUMN deleted_at INTEGER NULL",
+        )?;
+        Self::ensure_column(
+            conn,
+            "transcriptions",
+            "flagged",
+            "ALTER TABLE transcriptions ADD COLUMN flagged INTEGER NOT NULL DEFAULT 0",
+        )?;
+        Ok(())
+    }
+
+    /// Trigram index over `text` and `raw_text`, kept in sync by triggers. Its rowids mirror
+    /// `transcriptions`, so it is built from scratch when first created.
+    fn ensure_fts_index(conn: &Connection) -> Result<()> {
+        let exists = conn
+            .query_row(
+                "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'transcriptions_fts'",
+                [],
+                |_| Ok(()),
+            )
+            .optional()?
+            .is_some();
+
+        conn.execute_batch(
+            "CREATE VIRTUAL TABLE IF NOT EXISTS transcriptions_fts USING fts5(
+                id UNINDEXED,
+                text,
+                raw_text,
+                tokenize = 'trigram'
+            );
+            CREATE TRIGGER IF NOT EXISTS transcriptions_fts_insert
+            AFTER INSERT ON transcriptions BEGIN
+                INSERT INTO transcriptions_fts (rowid, id, text, raw_text)
+                VALUES (new.rowid, new.id, new.text, new.raw_text);
+            END;
+            CREATE TRIGGER IF NOT EXISTS transcriptions_fts_update
+            AFTER UPDATE OF text, raw_text ON transcriptions BEGIN
+                UPDATE transcriptions_fts SET text = new.text, raw_text = new.raw_text
+                WHERE rowid = old.rowid;
+            END;
+            CREATE TRIGGER IF NOT EXISTS transcriptions_fts_delete
+            AFTER DELETE ON transcriptions BEGIN
+                DELETE FROM transcriptions_fts WHERE rowid = old.rowid;
+            END;",
+        )?;
+
+        if !exists {
+            Self::rebuild_fts_index(conn)?;
+        }
+        Ok(())
+    }
+
+    fn rebuild_fts_index(conn: &Connection) -> Result<()> {
+        conn.execute_batch(
+            "BEGIN;
+            DELETE FROM transcriptions_fts;
+            INSERT INTO transcriptions_fts (rowid, id, text, raw_text)
+                SELECT rowid, id, text, raw_text FROM transcriptions;
+            COMMIT;",
+        )?;
         Ok(())
     }
 
@@ -549,3 +1887,1179 @@ fn count_words(text: &str) -> u32 {
         .filter(|word| !word.is_empty())
         .count() as u32
 }
+
+/// Lowercased words of `text` with surrounding punctuation removed.
+fn word_set(text: &str) -> HashSet<String> {
+    text.split_whitespace()
+        .map(|word| {
+            word.trim_matches(|c: char| !c.is_alphanumeric())
+                .to_lowercase()
+        })
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+/// Shared words over all distinct words, from 0 (nothing shared) to 1 (same words).
+fn jaccard_similarity(a: &HashSet<String>, b: &HashSet<String>) -> f32 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f32 / union as f32
+}
+
+fn fill_heatmap(totals: &HashMap<(u8, u8), (u32, u32)>) -> Vec<HeatmapCell> {
+    (0..7u8)
+        .flat_map(|day_of_week| (0..24u8).map(move |hour_of_day| (day_of_week, hour_of_day)))
+        .map(|(day_of_week, hour_of_day)| {
+            let (count, total_words) = totals
+                .get(&(day_of_week, hour_of_day))
+                .copied()
+                .unwrap_or((0, 0));
+            HeatmapCell {
+                day_of_week,
+                hour_of_day,
+                count,
+                total_words,
+            }
+        })
+        .collect()
+}
+
+fn peak_hour(cells: &[HeatmapCell]) -> Option<u8> {
+    let mut per_hour = [0u32; 24];
+    for cell in cells {
+        per_hour[usize::from(cell.hour_of_day) % 24] += cell.count;
+    }
+    let mut peak = None;
+    let mut best = 0;
+    for (hour, count) in per_hour.into_iter().enumerate() {
+        if count > best {
+            best = count;
+            peak = Some(hour as u8);
+        }
+    }
+    peak
+}
+
+fn lowercase_char(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+/// Char offsets of the non-overlapping occurrences of `needle` in `haystack`.
+fn find_char_matches(haystack: &[char], needle: &[char]) -> Vec<usize> {
+    let mut matches = Vec::new();
+    if needle.is_empty() {
+        return matches;
+    }
+    let mut index = 0;
+    while index + needle.len() <= haystack.len() {
+        if haystack[index..index + needle.len()] == *needle {
+            matches.push(index);
+            index += needle.len();
+        } else {
+            index += 1;
+        }
+    }
+    matches
+}
+
+/// Up to `max_chars` characters of `text` centred on `highlight` (char offset and length),
+/// counting the `…` added at each cut end.
+fn snippet_around(text: &str, highlight: Option<(usize, usize)>, max_chars: usize) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let len = chars.len();
+    if len <= max_chars {
+        return text.to_string();
+    }
+    if max_chars < 3 {
+        return chars[..max_chars].iter().collect();
+    }
+
+    let center = highlight
+        .map(|(start, match_len)| start + match_len / 2)
+        .unwrap_or(0);
+    // Assume both ends get an ellipsis, then widen the window if one doesn't
+    let body = max_chars - 2;
+    let mut start = center.saturating_sub(body / 2);
+    let end;
+    if start + body >= len {
+        start = len - (max_chars - 1);
+        end = len;
+    } else if start == 0 {
+        end = max_chars - 1;
+    } else {
+        end = start + body;
+    }
+
+    let mut snippet = String::new();
+    if start > 0 {
+        snippet.push('…');
+    }
+    snippet.extend(&chars[start..end]);
+    if end < len {
+        snippet.push('…');
+    }
+    snippet
+}
+
+/// Trigrams can't match queries shorter than three characters, and LIKE wildcards in the
+/// query have no FTS equivalent; those searches use LIKE instead.
+fn fts_can_match(query: &str) -> bool {
+    query.chars().count() >= 3 && !query.contains(['%', '_'])
+}
+
+fn file_size(path: &Path) -> u64 {
+    fs::metadata(path).map(|meta| meta.len()).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    /// A store in its own temporary directory. The database and its WAL files are
+    /// removed when the guard drops, after the connection has closed.
+    struct TempStorage {
+        storage: StorageManager,
+        _dir: tempfile::TempDir,
+    }
+
+    impl std::ops::Deref for TempStorage {
+        type Target = StorageManager;
+
+        fn deref(&self) -> &StorageManager {
+            &self.storage
+        }
+    }
+
+    impl std::ops::DerefMut for TempStorage {
+        fn deref_mut(&mut self) -> &mut StorageManager {
+            &mut self.storage
+        }
+    }
+
+    fn temp_storage() -> TempStorage {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = StorageManager::new(dir.path().join("transcriptions.db")).unwrap();
+        TempStorage { storage, _dir: dir }
+    }
+
+    /// Every live transcription, redacted ones included.
+    fn everything<'a>() -> TranscriptionFilter<'a> {
+        TranscriptionFilter {
+            include_redacted: true,
+            ..TranscriptionFilter::default()
+        }
+    }
+
+    fn record_at(timestamp: DateTime<Local>, status: TranscriptionStatus) -> TranscriptionRecord {
+        TranscriptionRecord {
+            id: Uuid::new_v4().to_string(),
+            timestamp,
+            text: "hello world".to_string(),
+            raw_text: None,
+            audio_path: String::new(),
+            status,
+            error_message: None,
+            llm_cleaned: false,
+            speech_model: String::new(),
+            llm_model: None,
+            word_count: 2,
+            audio_duration_seconds: 1.0,
+            synced: false,
+            tags: Vec::new(),
+            feedback: None,
+            replacement_applied_text: None,
+            confidence: None,
+            detected_language: None,
+            is_redacted: false,
+            flagged: false,
+            deleted_at: None,
+        }
+    }
+
+    #[test]
+    fn test_paginated_filters_by_date_and_status() {
+        let storage = temp_storage();
+        let today = Local::now();
+        let yesterday = today - Duration::days(1);
+
+        storage
+            .import_transcription(record_at(yesterday, TranscriptionStatus::Success))
+            .unwrap();
+        storage
+            .import_transcription(record_at(today, TranscriptionStatus::Success))
+            .unwrap();
+        storage
+            .import_transcription(record_at(today, TranscriptionStatus::Error))
+            .unwrap();
+
+        let since = today - Duration::hours(1);
+        let todays = storage
+            .get_paginated(
+                10,
+                0,
+                &TranscriptionFilter {
+                    since: Some(since),
+                    ..everything()
+                },
+            )
+            .unwrap();
+        assert_eq!(todays.len(), 2);
+        assert_eq!(
+            storage
+                .get_count(&TranscriptionFilter {
+                    since: Some(since),
+                    ..everything()
+                })
+                .unwrap(),
+            2
+        );
+
+        let older = storage
+            .get_paginated(
+                10,
+                0,
+                &TranscriptionFilter {
+                    until: Some(since),
+                    ..everything()
+                },
+            )
+            .unwrap();
+        assert_eq!(older.len(), 1);
+
+        let todays_success = storage
+            .get_paginated(
+                10,
+                0,
+                &TranscriptionFilter {
+                    search_query: Some("hello"),
+                    since: Some(since),
+                    status: Some(TranscriptionStatus::Success),
+                    ..everything()
+                },
+            )
+            .unwrap();
+        assert_eq!(todays_success.len(), 1);
+        assert_eq!(todays_success[0].status, TranscriptionStatus::Success);
+        assert_eq!(
+            storage
+                .get_count(&TranscriptionFilter {
+                    status: Some(TranscriptionStatus::Error),
+                    ..everything()
+                })
+                .unwrap(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_backfill_word_counts() {
+        let storage = temp_storage();
+        let mut legacy = record_at(Local::now(), TranscriptionStatus::Success);
+        legacy.text = "one two three".to_string();
+        legacy.word_count = 0;
+        let legacy_id = legacy.id.clone();
+        storage.import_transcription(legacy).unwrap();
+        storage
+            .import_transcription(record_at(Local::now(), TranscriptionStatus::Success))
+            .unwrap();
+
+        assert_eq!(storage.backfill_word_counts().unwrap(), 1);
+        assert_eq!(storage.get_by_id(&legacy_id).unwrap().word_count, 3);
+        assert_eq!(storage.backfill_word_counts().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_backfill_audio_durations_skips_missing_files() {
+        let storage = temp_storage();
+        let mut legacy = record_at(Local::now(), TranscriptionStatus::Success);
+        legacy.audio_path = "missing.mp3".to_string();
+        legacy.audio_duration_seconds = 0.0;
+        let legacy_id = legacy.id.clone();
+        storage.import_transcription(legacy).unwrap();
+
+        let base_dir = std::env::temp_dir().join(format!("glimpse-backfill-{}", Uuid::new_v4()));
+        assert_eq!(storage.backfill_audio_durations(&base_dir).unwrap(), 0);
+        let record = storage.get_by_id(&legacy_id).unwrap();
+        assert_eq!(record.audio_duration_seconds, 0.0);
+    }
+
+    #[test]
+    fn test_get_all_failed_skips_missing_audio() {
+        let storage = temp_storage();
+        let audio = std::env::temp_dir().join(format!("glimpse-failed-{}.mp3", Uuid::new_v4()));
+        fs::write(&audio, b"audio").unwrap();
+
+        let mut retryable = record_at(Local::now(), TranscriptionStatus::Error);
+        retryable.audio_path = audio.display().to_string();
+        let retryable_id = retryable.id.clone();
+        let mut missing = record_at(Local::now(), TranscriptionStatus::Error);
+        missing.audio_path = "missing.mp3".to_string();
+        let mut succeeded = record_at(Local::now(), TranscriptionStatus::Success);
+        succeeded.audio_path = audio.display().to_string();
+        for record in [retryable, missing, succeeded] {
+            storage.import_transcription(record).unwrap();
+        }
+
+        let failed = storage.get_all_failed().unwrap();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].id, retryable_id);
+        let _ = fs::remove_file(audio);
+    }
+
+    #[test]
+    fn test_get_pending_webhooks() {
+        let storage = temp_storage();
+        let pending = record_at(Local::now(), TranscriptionStatus::Success);
+        let pending_id = pending.id.clone();
+        let cleared = record_at(Local::now(), TranscriptionStatus::Success);
+        let cleared_id = cleared.id.clone();
+        let untouched = record_at(Local::now(), TranscriptionStatus::Success);
+        for record in [pending, cleared, untouched] {
+            storage.import_transcription(record).unwrap();
+        }
+        storage.mark_webhook_pending(&pending_id, true).unwrap();
+        storage.mark_webhook_pending(&cleared_id, true).unwrap();
+        storage.mark_webhook_pending(&cleared_id, false).unwrap();
+
+        let records = storage.get_pending_webhooks().unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].id, pending_id);
+    }
+
+    #[test]
+    fn test_detected_language_is_stored() {
+        let storage = temp_storage();
+        let record = storage
+            .save_transcription(
+                "bonjour".to_string(),
+                "recording.mp3".to_string(),
+                TranscriptionStatus::Success,
+                None,
+                None,
+                TranscriptionMetadata {
+                    detected_language: Some("fr".to_string()),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+        let stored = storage.get_by_id(&record.id).unwrap();
+        assert_eq!(stored.detected_language.as_deref(), Some("fr"));
+    }
+
+    #[test]
+    fn test_anonymize_keeps_row_and_hides_it_when_asked() {
+        let storage = temp_storage();
+        let mut record = record_at(Local::now(), TranscriptionStatus::Success);
+        record.raw_text = Some("my card number is 4242".to_string());
+        record.word_count = 5;
+        record.audio_duration_seconds = 3.0;
+        record.synced = true;
+        storage.import_transcription(record.clone()).unwrap();
+        storage
+            .import_transcription(record_at(Local::now(), TranscriptionStatus::Success))
+            .unwrap();
+
+        let audio_path = storage.anonymize_transcription(&record.id).unwrap();
+        assert_eq!(audio_path, Some(record.audio_path.clone()));
+        assert_eq!(storage.anonymize_transcription("missing").unwrap(), None);
+
+        let redacted = storage.get_by_id(&record.id).unwrap();
+        assert!(redacted.is_redacted);
+        assert!(!redacted.synced);
+        assert_eq!(redacted.text, REDACTED_PLACEHOLDER);
+        assert_eq!(redacted.raw_text.as_deref(), Some(REDACTED_PLACEHOLDER));
+        assert_eq!(redacted.audio_path, REDACTED_PLACEHOLDER);
+        assert_eq!(redacted.word_count, 0);
+        assert_eq!(redacted.audio_duration_seconds, 0.0);
+        assert_eq!(
+            storage
+                .get_count(&TranscriptionFilter {
+                    search_query: Some("4242"),
+                    ..everything()
+                })
+                .unwrap(),
+            0
+        );
+
+        let all = storage.get_paginated(10, 0, &everything()).unwrap();
+        assert_eq!(all.len(), 2);
+        let visible = storage
+            .get_paginated(10, 0, &TranscriptionFilter::default())
+            .unwrap();
+        assert_eq!(visible.len(), 1);
+        assert_ne!(visible[0].id, record.id);
+        assert_eq!(
+            storage.get_count(&TranscriptionFilter::default()).unwrap(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_vacuum_reclaims_deleted_rows() {
+        let storage = temp_storage();
+        for _ in 0..1000 {
+            storage
+                .import_transcription(record_at(Local::now(), TranscriptionStatus::Success))
+                .unwrap();
+        }
+        assert_eq!(storage.delete_all().unwrap(), 1000);
+        storage
+            .connection
+            .lock()
+            .execute("UPDATE transcriptions SET deleted_at = 0", [])
+            .unwrap();
+        assert_eq!(storage.purge_old_deleted().unwrap(), 1000);
+
+        assert!(storage.vacuum().unwrap() > 0);
+    }
+
+    #[test]
+    fn test_flagged_transcriptions() {
+        let storage = temp_storage();
+        let flagged = record_at(Local::now(), TranscriptionStatus::Success);
+        let other = record_at(Local::now(), TranscriptionStatus::Success);
+        storage.import_transcription(flagged.clone()).unwrap();
+        storage.import_transcription(other).unwrap();
+
+        assert!(storage.set_flagged(&flagged.id, true).unwrap());
+        assert!(!storage.set_flagged("missing", true).unwrap());
+        assert!(storage.get_by_id(&flagged.id).unwrap().flagged);
+
+        let ids = |records: Vec<TranscriptionRecord>| -> Vec<String> {
+            records.into_iter().map(|record| record.id).collect()
+        };
+        assert_eq!(
+            ids(storage.get_flagged().unwrap()),
+            vec![flagged.id.clone()]
+        );
+        let page = storage
+            .get_paginated(
+                10,
+                0,
+                &TranscriptionFilter {
+                    flagged_only: true,
+                    ..everything()
+                },
+            )
+            .unwrap();
+        assert_eq!(ids(page), vec![flagged.id.clone()]);
+        assert_eq!(
+            storage
+                .get_count(&TranscriptionFilter {
+                    flagged_only: true,
+                    ..everything()
+                })
+                .unwrap(),
+            1
+        );
+        assert_eq!(storage.get_count(&everything()).unwrap(), 2);
+
+        assert!(storage.set_flagged(&flagged.id, false).unwrap());
+        assert!(storage.get_flagged().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_set_tags_validation() {
+        let storage = temp_storage();
+        let record = record_at(Local::now(), TranscriptionStatus::Success);
+        storage.import_transcription(record.clone()).unwrap();
+        let tags = |tags: &[&str]| tags.iter().map(|tag| tag.to_string()).collect();
+
+        assert!(storage.set_tags(&record.id, tags(&[""])).is_err());
+        assert!(storage.set_tags(&record.id, tags(&["a,b"])).is_err());
+        let too_long = "x".repeat(MAX_TAG_LENGTH + 1);
+        assert!(storage.set_tags(&record.id, tags(&[&too_long])).is_err());
+        let too_many: Vec<String> = (0..=MAX_TAGS).map(|i| format!("tag{i}")).collect();
+        assert!(storage.set_tags(&record.id, too_many.clone()).is_err());
+        assert!(storage.get_by_id(&record.id).unwrap().tags.is_empty());
+
+        let longest = "x".repeat(MAX_TAG_LENGTH);
+        let updated = storage
+            .set_tags(&record.id, too_many[..MAX_TAGS].to_vec())
+            .unwrap()
+            .unwrap();
+        assert_eq!(updated.tags.len(), MAX_TAGS);
+        let updated = storage
+            .set_tags(&record.id, tags(&[&longest]))
+            .unwrap()
+            .unwrap();
+        assert_eq!(updated.tags, vec![longest]);
+        assert!(storage
+            .set_tags("missing", tags(&["work"]))
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_get_by_tag_treats_wildcards_literally() {
+        let storage = temp_storage();
+        let mut ids = Vec::new();
+        for tag in ["100%", "1000", "a_b", "axb", "c\\d", "cd"] {
+            let record = record_at(Local::now(), TranscriptionStatus::Success);
+            storage.import_transcription(record.clone()).unwrap();
+            storage.set_tags(&record.id, vec![tag.to_string()]).unwrap();
+            ids.push(record.id);
+        }
+        let matches = |tag: &str| -> Vec<String> {
+            storage
+                .get_by_tag(tag)
+                .unwrap()
+                .into_iter()
+                .map(|record| record.id)
+                .collect()
+        };
+
+        assert_eq!(matches("100%"), vec![ids[0].clone()]);
+        assert_eq!(matches("a_b"), vec![ids[2].clone()]);
+        assert_eq!(matches("c\\d"), vec![ids[4].clone()]);
+        assert!(matches("%").is_empty());
+        assert!(matches("_").is_empty());
+        assert!(matches("10%").is_empty());
+    }
+
+    #[test]
+    fn test_checkpoint_reports_wal_frames() {
+        let storage = temp_storage();
+        for _ in 0..100 {
+            storage
+                .import_transcription(record_at(Local::now(), TranscriptionStatus::Success))
+                .unwrap();
+        }
+
+        let stats = storage.checkpoint().unwrap();
+        assert!(stats.log_frames > 0);
+        assert_eq!(stats.checkpointed_frames, stats.log_frames);
+    }
+
+    #[test]
+    fn test_auto_checkpoint_truncates_the_wal() {
+        const THRESHOLD: u64 = 64 * 1024;
+        let storage = temp_storage();
+        while file_size(&storage.wal_path()) < THRESHOLD {
+            storage
+                .import_transcription(record_at(Local::now(), TranscriptionStatus::Success))
+                .unwrap();
+        }
+
+        assert!(storage.truncate_wal_past(&storage.connection.lock(), THRESHOLD));
+        assert_eq!(file_size(&storage.wal_path()), 0);
+
+        // The next write only adds a few frames, so it must not checkpoint again.
+        storage
+            .import_transcription(record_at(Local::now(), TranscriptionStatus::Success))
+            .unwrap();
+        assert!(!storage.truncate_wal_past(&storage.connection.lock(), THRESHOLD));
+    }
+
+    #[test]
+    fn test_soft_delete_restore_and_purge() {
+        let storage = temp_storage();
+        let kept = record_at(Local::now(), TranscriptionStatus::Success);
+        let recent = record_at(Local::now(), TranscriptionStatus::Success);
+        let old = record_at(Local::now(), TranscriptionStatus::Success);
+        for record in [&kept, &recent, &old] {
+            storage.import_transcription(record.clone()).unwrap();
+        }
+
+        assert!(storage.delete(&recent.id).unwrap());
+        assert!(storage.delete(&old.id).unwrap());
+        assert!(!storage.delete(&old.id).unwrap());
+        assert!(storage.get_by_id(&recent.id).is_none());
+        assert_eq!(storage.get_all().len(), 1);
+        assert_eq!(storage.get_count(&everything()).unwrap(), 1);
+        assert_eq!(storage.get_deleted().unwrap().len(), 2);
+
+        let expired = Local::now() - Duration::days(DELETED_RETENTION_DAYS + 1);
+        storage
+            .connection
+            .lock()
+            .execute(
+                "UPDATE transcriptions SET deleted_at = ?1 WHERE id = ?2",
+                params![expired.timestamp_millis(), old.id],
+            )
+            .unwrap();
+        assert_eq!(storage.purge_old_deleted().unwrap(), 1);
+        assert!(!storage.restore(&old.id).unwrap());
+
+        assert!(storage.restore(&recent.id).unwrap());
+        assert!(!storage.restore(&kept.id).unwrap());
+        assert!(storage.get_by_id(&recent.id).is_some());
+        assert!(storage.get_deleted().unwrap().is_empty());
+
+        assert_eq!(storage.delete_all().unwrap(), 2);
+        assert!(storage.get_all().is_empty());
+        assert_eq!(storage.get_deleted().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_writes_skip_deleted_transcriptions() {
+        let storage = temp_storage();
+        let record = record_at(Local::now(), TranscriptionStatus::Success);
+        storage.import_transcription(record.clone()).unwrap();
+        assert!(storage.delete(&record.id).unwrap());
+
+        assert!(storage
+            .set_tags(&record.id, vec!["work".to_string()])
+            .unwrap()
+            .is_none());
+        assert!(storage.set_feedback(&record.id, true).unwrap().is_none());
+        assert!(storage
+            .update_with_llm_cleanup(&record.id, "cleaned".to_string(), None)
+            .unwrap()
+            .is_none());
+        assert!(storage
+            .anonymize_transcription(&record.id)
+            .unwrap()
+            .is_none());
+
+        assert!(storage.restore(&record.id).unwrap());
+        let restored = storage.get_by_id(&record.id).unwrap();
+        assert_eq!(restored.text, record.text);
+        assert!(restored.tags.is_empty());
+        assert_eq!(restored.feedback, None);
+        assert!(!restored.is_redacted);
+    }
+
+    #[test]
+    fn test_purge_keeps_audio_still_in_use() {
+        let storage = temp_storage();
+        let audio = std::env::temp_dir().join(format!("glimpse-shared-{}.mp3", Uuid::new_v4()));
+        fs::write(&audio, b"audio").unwrap();
+
+        let now = Local::now();
+        let mut ids = Vec::new();
+        for offset in 0..2 {
+            let mut record = record_at(
+                now + Duration::minutes(offset),
+                TranscriptionStatus::Success,
+            );
+            record.audio_path = audio.display().to_string();
+            ids.push(record.id.clone());
+            storage.import_transcription(record).unwrap();
+        }
+        let merged = storage.merge_transcriptions(ids).unwrap();
+
+        let expired = Local::now() - Duration::days(DELETED_RETENTION_DAYS + 1);
+        storage
+            .connection
+            .lock()
+            .execute(
+                "UPDATE transcriptions SET deleted_at = ?1 WHERE deleted_at IS NOT NULL",
+                params![expired.timestamp_millis()],
+            )
+            .unwrap();
+        assert_eq!(storage.purge_old_deleted().unwrap(), 2);
+        // The merged record still points at the first part's recording
+        assert!(audio.is_file());
+
+        assert!(storage.delete(&merged.id).unwrap());
+        storage
+            .connection
+            .lock()
+            .execute(
+                "UPDATE transcriptions SET deleted_at = ?1 WHERE deleted_at IS NOT NULL",
+                params![expired.timestamp_millis()],
+            )
+            .unwrap();
+        assert_eq!(storage.purge_old_deleted().unwrap(), 1);
+        assert!(!audio.is_file());
+    }
+
+    #[test]
+    fn test_usage_stats_by_model_and_day() {
+        let storage = temp_storage();
+        assert!(storage.get_usage_stats_by_model().unwrap().is_empty());
+        let empty_days = storage.get_usage_stats_by_day(2).unwrap();
+        assert_eq!(empty_days.len(), 2);
+        assert!(empty_days
+            .iter()
+            .all(|day| day.count == 0 && day.words == 0));
+
+        let now = Local::now();
+        for (timestamp, model, status) in [
+            (now, "Parakeet", TranscriptionStatus::Success),
+            (now, "Parakeet", TranscriptionStatus::Success),
+            (now, "Parakeet", TranscriptionStatus::Error),
+            (
+                now - Duration::days(1),
+                "Whisper",
+                TranscriptionStatus::Success,
+            ),
+            (
+                now - Duration::days(5),
+                "Whisper",
+                TranscriptionStatus::Success,
+            ),
+        ] {
+            let mut record = record_at(timestamp, status);
+            record.speech_model = model.to_string();
+            storage.import_transcription(record).unwrap();
+        }
+
+        let by_model = storage.get_usage_stats_by_model().unwrap();
+        assert_eq!(by_model.len(), 2);
+        assert_eq!(by_model[0].speech_model, "Parakeet");
+        assert_eq!(by_model[0].transcription_count, 2);
+        assert_eq!(by_model[0].total_words, 4);
+        assert_eq!(by_model[0].total_audio_seconds, 2.0);
+        assert!((by_model[0].avg_words_per_minute - 120.0).abs() < 0.01);
+
+        let by_day = storage.get_usage_stats_by_day(3).unwrap();
+        assert_eq!(by_day.len(), 3);
+        assert_eq!(by_day[2].date, now.format("%Y-%m-%d").to_string());
+        assert_eq!((by_day[0].count, by_day[0].words), (0, 0));
+        assert_eq!((by_day[1].count, by_day[1].words), (1, 2));
+        assert_eq!((by_day[2].count, by_day[2].words), (2, 4));
+        assert!(storage.get_usage_stats_by_day(0).unwrap().is_empty());
+        assert_eq!(
+            storage.get_usage_stats_by_day(u32::MAX).unwrap().len(),
+            MAX_REPORT_DAYS as usize
+        );
+    }
+
+    #[test]
+    fn test_merge_transcriptions() {
+        let storage = temp_storage();
+        let now = Local::now();
+
+        let mut ids = Vec::new();
+        for (offset, text, audio_path, tags) in [
+            (2, "third part", "c.mp3", vec!["meeting"]),
+            (0, "first part", "a.mp3", vec!["meeting", "work"]),
+            (1, "second part", "b.mp3", Vec::new()),
+        ] {
+            let mut record = record_at(
+                now + Duration::minutes(offset),
+                TranscriptionStatus::Success,
+            );
+            record.text = text.to_string();
+            record.audio_path = audio_path.to_string();
+            record.audio_duration_seconds = 1.5;
+            record.tags = tags.into_iter().map(String::from).collect();
+            ids.push(record.id.clone());
+            storage.import_transcription(record).unwrap();
+        }
+        let failed = record_at(now, TranscriptionStatus::Error);
+        let failed_id = failed.id.clone();
+        storage.import_transcription(failed).unwrap();
+
+        assert!(storage.merge_transcriptions(vec![ids[0].clone()]).is_err());
+        assert!(storage
+            .merge_transcriptions(vec![ids[0].clone(), ids[0].clone()])
+            .is_err());
+        assert!(storage
+            .merge_transcriptions(vec![ids[0].clone(), "missing".to_string()])
+            .is_err());
+        assert!(storage
+            .merge_transcriptions(vec![ids[0].clone(), failed_id])
+            .is_err());
+        // Failed validation leaves everything in place
+        assert_eq!(storage.get_all().len(), 4);
+
+        let merged = storage.merge_transcriptions(ids.clone()).unwrap();
+        assert_eq!(merged.text, "first part second part third part");
+        assert_eq!(merged.word_count, 6);
+        assert_eq!(merged.audio_duration_seconds, 4.5);
+        assert_eq!(merged.audio_path, "a.mp3");
+        assert_eq!(merged.status, TranscriptionStatus::Success);
+        assert_eq!(merged.tags, vec!["meeting", "work"]);
+        assert_eq!(merged.timestamp.timestamp_millis(), now.timestamp_millis());
+
+        for id in &ids {
+            assert!(storage.get_by_id(id).is_none());
+        }
+        let stored = storage.get_by_id(&merged.id).unwrap();
+        assert_eq!(stored.text, merged.text);
+        assert_eq!(storage.get_all().len(), 2);
+        // The parts stay restorable
+        assert_eq!(storage.get_deleted().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_jaccard_similarity() {
+        let similarity = |a: &str, b: &str| jaccard_similarity(&word_set(a), &word_set(b));
+
+        assert_eq!(similarity("send the report", "send the report"), 1.0);
+        // Case, punctuation and word order don't matter
+        assert_eq!(similarity("Send the report.", "the report, send"), 1.0);
+        assert_eq!(similarity("send the report", "send the invoice"), 0.5);
+        assert_eq!(similarity("hello world", "goodbye moon"), 0.0);
+        assert_eq!(similarity("", ""), 0.0);
+        assert_eq!(similarity("a b c d", "a b c d e"), 0.8);
+    }
+
+    #[test]
+    fn test_find_and_delete_near_duplicates() {
+        let storage = temp_storage();
+        let now = Local::now();
+
+        let mut ids = Vec::new();
+        for (offset, text) in [
+            (0, "meet me at the station at noon"),
+            (30, "meet me at the station at noon please"),
+            // Same text, but too long after the others to be a retry
+            (200, "meet me at the station at noon"),
+            (210, "something else entirely"),
+        ] {
+            let mut record = record_at(
+                now + Duration::seconds(offset),
+                TranscriptionStatus::Success,
+            );
+            record.text = text.to_string();
+            record.word_count = count_words(text);
+            ids.push(record.id.clone());
+            storage.import_transcription(record).unwrap();
+        }
+
+        let pairs = storage.find_near_duplicates(0.8).unwrap();
+        assert_eq!(pairs.len(), 1);
+        let (older, newer, similarity) = &pairs[0];
+        assert_eq!((older, newer), (&ids[0], &ids[1]));
+        assert!((similarity - 6.0 / 7.0).abs() < 1e-6);
+        assert_eq!(storage.find_near_duplicates(0.95).unwrap().len(), 0);
+
+        assert!(storage
+            .delete_duplicate_keeping_longer(&ids[0], &ids[0])
+            .is_err());
+        assert!(storage
+            .delete_duplicate_keeping_longer(&ids[0], "missing")
+            .is_err());
+
+        let (survivor, removed) = storage
+            .delete_duplicate_keeping_longer(&ids[1], &ids[0])
+            .unwrap();
+        assert_eq!(survivor.id, ids[1]);
+        assert_eq!(removed.id, ids[0]);
+        assert!(storage.get_by_id(&ids[0]).is_none());
+        assert!(storage.get_by_id(&ids[1]).is_some());
+        assert!(storage.restore(&ids[0]).unwrap());
+    }
+
+    #[test]
+    fn test_words_today() {
+        let storage = temp_storage();
+        assert_eq!(storage.get_words_today().unwrap(), 0);
+
+        let now = Local::now();
+        for (timestamp, status) in [
+            (now, TranscriptionStatus::Success),
+            (now, TranscriptionStatus::Success),
+            (now, TranscriptionStatus::Error),
+            (now - Duration::days(1), TranscriptionStatus::Success),
+        ] {
+            storage
+                .import_transcription(record_at(timestamp, status))
+                .unwrap();
+        }
+        assert_eq!(storage.get_words_today().unwrap(), 4);
+    }
+
+    #[test]
+    fn test_daily_word_counts() {
+        let storage = temp_storage();
+        let now = Local::now();
+        for (timestamp, status, seconds) in [
+            (now, TranscriptionStatus::Success, 1.5),
+            (now, TranscriptionStatus::Success, 2.0),
+            (now, TranscriptionStatus::Error, 4.0),
+            (now - Duration::days(2), TranscriptionStatus::Success, 3.0),
+        ] {
+            let mut record = record_at(timestamp, status);
+            record.audio_duration_seconds = seconds;
+            storage.import_transcription(record).unwrap();
+        }
+
+        let days = storage.get_daily_word_counts(3).unwrap();
+        assert_eq!(days.len(), 3);
+        assert_eq!(days[2].date, now.format("%Y-%m-%d").to_string());
+        assert_eq!((days[0].words, days[0].recordings), (2, 1));
+        assert_eq!(days[0].total_seconds, 3.0);
+        assert_eq!((days[1].words, days[1].recordings), (0, 0));
+        assert_eq!(days[1].total_seconds, 0.0);
+        assert_eq!((days[2].words, days[2].recordings), (4, 2));
+        assert_eq!(days[2].total_seconds, 3.5);
+
+        assert_eq!(storage.get_daily_word_counts(1).unwrap().len(), 1);
+        assert!(storage.get_daily_word_counts(0).unwrap().is_empty());
+        assert_eq!(
+            storage.get_daily_word_counts(u32::MAX).unwrap().len(),
+            MAX_REPORT_DAYS as usize
+        );
+    }
+
+    #[test]
+    fn test_get_range_is_inclusive_and_ascending() {
+        let storage = temp_storage();
+        let now = Local::now();
+        let since = now - Duration::days(3);
+        let until = now - Duration::days(1);
+        for timestamp in [
+            now,
+            until,
+            now - Duration::days(2),
+            since,
+            since - Duration::days(1),
+        ] {
+            storage
+                .import_transcription(record_at(timestamp, TranscriptionStatus::Success))
+                .unwrap();
+        }
+
+        let records = storage.get_range(since, until).unwrap();
+        let timestamps: Vec<i64> = records
+            .iter()
+            .map(|record| record.timestamp.timestamp_millis())
+            .collect();
+        assert_eq!(
+            timestamps,
+            vec![
+                since.timestamp_millis(),
+                (now - Duration::days(2)).timestamp_millis(),
+                until.timestamp_millis(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_revert_to_pre_replacement() {
+        let storage = temp_storage();
+        let record = storage
+            .save_transcription(
+                "use OpenAI today".to_string(),
+                String::new(),
+                TranscriptionStatus::Success,
+                None,
+                Some("use open ai today".to_string()),
+                TranscriptionMetadata::default(),
+            )
+            .unwrap();
+
+        let reverted = storage
+            .revert_to_pre_replacement(&record.id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(reverted.text, "use open ai today");
+        assert_eq!(reverted.word_count, 4);
+        assert!(reverted.replacement_applied_text.is_none());
+
+        let stored = storage.get_by_id(&record.id).unwrap();
+        assert_eq!(stored.text, "use open ai today");
+        assert!(storage
+            .revert_to_pre_replacement(&record.id)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_fts_search_matches_like_search() {
+        let mut storage = temp_storage();
+        assert!(storage.fts_enabled);
+
+        let words = ["alpha", "bravo", "charlie", "delta", "echo"];
+        let now = Local::now();
+        {
+            let conn = storage.connection.lock();
+            let tx = conn.unchecked_transaction().unwrap();
+            for i in 0..10_000usize {
+                let mut record = record_at(
+                    now - Duration::seconds(i as i64),
+                    TranscriptionStatus::Success,
+                );
+                record.text = format!(
+                    "entry {i} mentions {} and {}",
+                    words[i % 5],
+                    words[i / 5 % 5]
+                );
+                if i % 500 == 0 {
+                    record.raw_text = Some(format!("Quarterly budget review {i}"));
+                }
+                StorageManager::insert_record(&tx, &record).unwrap();
+            }
+            tx.commit().unwrap();
+        }
+
+        let queries = [
+            "BUDGET rev",
+            "charlie and delta",
+            "entry 12",
+            "ry 99",
+            "\"quoted\"",
+        ];
+        let mut results = Vec::new();
+        for use_fts in [true, false] {
+            storage.fts_enabled = use_fts;
+            let mut found = Vec::new();
+            for query in queries {
+                let ids: Vec<String> = storage
+                    .get_paginated(
+                        10_000,
+                        0,
+                        &TranscriptionFilter {
+                            search_query: Some(query),
+                            ..everything()
+                        },
+                    )
+                    .unwrap()
+                    .into_iter()
+                    .map(|record| record.id)
+                    .collect();
+                assert_eq!(
+                    storage
+                        .get_count(&TranscriptionFilter {
+                            search_query: Some(query),
+                            ..everything()
+                        })
+                        .unwrap(),
+                    ids.len()
+                );
+                found.push(ids);
+            }
+            results.push(found);
+        }
+
+        assert_eq!(results[0], results[1]);
+        assert_eq!(results[0][0].len(), 20);
+        assert!(results[0][4].is_empty());
+
+        storage.fts_enabled = true;
+        storage.delete(&results[0][0][0]).unwrap();
+        assert_eq!(
+            storage
+                .get_count(&TranscriptionFilter {
+                    search_query: Some("budget"),
+                    ..everything()
+                })
+                .unwrap(),
+            19
+        );
+    }
+
+    #[test]
+    fn test_streaks_with_gaps() {
+        let storage = temp_storage();
+        assert_eq!(storage.get_transcription_streak().unwrap(), 0);
+        assert_eq!(storage.get_longest_streak().unwrap(), 0);
+
+        let now = Local::now();
+        for days_ago in [0, 0, 1, 3, 5, 6, 7, 8] {
+            storage
+                .import_transcription(record_at(
+                    now - Duration::days(days_ago),
+                    TranscriptionStatus::Success,
+                ))
+                .unwrap();
+        }
+
+        assert_eq!(storage.get_transcription_streak().unwrap(), 2);
+        assert_eq!(storage.get_longest_streak().unwrap(), 4);
+    }
+
+    #[test]
+    fn test_search_with_highlight_snippets() {
+        let storage = temp_storage();
+        let now = Local::now();
+
+        let filler = "lorem ipsum dolor sit amet ".repeat(37);
+        let long_text = format!("{filler}the Zebra crossed here {filler}");
+        assert!(long_text.chars().count() >= 2000);
+        for (offset, text) in [
+            (0, long_text.as_str()),
+            (10, "a zebra and another zebra"),
+            (20, "nothing to see"),
+        ] {
+            let mut record = record_at(
+                now + Duration::seconds(offset),
+                TranscriptionStatus::Success,
+            );
+            record.text = text.to_string();
+            storage.import_transcription(record).unwrap();
+        }
+
+        let results = storage
+            .full_text_search_with_highlight("ZEBRA", 120)
+            .unwrap();
+        assert_eq!(results.len(), 2);
+
+        assert_eq!(results[0].snippet, "a zebra and another zebra");
+        assert_eq!(results[0].match_count, 2);
+
+        let snippet = &results[1].snippet;
+        assert!(snippet.contains("Zebra"));
+        assert!(snippet.chars().count() <= 120);
+        assert!(snippet.starts_with('…') && snippet.ends_with('…'));
+        assert_eq!(results[1].match_count, 1);
+
+        assert!(storage
+            .full_text_search_with_highlight("  ", 120)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_search_with_highlight_folds_unicode_case() {
+        let mut storage = temp_storage();
+        let mut record = record_at(Local::now(), TranscriptionStatus::Success);
+        record.text = "Rendez-vous à l'ÉCOLE, près du café".to_string();
+        storage.import_transcription(record).unwrap();
+
+        for use_fts in [true, false] {
+            storage.fts_enabled = use_fts;
+            for query in ["école", "CAFÉ", "À"] {
+                let results = storage.full_text_search_with_highlight(query, 120).unwrap();
+                assert_eq!(results.len(), 1, "{query} (fts: {use_fts})");
+                assert_eq!(results[0].match_count, 1);
+            }
+        }
+    }
+
+    #[test]
+    fn test_heatmap_fills_empty_cells() {
+        let totals = HashMap::from([((0, 0), (2, 30)), ((3, 14), (5, 120)), ((6, 23), (1, 4))]);
+        let cells = fill_heatmap(&totals);
+
+        assert_eq!(cells.len(), 7 * 24);
+        assert_eq!(
+            cells[0],
+            HeatmapCell {
+                day_of_week: 0,
+                hour_of_day: 0,
+                count: 2,
+                total_words: 30,
+            }
+        );
+        assert_eq!(cells[3 * 24 + 14].count, 5);
+        assert_eq!(cells[3 * 24 + 14].total_words, 120);
+        assert_eq!(cells[7 * 24 - 1].hour_of_day, 23);
+        assert_eq!(cells[7 * 24 - 1].count, 1);
+        assert_eq!(cells.iter().filter(|cell| cell.count > 0).count(), 3);
+
+        assert_eq!(peak_hour(&cells), Some(14));
+        assert_eq!(peak_hour(&fill_heatmap(&HashMap::new())), None);
+    }
+
+    #[test]
+    fn test_heatmap_data_groups_by_local_hour() {
+        let storage = temp_storage();
+        let at = Local.with_ymd_and_hms(2024, 5, 7, 9, 30, 0).unwrap();
+        for (minutes, status) in [
+            (0, TranscriptionStatus::Success),
+            (10, TranscriptionStatus::Success),
+            (20, TranscriptionStatus::Error),
+        ] {
+            let mut record = record_at(at + Duration::minutes(minutes), status);
+            record.word_count = 10;
+            storage.import_transcription(record).unwrap();
+        }
+
+        let cells = storage.get_heatmap_data().unwrap();
+        // 7 May 2024 was a Tuesday
+        let cell = &cells[2 * 24 + 9];
+        assert_eq!((cell.day_of_week, cell.hour_of_day), (2, 9));
+        assert_eq!((cell.count, cell.total_words), (2, 20));
+        assert_eq!(storage.get_peak_recording_hour().unwrap(), Some(9));
+    }
+}
@@ -1,4 +1,8 @@
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+
 use crate::AppRuntime;
+use parking_lot::Mutex;
 use serde::Serialize;
 use tauri::{AppHandle, Emitter, Manager, WebviewWindow};
 
@@ -6,6 +10,9 @@ pub const WINDOW_LABEL: &str = "toast";
 pub const EVENT_SHOW: &str = "toast:show";
 pub const EVENT_HIDE: &str = "toast:hide";
 
+/// Toasts waiting behind the visible one, beyond which older ones are dropped.
+const MAX_QUEUED_TOASTS: usize = 5;
+
 #[derive(Serialize, Clone)]
 pub struct Payload {
     #[serde(rename = "type")]
@@ -24,7 +31,103 @@ pub struct Payload {
     pub action_label: Option<String>,
 }
 
+/// Toasts that arrived while another was on screen, shown in order as each is dismissed.
+#[derive(Default)]
+pub struct ToastQueue {
+    pending: Mutex<VecDeque<Payload>>,
+    showing: AtomicBool,
+}
+
+impl ToastQueue {
+    /// Returns the payload if it can be shown right away, otherwise queues it.
+    fn enqueue(&self, payload: Payload) -> Option<Payload> {
+        let mut pending = self.pending.lock();
+        if !self.showing.swap(true, Ordering::SeqCst) {
+            return Some(payload);
+        }
+
+        if pending.len() >= MAX_QUEUED_TOASTS {
+            // Errors are what users can't afford to miss, so they're dropped last
+            match pending.iter().position(|toast| toast.toast_type != "error") {
+                Some(index) => {
+                    pending.remove(index);
+                }
+                None if payload.toast_type == "error" => {
+                    pending.pop_front();
+                }
+                None => return None,
+            }
+        }
+        pending.push_back(payload);
+        None
+    }
+
+    /// The next toast to show once the visible one is dismissed, if any.
+    fn advance(&self) -> Option<Payload> {
+        let mut pending = self.pending.lock();
+        let next = pending.pop_front();
+        self.showing.store(next.is_some(), Ordering::SeqCst);
+        next
+    }
+
+    fn clear(&self) {
+        let mut pending = self.pending.lock();
+        pending.clear();
+        self.showing.store(false, Ordering::SeqCst);
+    }
+
+    pub fn depth(&self) -> usize {
+        self.pending.lock().len()
+    }
+}
+
 pub fn emit_toast(app: &AppHandle<AppRuntime>, payload: Payload) {
+    if payload.toast_type != "error" && is_focus_mode(app) {
+        return;
+    }
+
+    // Before the app state exists there's nothing to queue behind
+    let payload = match app.try_state::<crate::AppState>() {
+        Some(state) => match state.toast_queue().enqueue(payload) {
+            Some(payload) => payload,
+            None => return,
+        },
+        None => payload,
+    };
+    display(app, payload);
+}
+
+/// Show the next queued toast after the visible one was dismissed, or hide the toast
+/// window if none is waiting. Returns whether another toast is now showing.
+pub fn show_next(app: &AppHandle<AppRuntime>) -> bool {
+    let next = app
+        .try_state::<crate::AppState>()
+        .and_then(|state| state.toast_queue().advance());
+    match next {
+        Some(payload) => {
+            display(app, payload);
+            true
+        }
+        None => {
+            hide(app);
+            false
+        }
+    }
+}
+
+/// Drop queued toasts, for when the toast window has been hidden by the frontend.
+pub fn clear_queue(app: &AppHandle<AppRuntime>) {
+    if let Some(state) = app.try_state::<crate::AppState>() {
+        state.toast_queue().clear();
+    }
+}
+
+#[tauri::command]
+pub fn toast_queue_depth(state: tauri::State<crate::AppState>) -> usize {
+    state.toast_queue().depth()
+}
+
+fn display(app: &AppHandle<AppRuntime>, payload: Payload) {
     if let Some(toast_window) = app.get_webview_window(WINDOW_LABEL) {
         position_toast_window(app, &toast_window);
         crate::platform::toast::show(app, &toast_window);
@@ -98,7 +201,6 @@ pub fn show_with_action(
     );
 }
 
-#[allow(dead_code)]
 pub fn hide(app: &AppHandle<AppRuntime>) {
     let _ = app.emit(EVENT_HIDE, ());
 
@@ -108,6 +210,13 @@ pub fn hide(app: &AppHandle<AppRuntime>) {
     }
 }
 
+/// Focus mode keeps transcription silent, so only errors are allowed through.
+fn is_focus_mode(app: &AppHandle<AppRuntime>) -> bool {
+    app.try_state::<crate::AppState>()
+        .map(|state| state.current_settings().focus_mode)
+        .unwrap_or(false)
+}
+
 fn position_toast_window(_app: &AppHandle<AppRuntime>, toast_window: &WebviewWindow<AppRuntime>) {
     let scale_factor = toast_window.scale_factor().unwrap_or(1.0);
     let toast_width = (320.0 * scale_factor) as i32;
@@ -120,3 +229,75 @@ fn position_toast_window(_app: &AppHandle<AppRuntime>, toast_window: &WebviewWin
         let _ = toast_window.set_position(tauri::PhysicalPosition::new(x, y));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn toast(toast_type: &str, message: &str) -> Payload {
+        Payload {
+            toast_type: toast_type.to_string(),
+            title: None,
+            message: message.to_string(),
+            auto_dismiss: None,
+            duration: None,
+            retry_id: None,
+            mode: None,
+            action: None,
+            action_label: None,
+        }
+    }
+
+    fn messages(queue: &ToastQueue) -> Vec<String> {
+        queue
+            .pending
+            .lock()
+            .iter()
+            .map(|toast| toast.message.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_toasts_queue_behind_the_visible_one() {
+        let queue = ToastQueue::default();
+        assert!(queue.enqueue(toast("info", "stopped")).is_some());
+        assert!(queue.enqueue(toast("error", "failed")).is_none());
+        assert_eq!(queue.depth(), 1);
+
+        assert_eq!(queue.advance().unwrap().message, "failed");
+        assert!(queue.advance().is_none());
+        // Nothing showing anymore, so the next toast goes straight through
+        assert!(queue.enqueue(toast("info", "again")).is_some());
+
+        queue.clear();
+        assert!(queue.enqueue(toast("info", "after clear")).is_some());
+    }
+
+    #[test]
+    fn test_full_queue_drops_oldest_non_error_first() {
+        let queue = ToastQueue::default();
+        queue.enqueue(toast("info", "visible"));
+        for (toast_type, message) in [
+            ("error", "e1"),
+            ("info", "i1"),
+            ("error", "e2"),
+            ("info", "i2"),
+            ("error", "e3"),
+        ] {
+            queue.enqueue(toast(toast_type, message));
+        }
+
+        queue.enqueue(toast("error", "e4"));
+        assert_eq!(messages(&queue), vec!["e1", "e2", "i2", "e3", "e4"]);
+        queue.enqueue(toast("info", "i3"));
+        assert_eq!(messages(&queue), vec!["e1", "e2", "e3", "e4", "i3"]);
+        queue.enqueue(toast("error", "e5"));
+        assert_eq!(messages(&queue), vec!["e1", "e2", "e3", "e4", "e5"]);
+
+        // With only errors left, another info toast is dropped instead
+        queue.enqueue(toast("info", "i4"));
+        assert_eq!(messages(&queue), vec!["e1", "e2", "e3", "e4", "e5"]);
+        queue.enqueue(toast("error", "e6"));
+        assert_eq!(messages(&queue), vec!["e2", "e3", "e4", "e5", "e6"]);
+    }
+}
@@ -1,8 +1,8 @@
 use std::fs;
 
 use anyhow::{anyhow, Context, Result};
-use reqwest::{multipart, Client};
-use serde::Deserialize;
+use reqwest::{multipart, Client, RequestBuilder};
+use serde::{Deserialize, Serialize};
 
 use crate::recorder::RecordingSaved;
 
@@ -12,8 +12,20 @@ pub struct TranscriptionConfig {
     pub api_key: String,
     pub include_word_timestamps: bool,
     pub auto_paste: bool,
+    /// Words the API scores below this are dropped from the transcript; 0 disables filtering.
+    pub min_word_confidence: f32,
+    /// Spoken language hint sent with the audio.
+    pub language: Option<String>,
+    /// Extra headers for API gateways in front of the transcription service.
+    pub custom_headers: Vec<(String, String)>,
 }
 
+/// Most custom headers a transcription request may carry.
+pub const MAX_CUSTOM_HEADERS: usize = 10;
+/// Headers the app sets itself, which custom headers must not replace. The app never sends
+/// `Authorization`, so gateways with their own auth scheme can use it.
+const RESERVED_HEADERS: [&str; 2] = ["x-api-key", "content-type"];
+
 impl TranscriptionConfig {
     pub fn from_env() -> Self {
         dotenvy::dotenv().ok();
@@ -24,11 +36,20 @@ impl TranscriptionConfig {
             api_key: std::env::var("GLIMPSE_API_KEY").unwrap_or_else(|_| "local-dev-key".into()),
             include_word_timestamps: env_flag("GLIMPSE_INCLUDE_WORD_TIMESTAMPS", false),
             auto_paste: env_flag("GLIMPSE_AUTO_PASTE", true),
+            min_word_confidence: 0.0,
+            language: None,
+            custom_headers: Vec::new(),
         }
     }
 
-    pub fn from_settings(_settings: &crate::settings::UserSettings) -> Self {
-        Self::from_env()
+    pub fn from_settings(settings: &crate::settings::UserSettings) -> Self {
+        Self {
+            min_word_confidence: settings.min_word_confidence,
+            language: Some(settings.language.as_str().to_string())
+                .filter(|language| !language.is_empty()),
+            custom_headers: settings.custom_api_headers.clone(),
+            ..Self::from_env()
+        }
     }
 
     pub fn endpoint_url(&self) -> String {
@@ -42,10 +63,99 @@ fn env_flag(key: &str, default: bool) -> bool {
         .unwrap_or(default)
 }
 
+/// Check user-supplied headers before they are saved: names are letters, digits and dashes
+/// and not one the app sets itself, values are printable ASCII, and there are at most
+/// `MAX_CUSTOM_HEADERS` of them.
+pub fn validate_custom_headers(headers: &[(String, String)]) -> Result<(), String> {
+    if headers.len() > MAX_CUSTOM_HEADERS {
+        return Err(format!(
+            "At most {MAX_CUSTOM_HEADERS} custom API headers are allowed"
+        ));
+    }
+    for (name, value) in headers {
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            return Err(format!(
+                "Invalid header name \"{name}\": use only letters, digits and dashes"
+            ));
+        }
+        if is_reserved_header(name) {
+            return Err(format!(
+                "Header \"{name}\" is set by Glimpse and can't be overridden"
+            ));
+        }
+        if !value.chars().all(|c| matches!(c, ' '..='~')) {
+            return Err(format!(
+                "Header \"{name}\" must contain only printable ASCII characters"
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn is_reserved_header(name: &str) -> bool {
+    RESERVED_HEADERS
+        .iter()
+        .any(|reserved| reserved.eq_ignore_ascii_case(name))
+}
+
+/// Headers saved before reserved names were rejected are skipped rather than sent twice.
+fn apply_custom_headers(request: RequestBuilder, headers: &[(String, String)]) -> RequestBuilder {
+    headers
+        .iter()
+        .filter(|(name, _)| !is_reserved_header(name))
+        .fold(request, |request, (name, value)| {
+            request.header(name, value)
+        })
+}
+
+/// Filtered spans longer than this many words are marked instead of silently dropped.
+const INAUDIBLE_SPAN_WORDS: usize = 3;
+const INAUDIBLE_PLACEHOLDER: &str = "[inaudible]";
+
 #[derive(Debug, Deserialize)]
 pub struct TranscriptionSuccess {
     pub transcript: String,
     pub speech_model: Option<String>,
+    #[serde(default)]
+    pub word_confidences: Option<Vec<WordResult>>,
+    /// Model confidence in the whole transcript, 0–1, when the engine reports one.
+    #[serde(default)]
+    pub avg_confidence: Option<f32>,
+    /// Language the engine heard, when it was left to detect it.
+    #[serde(default)]
+    pub detected_language: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WordResult {
+    pub word: String,
+    pub start_ms: u32,
+    pub end_ms: u32,
+    pub confidence: f32,
+}
+
+/// Rebuild a transcript from the words scoring at least `min_confidence`. Runs of more than
+/// `INAUDIBLE_SPAN_WORDS` dropped words become `[inaudible]`.
+pub fn filter_low_confidence_words(words: &[WordResult], min_confidence: f32) -> String {
+    let mut kept: Vec<&str> = Vec::with_capacity(words.len());
+    let mut dropped = 0;
+
+    for word in words {
+        if word.confidence >= min_confidence {
+            if dropped > INAUDIBLE_SPAN_WORDS {
+                kept.push(INAUDIBLE_PLACEHOLDER);
+            }
+            dropped = 0;
+            kept.push(&word.word);
+        } else {
+            dropped += 1;
+        }
+    }
+    if dropped > INAUDIBLE_SPAN_WORDS {
+        kept.push(INAUDIBLE_PLACEHOLDER);
+    }
+
+    normalize_transcript(&kept.join(" "))
 }
 
 pub fn normalize_transcript(input: &str) -> String {
@@ -74,6 +184,12 @@ struct ApiResponse {
     transcript: String,
     #[serde(default)]
     model: Option<String>,
+    #[serde(default)]
+    words: Option<Vec<WordResult>>,
+    #[serde(default)]
+    confidence: Option<f32>,
+    #[serde(default)]
+    language: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -81,13 +197,28 @@ struct ApiErrorResponse {
     error: String,
 }
 
-pub async fn request_transcription(
-    client: &Client,
+/// Fields sent next to the audio so the server can stamp the record with when it was
+/// recorded, rather than when the upload arrived.
+fn recording_metadata_fields(
     saved: &RecordingSaved,
-    config: &TranscriptionConfig,
-) -> Result<TranscriptionSuccess> {
-    let bytes = fs::read(&saved.path)
-        .with_context(|| format!("Failed to read recording at {}", saved.path.display()))?;
+    language: Option<&str>,
+) -> Vec<(&'static str, String)> {
+    let mut fields = vec![
+        ("started_at", saved.started_at.to_rfc3339()),
+        ("ended_at", saved.ended_at.to_rfc3339()),
+    ];
+    if let Some(language) = language {
+        fields.push(("language", language.to_string()));
+    }
+    fields
+}
+
+/// The upload form: the recording as `file`, followed by its metadata fields.
+pub fn build_multipart_with_metadata(
+    saved: &RecordingSaved,
+    bytes: Vec<u8>,
+    language: Option<&str>,
+) -> Result<multipart::Form> {
     let file_name = saved
         .path
         .file_name()
@@ -99,18 +230,39 @@ pub async fn request_transcription(
         .file_name(file_name)
         .mime_str(mime.as_ref())?;
 
-    let form = multipart::Form::new().part("file", part);
+    let mut form = multipart::Form::new().part("file", part);
+    for (name, value) in recording_metadata_fields(saved, language) {
+        form = form.text(name, value);
+    }
+    Ok(form)
+}
+
+pub async fn request_transcription(
+    client: &Client,
+    saved: &RecordingSaved,
+    config: &TranscriptionConfig,
+) -> Result<TranscriptionSuccess> {
+    let bytes = fs::read(&saved.path)
+        .with_context(|| format!("Failed to read recording at {}", saved.path.display()))?;
+    let form = build_multipart_with_metadata(saved, bytes, config.language.as_deref())?;
 
     let request = client
         .post(config.endpoint_url())
         .query(&[("include_word_timestamps", config.include_word_timestamps)])
         .multipart(form);
 
+    let request = if config.min_word_confidence > 0.0 {
+        request.query(&[("min_word_confidence", config.min_word_confidence)])
+    } else {
+        request
+    };
+
     let request = if config.api_key.is_empty() {
         request
     } else {
         request.header("x-api-key", &config.api_key)
     };
+    let request = apply_custom_headers(request, &config.custom_headers);
 
     let response = request
         .send()
@@ -122,9 +274,20 @@ pub async fn request_transcription(
     if status.is_success() {
         let parsed: ApiResponse = serde_json::from_str(&text)
             .with_context(|| format!("Unexpected transcription response: {text}"))?;
+        let transcript = match &parsed.words {
+            Some(words) if config.min_word_confidence > 0.0 && !words.is_empty() => {
+                filter_low_confidence_words(words, config.min_word_confidence)
+            }
+            _ => normalize_transcript(&parsed.transcript),
+        };
         return Ok(TranscriptionSuccess {
-            transcript: normalize_transcript(&parsed.transcript),
+            transcript,
             speech_model: parsed.model,
+            word_confidences: parsed.words,
+            avg_confidence: parsed
+                .confidence
+                .map(|confidence| confidence.clamp(0.0, 1.0)),
+            detected_language: parsed.language,
         });
     }
 
@@ -138,3 +301,132 @@ pub async fn request_transcription(
         Err(anyhow!(text))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(word: &str, confidence: f32) -> WordResult {
+        WordResult {
+            word: word.to_string(),
+            start_ms: 0,
+            end_ms: 0,
+            confidence,
+        }
+    }
+
+    /// The form exactly as it goes over the wire.
+    fn encoded_body(form: multipart::Form) -> String {
+        use futures_util::TryStreamExt;
+
+        let chunks: Vec<_> =
+            tauri::async_runtime::block_on(form.into_stream().try_collect()).unwrap();
+        String::from_utf8(chunks.concat()).unwrap()
+    }
+
+    fn form_field(name: &str, value: &str) -> String {
+        format!("Content-Disposition: form-data; name=\"{name}\"\r\n\r\n{value}\r\n")
+    }
+
+    #[test]
+    fn test_multipart_carries_recording_metadata() {
+        let ended_at = chrono::Local::now();
+        let saved = RecordingSaved {
+            path: std::path::PathBuf::from("/tmp/recording.mp3"),
+            started_at: ended_at - chrono::Duration::seconds(5),
+            ended_at,
+        };
+
+        let form = build_multipart_with_metadata(&saved, b"ID3".to_vec(), Some("de")).unwrap();
+        let boundary = form.boundary().to_string();
+        let body = encoded_body(form);
+        assert!(body.contains(&format!(
+            "name=\"file\"; filename=\"recording.mp3\"\r\nContent-Type: audio/mpeg\r\n\r\nID3\r\n--{boundary}"
+        )));
+        assert!(body.contains(&form_field("started_at", &saved.started_at.to_rfc3339())));
+        assert!(body.contains(&form_field("ended_at", &saved.ended_at.to_rfc3339())));
+        assert!(body.contains(&form_field("language", "de")));
+        assert!(body.ends_with(&format!("--{boundary}--\r\n")));
+
+        let form = build_multipart_with_metadata(&saved, b"ID3".to_vec(), None).unwrap();
+        let body = encoded_body(form);
+        assert!(body.contains(&form_field("started_at", &saved.started_at.to_rfc3339())));
+        assert!(!body.contains("name=\"language\""));
+    }
+
+    #[test]
+    fn test_custom_headers_are_sent() {
+        let headers = vec![
+            ("X-Tenant-ID".to_string(), "acme".to_string()),
+            ("X-API-Version".to_string(), "2".to_string()),
+            ("Authorization".to_string(), "Gateway token".to_string()),
+        ];
+        let request = Client::new()
+            .post("http://127.0.0.1:9001/transcribe")
+            .header("x-api-key", "key");
+        let request = apply_custom_headers(request, &headers).build().unwrap();
+
+        assert_eq!(request.headers()["x-api-key"], "key");
+        assert_eq!(request.headers()["x-tenant-id"], "acme");
+        assert_eq!(request.headers()["x-api-version"], "2");
+        assert_eq!(request.headers()["authorization"], "Gateway token");
+
+        let sneaky = vec![("X-API-Key".to_string(), "stolen".to_string())];
+        let request = Client::new()
+            .post("http://127.0.0.1:9001/transcribe")
+            .header("x-api-key", "key");
+        let request = apply_custom_headers(request, &sneaky).build().unwrap();
+        assert_eq!(request.headers().get_all("x-api-key").iter().count(), 1);
+        assert_eq!(request.headers()["x-api-key"], "key");
+    }
+
+    #[test]
+    fn test_validate_custom_headers() {
+        let header = |name: &str, value: &str| (name.to_string(), value.to_string());
+
+        assert!(validate_custom_headers(&[]).is_ok());
+        assert!(validate_custom_headers(&[header("X-Tenant-ID", "acme corp/1")]).is_ok());
+        assert!(validate_custom_headers(&[header("", "value")]).is_err());
+        assert!(validate_custom_headers(&[header("X Tenant", "acme")]).is_err());
+        assert!(validate_custom_headers(&[header("X-Tenant:", "acme")]).is_err());
+        assert!(validate_custom_headers(&[header("X-Tenant", "line\nbreak")]).is_err());
+        assert!(validate_custom_headers(&[header("X-Tenant", "café")]).is_err());
+        assert!(validate_custom_headers(&[header("Authorization", "Bearer x")]).is_ok());
+        assert!(validate_custom_headers(&[header("X-API-KEY", "other")]).is_err());
+        assert!(validate_custom_headers(&[header("content-type", "text/plain")]).is_err());
+
+        let too_many: Vec<_> = (0..=MAX_CUSTOM_HEADERS)
+            .map(|i| header(&format!("X-Header-{i}"), "value"))
+            .collect();
+        assert!(validate_custom_headers(&too_many).is_err());
+        assert!(validate_custom_headers(&too_many[..MAX_CUSTOM_HEADERS]).is_ok());
+    }
+
+    #[test]
+    fn test_filter_low_confidence_words() {
+        let words = vec![
+            word("send", 0.9),
+            word("uh", 0.2),
+            word("the", 0.8),
+            word("mumble", 0.1),
+            word("mumble", 0.1),
+            word("mumble", 0.1),
+            word("mumble", 0.1),
+            word("report", 0.95),
+            word("today", 0.3),
+        ];
+
+        assert_eq!(
+            filter_low_confidence_words(&words, 0.5),
+            "send the [inaudible] report"
+        );
+        assert_eq!(
+            filter_low_confidence_words(&words, 0.05),
+            "send uh the mumble mumble mumble mumble report today"
+        );
+        assert_eq!(
+            filter_low_confidence_words(&words[3..7], 0.5),
+            "[inaudible]"
+        );
+    }
+}
@@ -1,4 +1,8 @@
-use crate::settings::{TranscriptionMode, UserSettings};
+use crate::analytics::AnalyticsEvent;
+use crate::keyboard::normalize_shortcut_display;
+use crate::pill::{PillStatePayload, PillStatus, EVENT_PILL_STATE};
+use crate::settings::{TranscriptionMode, TrayIconMode, UserSettings};
+use crate::shortcuts_display::format_shortcut;
 use crate::{
     audio, model_manager, AppRuntime, AppState, EVENT_SETTINGS_CHANGED, FEEDBACK_URL,
     SETTINGS_WINDOW_LABEL,
@@ -6,7 +10,7 @@ use crate::{
 use std::sync::atomic::Ordering;
 use tauri::menu::{CheckMenuItemBuilder, Menu, MenuBuilder, MenuItem, SubmenuBuilder};
 use tauri::tray::{MouseButton, MouseButtonState, TrayIcon, TrayIconBuilder, TrayIconEvent};
-use tauri::{AppHandle, Emitter, Manager, WebviewUrl, WebviewWindowBuilder, WindowEvent};
+use tauri::{AppHandle, Emitter, Listener, Manager, WebviewUrl, WebviewWindowBuilder, WindowEvent};
 
 #[cfg(target_os = "macos")]
 use tauri::ActivationPolicy;
@@ -14,11 +18,31 @@ use tauri_plugin_opener::OpenerExt;
 
 const MENU_ID_MODE_LOCAL: &str = "menu_mode_local";
 const MENU_ID_MODE_CLOUD: &str = "menu_mode_cloud";
+const MENU_ID_MODE_HYBRID: &str = "menu_mode_hybrid";
 const MENU_ID_MODEL_PREFIX: &str = "menu_model_";
 const MENU_ID_MIC_PREFIX: &str = "menu_mic_";
 const MENU_ID_MIC_DEFAULT: &str = "menu_mic_default";
 const MENU_ID_FEEDBACK: &str = "menu_send_feedback";
 const MENU_ID_CHECK_UPDATES: &str = "menu_check_updates";
+const MENU_ID_FOCUS_MODE: &str = "menu_focus_mode";
+const MENU_ID_RECORD: &str = "menu_record";
+const MIN_RATINGS_FOR_ACCURACY: u32 = 5;
+
+const TRAY_ICON: &[u8] = include_bytes!("../icons/tray.png");
+const TRAY_ICON_RECORDING: &[u8] = include_bytes!("../icons/tray_recording.png");
+const TRAY_ICON_PROCESSING: &[u8] = include_bytes!("../icons/tray_processing.png");
+const TRAY_ICON_ERROR: &[u8] = include_bytes!("../icons/tray_error.png");
+
+/// Label and enabled state of the record item for a pill status.
+pub(crate) fn recording_menu_item(status: PillStatus) -> (&'static str, bool) {
+    match status {
+        PillStatus::Idle | PillStatus::Error => ("Start Recording", true),
+        PillStatus::Listening => ("Stop Recording", true),
+        PillStatus::Processing | PillStatus::ModelLoading | PillStatus::Cancelling => {
+            ("Start Recording", false)
+        }
+    }
+}
 
 fn build_tray_menu(
     app: &AppHandle<AppRuntime>,
@@ -26,6 +50,24 @@ fn build_tray_menu(
 ) -> tauri::Result<Menu<AppRuntime>> {
     let mut menu = MenuBuilder::new(app);
 
+    let state = app.try_state::<AppState>();
+    let status = state
+        .as_ref()
+        .map(|state| state.pill().status())
+        .unwrap_or(PillStatus::Idle);
+    let (record_label, record_enabled) = recording_menu_item(status);
+    let record = MenuItem::with_id(
+        app,
+        MENU_ID_RECORD,
+        record_label,
+        record_enabled,
+        None::<&str>,
+    )?;
+    menu = menu.item(&record).separator();
+    if let Some(state) = &state {
+        *state.tray_record_item.lock() = Some(record.clone());
+    }
+
     let mode_cloud = CheckMenuItemBuilder::with_id(MENU_ID_MODE_CLOUD, "Cloud")
         .checked(matches!(
             settings.transcription_mode,
@@ -38,11 +80,46 @@ fn build_tray_menu(
             TranscriptionMode::Local
         ))
         .build(app)?;
-    let mode_submenu = SubmenuBuilder::new(app, "Mode")
+    let mode_hybrid = CheckMenuItemBuilder::with_id(MENU_ID_MODE_HYBRID, "Hybrid")
+        .checked(matches!(
+            settings.transcription_mode,
+            TranscriptionMode::Hybrid
+        ))
+        .build(app)?;
+    let mut mode_submenu = SubmenuBuilder::new(app, "Mode")
         .item(&mode_cloud)
         .item(&mode_local)
-        .build()?;
-    menu = menu.item(&mode_submenu);
+        .item(&mode_hybrid);
+
+    let shortcuts = [
+        ("Smart", settings.smart_enabled, &settings.smart_shortcut),
+        ("Hold", settings.hold_enabled, &settings.hold_shortcut),
+        ("Toggle", settings.toggle_enabled, &settings.toggle_shortcut),
+    ];
+    if shortcuts.iter().any(|(_, enabled, _)| *enabled) {
+        mode_submenu = mode_submenu.separator();
+    }
+    for (name, enabled, shortcut) in shortcuts {
+        if !enabled {
+            continue;
+        }
+        let label = format!(
+            "{name}: {}",
+            format_shortcut(
+                &normalize_shortcut_display(shortcut, settings.keyboard_layout),
+                settings.shortcut_display_format
+            )
+        );
+        let item = MenuItem::with_id(
+            app,
+            format!("menu_shortcut_{}", name.to_lowercase()),
+            label,
+            false,
+            None::<&str>,
+        )?;
+        mode_submenu = mode_submenu.item(&item);
+    }
+    menu = menu.item(&mode_submenu.build()?);
 
     let mut mic_submenu = SubmenuBuilder::new(app, "Microphone");
     let default_mic = CheckMenuItemBuilder::with_id(MENU_ID_MIC_DEFAULT, "System Default")
@@ -50,7 +127,7 @@ fn build_tray_menu(
         .build(app)?;
     mic_submenu = mic_submenu.item(&default_mic);
 
-    match audio::list_input_devices() {
+    match audio::list_devices_with_capabilities() {
         Ok(devices) => {
             if devices.is_empty() {
                 let unavailable = MenuItem::with_id(
@@ -63,11 +140,14 @@ fn build_tray_menu(
                 mic_submenu = mic_submenu.item(&unavailable);
             } else {
                 for device in devices {
-                    let label = if device.is_default {
+                    let mut label = if device.is_default {
                         format!("{} (Default)", device.name)
                     } else {
                         device.name.clone()
                     };
+                    if device.needs_resampling() {
+                        label.push_str(" (needs resampling)");
+                    }
                     let checked = settings.microphone_device.as_deref() == Some(device.id.as_str());
                     let item = CheckMenuItemBuilder::with_id(
                         format!("{MENU_ID_MIC_PREFIX}dev:{}", device.id),
@@ -92,13 +172,29 @@ fn build_tray_menu(
     }
     menu = menu.item(&mic_submenu.build()?);
 
-    if matches!(settings.transcription_mode, TranscriptionMode::Local) {
+    if matches!(
+        settings.transcription_mode,
+        TranscriptionMode::Local | TranscriptionMode::Hybrid
+    ) {
+        let accuracy = app
+            .try_state::<AppState>()
+            .and_then(|state| state.storage().get_model_accuracy_report().ok())
+            .unwrap_or_default();
         let mut model_submenu = SubmenuBuilder::new(app, "Model");
-        for model in model_manager::list_models() {
-            let installed = model_manager::check_model_status(app.clone(), model.key.clone())
-                .map(|s| s.installed)
-                .unwrap_or(false);
-            let label = if installed {
+        let models = model_manager::list_models_with_status(app.clone()).unwrap_or_default();
+        for model in models {
+            let installed = model.installed;
+            let rating = accuracy
+                .iter()
+                .find(|entry| entry.speech_model == model.label)
+                .filter(|entry| entry.total() >= MIN_RATINGS_FOR_ACCURACY);
+            let label = if let (true, Some(rating)) = (installed, rating) {
+                format!(
+                    "{} — {}% positive",
+                    model.label,
+                    rating.positive_percentage()
+                )
+            } else if installed {
                 model.label.clone()
             } else {
                 format!("{} (Not downloaded)", model.label)
@@ -115,6 +211,12 @@ fn build_tray_menu(
         menu = menu.item(&model_submenu.build()?);
     }
 
+    menu = menu.separator();
+    let focus_mode = CheckMenuItemBuilder::with_id(MENU_ID_FOCUS_MODE, "Focus Mode")
+        .checked(settings.focus_mode)
+        .build(app)?;
+    menu = menu.item(&focus_mode);
+
     menu = menu.separator();
     let check_updates = MenuItem::with_id(
         app,
@@ -148,6 +250,19 @@ pub(crate) fn refresh_tray_menu(
     Ok(())
 }
 
+/// Relabels the record item in place for a pill status, without rebuilding the menu.
+pub(crate) fn update_tray_record_item(
+    app: &AppHandle<AppRuntime>,
+    status: PillStatus,
+) -> tauri::Result<()> {
+    let (label, enabled) = recording_menu_item(status);
+    if let Some(item) = app.state::<AppState>().tray_record_item.lock().clone() {
+        item.set_text(label)?;
+        item.set_enabled(enabled)?;
+    }
+    Ok(())
+}
+
 fn set_transcription_mode_from_menu(app: &AppHandle<AppRuntime>, mode: TranscriptionMode) {
     let state = app.state::<AppState>();
     let mut settings = state.current_settings();
@@ -191,9 +306,16 @@ fn set_local_model_from_menu(app: &AppHandle<AppRuntime>, model_key: &str) {
     if settings.local_model == model_key {
         return;
     }
-    settings.local_model = model_key.to_string();
+    let previous_model = std::mem::replace(&mut settings.local_model, model_key.to_string());
     match state.persist_settings(settings.clone()) {
         Ok(saved) => {
+            crate::analytics::track(
+                app,
+                AnalyticsEvent::ModelSwitched {
+                    from: previous_model,
+                    to: saved.local_model.clone(),
+                },
+            );
             if let Err(err) = refresh_tray_menu(app, &saved) {
                 eprintln!("Failed to refresh tray menu: {err}");
             }
@@ -229,7 +351,21 @@ fn handle_tray_menu_event(app: &AppHandle<AppRuntime>, id: &str) {
     match id {
         MENU_ID_MODE_LOCAL => set_transcription_mode_from_menu(app, TranscriptionMode::Local),
         MENU_ID_MODE_CLOUD => set_transcription_mode_from_menu(app, TranscriptionMode::Cloud),
+        MENU_ID_MODE_HYBRID => set_transcription_mode_from_menu(app, TranscriptionMode::Hybrid),
         MENU_ID_MIC_DEFAULT => set_microphone_from_menu(app, None),
+        MENU_ID_RECORD => {
+            let state = app.state::<AppState>();
+            if state.pill().status() == PillStatus::Listening {
+                state.pill().stop_recording_from_menu(app);
+            } else {
+                state.pill().start_recording_from_menu(app);
+            }
+        }
+        MENU_ID_FOCUS_MODE => {
+            if let Err(err) = crate::switch_focus_mode(app) {
+                eprintln!("Failed to toggle focus mode: {err}");
+            }
+        }
         MENU_ID_FEEDBACK => {
             if let Err(err) = app.opener().open_url(FEEDBACK_URL, None::<&str>) {
                 eprintln!("Failed to open feedback link: {err}");
@@ -252,12 +388,52 @@ fn handle_tray_menu_event(app: &AppHandle<AppRuntime>, id: &str) {
     }
 }
 
+/// Icon for a pill status and whether macOS should draw it as a template image. The
+/// recording icon is drawn as is so its dot stays red.
+fn tray_icon_for(status: PillStatus, mode: TrayIconMode) -> (&'static [u8], bool) {
+    if mode == TrayIconMode::Static {
+        return (TRAY_ICON, true);
+    }
+    match status {
+        PillStatus::Idle => (TRAY_ICON, true),
+        PillStatus::Listening => (TRAY_ICON_RECORDING, false),
+        PillStatus::Processing | PillStatus::ModelLoading | PillStatus::Cancelling => {
+            (TRAY_ICON_PROCESSING, true)
+        }
+        PillStatus::Error => (TRAY_ICON_ERROR, true),
+    }
+}
+
+pub(crate) fn update_tray_icon(
+    app: &AppHandle<AppRuntime>,
+    status: PillStatus,
+) -> tauri::Result<()> {
+    let state = app.state::<AppState>();
+    let (bytes, as_template) = tray_icon_for(status, state.current_settings().tray_icon_mode);
+    if let Some(tray) = state.tray.lock().clone() {
+        tray.set_icon(Some(tauri::image::Image::from_bytes(bytes)?.to_owned()))?;
+        tray.set_icon_as_template(as_template)?;
+    }
+    Ok(())
+}
+
 pub fn build_tray(app: &AppHandle<AppRuntime>) -> tauri::Result<TrayIcon<AppRuntime>> {
     let settings = app.state::<AppState>().current_settings();
     let menu = build_tray_menu(app, &settings)?;
 
-    let icon_bytes = include_bytes!("../icons/tray.png");
-    let icon = tauri::image::Image::from_bytes(icon_bytes)?.to_owned();
+    let icon = tauri::image::Image::from_bytes(TRAY_ICON)?.to_owned();
+
+    let app_handle = app.clone();
+    app.listen_any(EVENT_PILL_STATE, move |event| {
+        match serde_json::from_str::<PillStatePayload>(event.payload()) {
+            Ok(payload) => {
+                if let Err(err) = update_tray_icon(&app_handle, payload.status) {
+                    eprintln!("Failed to update tray icon: {err}");
+                }
+            }
+            Err(err) => eprintln!("Failed to read pill state: {err}"),
+        }
+    });
 
     TrayIconBuilder::new()
         .icon(icon)
@@ -337,3 +513,34 @@ pub fn toggle_settings_window(app: &AppHandle<AppRuntime>) -> tauri::Result<()>
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tray_icon_follows_pill_transitions() {
+        let transitions = [
+            (PillStatus::Idle, TRAY_ICON),
+            (PillStatus::Listening, TRAY_ICON_RECORDING),
+            (PillStatus::Processing, TRAY_ICON_PROCESSING),
+            (PillStatus::Idle, TRAY_ICON),
+            (PillStatus::Listening, TRAY_ICON_RECORDING),
+            (PillStatus::ModelLoading, TRAY_ICON_PROCESSING),
+            (PillStatus::Error, TRAY_ICON_ERROR),
+            (PillStatus::Idle, TRAY_ICON),
+        ];
+        for (status, expected) in transitions {
+            assert_eq!(
+                tray_icon_for(status, TrayIconMode::Dynamic).0,
+                expected,
+                "{status}"
+            );
+            assert_eq!(
+                tray_icon_for(status, TrayIconMode::Static),
+                (TRAY_ICON, true)
+            );
+        }
+        assert!(!tray_icon_for(PillStatus::Listening, TrayIconMode::Dynamic).1);
+    }
+}
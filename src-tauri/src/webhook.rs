@@ -0,0 +1,144 @@
+use std::time::Duration;
+
+use anyhow::anyhow;
+use hmac::{Hmac, Mac};
+use reqwest::Client;
+use serde::Serialize;
+use sha2::Sha256;
+
+use crate::storage::TranscriptionRecord;
+
+const WEBHOOK_TIMEOUT_SECS: u64 = 10;
+pub const SIGNATURE_HEADER: &str = "X-Glimpse-Signature";
+
+#[derive(Debug, Serialize)]
+struct WebhookPayload<'a> {
+    id: &'a str,
+    timestamp: String,
+    text: &'a str,
+    word_count: u32,
+    speech_model: &'a str,
+    audio_duration_seconds: f32,
+}
+
+#[derive(Debug)]
+pub enum WebhookError {
+    /// The endpoint rejected the request (4xx); retrying will not help.
+    Permanent(anyhow::Error),
+    /// Network failures and 5xx responses that are worth sending again later.
+    Retriable(anyhow::Error),
+}
+
+impl std::fmt::Display for WebhookError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Permanent(err) | Self::Retriable(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+/// Webhooks carry transcript text, so only encrypted endpoints are accepted.
+pub fn validate_url(url: &str) -> Result<(), String> {
+    let has_https_scheme = url
+        .get(..8)
+        .is_some_and(|scheme| scheme.eq_ignore_ascii_case("https://"));
+    if !has_https_scheme || url.len() == 8 {
+        return Err("Webhook URL must start with https://".to_string());
+    }
+    Ok(())
+}
+
+/// HMAC-SHA256 of `body` keyed with `secret`, as lowercase hex.
+pub fn sign_payload(secret: &[u8], body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(body);
+    mac.finalize()
+        .into_bytes()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// POST `record` to `url`. With a `secret`, the body is signed in the
+/// `X-Glimpse-Signature: sha256=<hex>` header so receivers can verify where it came from.
+pub async fn push_transcription(
+    client: &Client,
+    url: &str,
+    secret: Option<&str>,
+    record: &TranscriptionRecord,
+) -> Result<(), WebhookError> {
+    let payload = WebhookPayload {
+        id: &record.id,
+        timestamp: record.timestamp.to_rfc3339(),
+        text: &record.text,
+        word_count: record.word_count,
+        speech_model: &record.speech_model,
+        audio_duration_seconds: record.audio_duration_seconds,
+    };
+
+    // Serialized up front so the signature covers the exact bytes sent
+    let body = serde_json::to_vec(&payload)
+        .map_err(|err| WebhookError::Permanent(anyhow!("Failed to serialize payload: {err}")))?;
+
+    let mut request = client
+        .post(url)
+        .header("Content-Type", "application/json")
+        .timeout(Duration::from_secs(WEBHOOK_TIMEOUT_SECS));
+    if let Some(secret) = secret.filter(|secret| !secret.is_empty()) {
+        let signature = sign_payload(secret.as_bytes(), &body);
+        request = request.header(SIGNATURE_HEADER, format!("sha256={signature}"));
+    }
+
+    let resp = request
+        .body(body)
+        .send()
+        .await
+        .map_err(|err| WebhookError::Retriable(anyhow!("Failed to reach webhook: {err}")))?;
+
+    let status = resp.status();
+    if status.is_success() {
+        return Ok(());
+    }
+
+    let body = resp.text().await.unwrap_or_default();
+    let err = anyhow!("Webhook returned {status}: {body}");
+    if status.is_client_error() {
+        Err(WebhookError::Permanent(err))
+    } else {
+        Err(WebhookError::Retriable(err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_payload_matches_rfc_4231_vectors() {
+        assert_eq!(
+            sign_payload(&[0x0b; 20], b"Hi There"),
+            "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7"
+        );
+        assert_eq!(
+            sign_payload(b"Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        // Keys longer than a block are hashed first
+        assert_eq!(
+            sign_payload(
+                &[0xaa; 131],
+                b"Test Using Larger Than Block-Size Key - Hash Key First"
+            ),
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+        );
+    }
+
+    #[test]
+    fn test_validate_url() {
+        assert!(validate_url("https://hooks.example.com/glimpse").is_ok());
+        assert!(validate_url("HTTPS://hooks.example.com").is_ok());
+        assert!(validate_url("http://hooks.example.com").is_err());
+        assert!(validate_url("https://").is_err());
+        assert!(validate_url("hooks.example.com").is_err());
+    }
+}
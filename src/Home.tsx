@@ -10,7 +10,7 @@ import TranscriptionList from "./components/TranscriptionList";
 import DictionaryView from "./components/DictionaryView";
 import { getCurrentUser, type User as AppwriteUser } from "./lib/auth";
 
-type TranscriptionMode = "cloud" | "local";
+type TranscriptionMode = "cloud" | "local" | "hybrid";
 
 type StoredSettings = {
     transcription_mode: TranscriptionMode;
@@ -78,6 +78,7 @@ const Home = () => {
     useEffect(() => {
         let unlistenSettings: UnlistenFn | null = null;
         let unlistenNavigate: UnlistenFn | null = null;
+        const unlistenTabs: UnlistenFn[] = [];
 
         const loadSettings = async () => {
             try {
@@ -114,9 +115,20 @@ const Home = () => {
             unlistenNavigate = fn;
         });
 
+        // Sent when a glimpse://settings?tab= link is opened
+        for (const tab of ["general", "account", "models"] as const) {
+            listen(`navigate:${tab}`, () => {
+                setSettingsTab(tab);
+                setIsSettingsOpen(true);
+            }).then((fn) => {
+                unlistenTabs.push(fn);
+            });
+        }
+
         return () => {
             unlistenSettings?.();
             unlistenNavigate?.();
+            unlistenTabs.forEach((fn) => fn());
         };
     }, [loadUser]);
 
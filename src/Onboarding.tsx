@@ -27,16 +27,6 @@ import FAQModal from "./components/FAQModal";
 import { LlmProviderConfig, type LlmProvider } from "./components/LlmProviderConfig";
 
 
-type ModelInfo = {
-    key: string;
-    label: string;
-    description: string;
-    size_mb: number;
-    engine: string;
-    variant: string;
-    tags: string[];
-};
-
 type StoredSettings = {
     local_model?: string;
 };
@@ -449,10 +439,10 @@ const Onboarding = ({ onComplete }: OnboardingProps) => {
         };
     }, [captureActive]);
 
-    const refreshModelStatus = useCallback((modelKey: string) => {
-        invoke<ModelStatus>("check_model_status", { model: modelKey })
-            .then((status) => {
-                setModelStatus((prev) => ({ ...prev, [modelKey]: status }));
+    const refreshModelStatus = useCallback(() => {
+        invoke<ModelStatus[]>("list_models_with_status")
+            .then((models) => {
+                setModelStatus(Object.fromEntries(models.map((model) => [model.key, model])));
             })
             .catch((err) => console.error("Failed to check model status", err));
     }, []);
@@ -460,13 +450,10 @@ const Onboarding = ({ onComplete }: OnboardingProps) => {
     useEffect(() => {
         let isMounted = true;
         const load = async () => {
+            refreshModelStatus();
             try {
-                const [models, settings] = await Promise.all([
-                    invoke<ModelInfo[]>("list_models"),
-                    invoke<StoredSettings>("get_settings"),
-                ]);
+                const settings = await invoke<StoredSettings>("get_settings");
                 if (!isMounted) return;
-                models.forEach((model) => refreshModelStatus(model.key));
                 if (
                     settings?.local_model &&
                     (settings.local_model === PARAKEET_KEY || settings.local_model === WHISPER_KEY)
@@ -474,9 +461,7 @@ const Onboarding = ({ onComplete }: OnboardingProps) => {
                     setLocalModelChoice(settings.local_model as typeof PARAKEET_KEY | typeof WHISPER_KEY);
                 }
             } catch (err) {
-                console.error("Failed to preload model info", err);
-                if (!isMounted) return;
-                [PARAKEET_KEY, WHISPER_KEY].forEach((model) => refreshModelStatus(model));
+                console.error("Failed to load settings", err);
             }
         };
         load();
@@ -517,7 +502,7 @@ const Onboarding = ({ onComplete }: OnboardingProps) => {
                                 message: prev[model]?.message,
                             },
                         }));
-                        refreshModelStatus(model as "parakeet_tdt_int8" | "whisper_small_q5");
+                        refreshModelStatus();
                     }),
                     listen<{ model: string; error: string }>("download:error", (event) => {
                         const { model, error } = event.payload;
@@ -575,7 +560,7 @@ const Onboarding = ({ onComplete }: OnboardingProps) => {
                 ...prev,
                 [modelKey]: { status: "idle", percent: 0 },
             }));
-            refreshModelStatus(modelKey);
+            refreshModelStatus();
         } catch (err) {
             console.error(err);
             setLocalDownload((prev) => ({
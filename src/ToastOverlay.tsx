@@ -14,7 +14,7 @@ export interface ToastPayload {
   autoDismiss?: boolean;
   duration?: number;
   retryId?: string;
-  mode?: "local" | "cloud";
+  mode?: "local" | "cloud" | "duplicate";
   action?: string;
   actionLabel?: string;
 }
@@ -44,8 +44,9 @@ const ToastOverlay: React.FC = () => {
 
   const closeAll = async () => {
     try {
-      await invoke("toast_dismissed");
-      await getCurrentWindow().hide();
+      // A queued toast may have taken this one's place
+      const showingNext = await invoke<boolean>("toast_dismissed");
+      if (!showingNext) await getCurrentWindow().hide();
     } catch (e) {
       console.error("closeAll failed:", e);
     }
@@ -63,10 +64,11 @@ const ToastOverlay: React.FC = () => {
     dismissAnimationTimerRef.current = setTimeout(async () => {
       dismissAnimationTimerRef.current = null;
       setToast(null);
+      let showingNext = false;
       try {
-        await invoke("toast_dismissed");
+        showingNext = await invoke<boolean>("toast_dismissed");
       } catch { /* ignore */ }
-      await getCurrentWindow().hide();
+      if (!showingNext) await getCurrentWindow().hide();
     }, 120);
   };
 
@@ -164,7 +166,8 @@ const ToastOverlay: React.FC = () => {
   if (!toast) return null;
 
   const colors = COLORS[toast.type];
-  const showRetry = toast.retryId && toast.mode === "cloud";
+  const showRetry = toast.retryId && (toast.mode === "cloud" || toast.mode === "duplicate");
+  const retryLabel = toast.mode === "duplicate" ? "Transcribe anyway" : "Retry transcription";
 
   const handleBackgroundClick = () => {
     dismissWithCleanup();
@@ -222,7 +225,7 @@ const ToastOverlay: React.FC = () => {
                 disabled={isRetrying}
                 className="mt-2 text-[11px] text-blue-400 hover:text-white disabled:text-gray-600 transition-colors"
               >
-                {isRetrying ? "Retrying…" : "Retry transcription"}
+                {isRetrying ? "Retrying…" : retryLabel}
               </button>
             )}
             {toast.action && toast.actionLabel && (
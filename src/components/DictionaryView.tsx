@@ -4,7 +4,7 @@ import { motion, AnimatePresence } from "framer-motion";
 import { AlertTriangle, ArrowRight, BookOpen, Edit3, Loader2, Plus, Replace, Trash2 } from "lucide-react";
 import DotMatrix from "./DotMatrix";
 
-type TranscriptionMode = "cloud" | "local";
+type TranscriptionMode = "cloud" | "local" | "hybrid";
 
 type StoredSettings = {
     transcription_mode: TranscriptionMode;
@@ -22,6 +22,8 @@ type ModelInfo = {
 type Replacement = {
     from: string;
     to: string;
+    /** Higher priorities are applied first. */
+    priority: number;
 };
 
 type ActivePage = "dictionary" | "replacements";
@@ -104,7 +106,7 @@ const DictionaryView = () => {
         try {
             const [settingsResp, modelsResp, replacementsResp] = await Promise.all([
                 invoke<StoredSettings>("get_settings"),
-                invoke<ModelInfo[]>("list_models"),
+                invoke<ModelInfo[]>("list_models_with_status"),
                 invoke<Replacement[]>("get_replacements"),
             ]);
             setSettings(settingsResp);
@@ -193,7 +195,7 @@ const DictionaryView = () => {
         if (!from) return;
         const exists = replacements.some((r) => r.from.toLowerCase() === from.toLowerCase());
         if (exists) return;
-        await persistReplacements([...replacements, { from, to }]);
+        await persistReplacements([...replacements, { from, to, priority: 0 }]);
     };
 
     const handleEditReplacementCommit = async () => {
@@ -206,7 +208,7 @@ const DictionaryView = () => {
             return;
         }
         const next = replacements.map((r, idx) =>
-            idx === editingReplacementIndex ? { from, to } : r
+            idx === editingReplacementIndex ? { ...r, from, to } : r
         );
         await persistReplacements(next);
     };
@@ -223,7 +225,7 @@ const DictionaryView = () => {
     };
 
     const currentModel = models.find((m) => m.key === settings?.local_model);
-    const isLocal = settings?.transcription_mode === "local";
+    const isLocal = settings?.transcription_mode === "local" || settings?.transcription_mode === "hybrid";
     const isWhisper =
         currentModel?.engine.toLowerCase().includes("whisper") ||
         currentModel?.variant.toLowerCase().includes("whisper");
@@ -43,7 +43,7 @@ import WhatsNewModal from "./WhatsNewModal";
 import { type LlmProvider, LlmProviderConfig } from "./LlmProviderConfig";
 
 
-type TranscriptionMode = "cloud" | "local";
+type TranscriptionMode = "cloud" | "local" | "hybrid";
 
 type StoredSettings = {
     smart_shortcut: string;
@@ -63,8 +63,20 @@ type StoredSettings = {
     llm_model: string;
     user_context: string;
     dictionary: string[];
+    duplicate_detection_enabled: boolean;
+    auto_stop_silence_seconds: number | null;
+    auto_stop_silence_rms: number;
+    backup_path: string | null;
+    auto_backup_enabled: boolean;
 };
 
+/** Input levels below which auto-stop counts the microphone as silent. */
+const AUTO_STOP_SENSITIVITIES = [
+    { rms: 0.005, label: "Quiet room" },
+    { rms: 0.01, label: "Normal" },
+    { rms: 0.03, label: "Noisy room" },
+];
+
 type AppInfo = {
     version: string;
     data_dir_size_bytes: number;
@@ -90,6 +102,8 @@ type ModelStatus = {
     directory: string;
 };
 
+type ModelInfoWithStatus = ModelInfo & ModelStatus & { recommended: boolean };
+
 type DownloadProgressPayload = {
     model: string;
     file: string;
@@ -113,6 +127,7 @@ type DownloadEvent =
 const modifierOrder = ["Control", "Shift", "Alt", "Command"];
 
 const languages = [
+    { code: "auto", name: "Auto-detect" },
     { code: "en", name: "English" },
     { code: "es", name: "Spanish" },
     { code: "fr", name: "French" },
@@ -172,6 +187,13 @@ const SettingsModal = ({
     const [llmEndpoint, setLlmEndpoint] = useState("");
     const [llmApiKey, setLlmApiKey] = useState("");
     const [llmModel, setLlmModel] = useState("");
+    const [duplicateDetectionEnabled, setDuplicateDetectionEnabled] = useState(true);
+    const [autoStopSilenceSeconds, setAutoStopSilenceSeconds] = useState<number | null>(null);
+    const [autoStopSilenceRms, setAutoStopSilenceRms] = useState(0.01);
+    const [backupPath, setBackupPath] = useState<string | null>(null);
+    const [backupFolderInput, setBackupFolderInput] = useState("");
+    const [autoBackupEnabled, setAutoBackupEnabled] = useState(false);
+    const [backupStatus, setBackupStatus] = useState<string | null>(null);
 
 
     const [authLoading, setAuthLoading] = useState(false);
@@ -291,24 +313,14 @@ const SettingsModal = ({
         };
     }, []);
 
-    const refreshModelStatus = useCallback((modelKey: string) => {
-        invoke<ModelStatus>("check_model_status", { model: modelKey })
-            .then((status) => {
-                setModelStatus((prev) => ({ ...prev, [modelKey]: status }));
-            })
-            .catch((err) => {
-                console.error(err);
-                setModelStatus((prev) => ({
-                    ...prev,
-                    [modelKey]: {
-                        key: modelKey,
-                        installed: false,
-                        bytes_on_disk: 0,
-                        missing_files: [],
-                        directory: "",
-                    },
-                }));
-            });
+    const refreshModels = useCallback(async () => {
+        try {
+            const models = await invoke<ModelInfoWithStatus[]>("list_models_with_status");
+            setModelCatalog(models);
+            setModelStatus(Object.fromEntries(models.map((model) => [model.key, model])));
+        } catch (err) {
+            console.error("Failed to list models:", err);
+        }
     }, []);
 
     useEffect(() => {
@@ -332,6 +344,12 @@ const SettingsModal = ({
                     setLlmEndpoint(settings.llm_endpoint ?? "");
                     setLlmApiKey(settings.llm_api_key ?? "");
                     setLlmModel(settings.llm_model ?? "");
+                    setDuplicateDetectionEnabled(settings.duplicate_detection_enabled ?? true);
+                    setAutoStopSilenceSeconds(settings.auto_stop_silence_seconds ?? null);
+                    setAutoStopSilenceRms(settings.auto_stop_silence_rms ?? 0.01);
+                    setBackupPath(settings.backup_path ?? null);
+                    setBackupFolderInput(settings.backup_path ?? "");
+                    setAutoBackupEnabled(settings.auto_backup_enabled ?? false);
                 } catch (err) {
                     console.error("Failed to load settings:", err);
                     setError("Failed to load settings");
@@ -344,18 +362,12 @@ const SettingsModal = ({
                     console.error("Failed to list input devices:", err);
                 }
 
-                try {
-                    const models = await invoke<ModelInfo[]>("list_models");
-                    setModelCatalog(models);
-                    models.forEach((model) => refreshModelStatus(model.key));
-                } catch (err) {
-                    console.error("Failed to list models:", err);
-                }
+                await refreshModels();
                 setLoading(false);
             };
             loadData();
         }
-    }, [isOpen, refreshModelStatus]);
+    }, [isOpen, refreshModels]);
 
     useEffect(() => {
         if (isOpen) {
@@ -435,7 +447,7 @@ const SettingsModal = ({
                         total: prev[model]?.total ?? 0,
                     },
                 }));
-                refreshModelStatus(model);
+                refreshModels();
             });
 
             unlistenError = await listen<{ model: string; error: string }>("download:error", (event) => {
@@ -460,7 +472,7 @@ const SettingsModal = ({
             unlistenComplete?.();
             unlistenError?.();
         };
-    }, [isOpen, refreshModelStatus]);
+    }, [isOpen, refreshModels]);
 
     useEffect(() => {
         if (!captureActive) return;
@@ -560,6 +572,10 @@ const SettingsModal = ({
                     llmApiKey,
                     llmModel,
                     userContext: "",
+                    duplicateDetectionEnabled,
+                    autoStopSilenceSeconds: autoStopSilenceSeconds ?? 0,
+                    autoStopSilenceRms,
+                    autoBackupEnabled,
                 });
                 setError(null);
             } catch (err) {
@@ -586,8 +602,34 @@ const SettingsModal = ({
         llmEndpoint,
         llmApiKey,
         llmModel,
+        duplicateDetectionEnabled,
+        autoStopSilenceSeconds,
+        autoStopSilenceRms,
+        autoBackupEnabled,
     ]);
 
+    const handleSetBackupFolder = async () => {
+        try {
+            await invoke("set_backup_path", { path: backupFolderInput });
+            const settings = await invoke<StoredSettings>("get_settings");
+            setBackupPath(settings.backup_path ?? null);
+            setBackupFolderInput(settings.backup_path ?? "");
+            setBackupStatus(null);
+        } catch (err) {
+            setBackupStatus(String(err));
+        }
+    };
+
+    const handleBackupNow = async () => {
+        setBackupStatus("Backing up…");
+        try {
+            const path = await invoke<string>("trigger_backup");
+            setBackupStatus(`Saved to ${path}`);
+        } catch (err) {
+            setBackupStatus(String(err));
+        }
+    };
+
     const handleDownload = async (modelKey: string) => {
         setDownloadState((prev) => ({
             ...prev,
@@ -595,7 +637,7 @@ const SettingsModal = ({
         }));
         try {
             await invoke("download_model", { model: modelKey });
-            refreshModelStatus(modelKey);
+            refreshModels();
         } catch (err) {
             console.error(err);
             setDownloadState((prev) => ({
@@ -625,7 +667,7 @@ const SettingsModal = ({
                 }
             }
 
-            refreshModelStatus(modelKey);
+            refreshModels();
         } catch (err) {
             console.error(err);
             setDownloadState((prev) => ({
@@ -742,7 +784,7 @@ const SettingsModal = ({
                                 </div>
 
                                 <AnimatePresence>
-                                    {!loading && transcriptionMode === "local" && (
+                                    {!loading && transcriptionMode !== "cloud" && (
                                         <motion.div
                                             initial={{ opacity: 0, height: 0 }}
                                             animate={{ opacity: 1, height: "auto" }}
@@ -1203,7 +1245,7 @@ const SettingsModal = ({
                                                             <p className="text-[11px] text-[#4a4a54]">Choose how your audio is processed</p>
                                                         </div>
                                                     </div>
-                                                    <div className="grid grid-cols-2 gap-2">
+                                                    <div className="grid grid-cols-3 gap-2">
                                                         <ModeButton
                                                             icon={<Cloud size={16} />}
                                                             label="Cloud"
@@ -1222,10 +1264,19 @@ const SettingsModal = ({
                                                             variant="local"
                                                             loading={loading}
                                                         />
+                                                        <ModeButton
+                                                            icon={<CloudCog size={16} />}
+                                                            label="Hybrid"
+                                                            description="Local, cloud backup"
+                                                            active={transcriptionMode === "hybrid"}
+                                                            onClick={() => setTranscriptionMode("hybrid")}
+                                                            variant="local"
+                                                            loading={loading}
+                                                        />
                                                     </div>
 
                                                     <AnimatePresence>
-                                                        {!loading && transcriptionMode === "local" && !modelStatus[localModel]?.installed && (
+                                                        {!loading && transcriptionMode !== "cloud" && !modelStatus[localModel]?.installed && (
                                                             <motion.div
                                                                 initial={{ opacity: 0, height: 0, marginTop: 0 }}
                                                                 animate={{ opacity: 1, height: "auto", marginTop: 12 }}
@@ -1481,6 +1532,161 @@ const SettingsModal = ({
                                                 <p className="mt-1 text-[12px] text-[#6b6b76]">System permissions and troubleshooting.</p>
                                             </header>
 
+                                            <div className="space-y-3">
+                                                <p className="text-[10px] font-medium uppercase tracking-wider text-[#4a4a54] px-1">Recording</p>
+
+                                                {/* Duplicate Detection */}
+                                                <div className="rounded-xl border border-[#1e1e22] bg-[#111113] p-4">
+                                                    <div className="flex items-center justify-between">
+                                                        <div className="flex items-center gap-3">
+                                                            <div className="flex h-9 w-9 items-center justify-center rounded-lg bg-[#1a1a1e] border border-[#2a2a30]">
+                                                                <Copy size={16} className="text-[#6b6b76]" />
+                                                            </div>
+                                                            <div>
+                                                                <p className="text-[13px] font-medium text-[#e8e8eb]">Duplicate Detection</p>
+                                                                <p className="text-[11px] text-[#6b6b76]">Hold back a recording that repeats the one before it</p>
+                                                            </div>
+                                                        </div>
+                                                        <motion.button
+                                                            onClick={() => setDuplicateDetectionEnabled(!duplicateDetectionEnabled)}
+                                                            className={`relative w-10 h-5 rounded-full transition-colors ${duplicateDetectionEnabled ? "bg-amber-400" : "bg-[#2a2a30]"}`}
+                                                            whileTap={{ scale: 0.95 }}
+                                                        >
+                                                            <motion.div
+                                                                className="absolute top-0.5 w-4 h-4 rounded-full bg-white shadow-sm"
+                                                                animate={{ left: duplicateDetectionEnabled ? "calc(100% - 18px)" : "2px" }}
+                                                                transition={{ type: "spring", stiffness: 500, damping: 30 }}
+                                                            />
+                                                        </motion.button>
+                                                    </div>
+                                                </div>
+
+                                                {/* Auto-stop */}
+                                                <div className="rounded-xl border border-[#1e1e22] bg-[#111113] p-4 space-y-4">
+                                                    <div className="flex items-center gap-3">
+                                                        <div className="flex h-9 w-9 items-center justify-center rounded-lg bg-[#1a1a1e] border border-[#2a2a30]">
+                                                            <Mic size={16} className="text-[#6b6b76]" />
+                                                        </div>
+                                                        <div>
+                                                            <p className="text-[13px] font-medium text-[#e8e8eb]">Auto-stop on Silence</p>
+                                                            <p className="text-[11px] text-[#6b6b76]">End a toggle recording once you stop talking</p>
+                                                        </div>
+                                                    </div>
+
+                                                    <div className="grid grid-cols-2 gap-4">
+                                                        <div className="space-y-1.5">
+                                                            <label className="text-[11px] font-medium text-[#6b6b76] ml-1">Stop after</label>
+                                                            <div className="relative">
+                                                                <select
+                                                                    value={autoStopSilenceSeconds ?? 0}
+                                                                    onChange={(e) => {
+                                                                        const seconds = Number(e.target.value);
+                                                                        setAutoStopSilenceSeconds(seconds > 0 ? seconds : null);
+                                                                    }}
+                                                                    className="w-full appearance-none rounded-lg bg-[#1a1a1e] border border-[#2a2a30] py-2 pl-3 pr-8 text-[12px] text-[#e8e8eb] focus:border-[#4a4a54] focus:outline-none transition-colors"
+                                                                >
+                                                                    <option value={0}>Never</option>
+                                                                    {[10, 20, 30, 60, 120].map((seconds) => (
+                                                                        <option key={seconds} value={seconds}>
+                                                                            {seconds} seconds of silence
+                                                                        </option>
+                                                                    ))}
+                                                                </select>
+                                                                <div className="pointer-events-none absolute right-2.5 top-1/2 -translate-y-1/2 text-[#6b6b76]">
+                                                                    <ChevronDown size={12} />
+                                                                </div>
+                                                            </div>
+                                                        </div>
+
+                                                        <div className="space-y-1.5">
+                                                            <label className="text-[11px] font-medium text-[#6b6b76] ml-1">Silence means</label>
+                                                            <div className="relative">
+                                                                <select
+                                                                    value={autoStopSilenceRms}
+                                                                    onChange={(e) => setAutoStopSilenceRms(Number(e.target.value))}
+                                                                    disabled={autoStopSilenceSeconds === null}
+                                                                    className="w-full appearance-none rounded-lg bg-[#1a1a1e] border border-[#2a2a30] py-2 pl-3 pr-8 text-[12px] text-[#e8e8eb] focus:border-[#4a4a54] focus:outline-none disabled:opacity-50 transition-colors"
+                                                                >
+                                                                    {AUTO_STOP_SENSITIVITIES.some((option) => option.rms === autoStopSilenceRms) ? null : (
+                                                                        <option value={autoStopSilenceRms}>Custom ({autoStopSilenceRms})</option>
+                                                                    )}
+                                                                    {AUTO_STOP_SENSITIVITIES.map((option) => (
+                                                                        <option key={option.rms} value={option.rms}>
+                                                                            {option.label}
+                                                                        </option>
+                                                                    ))}
+                                                                </select>
+                                                                <div className="pointer-events-none absolute right-2.5 top-1/2 -translate-y-1/2 text-[#6b6b76]">
+                                                                    <ChevronDown size={12} />
+                                                                </div>
+                                                            </div>
+                                                        </div>
+                                                    </div>
+                                                </div>
+                                            </div>
+
+                                            <div className="space-y-3">
+                                                <p className="text-[10px] font-medium uppercase tracking-wider text-[#4a4a54] px-1">Backups</p>
+
+                                                <div className="rounded-xl border border-[#1e1e22] bg-[#111113] p-4 space-y-4">
+                                                    <div className="flex items-center justify-between">
+                                                        <div className="flex items-center gap-3">
+                                                            <div className="flex h-9 w-9 items-center justify-center rounded-lg bg-[#1a1a1e] border border-[#2a2a30]">
+                                                                <FolderOpen size={16} className="text-[#6b6b76]" />
+                                                            </div>
+                                                            <div>
+                                                                <p className="text-[13px] font-medium text-[#e8e8eb]">Automatic Backups</p>
+                                                                <p className="text-[11px] text-[#6b6b76]">Back up your history every few transcriptions</p>
+                                                            </div>
+                                                        </div>
+                                                        <motion.button
+                                                            onClick={() => setAutoBackupEnabled(!autoBackupEnabled)}
+                                                            disabled={!backupPath}
+                                                            className={`relative w-10 h-5 rounded-full transition-colors ${autoBackupEnabled ? "bg-amber-400" : "bg-[#2a2a30]"} ${!backupPath ? "opacity-50 cursor-not-allowed" : ""}`}
+                                                            whileTap={backupPath ? { scale: 0.95 } : {}}
+                                                        >
+                                                            <motion.div
+                                                                className="absolute top-0.5 w-4 h-4 rounded-full bg-white shadow-sm"
+                                                                animate={{ left: autoBackupEnabled ? "calc(100% - 18px)" : "2px" }}
+                                                                transition={{ type: "spring", stiffness: 500, damping: 30 }}
+                                                            />
+                                                        </motion.button>
+                                                    </div>
+
+                                                    <div className="space-y-1.5">
+                                                        <label className="text-[11px] font-medium text-[#6b6b76] ml-1">Backup folder</label>
+                                                        <div className="flex items-center gap-2">
+                                                            <input
+                                                                type="text"
+                                                                value={backupFolderInput}
+                                                                onChange={(e) => setBackupFolderInput(e.target.value)}
+                                                                placeholder="/Users/you/Backups/Glimpse"
+                                                                className="w-full rounded-lg bg-[#1a1a1e] border border-[#2a2a30] py-2 px-3 text-[12px] text-[#e8e8eb] placeholder-[#4a4a54] focus:border-[#4a4a54] focus:outline-none transition-colors"
+                                                            />
+                                                            <motion.button
+                                                                onClick={handleSetBackupFolder}
+                                                                disabled={!backupFolderInput.trim() || backupFolderInput === backupPath}
+                                                                className="rounded-lg bg-[#1a1a1e] border border-[#2a2a30] px-3 py-1.5 text-[11px] font-medium text-[#a0a0ab] hover:bg-[#232328] hover:text-[#e8e8eb] disabled:opacity-50 transition-colors"
+                                                                whileTap={{ scale: 0.97 }}
+                                                            >
+                                                                Save
+                                                            </motion.button>
+                                                            <motion.button
+                                                                onClick={handleBackupNow}
+                                                                disabled={!backupPath}
+                                                                className="shrink-0 rounded-lg bg-[#1a1a1e] border border-[#2a2a30] px-3 py-1.5 text-[11px] font-medium text-[#a0a0ab] hover:bg-[#232328] hover:text-[#e8e8eb] disabled:opacity-50 transition-colors"
+                                                                whileTap={{ scale: 0.97 }}
+                                                            >
+                                                                Back up now
+                                                            </motion.button>
+                                                        </div>
+                                                        {backupStatus && (
+                                                            <p className="text-[10px] text-[#6b6b76] ml-1 truncate">{backupStatus}</p>
+                                                        )}
+                                                    </div>
+                                                </div>
+                                            </div>
+
                                             <div className="space-y-3">
                                                 <p className="text-[10px] font-medium uppercase tracking-wider text-[#4a4a54] px-1">Permissions</p>
 
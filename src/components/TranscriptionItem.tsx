@@ -1,12 +1,13 @@
 import React, { useState, useRef, useEffect } from "react";
 import { motion, AnimatePresence } from "framer-motion";
-import { Copy, Trash2, RotateCw, Check, ChevronDown, ChevronUp, MoreVertical, Wand2, AlertTriangle, Undo2 } from "lucide-react";
+import { Copy, Trash2, RotateCw, Check, ChevronDown, ChevronUp, MoreVertical, Wand2, AlertTriangle, Undo2, EyeOff } from "lucide-react";
 import { TranscriptionRecord } from "../hooks/useTranscriptions";
 import DotMatrix from "./DotMatrix";
 
 interface TranscriptionItemProps {
     record: TranscriptionRecord;
     onDelete: (id: string) => Promise<void>;
+    onRedact?: (id: string) => Promise<void>;
     onRetry: (id: string) => Promise<void>;
     onRetryLlm?: (id: string) => Promise<void>;
     onUndoLlm?: (id: string) => Promise<void>;
@@ -15,9 +16,10 @@ interface TranscriptionItemProps {
     skipAnimation?: boolean;
 }
 
-const TranscriptionItem: React.FC<TranscriptionItemProps> = ({ record, onDelete, onRetry, onRetryLlm, onUndoLlm, showLlmButtons = false, searchQuery = "", skipAnimation = false }) => {
+const TranscriptionItem: React.FC<TranscriptionItemProps> = ({ record, onDelete, onRedact, onRetry, onRetryLlm, onUndoLlm, showLlmButtons = false, searchQuery = "", skipAnimation = false }) => {
     const [copied, setCopied] = useState(false);
     const [isDeleting, setIsDeleting] = useState(false);
+    const [isRedacting, setIsRedacting] = useState(false);
     const [isRetrying, setIsRetrying] = useState(false);
     const [isRetryingLlm, setIsRetryingLlm] = useState(false);
     const [isUndoingLlm, setIsUndoingLlm] = useState(false);
@@ -84,6 +86,19 @@ const TranscriptionItem: React.FC<TranscriptionItemProps> = ({ record, onDelete,
         }
     };
 
+    const handleRedact = async () => {
+        if (isRedacting || !onRedact) return;
+        setIsRedacting(true);
+        setMenuOpen(false);
+        try {
+            await onRedact(record.id);
+        } catch (err) {
+            console.error("Failed to redact:", err);
+        } finally {
+            setIsRedacting(false);
+        }
+    };
+
     const handleRetry = async () => {
         if (isRetrying) return;
         setIsRetrying(true);
@@ -335,16 +350,18 @@ const TranscriptionItem: React.FC<TranscriptionItemProps> = ({ record, onDelete,
                                         right: menuRef.current ? window.innerWidth - menuRef.current.getBoundingClientRect().right : 0,
                                     }}
                                 >
-                                    <button
-                                        onClick={handleRetry}
-                                        disabled={isRetrying}
-                                        className="flex w-full items-center gap-2.5 px-3 py-2 text-[11px] text-[#c8c8d2] hover:bg-[#1a1a1e] transition-colors disabled:opacity-50"
-                                    >
-                                        <RotateCw size={12} className="text-[#fbbf24]" />
-                                        <span>Retry</span>
-                                    </button>
+                                    {!record.is_redacted && (
+                                        <button
+                                            onClick={handleRetry}
+                                            disabled={isRetrying}
+                                            className="flex w-full items-center gap-2.5 px-3 py-2 text-[11px] text-[#c8c8d2] hover:bg-[#1a1a1e] transition-colors disabled:opacity-50"
+                                        >
+                                            <RotateCw size={12} className="text-[#fbbf24]" />
+                                            <span>Retry</span>
+                                        </button>
+                                    )}
 
-                                    {!isError && onRetryLlm && showLlmButtons && (
+                                    {!isError && !record.is_redacted && onRetryLlm && showLlmButtons && (
                                         <button
                                             onClick={handleRetryLlm}
                                             disabled={isRetryingLlm}
@@ -355,7 +372,7 @@ const TranscriptionItem: React.FC<TranscriptionItemProps> = ({ record, onDelete,
                                         </button>
                                     )}
 
-                                    {!isError && record.llm_cleaned && record.raw_text && onUndoLlm && showLlmButtons && (
+                                    {!isError && !record.is_redacted && record.llm_cleaned && record.raw_text && onUndoLlm && showLlmButtons && (
                                         <button
                                             onClick={handleUndoLlm}
                                             disabled={isUndoingLlm}
@@ -368,6 +385,18 @@ const TranscriptionItem: React.FC<TranscriptionItemProps> = ({ record, onDelete,
 
                                     <div className="h-px bg-[#2a2a30] mx-2" />
 
+                                    {onRedact && !record.is_redacted && (
+                                        <button
+                                            onClick={handleRedact}
+                                            disabled={isRedacting}
+                                            className="flex w-full items-center gap-2.5 px-3 py-2 text-[11px] text-[#c8c8d2] hover:bg-[#1a1a1e] transition-colors disabled:opacity-50"
+                                            title="Remove the text and audio but keep the entry"
+                                        >
+                                            <EyeOff size={12} className="text-[#6b6b76]" />
+                                            <span>Redact</span>
+                                        </button>
+                                    )}
+
                                     <button
                                         onClick={handleDelete}
                                         disabled={isDeleting}
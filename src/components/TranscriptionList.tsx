@@ -16,6 +16,7 @@ const TranscriptionList: React.FC<TranscriptionListProps> = ({ showLlmButtons =
         totalCount,
         isLoading,
         deleteTranscription,
+        redactTranscription,
         retryTranscription,
         retryLlmCleanup,
         undoLlmCleanup,
@@ -184,6 +185,7 @@ const TranscriptionList: React.FC<TranscriptionListProps> = ({ showLlmButtons =
                                         key={record.id}
                                         record={record}
                                         onDelete={deleteTranscription}
+                                        onRedact={redactTranscription}
                                         onRetry={retryTranscription}
                                         onRetryLlm={retryLlmCleanup}
                                         onUndoLlm={undoLlmCleanup}
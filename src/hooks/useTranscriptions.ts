@@ -6,6 +6,7 @@ import {
     listTranscriptions,
     getCurrentUser,
     deleteCloudTranscription,
+    updateTranscription,
     findByLocalId
 } from "../lib";
 
@@ -23,8 +24,11 @@ export type TranscriptionRecord = {
     word_count: number;
     audio_duration_seconds: number;
     synced: boolean;
+    is_redacted?: boolean;
 };
 
+const REDACTED_PLACEHOLDER = "[redacted]";
+
 interface UseTranscriptionsOptions {
     cloudSyncEnabled?: boolean;
 }
@@ -322,6 +326,47 @@ export function useTranscriptions(options: UseTranscriptionsOptions = {}) {
         }
     }, [resolvedCloudSyncEnabled, userId, isSubscriber]);
 
+    // Unlike delete, the row stays so the cloud copy is overwritten rather than hidden
+    const redactTranscription = useCallback(async (id: string) => {
+        try {
+            const redacted = await invoke<boolean>("anonymize_transcription", { id });
+            if (!redacted) return;
+            setTranscriptions(prev => prev.map(t =>
+                t.id === id
+                    ? {
+                        ...t,
+                        text: REDACTED_PLACEHOLDER,
+                        raw_text: REDACTED_PLACEHOLDER,
+                        audio_path: REDACTED_PLACEHOLDER,
+                        word_count: 0,
+                        audio_duration_seconds: 0,
+                        synced: false,
+                        is_redacted: true,
+                    }
+                    : t
+            ));
+
+            if (resolvedCloudSyncEnabled && userId && isSubscriber) {
+                const cloudDoc = await findByLocalId(userId, id);
+                if (cloudDoc) {
+                    await updateTranscription(cloudDoc.$id, {
+                        text: REDACTED_PLACEHOLDER,
+                        raw_text: REDACTED_PLACEHOLDER,
+                        word_count: 0,
+                        audio_duration_seconds: 0,
+                    });
+                    await invoke("mark_transcription_synced", { id });
+                    setTranscriptions(prev => prev.map(t =>
+                        t.id === id ? { ...t, synced: true } : t
+                    ));
+                }
+            }
+        } catch (err) {
+            console.error("Failed to redact transcription:", err);
+            throw err;
+        }
+    }, [resolvedCloudSyncEnabled, userId, isSubscriber]);
+
     const retryTranscription = useCallback(async (id: string) => {
         try {
             await invoke("retry_transcription", { id });
@@ -424,6 +469,7 @@ export function useTranscriptions(options: UseTranscriptionsOptions = {}) {
         error,
         isSyncing,
         deleteTranscription,
+        redactTranscription,
         retryTranscription,
         retryLlmCleanup,
         undoLlmCleanup,
@@ -3,7 +3,7 @@ import { listen } from "@tauri-apps/api/event";
 import { getCurrentWindow } from "@tauri-apps/api/window";
 import { invoke } from "@tauri-apps/api/core";
 
-type PillStatus = "idle" | "listening" | "processing" | "error";
+type PillStatus = "idle" | "listening" | "processing" | "model_loading" | "cancelling" | "error";
 
 interface PillStatePayload {
   status: PillStatus;
@@ -18,9 +18,15 @@ interface GridInfo {
   offsetY: number;
 }
 
+interface OverlaySettings {
+  overlay_opacity: number;
+}
+
 const PILL_WIDTH = 97;
 const PILL_HEIGHT = 27;
 const DOT_SPACING = 3;
+// Matches platform::overlay::MIN_OPACITY so the pill can't be faded out of sight
+const MIN_OPACITY = 0.3;
 const DOT_RADIUS = {
   base: 0.9,
   icon: 1.2,
@@ -69,6 +75,7 @@ const PillOverlay: React.FC<PillOverlayProps> = ({
   const [status, setStatus] = useState<PillStatus>("idle");
   const statusRef = useRef<PillStatus>("idle");
   const [isErrorFlashing, setIsErrorFlashing] = useState(false);
+  const [opacity, setOpacity] = useState(1);
 
   // Web Audio Refs
   const audioContextRef = useRef<AudioContext | null>(null);
@@ -536,7 +543,7 @@ const PillOverlay: React.FC<PillOverlayProps> = ({
       setStatus(next);
 
       // Visuals
-      if (next === "processing") {
+      if (next === "processing" || next === "model_loading" || next === "cancelling") {
         runAnimation("processing");
       } else if (next === "error") {
         setIsErrorFlashing(true);
@@ -555,6 +562,25 @@ const PillOverlay: React.FC<PillOverlayProps> = ({
     };
   }, [startMic, stopMic, runAnimation, stopAllAnimations, drawBaseDots]);
 
+  // Opacity follows the saved setting, including changes made while the pill is shown
+  useEffect(() => {
+    const applyOpacity = (value: number) => {
+      setOpacity(Number.isFinite(value) ? Math.min(1, Math.max(MIN_OPACITY, value)) : 1);
+    };
+
+    invoke<OverlaySettings>("get_settings")
+      .then((settings) => applyOpacity(settings.overlay_opacity))
+      .catch((err) => console.error("Failed to load overlay opacity:", err));
+
+    const unlistenPromise = listen<OverlaySettings>("settings:changed", (e) => {
+      applyOpacity(e.payload.overlay_opacity);
+    });
+
+    return () => {
+      unlistenPromise.then(unlisten => unlisten());
+    };
+  }, []);
+
   useEffect(() => {
     if (status === "error" && !isErrorFlashing) {
       stopAllAnimations();
@@ -609,7 +635,7 @@ const PillOverlay: React.FC<PillOverlayProps> = ({
   return (
     <div
       className={`relative w-full h-full flex flex-col justify-end select-none ${className}`}
-      style={style}
+      style={{ ...style, opacity }}
       onContextMenu={(e) => e.preventDefault()}
     >
       <div className="relative flex flex-col items-center pb-2">